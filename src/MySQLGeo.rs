@@ -2,13 +2,32 @@
 //!
 //! This module provides a `Database` struct for interacting with a SQLite database
 //! to store and retrieve spatial data points. It also handles file-based storage
-//! for larger data objects associated with each point.
+//! for larger data objects associated with each point, optionally compressing
+//! data files that exceed a configurable size with zstd or LZ4.
+//!
+//! Data files are content-addressed: points with identical custom data share a
+//! single reference-counted file under `./data/blobs/<store id>/`, where the store id is
+//! unique to each database so that databases never delete each other's files.
+//!
+//! A database opened with `Database::in_memory` keeps both its tables and its data files in
+//! memory, so it never touches the disk and is gone once dropped.
 
-use rusqlite::{params, Connection, Result as SqlResult};
+use rusqlite::{params, params_from_iter, Connection, Result as SqlResult, Row};
+use rusqlite::types::{Value as SqlValue, ValueRef};
 use serde_json::{self, Value};
 use serde::{Serialize, Deserialize};
+use std::collections::{HashMap, HashSet};
 use std::fs;
+use std::io;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use uuid::Uuid;
+use crate::geometry::{validate_point, CoordinateError};
+use crate::oriented_box::enclosing_size;
+use crate::io_stats::{IoCounters, IoStats};
+use crate::config::{Compression, KeyFormat};
+use crate::capabilities::BackendCapabilities;
+use crate::connection_lock::{ConnectionGuard, ConnectionLock};
 
 /// Represents a spatial point with associated data.
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
@@ -25,6 +44,16 @@ pub struct Point {
     pub object_type: String,
     /// Custom data associated with the point
     pub custom_data: Value,
+    /// Extent of the object along each axis, centered on the point
+    pub size: [f64; 3],
+    /// Number of times the object has been changed, used to detect conflicting writes
+    pub version: u64,
+    /// Velocity of the object in units per second, if it moves on its own
+    #[serde(default)]
+    pub velocity: Option<[f64; 3]>,
+    /// Rotation of the object as a unit quaternion [x, y, z, w], if it isn't aligned with the world axes
+    #[serde(default)]
+    pub orientation: Option<[f64; 4]>,
 }
 
 /// Represents a region in the spatial database.
@@ -38,11 +67,101 @@ pub struct Region {
     pub radius: f64,
 }
 
+/// The world state stored by a named snapshot.
+#[derive(Serialize, Deserialize)]
+struct SnapshotArchive {
+    /// Every region
+    regions: Vec<Region>,
+    /// Every live point, with the region it belongs to
+    points: Vec<(Uuid, Point)>,
+    /// Expiry times of the points that have one, in milliseconds since the Unix epoch
+    expiries: Vec<(Uuid, i64)>,
+    /// `(child, parent)` pairs of the attached points; absent from snapshots taken by older releases
+    #[serde(default)]
+    attachments: Vec<(Uuid, Uuid)>,
+}
+
+/// A stored point whose row or custom data couldn't be decoded.
+#[derive(Debug, PartialEq)]
+pub struct CorruptPoint {
+    /// ID of the point as stored in the database
+    pub id: String,
+    /// X-coordinate
+    pub x: f64,
+    /// Y-coordinate
+    pub y: f64,
+    /// Z-coordinate
+    pub z: f64,
+    /// Object type
+    pub object_type: String,
+    /// Raw custom data payload, if the data file could be read
+    pub raw_custom_data: Option<String>,
+    /// Description of what went wrong
+    pub error: String,
+}
+
+/// A corrupt point that was moved out of the points table into quarantine.
+#[derive(Debug, PartialEq)]
+pub struct QuarantinedPoint {
+    /// The point as it was stored, including its raw payload
+    pub point: CorruptPoint,
+    /// UUID of the region the point belonged to
+    pub region_id: Uuid,
+    /// Unix timestamp (seconds) of when the point was quarantined
+    pub quarantined_at: i64,
+}
+
 /// Manages the connection to the SQLite database and provides methods for data manipulation.
+///
+/// A `Database` can be shared between threads. Statements from different threads are run one
+/// at a time, and a transaction keeps other threads waiting until it's committed or rolled back.
 pub struct Database {
-    conn: Connection,
+    conn: ConnectionLock,
+    /// Custom data payloads larger than this many bytes are stored compressed
+    compression_threshold: Option<usize>,
+    /// Algorithm payloads above the threshold are compressed with
+    compression: Compression,
+    /// Statements executed and data file traffic, for I/O accounting
+    io: IoCounters,
+    /// How point and region UUIDs are stored, read from the database's metadata
+    key_format: Mutex<KeyFormat>,
+    /// Where the data files holding custom data are kept
+    blobs: BlobStore,
 }
 
+/// A page of points read by `get_points_in_region_paged`, with the cursor of the next page
+type PointPage = (Vec<Result<Point, CorruptPoint>>, Option<i64>);
+
+/// Where a database keeps the data files holding custom data.
+enum BlobStore {
+    /// Files under `./data/blobs/<store id>/`
+    Files,
+    /// File contents by data file name, for databases that live in memory
+    Memory(Mutex<HashMap<String, Vec<u8>>>),
+}
+
+/// Columns written for every point, in the order `push_point_row` appends their values
+const POINT_COLUMNS: [&str; 24] = [
+    "id", "x", "y", "z", "dataFile", "region_id", "object_type", "data_encoding", "data_hash", "size_x", "size_y", "size_z", "version",
+    "velocity_x", "velocity_y", "velocity_z", "orientation_x", "orientation_y", "orientation_z", "orientation_w", "extent_x", "extent_y", "extent_z",
+    "updated_at",
+];
+/// Number of points written or removed by one statement in the batch methods
+pub(crate) const POINT_BATCH_SIZE: usize = 256;
+/// How long before a vacuum starts a data file must have been written for the vacuum to delete it,
+/// covering writes on other connections that haven't recorded their file yet and coarse file times
+const VACUUM_GRACE: Duration = Duration::from_secs(1);
+/// Number of prepared statements kept for reuse by each connection
+const STATEMENT_CACHE_CAPACITY: usize = 64;
+/// Encoding of a data file holding plain JSON
+const ENCODING_RAW: &str = "raw";
+/// Encoding of a data file holding zstd-compressed JSON
+const ENCODING_ZSTD: &str = "zstd";
+/// Encoding of a data file holding LZ4-compressed JSON, prefixed with its uncompressed size
+const ENCODING_LZ4: &str = "lz4";
+/// zstd compression level used for data files
+const ZSTD_LEVEL: i32 = 3;
+
 impl Point {
     /// Creates a new Point instance.
     ///
@@ -65,13 +184,33 @@ impl Point {
     /// let point = Point::new(Some(Uuid::new_v4()), 1.0, 2.0, 3.0, "Example Type".to_string(), json!({"name": "Example Point"}));
     /// ```
     pub fn new(id: Option<Uuid>, x: f64, y: f64, z: f64, object_type: String, custom_data: Value) -> Self {
-        Point { id, x, y, z, object_type, custom_data }
+        Point { id, x, y, z, object_type, custom_data, size: [0.0; 3], version: 0, velocity: None, orientation: None }
+    }
+
+    /// Creates a new Point instance, rejecting non-finite coordinates.
+    ///
+    /// # Returns
+    ///
+    /// A new Point instance, or an error naming the first coordinate that is NaN or infinite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let point = Point::try_new(None, 1.0, 2.0, 3.0, "Example Type".to_string(), json!({})).unwrap();
+    /// assert!(Point::try_new(None, f64::NAN, 2.0, 3.0, "Example Type".to_string(), json!({})).is_err());
+    /// ```
+    pub fn try_new(id: Option<Uuid>, x: f64, y: f64, z: f64, object_type: String, custom_data: Value) -> Result<Self, CoordinateError> {
+        validate_point([x, y, z])?;
+        Ok(Point::new(id, x, y, z, object_type, custom_data))
     }
 }
 
 impl Database {
     /// Creates a new Database instance.
     ///
+    /// The database is switched to write-ahead logging, so while it's open SQLite keeps `-wal`
+    /// and `-shm` files next to it. Copy all three, or close the database first, to back it up.
+    ///
     /// # Arguments
     ///
     /// * `db_path` - Path to the SQLite database file.
@@ -88,7 +227,203 @@ impl Database {
     pub fn new(db_path: &str) -> SqlResult<Self> {
         // Open a connection to the SQLite database
         let conn = Connection::open(db_path)?;
-        Ok(Database { conn })
+        // Write-ahead logging lets readers run alongside a writer and only syncs at checkpoints,
+        // instead of on every commit. In-memory databases keep their own journal mode.
+        conn.query_row("PRAGMA journal_mode = WAL", [], |row| row.get::<_, String>(0))?;
+        conn.execute_batch("PRAGMA synchronous = NORMAL")?;
+        Ok(Database::with_connection(conn, BlobStore::Files))
+    }
+
+    /// Creates a new Database instance that lives entirely in memory.
+    ///
+    /// Neither a database file nor data files are written, which suits tests and short-lived
+    /// worlds such as instanced maps. Everything stored is lost when the database is dropped.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing a new Database instance or a SQLite error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let db = Database::in_memory().expect("Failed to create database");
+    /// assert_eq!(db.path(), Some(String::new()));
+    /// ```
+    pub fn in_memory() -> SqlResult<Self> {
+        let conn = Connection::open_in_memory()?;
+        Ok(Database::with_connection(conn, BlobStore::Memory(Mutex::new(HashMap::new()))))
+    }
+
+    /// Wraps an open connection, keeping data files in `blobs`.
+    fn with_connection(conn: Connection, blobs: BlobStore) -> Self {
+        // Writing points runs a handful of statements each, which are prepared once and reused
+        conn.set_prepared_statement_cache_capacity(STATEMENT_CACHE_CAPACITY);
+        Database { conn: ConnectionLock::new(conn), compression_threshold: None, compression: Compression::Zstd, io: IoCounters::default(), key_format: Mutex::new(KeyFormat::Text), blobs }
+    }
+
+    /// Locks the connection for executing a statement, counting the statement.
+    fn conn(&self) -> ConnectionGuard<'_> {
+        self.io.statement();
+        self.conn.lock()
+    }
+
+    /// Encodes a UUID as a key in the database's key format.
+    fn key(&self, id: Uuid) -> SqlValue {
+        encode_key(*self.key_format.lock().unwrap(), id)
+    }
+
+    /// Encodes a point ID as reported by `CorruptPoint::id` back into the key it was read from.
+    fn stored_key(&self, id: &str) -> SqlValue {
+        match Uuid::parse_str(id) {
+            Ok(uuid) => self.key(uuid),
+            // Keys that aren't valid UUIDs can only have been stored as text
+            Err(_) => SqlValue::Text(id.to_string()),
+        }
+    }
+
+    /// Returns the features this backend supports natively.
+    ///
+    /// SQLite supports transactions, reads rows one at a time and writes points in multi-row
+    /// batches, but points are found by scanning their coordinates and custom data lives in data
+    /// files outside the database.
+    pub fn capabilities(&self) -> BackendCapabilities {
+        BackendCapabilities {
+            transactions: true,
+            spatial_index: false,
+            streaming: true,
+            batch_writes: true,
+            json_queries: false,
+        }
+    }
+
+    /// Returns the path of the database file, or `None` for an in-memory database.
+    pub fn path(&self) -> Option<String> {
+        self.conn.lock().path().map(str::to_string)
+    }
+
+    /// Returns how point and region UUIDs are stored in this database.
+    pub fn key_format(&self) -> KeyFormat {
+        *self.key_format.lock().unwrap()
+    }
+
+    /// Converts every point and region key to another format.
+    ///
+    /// The conversion runs in a single transaction. Keys that aren't valid UUIDs are left as
+    /// they are, so the lenient loader can still quarantine them.
+    ///
+    /// # Arguments
+    ///
+    /// * `format` - The key format to convert to.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the number of rows rewritten, or an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let migrated = db.migrate_key_format(KeyFormat::Binary).expect("Failed to migrate keys");
+    /// println!("Rewrote {} rows", migrated);
+    /// ```
+    pub fn migrate_key_format(&self, format: KeyFormat) -> SqlResult<usize> {
+        if *self.key_format.lock().unwrap() == format {
+            return Ok(0);
+        }
+
+        self.begin_transaction()?;
+        let migrated = (|| {
+            let mut migrated = 0;
+            // Each table's ID column, and the column referencing another key if it has one
+            let tables = [("points", Some("region_id")), ("regions", None), ("templates", None), ("expiries", None), ("attachments", Some("parent_id"))];
+            for (table, reference) in tables {
+                let conn = self.conn();
+                let mut stmt = conn.prepare(&format!("SELECT rowid, id, {} FROM {}", reference.unwrap_or("NULL"), table))?;
+                let rows = stmt.query_map([], |row| {
+                    let convert = |idx: usize| -> SqlResult<Option<SqlValue>> {
+                        Ok(decode_key(row.get_ref(idx)?).map(|uuid| encode_key(format, uuid)))
+                    };
+                    Ok((row.get::<_, i64>(0)?, convert(1)?, convert(2)?))
+                })?
+                .collect::<SqlResult<Vec<_>>>()?;
+
+                for (rowid, id, referenced) in rows {
+                    if let Some(id) = id {
+                        self.conn().execute(&format!("UPDATE {} SET id = ?1 WHERE rowid = ?2", table), params![id, rowid])?;
+                        migrated += 1;
+                    }
+                    if let (Some(reference), Some(referenced)) = (reference, referenced) {
+                        self.conn().execute(&format!("UPDATE {} SET {} = ?1 WHERE rowid = ?2", table, reference), params![referenced, rowid])?;
+                    }
+                }
+            }
+            self.conn().execute(
+                "UPDATE meta SET value = ?1 WHERE key = 'key_format'",
+                params![format.as_str()],
+            )?;
+            Ok(migrated)
+        })();
+
+        match migrated {
+            Ok(migrated) => {
+                self.commit_transaction()?;
+                *self.key_format.lock().unwrap() = format;
+                Ok(migrated)
+            }
+            Err(err) => {
+                let _ = self.rollback_transaction();
+                Err(err)
+            }
+        }
+    }
+
+    /// Returns the I/O this database has performed since it was opened.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let stats = db.io_stats();
+    /// println!("{} statements, {} bytes written", stats.statements, stats.bytes_written);
+    /// ```
+    pub fn io_stats(&self) -> IoStats {
+        self.io.snapshot()
+    }
+
+    /// Sets the size above which custom data payloads are stored compressed.
+    ///
+    /// Payloads at or below the threshold are stored as plain JSON, so small objects don't pay
+    /// the compression cost. Each point records how its data file is encoded, so changing the
+    /// threshold never affects reading existing data.
+    ///
+    /// # Arguments
+    ///
+    /// * `threshold` - Size in bytes of the serialized custom data above which it is compressed, or `None` to disable compression.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut db = Database::new("path/to/database.sqlite").expect("Failed to create database");
+    /// db.set_compression_threshold(Some(4096));
+    /// ```
+    pub fn set_compression_threshold(&mut self, threshold: Option<usize>) {
+        self.compression_threshold = threshold;
+    }
+
+    /// Sets the algorithm custom data payloads above the compression threshold are compressed with.
+    ///
+    /// Like the threshold, the algorithm only applies to data written from now on.
+    ///
+    /// # Arguments
+    ///
+    /// * `compression` - The algorithm to compress with.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// db.set_compression_threshold(Some(4096));
+    /// db.set_compression(Compression::Lz4);
+    /// ```
+    pub fn set_compression(&mut self, compression: Compression) {
+        self.compression = compression;
     }
 
     /// Creates the necessary tables in the database if they don't exist.
@@ -104,7 +439,7 @@ impl Database {
     /// ```
     pub fn create_table(&self) -> SqlResult<()> {
         // Create points table
-        self.conn.execute(
+        self.conn().execute(
             "CREATE TABLE IF NOT EXISTS points (
                 id TEXT PRIMARY KEY,
                 x REAL NOT NULL,
@@ -112,12 +447,87 @@ impl Database {
                 z REAL NOT NULL,
                 dataFile TEXT NOT NULL,
                 region_id TEXT,
-                object_type TEXT NOT NULL
+                object_type TEXT NOT NULL,
+                data_encoding TEXT NOT NULL DEFAULT 'raw',
+                data_hash TEXT,
+                size_x REAL NOT NULL DEFAULT 0,
+                size_y REAL NOT NULL DEFAULT 0,
+                size_z REAL NOT NULL DEFAULT 0
+            )",
+            [],
+        )?;
+        // Databases created before data files could be compressed lack the encoding column
+        self.ensure_column("points", "data_encoding", "TEXT NOT NULL DEFAULT 'raw'")?;
+        // Points written before deduplication have no hash and own their data file
+        self.ensure_column("points", "data_hash", "TEXT")?;
+        // Points written before objects had a size are treated as dimensionless
+        for column in ["size_x", "size_y", "size_z"] {
+            self.ensure_column("points", column, "REAL NOT NULL DEFAULT 0")?;
+        }
+        // Points written before versioning start at version 0
+        self.ensure_column("points", "version", "INTEGER NOT NULL DEFAULT 0")?;
+        // Points without a velocity only move when they're moved explicitly
+        for column in ["velocity_x", "velocity_y", "velocity_z"] {
+            self.ensure_column("points", column, "REAL")?;
+        }
+        // Points without an orientation are aligned with the world axes, and their extent is their size
+        for column in ["orientation_x", "orientation_y", "orientation_z", "orientation_w", "extent_x", "extent_y", "extent_z"] {
+            self.ensure_column("points", column, "REAL")?;
+        }
+        // Regions are loaded a page at a time, walking their points in row order
+        self.conn().execute("CREATE INDEX IF NOT EXISTS points_region ON points (region_id)", [])?;
+        // Box queries narrow their search by the x coordinate, which a point's extent always contains
+        self.conn().execute("CREATE INDEX IF NOT EXISTS points_x ON points (x)", [])?;
+        // Soft-deleted points keep their row until purged; NULL means the point is live
+        self.ensure_column("points", "deleted_at", "INTEGER")?;
+        // Time a point was last written, in milliseconds since the epoch; points written before it was tracked are never archived
+        self.ensure_column("points", "updated_at", "INTEGER")?;
+        // Create meta table holding the database's store id
+        self.conn().execute(
+            "CREATE TABLE IF NOT EXISTS meta (
+                key TEXT PRIMARY KEY,
+                value TEXT NOT NULL
+            )",
+            [],
+        )?;
+        self.conn().execute(
+            "INSERT OR IGNORE INTO meta (key, value) VALUES ('store_id', ?1)",
+            params![Uuid::new_v4().to_string()],
+        )?;
+        // Databases without a recorded key format predate binary keys
+        self.conn().execute(
+            "INSERT OR IGNORE INTO meta (key, value) VALUES ('key_format', ?1)",
+            params![KeyFormat::Text.as_str()],
+        )?;
+        let key_format: String = self.conn().query_row("SELECT value FROM meta WHERE key = 'key_format'", [], |row| row.get(0))?;
+        *self.key_format.lock().unwrap() = if key_format == KeyFormat::Binary.as_str() { KeyFormat::Binary } else { KeyFormat::Text };
+        // Create blobs table for deduplicated custom data files
+        self.conn().execute(
+            "CREATE TABLE IF NOT EXISTS blobs (
+                hash TEXT PRIMARY KEY,
+                dataFile TEXT NOT NULL,
+                data_encoding TEXT NOT NULL,
+                ref_count INTEGER NOT NULL
+            )",
+            [],
+        )?;
+        // Create quarantine table for points that couldn't be decoded
+        self.conn().execute(
+            "CREATE TABLE IF NOT EXISTS quarantine (
+                id TEXT PRIMARY KEY,
+                region_id TEXT NOT NULL,
+                x REAL NOT NULL,
+                y REAL NOT NULL,
+                z REAL NOT NULL,
+                object_type TEXT NOT NULL,
+                raw_data TEXT,
+                error TEXT NOT NULL,
+                quarantined_at INTEGER NOT NULL
             )",
             [],
         )?;
         // Create regions table
-        self.conn.execute(
+        self.conn().execute(
             "CREATE TABLE IF NOT EXISTS regions (
                 id TEXT PRIMARY KEY,
                 center_x REAL NOT NULL,
@@ -127,6 +537,83 @@ impl Database {
             )",
             [],
         )?;
+        // Create templates table holding reusable object sets as JSON
+        self.conn().execute(
+            "CREATE TABLE IF NOT EXISTS templates (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                objects TEXT NOT NULL
+            )",
+            [],
+        )?;
+        // Create expiries table holding when objects with a TTL are removed, in milliseconds since the epoch
+        self.conn().execute(
+            "CREATE TABLE IF NOT EXISTS expiries (
+                id TEXT PRIMARY KEY,
+                expires_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+        // Create attachments table holding the point each attached point follows
+        self.conn().execute(
+            "CREATE TABLE IF NOT EXISTS attachments (
+                id TEXT PRIMARY KEY,
+                parent_id TEXT NOT NULL
+            )",
+            [],
+        )?;
+        // Create snapshots table holding named copies of the world as compressed JSON archives
+        self.conn().execute(
+            "CREATE TABLE IF NOT EXISTS snapshots (
+                name TEXT PRIMARY KEY,
+                created_at INTEGER NOT NULL,
+                region_count INTEGER NOT NULL,
+                object_count INTEGER NOT NULL,
+                archive BLOB NOT NULL
+            )",
+            [],
+        )?;
+        // Create journal table holding changes made in memory that haven't been persisted yet, as JSON points
+        self.conn().execute(
+            "CREATE TABLE IF NOT EXISTS journal (
+                id TEXT PRIMARY KEY,
+                point TEXT NOT NULL
+            )",
+            [],
+        )?;
+        // Create archived table recording the archive chunk holding each point moved to cold storage
+        self.conn().execute(
+            "CREATE TABLE IF NOT EXISTS archived (
+                id TEXT PRIMARY KEY,
+                region_id TEXT NOT NULL,
+                chunk TEXT NOT NULL
+            )",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// Adds a column to an existing table if it isn't there yet.
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - Name of the table to migrate.
+    /// * `column` - Name of the column to add.
+    /// * `definition` - SQL type and constraints of the column.
+    ///
+    /// # Returns
+    ///
+    /// A Result indicating success or a SQLite error.
+    fn ensure_column(&self, table: &str, column: &str, definition: &str) -> SqlResult<()> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table))?;
+        let exists = stmt.query_map([], |row| row.get::<_, String>(1))?
+            .collect::<SqlResult<Vec<String>>>()?
+            .iter()
+            .any(|name| name == column);
+        if !exists {
+            self.conn().execute(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition), [])?;
+        }
         Ok(())
     }
 
@@ -149,27 +636,173 @@ impl Database {
     /// db.add_point(&point, region_id).expect("Failed to add point");
     /// ```
     pub fn add_point(&self, point: &Point, region_id: Uuid) -> SqlResult<()> {
-        let id = point.id.unwrap_or_else(Uuid::new_v4).to_string();
-        let custom_data_str = serde_json::to_string(&point.custom_data)
-            .map_err(|err| rusqlite::Error::ToSqlConversionFailure(Box::new(err)))?;
+        let id = point.id.unwrap_or_else(Uuid::new_v4);
 
-        let folder_name: String = id.chars().take(2).collect();
-        let file_path: String = format!("./data/{}/{}", folder_name, id);
+        // Replacing a point with unchanged data keeps its existing reference
+        let previous_hash = self.point_data_hash(&self.key(id))?;
+        let mut values = Vec::with_capacity(POINT_COLUMNS.len());
+        let hash = self.push_point_row(&mut values, id, point, region_id, previous_hash.as_deref())?;
+        self.conn().prepare_cached(&insert_points_sql("INSERT OR REPLACE", 1))?
+            .execute(params_from_iter(values))?;
 
-        fs::create_dir_all(format!("./data/{}", folder_name))
-            .map_err(|err| rusqlite::Error::ToSqlConversionFailure(Box::new(err)))?;
+        if let Some(previous_hash) = previous_hash.filter(|previous| *previous != hash) {
+            self.release_blob(&previous_hash)?;
+        }
+        
+        Ok(())
+    }
 
-        fs::write(&file_path, &custom_data_str)
-            .map_err(|err| rusqlite::Error::ToSqlConversionFailure(Box::new(err)))?;
+    /// Replaces every stored field of an existing point, including its custom data.
+    ///
+    /// Unlike `add_point`, the point must already be stored, and its row is updated in place,
+    /// so state kept outside the point, such as whether it's soft-deleted, is preserved.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - The point's new state. Its `id` names the point to update.
+    /// * `region_id` - UUID of the region the point belongs to.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing `true` if the point was updated, `false` if no point has its ID, or an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// point.size = [2.0, 4.0, 2.0];
+    /// point.custom_data = json!({"name": "Grown Tree"});
+    /// db.update_point(&point, region_id).expect("Failed to update point");
+    /// ```
+    pub fn update_point(&self, point: &Point, region_id: Uuid) -> SqlResult<bool> {
+        let id = point.id.ok_or_else(|| constraint_error("Updated points need an id".to_string()))?;
+        let Some(previous_hash) = self.point_data_hashes(&[id])?.remove(&id) else {
+            return Ok(false);
+        };
 
-        self.conn.execute(
-            "INSERT OR REPLACE INTO points (id, x, y, z, dataFile, region_id, object_type) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-            params![id, point.x, point.y, point.z, &file_path, region_id.to_string(), &point.object_type],
-        )?;
-        
+        let mut values = Vec::with_capacity(POINT_COLUMNS.len());
+        let hash = self.push_point_row(&mut values, id, point, region_id, previous_hash.as_deref())?;
+        let assignments: Vec<String> = POINT_COLUMNS[1..].iter().enumerate()
+            .map(|(index, column)| format!("{} = ?{}", column, index + 2))
+            .collect();
+        self.conn().prepare_cached(&format!("UPDATE points SET {} WHERE id = ?1", assignments.join(", ")))?
+            .execute(params_from_iter(values))?;
+
+        if let Some(previous_hash) = previous_hash.filter(|previous| *previous != hash) {
+            self.release_blob(&previous_hash)?;
+        }
+        Ok(true)
+    }
+
+    /// Inserts several new points, writing up to `POINT_BATCH_SIZE` of them per statement.
+    ///
+    /// This is the bulk form of `add_point` for points that aren't stored yet. Run it inside a
+    /// transaction to write all of the points or none of them.
+    ///
+    /// # Arguments
+    ///
+    /// * `points` - The points to add, each with the UUID of its region.
+    ///
+    /// # Returns
+    ///
+    /// A Result indicating success, or an error if a point is already stored or appears twice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let points = vec![(Point::new(Some(Uuid::new_v4()), 1.0, 2.0, 3.0, "resource".to_string(), json!({})), region_id)];
+    /// db.add_points(&points).expect("Failed to add points");
+    /// ```
+    pub fn add_points(&self, points: &[(Point, Uuid)]) -> SqlResult<()> {
+        self.write_point_batches(points, "INSERT")
+    }
+
+    /// Inserts or replaces several points, writing up to `POINT_BATCH_SIZE` of them per statement.
+    ///
+    /// This is the bulk form of `add_point`. Run it inside a transaction to write all of the
+    /// points or none of them.
+    ///
+    /// # Arguments
+    ///
+    /// * `points` - The points to write, each with the UUID of its region.
+    ///
+    /// # Returns
+    ///
+    /// A Result indicating success, or an error if a point appears twice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// db.upsert_points(&changed_points).expect("Failed to write points");
+    /// ```
+    pub fn upsert_points(&self, points: &[(Point, Uuid)]) -> SqlResult<()> {
+        self.write_point_batches(points, "INSERT OR REPLACE")
+    }
+
+    /// Writes points with multi-row `verb` statements, keeping the custom data references counted.
+    fn write_point_batches(&self, points: &[(Point, Uuid)], verb: &str) -> SqlResult<()> {
+        let mut seen = HashSet::with_capacity(points.len());
+        let ids: Vec<Uuid> = points.iter().map(|(point, _)| point.id.unwrap_or_else(Uuid::new_v4)).collect();
+        if let Some(duplicate) = ids.iter().find(|id| !seen.insert(**id)) {
+            return Err(constraint_error(format!("Point {} appears more than once in the batch", duplicate)));
+        }
+
+        for (chunk, chunk_ids) in points.chunks(POINT_BATCH_SIZE).zip(ids.chunks(POINT_BATCH_SIZE)) {
+            let mut previous_hashes = self.point_data_hashes(chunk_ids)?;
+            if verb == "INSERT" {
+                if let Some(existing) = chunk_ids.iter().find(|id| previous_hashes.contains_key(id)) {
+                    return Err(constraint_error(format!("Point {} already exists", existing)));
+                }
+            }
+
+            let mut values = Vec::with_capacity(chunk.len() * POINT_COLUMNS.len());
+            let mut replaced = Vec::new();
+            for ((point, region_id), id) in chunk.iter().zip(chunk_ids) {
+                let previous_hash = previous_hashes.remove(id).flatten();
+                let hash = self.push_point_row(&mut values, *id, point, *region_id, previous_hash.as_deref())?;
+                replaced.extend(previous_hash.filter(|previous| *previous != hash));
+            }
+            self.conn().prepare_cached(&insert_points_sql(verb, chunk.len()))?
+                .execute(params_from_iter(values))?;
+
+            for previous_hash in replaced {
+                self.release_blob(&previous_hash)?;
+            }
+        }
         Ok(())
     }
 
+    /// Stores a point's custom data and appends the values of its row, in `POINT_COLUMNS` order, to `values`.
+    ///
+    /// # Returns
+    ///
+    /// The hash of the point's custom data.
+    fn push_point_row(&self, values: &mut Vec<SqlValue>, id: Uuid, point: &Point, region_id: Uuid, previous_hash: Option<&str>) -> SqlResult<String> {
+        let custom_data_str = serde_json::to_string(&point.custom_data)
+            .map_err(|err| rusqlite::Error::ToSqlConversionFailure(Box::new(err)))?;
+        let hash = blake3::hash(custom_data_str.as_bytes()).to_hex().to_string();
+        let (file_path, encoding) = if previous_hash == Some(hash.as_str()) {
+            self.blob_location(&hash)?
+        } else {
+            self.retain_blob(&hash, custom_data_str)?
+        };
+
+        // Box queries run in SQL, so rotated points store the extent of the box enclosing them
+        let extent = point.orientation.map(|orientation| enclosing_size(point.size, orientation));
+        let optional = |value: Option<f64>| value.map_or(SqlValue::Null, SqlValue::Real);
+        values.extend([
+            self.key(id), SqlValue::Real(point.x), SqlValue::Real(point.y), SqlValue::Real(point.z),
+            SqlValue::Text(file_path), self.key(region_id), SqlValue::Text(point.object_type.clone()),
+            SqlValue::Text(encoding), SqlValue::Text(hash.clone()),
+            SqlValue::Real(point.size[0]), SqlValue::Real(point.size[1]), SqlValue::Real(point.size[2]),
+            SqlValue::Integer(point.version as i64),
+        ]);
+        values.extend((0..3).map(|axis| optional(point.velocity.map(|velocity| velocity[axis]))));
+        values.extend((0..4).map(|axis| optional(point.orientation.map(|orientation| orientation[axis]))));
+        values.extend((0..3).map(|axis| optional(extent.map(|extent| extent[axis]))));
+        values.push(SqlValue::Integer(now_millis()));
+        Ok(hash)
+    }
+
     /// Retrieves points within a specified radius from a given center point.
     ///
     /// # Arguments
@@ -193,31 +826,41 @@ impl Database {
     /// ```
     pub fn get_points_within_radius(&self, x1: f64, y1: f64, z1: f64, radius: f64) -> SqlResult<Vec<Point>> {
         let radius_sq = radius * radius;
-        let mut stmt = self.conn.prepare(
-            "SELECT id, x, y, z, dataFile, object_type FROM points
-             WHERE ((x - ?1) * (x - ?1) + (y - ?2) * (y - ?2) + (z - ?3) * (z - ?3)) <= ?4",
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT id, x, y, z, dataFile, object_type, data_encoding, size_x, size_y, size_z, version, velocity_x, velocity_y, velocity_z, orientation_x, orientation_y, orientation_z, orientation_w FROM points
+             WHERE deleted_at IS NULL AND ((x - ?1) * (x - ?1) + (y - ?2) * (y - ?2) + (z - ?3) * (z - ?3)) <= ?4",
         )?;
         
         let points_iter = stmt.query_map(params![x1, y1, z1, radius_sq], |row| {
-            let id: String = row.get(0)?;
+            let id = read_key(row, 0)?;
             let x: f64 = row.get(1)?;
             let y: f64 = row.get(2)?;
             let z: f64 = row.get(3)?;
             let data_file: String = row.get(4)?;
             let object_type: String = row.get(5)?;
+            let data_encoding: String = row.get(6)?;
+            let size: [f64; 3] = [row.get(7)?, row.get(8)?, row.get(9)?];
+            let version: i64 = row.get(10)?;
+            let velocity = read_velocity(row, 11)?;
+            let orientation = read_orientation(row, 14)?;
             
-            let custom_data_str = fs::read_to_string(&data_file)
+            let custom_data_str = self.read_custom_data(&data_file, &data_encoding)
                 .map_err(|err| rusqlite::Error::ToSqlConversionFailure(Box::new(err)))?;
             let custom_data: Value = serde_json::from_str(&custom_data_str)
                 .map_err(|err| rusqlite::Error::ToSqlConversionFailure(Box::new(err)))?;
             
             Ok(Point {
-                id: Some(Uuid::parse_str(&id).unwrap()),
+                id: Some(id),
                 x,
                 y,
                 z,
                 object_type,
                 custom_data,
+                size,
+                version: version as u64,
+                velocity,
+                orientation,
             })
         })?;
         
@@ -229,68 +872,120 @@ impl Database {
         Ok(points)
     }
 
-    /// Creates a new region in the database.
+    /// Retrieves all points whose extent lies within an axis-aligned box, along with their regions.
+    ///
+    /// A point's extent is its size centered on its position, matching how the in-memory
+    /// R-trees treat sized objects. The search is narrowed through the index on the x
+    /// coordinate, so a small box doesn't scan every stored point.
     ///
     /// # Arguments
     ///
-    /// * `region_id` - UUID of the region to create.
-    /// * `center` - Center coordinates of the region.
-    /// * `radius` - Radius of the region.
+    /// * `min` - Minimum corner of the box [x, y, z].
+    /// * `max` - Maximum corner of the box [x, y, z].
     ///
     /// # Returns
     ///
-    /// A Result indicating success or an error.
+    /// A Result containing `(region_id, point)` pairs, or an error.
     ///
     /// # Examples
     ///
     /// ```
-    /// let region_id = Uuid::new_v4();
-    /// let center = [0.0, 0.0, 0.0];
-    /// let radius = 100.0;
-    /// db.create_region(region_id, center, radius).expect("Failed to create region");
+    /// let points = db.get_points_in_aabb([0.0, 0.0, 0.0], [10.0, 10.0, 10.0]).expect("Failed to get points");
+    /// for (region_id, point) in points {
+    ///     println!("Found point {:?} in region {}", point.id, region_id);
+    /// }
     /// ```
-    pub fn create_region(&self, region_id: Uuid, center: [f64; 3], radius: f64) -> SqlResult<()> {
-        // Insert the region into the database
-        self.conn.execute(
-            "INSERT OR REPLACE INTO regions (id, center_x, center_y, center_z, radius) VALUES (?1, ?2, ?3, ?4, ?5)",
-            params![region_id.to_string(), center[0], center[1], center[2], radius],
+    pub fn get_points_in_aabb(&self, min: [f64; 3], max: [f64; 3]) -> SqlResult<Vec<(Uuid, Point)>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare_cached(
+            "SELECT id, x, y, z, dataFile, object_type, data_encoding, size_x, size_y, size_z, version, region_id, velocity_x, velocity_y, velocity_z, orientation_x, orientation_y, orientation_z, orientation_w FROM points
+             WHERE deleted_at IS NULL AND x BETWEEN ?1 AND ?4
+               AND x - COALESCE(extent_x, size_x) / 2 >= ?1 AND x + COALESCE(extent_x, size_x) / 2 <= ?4
+               AND y - COALESCE(extent_y, size_y) / 2 >= ?2 AND y + COALESCE(extent_y, size_y) / 2 <= ?5
+               AND z - COALESCE(extent_z, size_z) / 2 >= ?3 AND z + COALESCE(extent_z, size_z) / 2 <= ?6",
         )?;
-        Ok(())
-    }
 
-    /// Removes a point from the database.
-    ///
-    /// # Arguments
-    ///
-    /// * `point_id` - UUID of the point to remove.
-    ///
-    /// # Returns
-    ///
-    /// A Result indicating success or an error.
-    ///
-    /// # Examples
-    ///
+        let points_iter = stmt.query_map(params![min[0], min[1], min[2], max[0], max[1], max[2]], |row| {
+            let id = read_key(row, 0)?;
+            let x: f64 = row.get(1)?;
+            let y: f64 = row.get(2)?;
+            let z: f64 = row.get(3)?;
+            let data_file: String = row.get(4)?;
+            let object_type: String = row.get(5)?;
+            let data_encoding: String = row.get(6)?;
+            let size: [f64; 3] = [row.get(7)?, row.get(8)?, row.get(9)?];
+            let version: i64 = row.get(10)?;
+            let velocity = read_velocity(row, 12)?;
+            let orientation = read_orientation(row, 15)?;
+            let region_id = read_key(row, 11)?;
+
+            let custom_data_str = self.read_custom_data(&data_file, &data_encoding)
+                .map_err(|err| rusqlite::Error::ToSqlConversionFailure(Box::new(err)))?;
+            let custom_data: Value = serde_json::from_str(&custom_data_str)
+                .map_err(|err| rusqlite::Error::ToSqlConversionFailure(Box::new(err)))?;
+
+            Ok((region_id, Point {
+                id: Some(id),
+                x,
+                y,
+                z,
+                object_type,
+                custom_data,
+                size,
+                version: version as u64,
+                velocity,
+                orientation,
+            }))
+        })?;
+
+        let mut points = Vec::new();
+        for point in points_iter {
+            points.push(point?);
+        }
+
+        Ok(points)
+    }
+
+    /// Counts the points of a region whose extent lies within an axis-aligned box.
+    ///
+    /// This matches `get_points_in_aabb` restricted to one region, but never reads custom data.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_id` - UUID of the region to count points in.
+    /// * `min` - Minimum corner of the box [x, y, z].
+    /// * `max` - Maximum corner of the box [x, y, z].
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the number of matching points, or an error.
+    ///
+    /// # Examples
+    ///
     /// ```
-    /// let point_id = Uuid::new_v4();
-    /// db.remove_point(point_id).expect("Failed to remove point");
+    /// let count = db.count_points(region_id, [0.0, 0.0, 0.0], [10.0, 10.0, 10.0]).expect("Failed to count points");
+    /// println!("{} points in the box", count);
     /// ```
-    pub fn remove_point(&self, point_id: Uuid) -> SqlResult<()> {
-        // Delete the point from the database
-        self.conn.execute(
-            "DELETE FROM points WHERE id = ?1",
-            params![point_id.to_string()],
+    pub fn count_points(&self, region_id: Uuid, min: [f64; 3], max: [f64; 3]) -> SqlResult<usize> {
+        let count: i64 = self.conn().query_row(
+            "SELECT COUNT(*) FROM points
+             WHERE region_id = ?1 AND deleted_at IS NULL AND x BETWEEN ?2 AND ?5
+               AND x - COALESCE(extent_x, size_x) / 2 >= ?2 AND x + COALESCE(extent_x, size_x) / 2 <= ?5
+               AND y - COALESCE(extent_y, size_y) / 2 >= ?3 AND y + COALESCE(extent_y, size_y) / 2 <= ?6
+               AND z - COALESCE(extent_z, size_z) / 2 >= ?4 AND z + COALESCE(extent_z, size_z) / 2 <= ?7",
+            params![self.key(region_id), min[0], min[1], min[2], max[0], max[1], max[2]],
+            |row| row.get(0),
         )?;
-        Ok(())
+        Ok(count as usize)
     }
 
-    /// Updates the position of a point in the database.
+    /// Creates a new region in the database.
     ///
     /// # Arguments
     ///
-    /// * `point_id` - UUID of the point to update.
-    /// * `x` - New X-coordinate of the point.
-    /// * `y` - New Y-coordinate of the point.
-    /// * `z` - New Z-coordinate of the point.
+    /// * `region_id` - UUID of the region to create.
+    /// * `center` - Center coordinates of the region.
+    /// * `radius` - Radius of the region.
     ///
     /// # Returns
     ///
@@ -299,125 +994,1923 @@ impl Database {
     /// # Examples
     ///
     /// ```
-    /// let point_id = Uuid::new_v4();
-    /// db.update_point_position(point_id, 4.0, 5.0, 6.0).expect("Failed to update point position");
+    /// let region_id = Uuid::new_v4();
+    /// let center = [0.0, 0.0, 0.0];
+    /// let radius = 100.0;
+    /// db.create_region(region_id, center, radius).expect("Failed to create region");
     /// ```
-    pub fn update_point_position(&self, point_id: Uuid, x: f64, y: f64, z: f64) -> SqlResult<()> {
-        // Update the point's position in the database
-        self.conn.execute(
-            "UPDATE points SET x = ?1, y = ?2, z = ?3 WHERE id = ?4",
-            params![x, y, z, point_id.to_string()],
+    pub fn create_region(&self, region_id: Uuid, center: [f64; 3], radius: f64) -> SqlResult<()> {
+        // Insert the region into the database
+        self.conn().execute(
+            "INSERT OR REPLACE INTO regions (id, center_x, center_y, center_z, radius) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![self.key(region_id), center[0], center[1], center[2], radius],
         )?;
         Ok(())
     }
 
-    /// Retrieves all regions from the database.
+    /// Removes a region record from the database.
+    ///
+    /// Points assigned to the region are left in place; reassign or remove them first.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_id` - UUID of the region to remove.
     ///
     /// # Returns
     ///
-    /// A Result containing a vector of regions or an error.
+    /// A Result indicating success or an error.
     ///
     /// # Examples
     ///
     /// ```
-    /// let regions = db.get_all_regions().expect("Failed to get regions");
-    /// for region in regions {
-    ///     println!("Region: {:?}", region);
-    /// }
+    /// db.remove_region(region_id).expect("Failed to remove region");
     /// ```
-    pub fn get_all_regions(&self) -> SqlResult<Vec<Region>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT id, center_x, center_y, center_z, radius FROM regions",
+    pub fn remove_region(&self, region_id: Uuid) -> SqlResult<()> {
+        self.conn().execute(
+            "DELETE FROM regions WHERE id = ?1",
+            params![self.key(region_id)],
         )?;
-        
-        let regions_iter = stmt.query_map([], |row| {
-            let id: String = row.get(0)?;
-            let center_x: f64 = row.get(1)?;
-            let center_y: f64 = row.get(2)?;
-            let center_z: f64 = row.get(3)?;
-            let radius: f64 = row.get(4)?;
-            
-            Ok(Region {
-                id: Uuid::parse_str(&id).unwrap(),
-                center: [center_x, center_y, center_z],
-                radius,
-            })
-        })?;
-        
-        let mut regions = Vec::new();
-        for region in regions_iter {
-            let region = region?;
-            println!("Retrieved region: ID: {}, Center: {:?}, Radius: {}", region.id, region.center, region.radius);
-            regions.push(region);
+        Ok(())
+    }
+
+    /// Deletes a region, optionally together with its points.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_id` - UUID of the region to delete.
+    /// * `cascade` - Also delete the region's points. Without it, deleting a region that still has points fails.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the number of points deleted, or an error. A constraint error is
+    /// returned if the region has points and `cascade` is false.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let deleted = db.delete_region(region_id, true).expect("Failed to delete region");
+    /// println!("Deleted {} points", deleted);
+    /// ```
+    pub fn delete_region(&self, region_id: Uuid, cascade: bool) -> SqlResult<usize> {
+        if !cascade {
+            let points: usize = self.conn().query_row(
+                "SELECT COUNT(*) FROM points WHERE region_id = ?1 AND deleted_at IS NULL",
+                params![self.key(region_id)],
+                |row| row.get(0),
+            )?;
+            if points > 0 {
+                return Err(rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_CONSTRAINT),
+                    Some(format!("Region {} still has {} points", region_id, points)),
+                ));
+            }
         }
-        
-        println!("Total regions retrieved from database: {}", regions.len());
-        Ok(regions)
+
+        let deleted = self.clear_points_in_region(region_id)?;
+        self.conn().execute("DELETE FROM archived WHERE region_id = ?1", params![self.key(region_id)])?;
+        self.conn().execute("DELETE FROM regions WHERE id = ?1", params![self.key(region_id)])?;
+        Ok(deleted)
     }
 
-    /// Retrieves all points within a specified region from the database.
+    /// Deletes every point of a region, keeping the region itself.
+    ///
+    /// Soft-deleted points are deleted too. Everything is removed with a fixed number of
+    /// statements, however many points the region holds.
     ///
     /// # Arguments
     ///
-    /// * `region_id` - UUID of the region to query.
+    /// * `region_id` - UUID of the region to clear.
     ///
     /// # Returns
     ///
-    /// A Result containing a vector of points or an error.
+    /// A Result containing the number of points deleted, or an error.
     ///
     /// # Examples
     ///
     /// ```
-    /// let region_id = Uuid::new_v4();
-    /// let points = db.get_points_in_region(region_id).expect("Failed to get points in region");
-    /// for point in points {
-    ///     println!("Point in region: {:?}", point);
-    /// }
+    /// db.clear_points_in_region(region_id).expect("Failed to clear region");
     /// ```
-    pub fn get_points_in_region(&self, region_id: Uuid) -> SqlResult<Vec<Point>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT id, x, y, z, dataFile, object_type FROM points WHERE region_id = ?1",
+    pub fn clear_points_in_region(&self, region_id: Uuid) -> SqlResult<usize> {
+        self.remove_points_where("region_id = ?1", params![self.key(region_id)])
+    }
+
+    /// Removes every live point of a region, keeping the region itself.
+    ///
+    /// Unlike `clear_points_in_region`, soft-deleted points are kept so they can still be
+    /// restored. The points are removed with a single `DELETE` statement rather than one per point.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_id` - UUID of the region to remove points from.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the number of points removed, or an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let removed = db.remove_points_in_region(region_id).expect("Failed to remove points");
+    /// ```
+    pub fn remove_points_in_region(&self, region_id: Uuid) -> SqlResult<usize> {
+        self.remove_points_where("region_id = ?1 AND deleted_at IS NULL", params![self.key(region_id)])
+    }
+
+    /// Reassigns a point to a different region.
+    ///
+    /// # Arguments
+    ///
+    /// * `point_id` - UUID of the point to reassign.
+    /// * `region_id` - UUID of the region the point belongs to from now on.
+    ///
+    /// # Returns
+    ///
+    /// A Result indicating success or an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let point_id = Uuid::new_v4();
+    /// db.update_point_region(point_id, region_id).expect("Failed to update point region");
+    /// ```
+    pub fn update_point_region(&self, point_id: Uuid, region_id: Uuid) -> SqlResult<()> {
+        self.conn().execute(
+            "UPDATE points SET region_id = ?1, updated_at = ?3 WHERE id = ?2",
+            params![self.key(region_id), self.key(point_id), now_millis()],
         )?;
-        
-        let points_iter = stmt.query_map(params![region_id.to_string()], |row| {
-            let id: String = row.get(0)?;
-            let x: f64 = row.get(1)?;
-            let y: f64 = row.get(2)?;
-            let z: f64 = row.get(3)?;
-            let data_file: String = row.get(4)?;
-            let object_type: String = row.get(5)?;
-            
-            let custom_data_str = fs::read_to_string(&data_file)
-                .map_err(|err| rusqlite::Error::ToSqlConversionFailure(Box::new(err)))?;
-            let custom_data: Value = serde_json::from_str(&custom_data_str)
-                .map_err(|err| rusqlite::Error::ToSqlConversionFailure(Box::new(err)))?;
-            
-            Ok(Point {
-                id: Some(Uuid::parse_str(&id).unwrap()),
-                x,
-                y,
-                z,
-                object_type,
-                custom_data,
-            })
-        })?;
-        
-        let mut points = Vec::new();
-        for point in points_iter {
-            points.push(point?);
-        }
-        
-        println!("Retrieved {} points for region {}", points.len(), region_id);
-        Ok(points)
+        Ok(())
     }
 
-    /// Clears all points from the database.
+    /// Removes a point from the database.
+    ///
+    /// # Arguments
+    ///
+    /// * `point_id` - UUID of the point to remove.
     ///
     /// # Returns
     ///
     /// A Result indicating success or an error.
-    pub fn clear_all_points(&self) -> SqlResult<()> {
-        self.conn.execute("DELETE FROM points", [])?;
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let point_id = Uuid::new_v4();
+    /// db.remove_point(point_id).expect("Failed to remove point");
+    /// ```
+    pub fn remove_point(&self, point_id: Uuid) -> SqlResult<()> {
+        let previous_hash = self.point_data_hash(&self.key(point_id))?;
+        // Delete the point from the database
+        self.conn().prepare_cached("DELETE FROM points WHERE id = ?1")?.execute(params![self.key(point_id)])?;
+        self.conn().prepare_cached("DELETE FROM expiries WHERE id = ?1")?.execute(params![self.key(point_id)])?;
+        self.conn().prepare_cached("DELETE FROM attachments WHERE id = ?1 OR parent_id = ?1")?.execute(params![self.key(point_id)])?;
+        if let Some(previous_hash) = previous_hash {
+            self.release_blob(&previous_hash)?;
+        }
         Ok(())
     }
+
+    /// Removes several points, deleting up to `POINT_BATCH_SIZE` of them per statement.
+    ///
+    /// This is the bulk form of `remove_point`. Points that don't exist are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `point_ids` - The UUIDs of the points to remove.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the number of points removed, or an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let removed = db.remove_points(&[first_id, second_id]).expect("Failed to remove points");
+    /// ```
+    pub fn remove_points(&self, point_ids: &[Uuid]) -> SqlResult<usize> {
+        let mut removed = 0;
+        for chunk in point_ids.chunks(POINT_BATCH_SIZE) {
+            let keys: Vec<SqlValue> = chunk.iter().map(|id| self.key(*id)).collect();
+            let params: Vec<&dyn rusqlite::ToSql> = keys.iter().map(|key| key as &dyn rusqlite::ToSql).collect();
+            removed += self.remove_points_where(&format!("id IN ({})", placeholders(1, chunk.len())), &params)?;
+        }
+        Ok(removed)
+    }
+
+    /// Removes every live point of a region lying entirely inside a box.
+    ///
+    /// The points are removed with a single `DELETE` statement rather than one per point. Points
+    /// are matched like `count_points` matches them.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_id` - UUID of the region to remove points from.
+    /// * `min` - Minimum corner of the box.
+    /// * `max` - Maximum corner of the box.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the number of points removed, or an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let removed = db.remove_points_in_box(region_id, [-10.0, -10.0, -10.0], [10.0, 10.0, 10.0]).expect("Failed to remove points");
+    /// ```
+    pub fn remove_points_in_box(&self, region_id: Uuid, min: [f64; 3], max: [f64; 3]) -> SqlResult<usize> {
+        self.remove_points_where(
+            "region_id = ?1 AND deleted_at IS NULL AND x BETWEEN ?2 AND ?5
+               AND x - COALESCE(extent_x, size_x) / 2 >= ?2 AND x + COALESCE(extent_x, size_x) / 2 <= ?5
+               AND y - COALESCE(extent_y, size_y) / 2 >= ?3 AND y + COALESCE(extent_y, size_y) / 2 <= ?6
+               AND z - COALESCE(extent_z, size_z) / 2 >= ?4 AND z + COALESCE(extent_z, size_z) / 2 <= ?7",
+            params![self.key(region_id), min[0], min[1], min[2], max[0], max[1], max[2]],
+        )
+    }
+
+    /// Removes every live point of a region with the given object type.
+    ///
+    /// The points are removed with a single `DELETE` statement rather than one per point.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_id` - UUID of the region to remove points from.
+    /// * `object_type` - Object type of the points to remove.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the number of points removed, or an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let removed = db.remove_points_of_type(region_id, "resource").expect("Failed to remove points");
+    /// ```
+    pub fn remove_points_of_type(&self, region_id: Uuid, object_type: &str) -> SqlResult<usize> {
+        self.remove_points_where(
+            "region_id = ?1 AND deleted_at IS NULL AND object_type = ?2",
+            params![self.key(region_id), object_type],
+        )
+    }
+
+    /// Removes the points matching a condition on the points table, with their expiries, attachments and data references.
+    fn remove_points_where(&self, condition: &str, params: &[&dyn rusqlite::ToSql]) -> SqlResult<usize> {
+        self.conn().execute(
+            &format!(
+                "UPDATE blobs SET ref_count = ref_count - (SELECT COUNT(*) FROM points WHERE data_hash = blobs.hash AND {0})
+                 WHERE hash IN (SELECT data_hash FROM points WHERE {0})",
+                condition,
+            ),
+            params,
+        )?;
+        self.conn().execute(&format!("DELETE FROM expiries WHERE id IN (SELECT id FROM points WHERE {})", condition), params)?;
+        self.conn().execute(
+            &format!(
+                "DELETE FROM attachments WHERE id IN (SELECT id FROM points WHERE {0}) OR parent_id IN (SELECT id FROM points WHERE {0})",
+                condition,
+            ),
+            params,
+        )?;
+        let deleted = self.conn().execute(&format!("DELETE FROM points WHERE {}", condition), params)?;
+        if self.conn.lock().is_autocommit() {
+            self.purge_unreferenced_blobs()?;
+        }
+        Ok(deleted)
+    }
+
+    /// Marks a point as deleted without removing it, so it can be restored later.
+    ///
+    /// Soft-deleted points are skipped by every query until they're restored or purged.
+    ///
+    /// # Arguments
+    ///
+    /// * `point_id` - UUID of the point to delete.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing `true` if a live point was marked deleted, or an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let point_id = Uuid::new_v4();
+    /// db.soft_delete_point(point_id).expect("Failed to delete point");
+    /// ```
+    pub fn soft_delete_point(&self, point_id: Uuid) -> SqlResult<bool> {
+        let deleted_at = SystemTime::now().duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or_default();
+        let changed = self.conn().execute(
+            "UPDATE points SET deleted_at = ?1 WHERE id = ?2 AND deleted_at IS NULL",
+            params![deleted_at, self.key(point_id)],
+        )?;
+        Ok(changed > 0)
+    }
+
+    /// Retrieves a soft-deleted point along with its region.
+    ///
+    /// # Arguments
+    ///
+    /// * `point_id` - UUID of the point.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the region's UUID and the point, `None` if no soft-deleted point has this ID, or an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let point_id = Uuid::new_v4();
+    /// if let Some((region_id, point)) = db.get_deleted_point(point_id).expect("Failed to get point") {
+    ///     println!("Point {:?} was deleted from region {}", point.id, region_id);
+    /// }
+    /// ```
+    pub fn get_deleted_point(&self, point_id: Uuid) -> SqlResult<Option<(Uuid, Point)>> {
+        self.select_point(point_id, "deleted_at IS NOT NULL")
+    }
+
+    /// Retrieves a live point along with its region.
+    ///
+    /// # Arguments
+    ///
+    /// * `point_id` - UUID of the point.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the region's UUID and the point, `None` if no live point has this ID, or an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// if let Some((region_id, point)) = db.get_point(point_id).expect("Failed to get point") {
+    ///     println!("Point {:?} is in region {}", point.id, region_id);
+    /// }
+    /// ```
+    pub fn get_point(&self, point_id: Uuid) -> SqlResult<Option<(Uuid, Point)>> {
+        self.select_point(point_id, "deleted_at IS NULL")
+    }
+
+    /// Reads a single point and its region, if its row matches `condition`.
+    fn select_point(&self, point_id: Uuid, condition: &str) -> SqlResult<Option<(Uuid, Point)>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT id, x, y, z, dataFile, object_type, data_encoding, size_x, size_y, size_z, version, region_id, velocity_x, velocity_y, velocity_z, orientation_x, orientation_y, orientation_z, orientation_w FROM points
+             WHERE id = ?1 AND {}",
+            condition,
+        ))?;
+        let mut rows = stmt.query(params![self.key(point_id)])?;
+        let Some(row) = rows.next()? else {
+            return Ok(None);
+        };
+
+        let data_file: String = row.get(4)?;
+        let data_encoding: String = row.get(6)?;
+        let custom_data_str = self.read_custom_data(&data_file, &data_encoding)
+            .map_err(|err| rusqlite::Error::ToSqlConversionFailure(Box::new(err)))?;
+        let custom_data: Value = serde_json::from_str(&custom_data_str)
+            .map_err(|err| rusqlite::Error::ToSqlConversionFailure(Box::new(err)))?;
+        let version: i64 = row.get(10)?;
+        let velocity = read_velocity(row, 12)?;
+        let orientation = read_orientation(row, 15)?;
+
+        Ok(Some((read_key(row, 11)?, Point {
+            id: Some(read_key(row, 0)?),
+            x: row.get(1)?,
+            y: row.get(2)?,
+            z: row.get(3)?,
+            object_type: row.get(5)?,
+            custom_data,
+            size: [row.get(7)?, row.get(8)?, row.get(9)?],
+            version: version as u64,
+            velocity,
+            orientation,
+        })))
+    }
+
+    /// Retrieves the IDs and regions of live points in key order, starting after a given point.
+    ///
+    /// Repeated calls, each starting after the last ID of the previous one, walk every point
+    /// without reading them all at once.
+    ///
+    /// # Arguments
+    ///
+    /// * `after` - The ID to start after, or `None` to start from the first point.
+    /// * `limit` - The maximum number of points to return.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing `(id, region_id)` pairs, or an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let first = db.get_point_keys(None, 100).expect("Failed to get points");
+    /// let next = db.get_point_keys(first.last().map(|(id, _)| *id), 100).expect("Failed to get points");
+    /// ```
+    pub fn get_point_keys(&self, after: Option<Uuid>, limit: usize) -> SqlResult<Vec<(Uuid, Uuid)>> {
+        let limit = limit.min(i64::MAX as usize) as i64;
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT id, region_id FROM points
+             WHERE deleted_at IS NULL AND (?1 IS NULL OR id > ?1)
+             ORDER BY id LIMIT ?2",
+        )?;
+        let keys = stmt.query_map(params![after.map(|after| self.key(after)), limit], |row| Ok((read_key(row, 0)?, read_key(row, 1)?)))?;
+        keys.collect()
+    }
+
+    /// Clears the deleted mark of a soft-deleted point.
+    ///
+    /// # Arguments
+    ///
+    /// * `point_id` - UUID of the point to restore.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing `true` if a soft-deleted point was restored, or an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let point_id = Uuid::new_v4();
+    /// db.restore_point(point_id).expect("Failed to restore point");
+    /// ```
+    pub fn restore_point(&self, point_id: Uuid) -> SqlResult<bool> {
+        let changed = self.conn().execute(
+            "UPDATE points SET deleted_at = NULL WHERE id = ?1 AND deleted_at IS NOT NULL",
+            params![self.key(point_id)],
+        )?;
+        Ok(changed > 0)
+    }
+
+    /// Permanently removes points soft-deleted at or before a given time.
+    ///
+    /// # Arguments
+    ///
+    /// * `deleted_before` - Unix timestamp in seconds. Points deleted at or before it are removed.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the number of points removed, or an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let week_ago = 1_700_000_000;
+    /// let purged = db.purge_deleted_points(week_ago).expect("Failed to purge points");
+    /// println!("Purged {} points", purged);
+    /// ```
+    pub fn purge_deleted_points(&self, deleted_before: i64) -> SqlResult<usize> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare("SELECT id FROM points WHERE deleted_at <= ?1")?;
+        let point_ids = stmt.query_map(params![deleted_before], |row| read_key(row, 0))?
+            .collect::<SqlResult<Vec<Uuid>>>()?;
+        for point_id in &point_ids {
+            self.remove_point(*point_id)?;
+        }
+        Ok(point_ids.len())
+    }
+
+    /// Sets or clears the time at which a point expires.
+    ///
+    /// # Arguments
+    ///
+    /// * `point_id` - UUID of the point.
+    /// * `expires_at` - Expiry time in milliseconds since the Unix epoch, or `None` to make the point permanent.
+    ///
+    /// # Returns
+    ///
+    /// A Result indicating success or an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// db.set_point_expiry(point_id, Some(1_700_000_000_000)).expect("Failed to set expiry");
+    /// ```
+    pub fn set_point_expiry(&self, point_id: Uuid, expires_at: Option<i64>) -> SqlResult<()> {
+        match expires_at {
+            Some(expires_at) => self.conn().execute(
+                "INSERT OR REPLACE INTO expiries (id, expires_at) VALUES (?1, ?2)",
+                params![self.key(point_id), expires_at],
+            )?,
+            None => self.conn().execute("DELETE FROM expiries WHERE id = ?1", params![self.key(point_id)])?,
+        };
+        Ok(())
+    }
+
+    /// Retrieves the expiry time of every point that has one.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing `(id, expires_at)` pairs, with times in milliseconds since the Unix epoch, or an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// for (id, expires_at) in db.get_point_expiries().expect("Failed to get expiries") {
+    ///     println!("Point {} expires at {}", id, expires_at);
+    /// }
+    /// ```
+    pub fn get_point_expiries(&self) -> SqlResult<Vec<(Uuid, i64)>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare("SELECT id, expires_at FROM expiries")?;
+        let expiries = stmt.query_map([], |row| Ok((read_key(row, 0)?, row.get(1)?)))?;
+        expiries.collect()
+    }
+
+    /// Attaches a point to another one, or detaches it.
+    ///
+    /// # Arguments
+    ///
+    /// * `point_id` - UUID of the attached point.
+    /// * `parent_id` - UUID of the point it follows, or `None` to detach it.
+    ///
+    /// # Returns
+    ///
+    /// A Result indicating success or an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// db.set_point_parent(turret_id, Some(ship_id)).expect("Failed to attach point");
+    /// ```
+    pub fn set_point_parent(&self, point_id: Uuid, parent_id: Option<Uuid>) -> SqlResult<()> {
+        match parent_id {
+            Some(parent_id) => self.conn().execute(
+                "INSERT OR REPLACE INTO attachments (id, parent_id) VALUES (?1, ?2)",
+                params![self.key(point_id), self.key(parent_id)],
+            )?,
+            None => self.conn().execute("DELETE FROM attachments WHERE id = ?1", params![self.key(point_id)])?,
+        };
+        Ok(())
+    }
+
+    /// Retrieves the parent of every attached point.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing `(id, parent_id)` pairs, or an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// for (id, parent_id) in db.get_point_parents().expect("Failed to get attachments") {
+    ///     println!("Point {} follows {}", id, parent_id);
+    /// }
+    /// ```
+    pub fn get_point_parents(&self) -> SqlResult<Vec<(Uuid, Uuid)>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare("SELECT id, parent_id FROM attachments")?;
+        let parents = stmt.query_map([], |row| Ok((read_key(row, 0)?, read_key(row, 1)?)))?;
+        parents.collect()
+    }
+
+    /// Updates the position of a point in the database.
+    ///
+    /// # Arguments
+    ///
+    /// * `point_id` - UUID of the point to update.
+    /// * `x` - New X-coordinate of the point.
+    /// * `y` - New Y-coordinate of the point.
+    /// * `z` - New Z-coordinate of the point.
+    ///
+    /// # Returns
+    ///
+    /// A Result indicating success or an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let point_id = Uuid::new_v4();
+    /// db.update_point_position(point_id, 4.0, 5.0, 6.0).expect("Failed to update point position");
+    /// ```
+    pub fn update_point_position(&self, point_id: Uuid, x: f64, y: f64, z: f64) -> SqlResult<()> {
+        // Update the point's position in the database
+        self.conn().execute(
+            "UPDATE points SET x = ?1, y = ?2, z = ?3, updated_at = ?5 WHERE id = ?4",
+            params![x, y, z, self.key(point_id), now_millis()],
+        )?;
+        Ok(())
+    }
+
+    /// Updates the version of a point in the database.
+    ///
+    /// # Arguments
+    ///
+    /// * `point_id` - UUID of the point to update.
+    /// * `version` - New version of the point.
+    ///
+    /// # Returns
+    ///
+    /// A Result indicating success or an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let point_id = Uuid::new_v4();
+    /// db.update_point_version(point_id, 2).expect("Failed to update point version");
+    /// ```
+    pub fn update_point_version(&self, point_id: Uuid, version: u64) -> SqlResult<()> {
+        self.conn().execute(
+            "UPDATE points SET version = ?1, updated_at = ?3 WHERE id = ?2",
+            params![version as i64, self.key(point_id), now_millis()],
+        )?;
+        Ok(())
+    }
+
+    /// Saves a region template, replacing any template with the same ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `template_id` - UUID of the template.
+    /// * `name` - Name of the template.
+    /// * `objects` - The template's objects, serialized as a JSON array.
+    ///
+    /// # Returns
+    ///
+    /// A Result indicating success or an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// db.save_template(Uuid::new_v4(), "outpost", &json!([])).expect("Failed to save template");
+    /// ```
+    pub fn save_template(&self, template_id: Uuid, name: &str, objects: &Value) -> SqlResult<()> {
+        let objects = serde_json::to_string(objects)
+            .map_err(|err| rusqlite::Error::ToSqlConversionFailure(Box::new(err)))?;
+        self.conn().execute(
+            "INSERT OR REPLACE INTO templates (id, name, objects) VALUES (?1, ?2, ?3)",
+            params![self.key(template_id), name, objects],
+        )?;
+        Ok(())
+    }
+
+    /// Retrieves a region template.
+    ///
+    /// # Arguments
+    ///
+    /// * `template_id` - UUID of the template.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the template's name and objects, `None` if there's no such template, or an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// if let Some((name, objects)) = db.get_template(template_id).expect("Failed to get template") {
+    ///     println!("Template {} has {} objects", name, objects.as_array().map_or(0, |objects| objects.len()));
+    /// }
+    /// ```
+    pub fn get_template(&self, template_id: Uuid) -> SqlResult<Option<(String, Value)>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare("SELECT name, objects FROM templates WHERE id = ?1")?;
+        let mut rows = stmt.query(params![self.key(template_id)])?;
+        let Some(row) = rows.next()? else {
+            return Ok(None);
+        };
+        let objects: String = row.get(1)?;
+        let objects: Value = serde_json::from_str(&objects)
+            .map_err(|err| rusqlite::Error::FromSqlConversionFailure(1, rusqlite::types::Type::Text, Box::new(err)))?;
+        Ok(Some((row.get(0)?, objects)))
+    }
+
+    /// Retrieves the ID and name of every region template.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing `(id, name)` pairs ordered by name, or an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// for (id, name) in db.get_templates().expect("Failed to get templates") {
+    ///     println!("Template {}: {}", id, name);
+    /// }
+    /// ```
+    pub fn get_templates(&self) -> SqlResult<Vec<(Uuid, String)>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare("SELECT id, name FROM templates ORDER BY name, id")?;
+        let templates = stmt.query_map([], |row| Ok((read_key(row, 0)?, row.get(1)?)))?;
+        templates.collect()
+    }
+
+    /// Deletes a region template.
+    ///
+    /// # Arguments
+    ///
+    /// * `template_id` - UUID of the template.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing `true` if a template was deleted, or an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// db.delete_template(template_id).expect("Failed to delete template");
+    /// ```
+    pub fn delete_template(&self, template_id: Uuid) -> SqlResult<bool> {
+        let deleted = self.conn().execute("DELETE FROM templates WHERE id = ?1", params![self.key(template_id)])?;
+        Ok(deleted > 0)
+    }
+
+    /// Saves a copy of every region, live point and expiry under a name, replacing any snapshot with the same name.
+    ///
+    /// The copy is self-contained: it holds the points' custom data rather than references to
+    /// their data files, so it stays valid however the world changes afterwards.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the snapshot.
+    /// * `created_at` - Time the snapshot is taken, in milliseconds since the Unix epoch.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the number of regions and points saved, or an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let (regions, points) = db.save_snapshot("autosave", 1_700_000_000_000).expect("Failed to save snapshot");
+    /// println!("Saved {} regions and {} points", regions, points);
+    /// ```
+    pub fn save_snapshot(&self, name: &str, created_at: i64) -> SqlResult<(usize, usize)> {
+        let regions = self.get_all_regions()?;
+        let mut points = Vec::new();
+        for region in &regions {
+            points.extend(self.get_points_in_region(region.id)?.into_iter().map(|point| (region.id, point)));
+        }
+        let saved: HashSet<Uuid> = points.iter().filter_map(|(_, point)| point.id).collect();
+        let expiries = self.get_point_expiries()?.into_iter()
+            .filter(|(point_id, _)| saved.contains(point_id))
+            .collect();
+        let attachments = self.get_point_parents()?.into_iter()
+            .filter(|(point_id, parent_id)| saved.contains(point_id) && saved.contains(parent_id))
+            .collect();
+
+        let counts = (regions.len(), points.len());
+        let archive = serde_json::to_vec(&SnapshotArchive { regions, points, expiries, attachments })
+            .map_err(|err| rusqlite::Error::ToSqlConversionFailure(Box::new(err)))?;
+        let archive = zstd::encode_all(archive.as_slice(), ZSTD_LEVEL)
+            .map_err(|err| rusqlite::Error::ToSqlConversionFailure(Box::new(err)))?;
+        self.conn().execute(
+            "INSERT OR REPLACE INTO snapshots (name, created_at, region_count, object_count, archive) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![name, created_at, counts.0 as i64, counts.1 as i64, archive],
+        )?;
+        Ok(counts)
+    }
+
+    /// Replaces every region, point, expiry and attachment with the contents of a snapshot.
+    ///
+    /// Soft-deleted points are discarded along with the rest of the current world.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the snapshot.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing `false` if there's no such snapshot, in which case nothing changes, or an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// if !db.restore_snapshot("autosave").expect("Failed to restore snapshot") {
+    ///     println!("No autosave found");
+    /// }
+    /// ```
+    pub fn restore_snapshot(&self, name: &str) -> SqlResult<bool> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare("SELECT archive FROM snapshots WHERE name = ?1")?;
+        let mut rows = stmt.query(params![name])?;
+        let Some(row) = rows.next()? else {
+            return Ok(false);
+        };
+        let archive: Vec<u8> = row.get(0)?;
+        let archive = zstd::decode_all(archive.as_slice())
+            .map_err(|err| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Blob, Box::new(err)))?;
+        let archive: SnapshotArchive = serde_json::from_slice(&archive)
+            .map_err(|err| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Blob, Box::new(err)))?;
+
+        for region in self.get_all_regions()? {
+            self.delete_region(region.id, true)?;
+        }
+        self.conn().execute("DELETE FROM expiries", [])?;
+        self.conn().execute("DELETE FROM attachments", [])?;
+
+        for region in &archive.regions {
+            self.create_region(region.id, region.center, region.radius)?;
+        }
+        // The regions were just emptied, so every archived point is new
+        let points: Vec<(Point, Uuid)> = archive.points.into_iter().map(|(region_id, point)| (point, region_id)).collect();
+        self.add_points(&points)?;
+        for (point_id, expires_at) in &archive.expiries {
+            self.set_point_expiry(*point_id, Some(*expires_at))?;
+        }
+        for (point_id, parent_id) in &archive.attachments {
+            self.set_point_parent(*point_id, Some(*parent_id))?;
+        }
+        Ok(true)
+    }
+
+    /// Retrieves the name, creation time and size of every snapshot.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing `(name, created_at, region_count, object_count)` tuples, newest first, or an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// for (name, created_at, regions, points) in db.get_snapshots().expect("Failed to get snapshots") {
+    ///     println!("Snapshot {} taken at {}: {} regions, {} points", name, created_at, regions, points);
+    /// }
+    /// ```
+    pub fn get_snapshots(&self) -> SqlResult<Vec<(String, i64, usize, usize)>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT name, created_at, region_count, object_count FROM snapshots ORDER BY created_at DESC, name",
+        )?;
+        let snapshots = stmt.query_map([], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get::<_, i64>(2)? as usize, row.get::<_, i64>(3)? as usize))
+        })?;
+        snapshots.collect()
+    }
+
+    /// Deletes a snapshot.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the snapshot.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing `true` if a snapshot was deleted, or an error.
+    pub fn delete_snapshot(&self, name: &str) -> SqlResult<bool> {
+        let deleted = self.conn().execute("DELETE FROM snapshots WHERE name = ?1", params![name])?;
+        Ok(deleted > 0)
+    }
+
+    /// Retrieves all regions from the database.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing a vector of regions or an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let regions = db.get_all_regions().expect("Failed to get regions");
+    /// for region in regions {
+    ///     println!("Region: {:?}", region);
+    /// }
+    /// ```
+    pub fn get_all_regions(&self) -> SqlResult<Vec<Region>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT id, center_x, center_y, center_z, radius FROM regions",
+        )?;
+        
+        let regions_iter = stmt.query_map([], |row| {
+            let id = read_key(row, 0)?;
+            let center_x: f64 = row.get(1)?;
+            let center_y: f64 = row.get(2)?;
+            let center_z: f64 = row.get(3)?;
+            let radius: f64 = row.get(4)?;
+            
+            Ok(Region {
+                id,
+                center: [center_x, center_y, center_z],
+                radius,
+            })
+        })?;
+        
+        let mut regions = Vec::new();
+        for region in regions_iter {
+            regions.push(region?);
+        }
+        
+        Ok(regions)
+    }
+
+    /// Retrieves all points within a specified region from the database.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_id` - UUID of the region to query.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing a vector of points or an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let region_id = Uuid::new_v4();
+    /// let points = db.get_points_in_region(region_id).expect("Failed to get points in region");
+    /// for point in points {
+    ///     println!("Point in region: {:?}", point);
+    /// }
+    /// ```
+    pub fn get_points_in_region(&self, region_id: Uuid) -> SqlResult<Vec<Point>> {
+        let points = self.select_points("region_id = ?1 AND deleted_at IS NULL", params![self.key(region_id)])?;
+        println!("Retrieved {} points for region {}", points.len(), region_id);
+        Ok(points)
+    }
+
+    /// Retrieves the points of a given object type within a specified region from the database.
+    ///
+    /// Only the matching points' data files are read, so looking up a few objects of an
+    /// unloaded region doesn't cost as much as loading it.
+    ///
+    /// # Arguments
+    ///
+    /// * `object_type` - Object type of the points to retrieve.
+    /// * `region_id` - UUID of the region to query.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing a vector of points or an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let players = db.get_points_by_type("player", region_id).expect("Failed to get players");
+    /// println!("{} players in region", players.len());
+    /// ```
+    pub fn get_points_by_type(&self, object_type: &str, region_id: Uuid) -> SqlResult<Vec<Point>> {
+        self.select_points(
+            "region_id = ?1 AND object_type = ?2 AND deleted_at IS NULL",
+            params![self.key(region_id), object_type],
+        )
+    }
+
+    /// Reads the live points matching a condition on the points table, with their custom data.
+    fn select_points(&self, condition: &str, params: &[&dyn rusqlite::ToSql]) -> SqlResult<Vec<Point>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT id, x, y, z, dataFile, object_type, data_encoding, size_x, size_y, size_z, version, velocity_x, velocity_y, velocity_z, orientation_x, orientation_y, orientation_z, orientation_w FROM points WHERE {}",
+            condition,
+        ))?;
+        
+        let points_iter = stmt.query_map(params, |row| {
+            let id = read_key(row, 0)?;
+            let x: f64 = row.get(1)?;
+            let y: f64 = row.get(2)?;
+            let z: f64 = row.get(3)?;
+            let data_file: String = row.get(4)?;
+            let object_type: String = row.get(5)?;
+            let data_encoding: String = row.get(6)?;
+            let size: [f64; 3] = [row.get(7)?, row.get(8)?, row.get(9)?];
+            let version: i64 = row.get(10)?;
+            let velocity = read_velocity(row, 11)?;
+            let orientation = read_orientation(row, 14)?;
+            
+            let custom_data_str = self.read_custom_data(&data_file, &data_encoding)
+                .map_err(|err| rusqlite::Error::ToSqlConversionFailure(Box::new(err)))?;
+            let custom_data: Value = serde_json::from_str(&custom_data_str)
+                .map_err(|err| rusqlite::Error::ToSqlConversionFailure(Box::new(err)))?;
+            
+            Ok(Point {
+                id: Some(id),
+                x,
+                y,
+                z,
+                object_type,
+                custom_data,
+                size,
+                version: version as u64,
+                velocity,
+                orientation,
+            })
+        })?;
+        
+        let mut points = Vec::new();
+        for point in points_iter {
+            points.push(point?);
+        }
+        Ok(points)
+    }
+
+    /// Retrieves a page of the points within a specified region, reporting undecodable points instead of failing.
+    ///
+    /// Unlike `get_points_in_region`, a missing data file, malformed JSON, or an invalid ID only
+    /// affects the point in question, which is returned as a `CorruptPoint`. Points come back in
+    /// a stable order, so a large region can be read a page at a time without holding every
+    /// point and its custom data in memory at once.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_id` - UUID of the region to query.
+    /// * `after` - Cursor returned with the previous page, or 0 for the first page.
+    /// * `limit` - Maximum number of points in the page.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the page's points and the cursor of the next page, or `None` as the
+    /// cursor once the region has no more points.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut cursor = Some(0);
+    /// while let Some(after) = cursor {
+    ///     let (points, next) = db.get_points_in_region_paged(region_id, after, 1000).expect("Failed to get points");
+    ///     println!("Read {} points", points.len());
+    ///     cursor = next;
+    /// }
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// The cursor is a row position, so points removed between pages don't shift later pages.
+    pub fn get_points_in_region_paged(&self, region_id: Uuid, after: i64, limit: usize) -> SqlResult<PointPage> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare_cached(
+            "SELECT id, x, y, z, dataFile, object_type, data_encoding, size_x, size_y, size_z, version, velocity_x, velocity_y, velocity_z, orientation_x, orientation_y, orientation_z, orientation_w, rowid FROM points
+             WHERE region_id = ?1 AND deleted_at IS NULL AND rowid > ?2 ORDER BY rowid LIMIT ?3",
+        )?;
+
+        let mut last_row = after;
+        let rows_iter = stmt.query_map(params![self.key(region_id), after, limit.min(i64::MAX as usize) as i64], |row| {
+            let id = read_key_text(row, 0)?;
+            let x: f64 = row.get(1)?;
+            let y: f64 = row.get(2)?;
+            let z: f64 = row.get(3)?;
+            let data_file: String = row.get(4)?;
+            let object_type: String = row.get(5)?;
+            let data_encoding: String = row.get(6)?;
+            let size: [f64; 3] = [row.get(7)?, row.get(8)?, row.get(9)?];
+            let version: i64 = row.get(10)?;
+            let velocity = read_velocity(row, 11)?;
+            let orientation = read_orientation(row, 14)?;
+            let row_id: i64 = row.get(18)?;
+            Ok((id, x, y, z, data_file, object_type, data_encoding, size, version, velocity, orientation, row_id))
+        })?;
+
+        let mut points = Vec::new();
+        for row in rows_iter {
+            let (id, x, y, z, data_file, object_type, data_encoding, size, version, velocity, orientation, row_id) = row?;
+            last_row = row_id;
+            let corrupt = |raw_custom_data: Option<String>, error: String| CorruptPoint {
+                id: id.clone(),
+                x,
+                y,
+                z,
+                object_type: object_type.clone(),
+                raw_custom_data,
+                error,
+            };
+
+            let custom_data_str = match self.read_custom_data(&data_file, &data_encoding) {
+                Ok(custom_data_str) => custom_data_str,
+                Err(err) => {
+                    points.push(Err(corrupt(None, format!("Failed to read data file {}: {}", data_file, err))));
+                    continue;
+                }
+            };
+            let custom_data: Value = match serde_json::from_str(&custom_data_str) {
+                Ok(custom_data) => custom_data,
+                Err(err) => {
+                    points.push(Err(corrupt(Some(custom_data_str), format!("Malformed custom data: {}", err))));
+                    continue;
+                }
+            };
+            if let Err(err) = validate_point([x, y, z]) {
+                points.push(Err(corrupt(Some(custom_data_str), format!("Invalid position: {}", err))));
+                continue;
+            }
+            let uuid = match Uuid::parse_str(&id) {
+                Ok(uuid) => uuid,
+                Err(err) => {
+                    points.push(Err(corrupt(Some(custom_data_str), format!("Invalid point ID: {}", err))));
+                    continue;
+                }
+            };
+
+            points.push(Ok(Point {
+                id: Some(uuid),
+                x,
+                y,
+                z,
+                object_type,
+                custom_data,
+                size,
+                version: version as u64,
+                velocity,
+                orientation,
+            }));
+        }
+
+        let next = (points.len() == limit).then_some(last_row);
+        Ok((points, next))
+    }
+
+    /// Retrieves the IDs of points whose region doesn't exist in the database.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the stored IDs of orphaned points, or an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let orphans = db.get_orphaned_point_ids().expect("Failed to check for orphaned points");
+    /// println!("{} orphaned points", orphans.len());
+    /// ```
+    pub fn get_orphaned_point_ids(&self) -> SqlResult<Vec<String>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT id FROM points WHERE region_id IS NULL OR region_id NOT IN (SELECT id FROM regions)",
+        )?;
+
+        let ids_iter = stmt.query_map([], |row| read_key_text(row, 0))?;
+
+        let mut ids = Vec::new();
+        for id in ids_iter {
+            ids.push(id?);
+        }
+
+        Ok(ids)
+    }
+
+    /// Moves a corrupt point out of the points table and into quarantine.
+    ///
+    /// The raw payload and error are kept so the point can be inspected and repaired later.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - The corrupt point to quarantine.
+    /// * `region_id` - UUID of the region the point belongs to.
+    ///
+    /// # Returns
+    ///
+    /// A Result indicating success or an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let region_id = Uuid::new_v4();
+    /// let (points, _) = db.get_points_in_region_paged(region_id, 0, 1000).expect("Failed to get points in region");
+    /// for entry in points {
+    ///     if let Err(corrupt) = entry {
+    ///         db.quarantine_point(&corrupt, region_id).expect("Failed to quarantine point");
+    ///     }
+    /// }
+    /// ```
+    pub fn quarantine_point(&self, point: &CorruptPoint, region_id: Uuid) -> SqlResult<()> {
+        let quarantined_at = SystemTime::now().duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or_default();
+
+        self.conn().execute(
+            "INSERT OR REPLACE INTO quarantine (id, region_id, x, y, z, object_type, raw_data, error, quarantined_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![point.id, region_id.to_string(), point.x, point.y, point.z, &point.object_type, &point.raw_custom_data, &point.error, quarantined_at],
+        )?;
+        let key = self.stored_key(&point.id);
+        let previous_hash = self.point_data_hash(&key)?;
+        self.conn().execute(
+            "DELETE FROM points WHERE id = ?1",
+            params![key],
+        )?;
+        if let Some(previous_hash) = previous_hash {
+            self.release_blob(&previous_hash)?;
+        }
+        Ok(())
+    }
+
+    /// Retrieves all quarantined points.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the quarantined points, oldest first, or an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// for quarantined in db.get_quarantined_points().expect("Failed to get quarantined points") {
+    ///     println!("{}: {}", quarantined.point.id, quarantined.point.error);
+    /// }
+    /// ```
+    pub fn get_quarantined_points(&self) -> SqlResult<Vec<QuarantinedPoint>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT id, region_id, x, y, z, object_type, raw_data, error, quarantined_at FROM quarantine ORDER BY quarantined_at, id",
+        )?;
+
+        let points_iter = stmt.query_map([], |row| {
+            let region_id: String = row.get(1)?;
+            Ok(QuarantinedPoint {
+                point: CorruptPoint {
+                    id: row.get(0)?,
+                    x: row.get(2)?,
+                    y: row.get(3)?,
+                    z: row.get(4)?,
+                    object_type: row.get(5)?,
+                    raw_custom_data: row.get(6)?,
+                    error: row.get(7)?,
+                },
+                region_id: Uuid::parse_str(&region_id)
+                    .map_err(|err| rusqlite::Error::FromSqlConversionFailure(1, rusqlite::types::Type::Text, Box::new(err)))?,
+                quarantined_at: row.get(8)?,
+            })
+        })?;
+
+        let mut points = Vec::new();
+        for point in points_iter {
+            points.push(point?);
+        }
+
+        Ok(points)
+    }
+
+    /// Permanently removes a point from quarantine.
+    ///
+    /// # Arguments
+    ///
+    /// * `point_id` - ID of the quarantined point, as stored.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing `true` if a quarantined point was removed, or an error.
+    pub fn remove_quarantined_point(&self, point_id: &str) -> SqlResult<bool> {
+        let removed = self.conn().execute(
+            "DELETE FROM quarantine WHERE id = ?1",
+            params![point_id],
+        )?;
+        Ok(removed > 0)
+    }
+
+    /// Deletes every custom data blob that is no longer referenced by a point.
+    ///
+    /// This runs automatically after a point is removed or replaced outside a transaction and
+    /// after `commit_transaction`, so it rarely needs to be called directly.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the number of blobs deleted, or an error.
+    pub fn purge_unreferenced_blobs(&self) -> SqlResult<usize> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare("SELECT hash, dataFile FROM blobs WHERE ref_count <= 0")?;
+        let unreferenced = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?
+            .collect::<SqlResult<Vec<(String, String)>>>()?;
+
+        for (hash, data_file) in &unreferenced {
+            self.conn().execute("DELETE FROM blobs WHERE hash = ?1", params![hash])?;
+            let removed = match &self.blobs {
+                BlobStore::Files => fs::remove_file(data_file),
+                BlobStore::Memory(files) => match files.lock().unwrap().remove(data_file) {
+                    Some(_) => Ok(()),
+                    None => Err(io::ErrorKind::NotFound.into()),
+                },
+            };
+            match removed {
+                Ok(()) => self.io.file_deleted(),
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                Err(err) => return Err(rusqlite::Error::ToSqlConversionFailure(Box::new(err))),
+            }
+        }
+
+        Ok(unreferenced.len())
+    }
+
+    /// Deletes every data file no point refers to, including files the blobs table lost track of.
+    ///
+    /// Reference counts are recomputed from the points table first, so blobs whose count drifted
+    /// are released too. Then every file in this database's blob directory that isn't listed in
+    /// the blobs table is deleted; such files are left behind when a transaction that wrote them
+    /// is rolled back or the process stops before committing.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the number of data files deleted, or an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let deleted = db.vacuum().expect("Failed to vacuum data files");
+    /// println!("Deleted {} orphaned data files", deleted);
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - This fails inside a transaction, since the files it would delete could still be rolled back to.
+    /// - The vacuum runs in its own `BEGIN IMMEDIATE` transaction, so neither other threads nor other
+    ///   connections to the database can reference a blob halfway through it.
+    /// - Files modified shortly before or after the vacuum started are kept, since a write on
+    ///   another connection may not have recorded them yet.
+    /// - Files of points that predate deduplication are kept as long as a point refers to them.
+    pub fn vacuum(&self) -> SqlResult<usize> {
+        // The connection stays held from the first statement to the last deleted file
+        let conn = self.conn();
+        if !conn.is_autocommit() {
+            return Err(rusqlite::Error::ToSqlConversionFailure(
+                Box::new(io::Error::other("Data files can't be vacuumed inside a transaction")),
+            ));
+        }
+        let started = SystemTime::now();
+        conn.execute_batch("BEGIN IMMEDIATE")?;
+        self.conn.begin_transaction();
+        match self.sweep_blobs(started) {
+            Ok(deleted) => {
+                self.commit_transaction()?;
+                Ok(deleted)
+            }
+            Err(err) => {
+                let _ = self.rollback_transaction();
+                Err(err)
+            }
+        }
+    }
+
+    /// Deletes unreferenced blobs and the files in the blob directory that no blob or point lists.
+    ///
+    /// Must be called inside the transaction opened by `vacuum`. Deleting files before the
+    /// transaction commits is safe, since only files no committed row refers to are deleted.
+    ///
+    /// # Arguments
+    ///
+    /// * `started` - Time the vacuum started; files modified since `VACUUM_GRACE` before it are kept.
+    fn sweep_blobs(&self, started: SystemTime) -> SqlResult<usize> {
+        self.conn().execute(
+            "UPDATE blobs SET ref_count = (SELECT COUNT(*) FROM points WHERE data_hash = blobs.hash)",
+            [],
+        )?;
+        let mut deleted = self.purge_unreferenced_blobs()?;
+
+        let referenced = {
+            let conn = self.conn();
+            let mut stmt = conn.prepare("SELECT dataFile FROM blobs UNION SELECT dataFile FROM points")?;
+            let referenced = stmt.query_map([], |row| row.get::<_, String>(0))?
+                .collect::<SqlResult<HashSet<String>>>()?;
+            referenced
+        };
+
+        let store_dir = self.blob_store_dir()?;
+        match &self.blobs {
+            BlobStore::Files => {
+                let to_sql_error = |err: io::Error| rusqlite::Error::ToSqlConversionFailure(Box::new(err));
+                let folders = match fs::read_dir(&store_dir) {
+                    Ok(folders) => folders,
+                    Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(deleted),
+                    Err(err) => return Err(to_sql_error(err)),
+                };
+                for folder in folders {
+                    let folder = folder.map_err(to_sql_error)?;
+                    let folder_name = folder.file_name().to_string_lossy().into_owned();
+                    if !folder.file_type().map_err(to_sql_error)?.is_dir() {
+                        continue;
+                    }
+                    for file in fs::read_dir(folder.path()).map_err(to_sql_error)? {
+                        let file = file.map_err(to_sql_error)?;
+                        let data_file = format!("{}/{}/{}", store_dir, folder_name, file.file_name().to_string_lossy());
+                        if referenced.contains(&data_file) {
+                            continue;
+                        }
+                        // Another connection may be writing this file and about to record it
+                        let modified = file.metadata().and_then(|metadata| metadata.modified()).map_err(to_sql_error)?;
+                        if modified + VACUUM_GRACE >= started {
+                            continue;
+                        }
+                        fs::remove_file(&data_file).map_err(to_sql_error)?;
+                        self.io.file_deleted();
+                        deleted += 1;
+                    }
+                }
+            }
+            BlobStore::Memory(files) => {
+                // In-memory databases have a single connection, which the vacuum holds
+                let prefix = format!("{}/", store_dir);
+                files.lock().unwrap().retain(|data_file, _| {
+                    let orphaned = data_file.starts_with(&prefix) && !referenced.contains(data_file);
+                    if orphaned {
+                        self.io.file_deleted();
+                        deleted += 1;
+                    }
+                    !orphaned
+                });
+            }
+        }
+        Ok(deleted)
+    }
+
+    /// Returns the number of distinct custom data blobs currently stored.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the number of blobs, or an error.
+    pub fn count_blobs(&self) -> SqlResult<usize> {
+        self.conn().query_row("SELECT COUNT(*) FROM blobs", [], |row| row.get::<_, i64>(0))
+            .map(|count| count as usize)
+    }
+
+    /// Returns the path of the file holding a point's custom data.
+    ///
+    /// Points with identical custom data share the same file.
+    ///
+    /// # Arguments
+    ///
+    /// * `point_id` - The UUID of the point.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the data file path, or `None` if the point doesn't exist.
+    pub fn get_data_file(&self, point_id: Uuid) -> SqlResult<Option<String>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare("SELECT dataFile FROM points WHERE id = ?1")?;
+        let mut rows = stmt.query(params![self.key(point_id)])?;
+        match rows.next()? {
+            Some(row) => Ok(Some(row.get(0)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Looks up the region a point belongs to.
+    ///
+    /// # Arguments
+    ///
+    /// * `point_id` - The UUID of the point.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the region's UUID, or `None` if the point doesn't exist or is soft-deleted.
+    pub fn get_point_region(&self, point_id: Uuid) -> SqlResult<Option<Uuid>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare("SELECT region_id FROM points WHERE id = ?1 AND deleted_at IS NULL")?;
+        let mut rows = stmt.query(params![self.key(point_id)])?;
+        match rows.next()? {
+            Some(row) => Ok(Some(read_key(row, 0)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Records the latest state of a point changed in memory, replacing its previous journal entry.
+    ///
+    /// The journal holds changes between persists, so they survive a crash. Entries are replayed
+    /// into the points table by `replay_journal`.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - The changed point.
+    ///
+    /// # Returns
+    ///
+    /// A Result indicating success or an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// db.journal_point(&point).expect("Failed to journal point");
+    /// ```
+    pub fn journal_point(&self, point: &Point) -> SqlResult<()> {
+        let id = point.id.ok_or_else(|| constraint_error("Journaled points need an id".to_string()))?;
+        let json = serde_json::to_string(point)
+            .map_err(|err| rusqlite::Error::ToSqlConversionFailure(Box::new(err)))?;
+        self.conn().prepare_cached("INSERT OR REPLACE INTO journal (id, point) VALUES (?1, ?2)")?
+            .execute(params![self.key(id), json])?;
+        Ok(())
+    }
+
+    /// Drops the journal entries of points that have been written to the points table.
+    ///
+    /// # Arguments
+    ///
+    /// * `point_ids` - The UUIDs of the written points.
+    ///
+    /// # Returns
+    ///
+    /// A Result indicating success or an error.
+    pub fn clear_journal(&self, point_ids: &[Uuid]) -> SqlResult<()> {
+        for chunk in point_ids.chunks(POINT_BATCH_SIZE) {
+            let keys: Vec<SqlValue> = chunk.iter().map(|id| self.key(*id)).collect();
+            self.conn().prepare_cached(&format!("DELETE FROM journal WHERE id IN ({})", placeholders(1, chunk.len())))?
+                .execute(params_from_iter(keys))?;
+        }
+        Ok(())
+    }
+
+    /// Writes the journaled changes into the points table and empties the journal.
+    ///
+    /// An entry is only replayed if its point still exists and hasn't been written with a newer
+    /// version since; the point keeps the region it's stored in. Run it inside a transaction, so
+    /// a failed replay leaves the journal intact.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the number of points replayed, or an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let replayed = db.replay_journal().expect("Failed to replay journal");
+    /// ```
+    pub fn replay_journal(&self) -> SqlResult<usize> {
+        let entries = {
+            let conn = self.conn();
+            let mut stmt = conn.prepare("SELECT point FROM journal")?;
+            let entries = stmt.query_map([], |row| row.get::<_, String>(0))?
+                .collect::<SqlResult<Vec<String>>>()?;
+            entries
+        };
+        if entries.is_empty() {
+            return Ok(0);
+        }
+
+        let mut points = Vec::with_capacity(entries.len());
+        for entry in entries {
+            let point: Point = serde_json::from_str(&entry)
+                .map_err(|err| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(err)))?;
+            let Some(id) = point.id else { continue };
+            let stored = {
+                let conn = self.conn();
+                let mut stmt = conn.prepare_cached("SELECT region_id, version FROM points WHERE id = ?1 AND deleted_at IS NULL")?;
+                let mut rows = stmt.query(params![self.key(id)])?;
+                match rows.next()? {
+                    Some(row) => Some((read_key(row, 0)?, row.get::<_, i64>(1)?)),
+                    None => None,
+                }
+            };
+            // Points removed since, or written with a newer version, are left as they are
+            if let Some((region_id, _)) = stored.filter(|(_, version)| point.version as i64 >= *version) {
+                points.push((point, region_id));
+            }
+        }
+        self.upsert_points(&points)?;
+        self.conn().execute("DELETE FROM journal", [])?;
+        Ok(points.len())
+    }
+
+    /// Lists the live points of a region that haven't been written since a given time.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_id` - UUID of the region to search.
+    /// * `cutoff` - Time in milliseconds since the epoch; points last written before it are listed.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the UUIDs of the points, or an error. Points written before write
+    /// times were tracked are never listed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let cold = db.get_points_untouched_since(region_id, cutoff).expect("Failed to list cold points");
+    /// ```
+    pub fn get_points_untouched_since(&self, region_id: Uuid, cutoff: i64) -> SqlResult<Vec<Uuid>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT id FROM points WHERE region_id = ?1 AND deleted_at IS NULL AND updated_at < ?2 ORDER BY id",
+        )?;
+        let ids = stmt.query_map(params![self.key(region_id), cutoff], |row| read_key(row, 0))?
+            .collect::<SqlResult<Vec<Uuid>>>()?;
+        Ok(ids)
+    }
+
+    /// Moves points to an archive chunk, removing them from the points table.
+    ///
+    /// The points are recorded in the archived table so they can be found again. Run it inside
+    /// a transaction, so the points aren't lost if recording them fails.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunk_id` - UUID of the archive chunk holding the points.
+    /// * `region_id` - UUID of the region the points belong to.
+    /// * `point_ids` - The UUIDs of the points to archive.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the number of points removed, or an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// db.archive_points(chunk_id, region_id, &cold).expect("Failed to archive points");
+    /// ```
+    pub fn archive_points(&self, chunk_id: Uuid, region_id: Uuid, point_ids: &[Uuid]) -> SqlResult<usize> {
+        for point_id in point_ids {
+            self.conn().prepare_cached("INSERT OR REPLACE INTO archived (id, region_id, chunk) VALUES (?1, ?2, ?3)")?
+                .execute(params![self.key(*point_id), self.key(region_id), chunk_id.to_string()])?;
+        }
+        self.remove_points(point_ids)
+    }
+
+    /// Looks up where an archived point is kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `point_id` - The UUID of the point.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the UUIDs of the point's region and of the archive chunk holding it,
+    /// or `None` if the point isn't archived.
+    pub fn get_archived_point(&self, point_id: Uuid) -> SqlResult<Option<(Uuid, Uuid)>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare("SELECT region_id, chunk FROM archived WHERE id = ?1")?;
+        let mut rows = stmt.query(params![self.key(point_id)])?;
+        match rows.next()? {
+            Some(row) => {
+                let chunk: String = row.get(1)?;
+                let chunk_id = Uuid::parse_str(&chunk)
+                    .map_err(|err| rusqlite::Error::FromSqlConversionFailure(1, rusqlite::types::Type::Text, Box::new(err)))?;
+                Ok(Some((read_key(row, 0)?, chunk_id)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Forgets that a point is archived, once it has been restored.
+    ///
+    /// # Arguments
+    ///
+    /// * `point_id` - The UUID of the point.
+    /// * `chunk_id` - UUID of the archive chunk that held it.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the number of points still archived in the chunk, or an error.
+    pub fn unarchive_point(&self, point_id: Uuid, chunk_id: Uuid) -> SqlResult<usize> {
+        self.conn().execute("DELETE FROM archived WHERE id = ?1", params![self.key(point_id)])?;
+        self.conn().query_row(
+            "SELECT COUNT(*) FROM archived WHERE chunk = ?1",
+            params![chunk_id.to_string()],
+            |row| row.get(0),
+        )
+    }
+
+    /// Looks up the content hash of a point's custom data.
+    ///
+    /// Returns `None` if the point doesn't exist or predates deduplication.
+    fn point_data_hash(&self, key: &SqlValue) -> SqlResult<Option<String>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare_cached("SELECT data_hash FROM points WHERE id = ?1")?;
+        let mut rows = stmt.query(params![key])?;
+        match rows.next()? {
+            Some(row) => row.get(0),
+            None => Ok(None),
+        }
+    }
+
+    /// Looks up the content hashes of several points' custom data, by point.
+    ///
+    /// Points that don't exist are left out; points that predate deduplication map to `None`.
+    fn point_data_hashes(&self, ids: &[Uuid]) -> SqlResult<HashMap<Uuid, Option<String>>> {
+        let keys: Vec<SqlValue> = ids.iter().map(|id| self.key(*id)).collect();
+        let conn = self.conn();
+        let mut stmt = conn.prepare_cached(&format!("SELECT id, data_hash FROM points WHERE id IN ({})", placeholders(1, ids.len())))?;
+        let hashes = stmt.query_map(params_from_iter(keys), |row| Ok((read_key(row, 0)?, row.get(1)?)))?
+            .collect();
+        hashes
+    }
+
+    /// Returns the directory holding this database's blob files.
+    ///
+    /// Blob files are shared between points of this database only, so each database has its own directory.
+    fn blob_store_dir(&self) -> SqlResult<String> {
+        let store_id: String = self.conn().query_row(
+            "SELECT value FROM meta WHERE key = 'store_id'",
+            [],
+            |row| row.get(0),
+        )?;
+        Ok(format!("./data/blobs/{}", store_id))
+    }
+
+    /// Looks up the data file and encoding of a stored blob.
+    fn blob_location(&self, hash: &str) -> SqlResult<(String, String)> {
+        self.conn().prepare_cached("SELECT dataFile, data_encoding FROM blobs WHERE hash = ?1")?
+            .query_row(params![hash], |row| Ok((row.get(0)?, row.get(1)?)))
+    }
+
+    /// Adds a reference to the blob holding `custom_data_str`, writing it first if it doesn't exist yet.
+    ///
+    /// # Returns
+    ///
+    /// The blob's data file and encoding.
+    fn retain_blob(&self, hash: &str, custom_data_str: String) -> SqlResult<(String, String)> {
+        let updated = self.conn().prepare_cached("UPDATE blobs SET ref_count = ref_count + 1 WHERE hash = ?1")?
+            .execute(params![hash])?;
+        if updated > 0 {
+            return self.blob_location(hash);
+        }
+
+        let store_dir = self.blob_store_dir()?;
+        let folder_name: String = hash.chars().take(2).collect();
+        let file_path: String = format!("{}/{}/{}", store_dir, folder_name, hash);
+
+        let (contents, encoding) = encode_custom_data(custom_data_str, self.compression_threshold, self.compression)
+            .map_err(|err| rusqlite::Error::ToSqlConversionFailure(Box::new(err)))?;
+        let written = contents.len();
+        match &self.blobs {
+            BlobStore::Files => {
+                fs::create_dir_all(format!("{}/{}", store_dir, folder_name))
+                    .and_then(|_| fs::write(&file_path, contents))
+                    .map_err(|err| rusqlite::Error::ToSqlConversionFailure(Box::new(err)))?;
+            }
+            BlobStore::Memory(files) => {
+                files.lock().unwrap().insert(file_path.clone(), contents);
+            }
+        }
+        self.io.file_written(written);
+
+        self.conn().prepare_cached("INSERT INTO blobs (hash, dataFile, data_encoding, ref_count) VALUES (?1, ?2, ?3, 1)")?
+            .execute(params![hash, &file_path, encoding])?;
+        Ok((file_path, encoding.to_string()))
+    }
+
+    /// Reads a data file and decodes it back into serialized custom data.
+    ///
+    /// # Returns
+    ///
+    /// The JSON text stored in the file, or an error if it can't be read or decoded.
+    fn read_custom_data(&self, data_file: &str, encoding: &str) -> io::Result<String> {
+        let contents = match &self.blobs {
+            BlobStore::Files => fs::read(data_file)?,
+            BlobStore::Memory(files) => files.lock().unwrap().get(data_file).cloned()
+                .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, format!("No data file {}", data_file)))?,
+        };
+        self.io.file_read(contents.len());
+        decode_custom_data(contents, encoding)
+    }
+
+    /// Drops a reference to a blob, deleting it once unreferenced unless a transaction is open.
+    fn release_blob(&self, hash: &str) -> SqlResult<()> {
+        self.conn().prepare_cached("UPDATE blobs SET ref_count = ref_count - 1 WHERE hash = ?1")?
+            .execute(params![hash])?;
+        if self.conn.lock().is_autocommit() {
+            self.purge_unreferenced_blobs()?;
+        }
+        Ok(())
+    }
+
+    /// Starts a transaction on the database connection.
+    ///
+    /// Every statement executed until `commit_transaction` or `rollback_transaction` is called
+    /// becomes part of the transaction.
+    ///
+    /// # Returns
+    ///
+    /// A Result indicating success or an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// db.begin_transaction().expect("Failed to begin transaction");
+    /// db.add_point(&point, region_id).expect("Failed to add point");
+    /// db.commit_transaction().expect("Failed to commit transaction");
+    /// ```
+    pub fn begin_transaction(&self) -> SqlResult<()> {
+        let conn = self.conn();
+        conn.execute_batch("BEGIN")?;
+        // Other threads wait until the transaction ends
+        self.conn.begin_transaction();
+        Ok(())
+    }
+
+    /// Commits the current transaction.
+    ///
+    /// If the commit fails, the transaction is rolled back instead.
+    ///
+    /// # Returns
+    ///
+    /// A Result indicating success or an error.
+    pub fn commit_transaction(&self) -> SqlResult<()> {
+        let committed = self.conn().execute_batch("COMMIT");
+        if committed.is_err() && !self.conn.lock().is_autocommit() {
+            let _ = self.conn().execute_batch("ROLLBACK");
+        }
+        self.conn.end_transaction();
+        committed?;
+        // Blob files are only deleted once nothing can roll back their references
+        self.purge_unreferenced_blobs()?;
+        Ok(())
+    }
+
+    /// Rolls back the current transaction, discarding every change made since `begin_transaction`.
+    ///
+    /// # Returns
+    ///
+    /// A Result indicating success or an error.
+    pub fn rollback_transaction(&self) -> SqlResult<()> {
+        let rolled_back = self.conn().execute_batch("ROLLBACK");
+        self.conn.end_transaction();
+        rolled_back
+    }
+}
+
+/// Returns the `INSERT` statement writing `rows` points with `verb` (`INSERT` or `INSERT OR REPLACE`).
+fn insert_points_sql(verb: &str, rows: usize) -> String {
+    let values: Vec<String> = (0..rows)
+        .map(|row| format!("({})", placeholders(row * POINT_COLUMNS.len() + 1, POINT_COLUMNS.len())))
+        .collect();
+    format!("{} INTO points ({}) VALUES {}", verb, POINT_COLUMNS.join(", "), values.join(", "))
+}
+
+/// Returns the current time in milliseconds since the Unix epoch.
+fn now_millis() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or_default()
+}
+
+/// Returns `count` numbered SQL parameters starting at `?first`, separated by commas.
+fn placeholders(first: usize, count: usize) -> String {
+    (first..first + count).map(|index| format!("?{}", index)).collect::<Vec<_>>().join(", ")
+}
+
+/// Returns the error reported when a batch would break the points table's primary key.
+fn constraint_error(message: String) -> rusqlite::Error {
+    rusqlite::Error::SqliteFailure(rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_CONSTRAINT_PRIMARYKEY), Some(message))
+}
+
+/// Encodes a UUID as a key in the given format.
+fn encode_key(format: KeyFormat, id: Uuid) -> SqlValue {
+    match format {
+        KeyFormat::Text => SqlValue::Text(id.to_string()),
+        KeyFormat::Binary => SqlValue::Blob(id.as_bytes().to_vec()),
+    }
+}
+
+/// Decodes a key stored in either format, returning `None` if it isn't a valid UUID.
+fn decode_key(value: ValueRef<'_>) -> Option<Uuid> {
+    match value {
+        ValueRef::Text(text) => std::str::from_utf8(text).ok().and_then(|text| Uuid::parse_str(text).ok()),
+        ValueRef::Blob(bytes) => Uuid::from_slice(bytes).ok(),
+        _ => None,
+    }
+}
+
+/// Reads a UUID key from a column, in either format.
+fn read_key(row: &Row<'_>, idx: usize) -> SqlResult<Uuid> {
+    let value = row.get_ref(idx)?;
+    decode_key(value).ok_or_else(|| {
+        rusqlite::Error::FromSqlConversionFailure(idx, value.data_type(), "Invalid UUID key".into())
+    })
+}
+
+/// Reads a velocity stored in three consecutive columns starting at `idx`, which are all NULL for points without one.
+fn read_velocity(row: &Row<'_>, idx: usize) -> SqlResult<Option<[f64; 3]>> {
+    let components: [Option<f64>; 3] = [row.get(idx)?, row.get(idx + 1)?, row.get(idx + 2)?];
+    Ok(match components {
+        [Some(x), Some(y), Some(z)] => Some([x, y, z]),
+        _ => None,
+    })
+}
+
+/// Reads an orientation stored in four consecutive columns starting at `idx`, which are all NULL for points without one.
+fn read_orientation(row: &Row<'_>, idx: usize) -> SqlResult<Option<[f64; 4]>> {
+    let components: [Option<f64>; 4] = [row.get(idx)?, row.get(idx + 1)?, row.get(idx + 2)?, row.get(idx + 3)?];
+    Ok(match components {
+        [Some(x), Some(y), Some(z), Some(w)] => Some([x, y, z, w]),
+        _ => None,
+    })
+}
+
+/// Reads a key from a column as text, without requiring it to be a valid UUID.
+///
+/// Binary keys are rendered as hyphenated UUIDs, or as hex if they aren't 16 bytes long.
+fn read_key_text(row: &Row<'_>, idx: usize) -> SqlResult<String> {
+    match row.get_ref(idx)? {
+        ValueRef::Blob(bytes) => Ok(match Uuid::from_slice(bytes) {
+            Ok(uuid) => uuid.to_string(),
+            Err(_) => bytes.iter().map(|byte| format!("{:02x}", byte)).collect(),
+        }),
+        _ => row.get(idx),
+    }
+}
+
+/// Encodes serialized custom data for storage in a data file.
+///
+/// Payloads larger than `threshold` are compressed with `compression`; everything else is stored as-is.
+///
+/// # Returns
+///
+/// The bytes to write and the encoding to record for the point.
+fn encode_custom_data(custom_data_str: String, threshold: Option<usize>, compression: Compression) -> io::Result<(Vec<u8>, &'static str)> {
+    match (threshold, compression) {
+        (Some(threshold), Compression::Zstd) if custom_data_str.len() > threshold => {
+            let compressed = zstd::encode_all(custom_data_str.as_bytes(), ZSTD_LEVEL)?;
+            Ok((compressed, ENCODING_ZSTD))
+        }
+        (Some(threshold), Compression::Lz4) if custom_data_str.len() > threshold => {
+            Ok((lz4_flex::compress_prepend_size(custom_data_str.as_bytes()), ENCODING_LZ4))
+        }
+        _ => Ok((custom_data_str.into_bytes(), ENCODING_RAW)),
+    }
+}
+
+/// Decodes the contents of a data file back into serialized custom data.
+///
+/// # Returns
+///
+/// The JSON text stored in the file, or an error if it can't be decoded.
+fn decode_custom_data(contents: Vec<u8>, encoding: &str) -> io::Result<String> {
+    let decoded = match encoding {
+        ENCODING_RAW => contents,
+        ENCODING_ZSTD => zstd::decode_all(contents.as_slice())?,
+        ENCODING_LZ4 => lz4_flex::decompress_size_prepended(&contents)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?,
+        other => return Err(io::Error::new(io::ErrorKind::InvalidData, format!("Unknown data encoding: {}", other))),
+    };
+    String::from_utf8(decoded).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
 }
\ No newline at end of file
@@ -0,0 +1,98 @@
+//! # Cold-Storage Archive
+//!
+//! This module defines the chunk files written by `VaultManager::archive_cold_objects`. Objects
+//! that haven't been written for a while are moved out of the persistent database into
+//! compressed chunks under `VaultConfig::archive_dir`, one chunk per region and archival pass,
+//! keeping the hot database small for worlds that grow without bound.
+//!
+//! The database remembers the chunk holding each archived object, and `VaultManager::get_object`
+//! faults an archived object back in transparently. A chunk file is deleted once every object
+//! in it has been restored. The archive directory can be a mounted object store bucket, since
+//! chunks are only ever written once and read whole.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use std::time::Duration;
+//! use your_crate::{VaultManager, VaultConfig, CustomData};
+//!
+//! let config = VaultConfig { archive_dir: Some("archive".into()), ..VaultConfig::default() };
+//! let vault_manager: VaultManager<CustomData> = VaultManager::with_config("path/to/database.db", config).unwrap();
+//! let archived = vault_manager.archive_cold_objects(Duration::from_secs(30 * 24 * 60 * 60)).unwrap();
+//! println!("Archived {} objects", archived);
+//! ```
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::region_file::ObjectRecord;
+
+/// Name of the format, stored in the header of every chunk
+const FORMAT_NAME: &str = "pebblevault-archive";
+
+/// Version of the format written by this release
+const FORMAT_VERSION: u32 = 1;
+
+/// zstd compression level used for chunks, favoring size since they're rarely read
+const ZSTD_LEVEL: i32 = 9;
+
+/// The contents of an archive chunk.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct ArchiveChunk {
+    /// Name of the format, always `FORMAT_NAME`
+    format: String,
+    /// Version of the format the chunk was written in
+    version: u32,
+    /// The region the archived objects belong to
+    pub(crate) region_id: Uuid,
+    /// The archived objects, sorted by UUID
+    pub(crate) objects: Vec<ObjectRecord>,
+}
+
+impl ArchiveChunk {
+    /// Creates the contents of a chunk, sorting the objects by UUID.
+    pub(crate) fn new(region_id: Uuid, mut objects: Vec<ObjectRecord>) -> Self {
+        objects.sort_unstable_by_key(|object| object.uuid);
+        ArchiveChunk { format: FORMAT_NAME.to_string(), version: FORMAT_VERSION, region_id, objects }
+    }
+
+    /// Returns the path of the chunk with the given UUID inside `archive_dir`.
+    pub(crate) fn path(archive_dir: &Path, chunk_id: Uuid) -> PathBuf {
+        archive_dir.join(format!("{}.json.zst", chunk_id))
+    }
+
+    /// Writes the chunk to `path`, through a temporary file so a partly written chunk is never read.
+    pub(crate) fn write(&self, path: &Path) -> Result<(), String> {
+        let contents = serde_json::to_vec(self)
+            .map_err(|e| format!("Failed to serialize archive chunk: {}", e))?;
+        let compressed = zstd::encode_all(contents.as_slice(), ZSTD_LEVEL)
+            .map_err(|e| format!("Failed to compress archive chunk: {}", e))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|e| format!("Failed to create archive directory {}: {}", dir.display(), e))?;
+        }
+        let temp_path = path.with_extension("tmp");
+        fs::write(&temp_path, compressed)
+            .map_err(|e| format!("Failed to write archive chunk {}: {}", temp_path.display(), e))?;
+        fs::rename(&temp_path, path)
+            .map_err(|e| format!("Failed to write archive chunk {}: {}", path.display(), e))
+    }
+
+    /// Reads a chunk from `path`, rejecting files of another format or a newer version.
+    pub(crate) fn read(path: &Path) -> Result<Self, String> {
+        let compressed = fs::read(path)
+            .map_err(|e| format!("Failed to read archive chunk {}: {}", path.display(), e))?;
+        let contents = zstd::decode_all(compressed.as_slice())
+            .map_err(|e| format!("Failed to decompress archive chunk {}: {}", path.display(), e))?;
+        let chunk: ArchiveChunk = serde_json::from_slice(&contents)
+            .map_err(|e| format!("Failed to parse archive chunk {}: {}", path.display(), e))?;
+        if chunk.format != FORMAT_NAME {
+            return Err(format!("{} is not a PebbleVault archive chunk", path.display()));
+        }
+        if chunk.version > FORMAT_VERSION {
+            return Err(format!("Unsupported archive chunk version {} in {}", chunk.version, path.display()));
+        }
+        Ok(chunk)
+    }
+}
@@ -0,0 +1,246 @@
+//! # Async VaultManager
+//!
+//! This module provides `VaultManagerAsync`, an async front end to a `VaultManager`. The manager
+//! is moved onto a dedicated thread, and every call is sent to that thread and answered through a
+//! `Reply` future, so persistence I/O never blocks the executor of an async game server.
+//!
+//! The futures don't depend on any particular runtime; they work with tokio, async-std or a
+//! hand-written executor alike. Calls are executed one at a time, in the order they were made.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use your_crate::{VaultManager, VaultManagerAsync, CustomData};
+//! use uuid::Uuid;
+//!
+//! # async fn example() -> Result<(), String> {
+//! let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db")?;
+//! let vault = VaultManagerAsync::new(vault_manager)?;
+//!
+//! let region_id = vault.run(|vm| vm.create_or_load_region([0.0, 0.0, 0.0], 100.0)).await??;
+//! let nearby = vault.query_region(region_id, [-10.0; 3], [10.0; 3]).await?;
+//! vault.shutdown().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::thread::{self, JoinHandle};
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use crate::batch::Mutation;
+use crate::structs::SpatialObject;
+use crate::vault_manager::VaultManager;
+
+/// A call to run on the manager's thread.
+type Job<T> = Box<dyn FnOnce(&mut VaultManager<T>) + Send>;
+
+/// Messages sent to the manager's thread.
+enum Message<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> {
+    /// Runs a call against the manager
+    Run(Job<T>),
+    /// Shuts the manager down and exits
+    Shutdown(Completer<()>),
+}
+
+/// The result of a call, and the task waiting for it.
+struct ReplyState<R> {
+    result: Option<Result<R, String>>,
+    waker: Option<Waker>,
+}
+
+/// A future resolving to the result of a call made through `VaultManagerAsync`.
+///
+/// Resolves to an error if the manager's thread stopped before answering.
+pub struct Reply<R> {
+    state: Arc<Mutex<ReplyState<R>>>,
+}
+
+impl<R> Future for Reply<R> {
+    type Output = Result<R, String>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.state.lock().unwrap();
+        match state.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// The sending side of a `Reply`. Dropping it unanswered resolves the reply with an error.
+struct Completer<R> {
+    state: Arc<Mutex<ReplyState<R>>>,
+}
+
+impl<R> Completer<R> {
+    fn complete(self, result: Result<R, String>) {
+        self.resolve(result);
+    }
+
+    fn resolve(&self, result: Result<R, String>) {
+        let mut state = self.state.lock().unwrap();
+        if state.result.is_none() {
+            state.result = Some(result);
+        }
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl<R> Drop for Completer<R> {
+    fn drop(&mut self) {
+        let answered = self.state.lock().unwrap().result.is_some();
+        if !answered {
+            self.resolve(Err("VaultManager thread stopped before answering".to_string()));
+        }
+    }
+}
+
+/// Creates a reply and the completer that answers it.
+fn reply<R>() -> (Reply<R>, Completer<R>) {
+    let state = Arc::new(Mutex::new(ReplyState { result: None, waker: None }));
+    (Reply { state: state.clone() }, Completer { state })
+}
+
+/// An async front end to a `VaultManager` running on its own thread.
+///
+/// Every method returns a `Reply` future. Methods mirroring `VaultManager` resolve to the same
+/// result the synchronous method returns; `run` gives access to the rest of its API.
+pub struct VaultManagerAsync<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> {
+    sender: Sender<Message<T>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized + Send + Sync + 'static> VaultManagerAsync<T> {
+    /// Moves a manager onto a thread of its own.
+    ///
+    /// # Arguments
+    ///
+    /// * `vault_manager` - The manager to serve calls from.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Self, String>` - The async front end if successful, or an error message if the thread couldn't be started.
+    pub fn new(vault_manager: VaultManager<T>) -> Result<Self, String> {
+        let (sender, receiver) = mpsc::channel::<Message<T>>();
+        let handle = thread::Builder::new()
+            .name("pebblevault-async".to_string())
+            .spawn(move || {
+                let mut vault_manager = vault_manager;
+                while let Ok(message) = receiver.recv() {
+                    match message {
+                        Message::Run(job) => job(&mut vault_manager),
+                        Message::Shutdown(completer) => {
+                            completer.complete(vault_manager.shutdown());
+                            return;
+                        }
+                    }
+                }
+            })
+            .map_err(|e| format!("Failed to start VaultManager thread: {}", e))?;
+        Ok(VaultManagerAsync { sender, handle: Some(handle) })
+    }
+
+    /// Runs a closure against the manager on its thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManagerAsync, CustomData};
+    /// # async fn example(vault: VaultManagerAsync<CustomData>) -> Result<(), String> {
+    /// let regions = vault.run(|vm| vm.region_count()).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn run<R, F>(&self, call: F) -> Reply<R>
+    where
+        R: Send + 'static,
+        F: FnOnce(&mut VaultManager<T>) -> R + Send + 'static,
+    {
+        self.try_run(move |vault_manager| Ok(call(vault_manager)))
+    }
+
+    /// Runs a fallible closure against the manager, flattening its error into the reply's.
+    fn try_run<R, F>(&self, call: F) -> Reply<R>
+    where
+        R: Send + 'static,
+        F: FnOnce(&mut VaultManager<T>) -> Result<R, String> + Send + 'static,
+    {
+        let (reply, completer) = reply();
+        // If the thread is gone the job is dropped, and the completer answers with an error
+        let _ = self.sender.send(Message::Run(Box::new(move |vault_manager| completer.complete(call(vault_manager)))));
+        reply
+    }
+
+    /// Adds an object to a region. See `VaultManager::add_spatial_object`.
+    pub fn add_spatial_object(&self, region_id: Uuid, object: SpatialObject<T>) -> Reply<()> {
+        self.try_run(move |vm| vm.add_spatial_object(region_id, object))
+    }
+
+    /// Gets an object by its ID. See `VaultManager::get_object`.
+    pub fn get_object(&self, object_id: Uuid) -> Reply<Option<SpatialObject<T>>> {
+        self.try_run(move |vm| vm.get_object(object_id))
+    }
+
+    /// Queries the objects of a region within a bounding box. See `VaultManager::query_region`.
+    pub fn query_region(&self, region_id: Uuid, min: [f64; 3], max: [f64; 3]) -> Reply<Vec<SpatialObject<T>>> {
+        self.try_run(move |vm| vm.query_region(region_id, min[0], min[1], min[2], max[0], max[1], max[2]))
+    }
+
+    /// Updates an object in memory. See `VaultManager::update_object`.
+    pub fn update_object(&self, object: SpatialObject<T>) -> Reply<u64> {
+        self.try_run(move |vm| vm.update_object(&object))
+    }
+
+    /// Moves an object, changing its region if needed. See `VaultManager::move_object`.
+    pub fn move_object(&self, object_id: Uuid, new_position: [f64; 3]) -> Reply<Uuid> {
+        self.try_run(move |vm| vm.move_object(object_id, new_position))
+    }
+
+    /// Removes an object. See `VaultManager::remove_object`.
+    pub fn remove_object(&self, object_id: Uuid) -> Reply<()> {
+        self.try_run(move |vm| vm.remove_object(object_id))
+    }
+
+    /// Applies several mutations atomically. See `VaultManager::apply_batch`.
+    pub fn apply_batch(&self, mutations: Vec<Mutation<T>>) -> Reply<()> {
+        self.try_run(move |vm| vm.apply_batch(mutations))
+    }
+
+    /// Persists all in-memory changes. See `VaultManager::persist_to_disk`.
+    pub fn persist_to_disk(&self) -> Reply<()> {
+        self.try_run(|vm| vm.persist_to_disk())
+    }
+
+    /// Shuts the manager down gracefully once every earlier call has been answered.
+    ///
+    /// See `VaultManager::shutdown`.
+    pub fn shutdown(mut self) -> Reply<()> {
+        let (reply, completer) = reply();
+        let _ = self.sender.send(Message::Shutdown(completer));
+        // The thread exits on its own; joining here would block the caller's executor
+        self.handle.take();
+        reply
+    }
+}
+
+impl<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> Drop for VaultManagerAsync<T> {
+    /// Waits for the manager's thread to finish the calls already made and drop the manager.
+    fn drop(&mut self) {
+        if let Some(handle) = self.handle.take() {
+            // Closing the channel ends the thread's loop
+            let (sender, _) = mpsc::channel();
+            drop(std::mem::replace(&mut self.sender, sender));
+            let _ = handle.join();
+        }
+    }
+}
@@ -0,0 +1,182 @@
+//! # Background Auto-Persist
+//!
+//! This module provides `AutoPersist`, the settings of an optional background thread that
+//! flushes changed objects to the persistent database, so game servers don't have to schedule
+//! `VaultManager::persist_to_disk` on their main tick.
+//!
+//! The worker opens its own connection to the database file. It flushes one region at a time,
+//! keeping the region locked while its objects are written, so a flush never overwrites a change
+//! made to the region after the flushed objects were read.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use std::time::Duration;
+//! use your_crate::{AutoPersist, VaultManager, CustomData};
+//!
+//! let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+//! vault_manager.enable_auto_persist(AutoPersist::every(Duration::from_secs(30)).or_after(1_000)).unwrap();
+//!
+//! // ... run the game ...
+//!
+//! // Drains the worker and flushes whatever is left
+//! vault_manager.shutdown().unwrap();
+//! ```
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex, Weak};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use crate::lock_stats::LockRecorder;
+use crate::structs::{SpatialObject, VaultRegion};
+use crate::vault_manager::write_points;
+use crate::MySQLGeo::Database;
+
+/// When the background worker flushes changed objects.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AutoPersist {
+    /// Time between flushes
+    pub interval: Duration,
+    /// Number of in-memory changes that trigger a flush before the interval is over.
+    ///
+    /// `None` flushes on the interval only.
+    pub max_mutations: Option<usize>,
+}
+
+impl AutoPersist {
+    /// Flushes changed objects every `interval`.
+    pub fn every(interval: Duration) -> Self {
+        AutoPersist { interval, max_mutations: None }
+    }
+
+    /// Additionally flushes as soon as `mutations` changes have accumulated.
+    pub fn or_after(mut self, mutations: usize) -> Self {
+        self.max_mutations = Some(mutations);
+        self
+    }
+}
+
+/// The regions a worker flushes. Weak references let removed regions be freed.
+type SharedRegions<T> = Arc<Mutex<Vec<(Uuid, Weak<Mutex<VaultRegion<T>>>)>>>;
+
+/// Messages sent to the worker thread.
+enum Signal {
+    /// Flush now instead of waiting for the interval
+    Flush,
+    /// Flush one last time and exit
+    Stop,
+}
+
+/// A running background persistence thread.
+pub(crate) struct AutoPersistWorker<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> {
+    regions: SharedRegions<T>,
+    sender: Sender<Signal>,
+    handle: Option<JoinHandle<Result<(), String>>>,
+    mutations: AtomicUsize,
+    max_mutations: Option<usize>,
+}
+
+impl<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized + Send + Sync + 'static> AutoPersistWorker<T> {
+    /// Starts a worker writing through `db`, which must be a connection of its own.
+    pub(crate) fn start(settings: AutoPersist, db: Database, locks: Arc<LockRecorder>) -> Result<Self, String> {
+        let regions: SharedRegions<T> = Arc::default();
+        let (sender, receiver) = mpsc::channel();
+        let shared = regions.clone();
+        let handle = thread::Builder::new()
+            .name("pebblevault-auto-persist".to_string())
+            .spawn(move || loop {
+                let stop = match receiver.recv_timeout(settings.interval) {
+                    Ok(Signal::Flush) | Err(RecvTimeoutError::Timeout) => false,
+                    Ok(Signal::Stop) | Err(RecvTimeoutError::Disconnected) => true,
+                };
+                let flushed = flush(&db, &shared, &locks);
+                if stop {
+                    return flushed;
+                }
+                if let Err(e) = flushed {
+                    eprintln!("Warning: auto-persist failed, retrying next interval: {}", e);
+                }
+            })
+            .map_err(|e| format!("Failed to start auto-persist thread: {}", e))?;
+
+        Ok(AutoPersistWorker {
+            regions,
+            sender,
+            handle: Some(handle),
+            mutations: AtomicUsize::new(0),
+            max_mutations: settings.max_mutations,
+        })
+    }
+}
+
+impl<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> AutoPersistWorker<T> {
+    /// Replaces the set of regions the worker flushes.
+    pub(crate) fn set_regions(&self, regions: Vec<(Uuid, Weak<Mutex<VaultRegion<T>>>)>) {
+        *self.regions.lock().unwrap() = regions;
+    }
+
+    /// Counts an in-memory change, waking the worker once `max_mutations` have accumulated.
+    pub(crate) fn record_mutation(&self) {
+        let Some(max_mutations) = self.max_mutations else {
+            return;
+        };
+        if self.mutations.fetch_add(1, Ordering::Relaxed) + 1 >= max_mutations {
+            self.mutations.store(0, Ordering::Relaxed);
+            let _ = self.sender.send(Signal::Flush);
+        }
+    }
+
+    /// Stops the worker after a final flush and waits for it to exit.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), String>` - An empty result if the final flush succeeded, or an error message if not.
+    pub(crate) fn stop(&mut self) -> Result<(), String> {
+        let Some(handle) = self.handle.take() else {
+            return Ok(());
+        };
+        let _ = self.sender.send(Signal::Stop);
+        handle.join().map_err(|_| "Auto-persist thread panicked".to_string())?
+    }
+}
+
+impl<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> Drop for AutoPersistWorker<T> {
+    /// Stops the worker so the thread never outlives the manager.
+    fn drop(&mut self) {
+        if let Err(e) = self.stop() {
+            eprintln!("Warning: final auto-persist failed: {}", e);
+        }
+    }
+}
+
+/// Writes the changed objects of every region, one region per transaction.
+fn flush<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized>(db: &Database, regions: &SharedRegions<T>, locks: &LockRecorder) -> Result<(), String> {
+    let regions = regions.lock().unwrap().clone();
+    for (region_id, region) in regions {
+        let Some(region) = region.upgrade() else {
+            continue;
+        };
+        let mut region = locks.acquire(region_id, &region);
+        if region.dirty_count() == 0 {
+            continue;
+        }
+        let objects: Vec<SpatialObject<T>> = region.rtree.iter()
+            .filter(|obj| region.is_dirty(obj.uuid))
+            .cloned()
+            .collect();
+
+        db.begin_transaction()
+            .map_err(|e| format!("Failed to begin transaction: {}", e))?;
+        if let Err(e) = write_points(db, region_id, &objects) {
+            let _ = db.rollback_transaction();
+            return Err(e);
+        }
+        db.commit_transaction()
+            .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+        region.take_dirty();
+    }
+    Ok(())
+}
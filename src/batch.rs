@@ -0,0 +1,154 @@
+//! # Batch Mutations
+//!
+//! This module defines `Mutation`, a single change to the objects of a `VaultManager`. A list of
+//! mutations is applied with `VaultManager::apply_batch`, which applies all of them or none, so a
+//! composite game action can't be left half done.
+//!
+//! `VaultTransaction`, started with `VaultManager::begin`, collects mutations as the game logic
+//! produces them and applies them as one batch on `commit`. Nothing changes until then, so a
+//! transaction that's rolled back or dropped leaves the vault untouched.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use your_crate::{Mutation, SpatialObject, VaultManager, CustomData};
+//! use uuid::Uuid;
+//! use std::sync::Arc;
+//!
+//! let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+//! # let region_id = Uuid::new_v4();
+//! # let (wood, stone) = (Uuid::new_v4(), Uuid::new_v4());
+//! // Crafting a building consumes its resources
+//! let building = SpatialObject::new(Uuid::new_v4(), "building", [10.0, 0.0, 10.0], Arc::new(CustomData { /* ... */ })).unwrap();
+//! vault_manager.apply_batch(vec![
+//!     Mutation::Remove { object_id: wood },
+//!     Mutation::Remove { object_id: stone },
+//!     Mutation::Add { region_id, object: building },
+//! ]).expect("Failed to craft building");
+//!
+//! // The same, collected step by step
+//! # let building = SpatialObject::new(Uuid::new_v4(), "building", [10.0, 0.0, 10.0], Arc::new(CustomData { /* ... */ })).unwrap();
+//! let mut transaction = vault_manager.begin();
+//! transaction.remove(wood).remove(stone);
+//! transaction.add(region_id, building);
+//! transaction.commit().expect("Failed to craft building");
+//! ```
+
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use crate::structs::SpatialObject;
+use crate::vault_manager::VaultManager;
+
+/// A single change applied by `VaultManager::apply_batch`.
+#[derive(Clone)]
+pub enum Mutation<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> {
+    /// Adds an object to a region
+    Add {
+        /// The region to add the object to
+        region_id: Uuid,
+        /// The object to add
+        object: SpatialObject<T>,
+    },
+    /// Removes an object from whichever region holds it
+    Remove {
+        /// The object to remove
+        object_id: Uuid,
+    },
+    /// Moves an object, changing its region if the new position lies outside the current one
+    Move {
+        /// The object to move
+        object_id: Uuid,
+        /// The object's new position [x, y, z]
+        position: [f64; 3],
+    },
+    /// Replaces an object with a new version, keeping it in its region
+    Update {
+        /// The new version of the object, identified by its UUID
+        object: SpatialObject<T>,
+    },
+}
+
+impl<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> Mutation<T> {
+    /// Returns the UUID of the object the mutation changes.
+    pub fn object_id(&self) -> Uuid {
+        match self {
+            Mutation::Add { object, .. } | Mutation::Update { object } => object.uuid,
+            Mutation::Remove { object_id } | Mutation::Move { object_id, .. } => *object_id,
+        }
+    }
+}
+
+/// Mutations collected to be applied together, started with `VaultManager::begin`.
+///
+/// The mutations are only applied by `commit`, all of them or none, as one `apply_batch`.
+/// Dropping the transaction without committing discards them, like `rollback`.
+#[must_use = "a transaction does nothing unless it's committed"]
+pub struct VaultTransaction<'a, T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> {
+    /// The vault the mutations are applied to
+    vault: &'a VaultManager<T>,
+    /// The mutations collected so far, in order
+    mutations: Vec<Mutation<T>>,
+}
+
+impl<'a, T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> VaultTransaction<'a, T> {
+    /// Creates an empty transaction on `vault`.
+    pub(crate) fn new(vault: &'a VaultManager<T>) -> Self {
+        VaultTransaction { vault, mutations: Vec::new() }
+    }
+
+    /// Adds an object to a region when the transaction is committed.
+    pub fn add(&mut self, region_id: Uuid, object: SpatialObject<T>) -> &mut Self {
+        self.push(Mutation::Add { region_id, object })
+    }
+
+    /// Removes an object when the transaction is committed.
+    pub fn remove(&mut self, object_id: Uuid) -> &mut Self {
+        self.push(Mutation::Remove { object_id })
+    }
+
+    /// Moves an object when the transaction is committed, changing its region if needed.
+    pub fn move_object(&mut self, object_id: Uuid, position: [f64; 3]) -> &mut Self {
+        self.push(Mutation::Move { object_id, position })
+    }
+
+    /// Replaces an object with a new version when the transaction is committed.
+    pub fn update(&mut self, object: SpatialObject<T>) -> &mut Self {
+        self.push(Mutation::Update { object })
+    }
+
+    /// Adds any mutation to the transaction.
+    pub fn push(&mut self, mutation: Mutation<T>) -> &mut Self {
+        self.mutations.push(mutation);
+        self
+    }
+
+    /// Returns the mutations collected so far, in order.
+    pub fn mutations(&self) -> &[Mutation<T>] {
+        &self.mutations
+    }
+
+    /// Returns the number of mutations collected so far.
+    pub fn len(&self) -> usize {
+        self.mutations.len()
+    }
+
+    /// Returns `true` if no mutation has been collected.
+    pub fn is_empty(&self) -> bool {
+        self.mutations.is_empty()
+    }
+
+    /// Applies every collected mutation, or none if any of them fails.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), String>` - An empty result if every mutation was applied, or an error message if none was.
+    pub fn commit(self) -> Result<(), String> {
+        if self.mutations.is_empty() {
+            return Ok(());
+        }
+        self.vault.apply_batch(self.mutations)
+    }
+
+    /// Discards every collected mutation without applying any.
+    pub fn rollback(self) {}
+}
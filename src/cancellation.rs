@@ -0,0 +1,105 @@
+//! # Query Limits and Cancellation
+//!
+//! This module provides the types used to bound how long a query or persistence operation may run.
+//! A pathological query over a dense region can otherwise stall a game tick indefinitely.
+//!
+//! ## Key Components
+//!
+//! - `CancellationToken`: A cheap, cloneable flag that can be set from another thread to abort an operation.
+//! - `QueryLimits`: An optional deadline and an optional cancellation token passed to `VaultManager` APIs.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use std::time::Duration;
+//! use your_crate::{CancellationToken, QueryLimits};
+//!
+//! let token = CancellationToken::new();
+//! let limits = QueryLimits::with_timeout(Duration::from_millis(2)).cancellable(token.clone());
+//!
+//! // Later, possibly from another thread
+//! token.cancel();
+//! assert!(limits.check().is_err());
+//! ```
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// A shared flag used to cancel a running operation.
+///
+/// Clones share the same flag, so a token can be handed to an operation while another
+/// thread keeps a clone to cancel it.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken {
+    /// Set once the operation should stop
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Creates a new token that is not cancelled.
+    pub fn new() -> Self {
+        CancellationToken::default()
+    }
+
+    /// Requests cancellation of every operation observing this token.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// Returns `true` if `cancel` has been called on this token or any of its clones.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+/// Bounds on how long an operation may run.
+///
+/// The default value has no deadline and no cancellation token, so the operation always runs to completion.
+#[derive(Clone, Debug, Default)]
+pub struct QueryLimits {
+    /// Point in time after which the operation is aborted
+    pub deadline: Option<Instant>,
+    /// Token that aborts the operation when cancelled
+    pub cancellation: Option<CancellationToken>,
+}
+
+impl QueryLimits {
+    /// Creates limits that never abort the operation.
+    pub fn none() -> Self {
+        QueryLimits::default()
+    }
+
+    /// Creates limits that abort the operation once `timeout` has elapsed from now.
+    pub fn with_timeout(timeout: Duration) -> Self {
+        QueryLimits {
+            deadline: Some(Instant::now() + timeout),
+            cancellation: None,
+        }
+    }
+
+    /// Adds a cancellation token to these limits.
+    pub fn cancellable(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Checks whether the operation may continue.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), String>` - Ok if the operation may continue, or an error message if it was cancelled or ran past its deadline.
+    pub fn check(&self) -> Result<(), String> {
+        if let Some(token) = &self.cancellation {
+            if token.is_cancelled() {
+                return Err("Operation cancelled".to_string());
+            }
+        }
+        if let Some(deadline) = self.deadline {
+            if Instant::now() >= deadline {
+                return Err("Operation deadline exceeded".to_string());
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,33 @@
+//! # Backend Capabilities
+//!
+//! This module defines `BackendCapabilities`, a description of what the persistent storage
+//! backend can do natively. The manager checks it to pick the best strategy for an operation
+//! rather than always falling back to the lowest common denominator (persistence, for example,
+//! only writes points in multi-row batches when `batch_writes` is set), and callers can inspect
+//! it through `VaultManager::backend_capabilities`.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use your_crate::{VaultManager, CustomData};
+//!
+//! let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+//! if !vault_manager.backend_capabilities().spatial_index {
+//!     println!("Cold queries will scan stored points");
+//! }
+//! ```
+
+/// Features a persistent storage backend supports natively.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BackendCapabilities {
+    /// Groups of writes can be committed or rolled back atomically
+    pub transactions: bool,
+    /// Spatial queries are answered from an index instead of a scan
+    pub spatial_index: bool,
+    /// Query results can be read row by row without loading them all at once
+    pub streaming: bool,
+    /// Many rows can be written with a single statement
+    pub batch_writes: bool,
+    /// Custom data can be filtered by the backend without deserializing it
+    pub json_queries: bool,
+}
@@ -0,0 +1,77 @@
+//! # Late-Join Catch-Up
+//!
+//! This module defines the types of `VaultManager::catch_up`, which brings a reconnecting client
+//! up to date with a region. The client keeps the `CatchUpToken` it was last sent and hands it
+//! back on reconnect. If the region still remembers every change made since that token, the
+//! client gets a delta of just those changes; otherwise it gets a full snapshot of the region.
+//!
+//! Tokens are cheap to keep and serializable, so a server can send them to clients along with
+//! the region state.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use your_crate::{CatchUp, VaultManager, CustomData};
+//!
+//! let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+//! # let region_id = uuid::Uuid::new_v4();
+//! # let client_token = None;
+//! match vault_manager.catch_up(region_id, client_token).unwrap() {
+//!     CatchUp::Snapshot { objects, token } => { /* replace the client's view of the region */ }
+//!     CatchUp::Delta { changed, removed, token } => { /* patch the client's view */ }
+//! }
+//! ```
+
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use crate::structs::SpatialObject;
+
+/// The state of a region a client was last brought up to date with.
+///
+/// Tokens are only meaningful to the region that issued them. A token from before the region
+/// was reopened, split or merged is answered with a full snapshot, or an error if the region
+/// no longer exists.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CatchUpToken {
+    /// The region the token was issued by
+    pub region_id: Uuid,
+    /// Instance of the region in memory; changes whenever the manager is reopened
+    pub(crate) epoch: Uuid,
+    /// Revision of the region at the time the token was issued
+    pub(crate) revision: u64,
+}
+
+/// What a client needs to catch up with a region.
+#[derive(Clone, PartialEq)]
+pub enum CatchUp<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> {
+    /// The full contents of the region, replacing whatever the client had
+    Snapshot {
+        /// Every object in the region
+        objects: Vec<SpatialObject<T>>,
+        /// Token to catch up from next time
+        token: CatchUpToken,
+    },
+    /// The changes since the client's token
+    Delta {
+        /// Objects added or changed since the token, in their current state
+        changed: Vec<SpatialObject<T>>,
+        /// UUIDs of objects that left the region since the token
+        removed: Vec<Uuid>,
+        /// Token to catch up from next time
+        token: CatchUpToken,
+    },
+}
+
+impl<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> CatchUp<T> {
+    /// Returns the token to hand back on the next catch-up.
+    pub fn token(&self) -> CatchUpToken {
+        match self {
+            CatchUp::Snapshot { token, .. } | CatchUp::Delta { token, .. } => *token,
+        }
+    }
+
+    /// Returns `true` if this is a full snapshot rather than a delta.
+    pub fn is_snapshot(&self) -> bool {
+        matches!(self, CatchUp::Snapshot { .. })
+    }
+}
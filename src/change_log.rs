@@ -0,0 +1,102 @@
+//! # Change Feed
+//!
+//! This module defines `Change`, an entry of the change log a `VaultManager` keeps when
+//! `VaultConfig::change_log_capacity` is set. Every mutation event is recorded in the log with
+//! a sequence number, in the order the changes were made. External systems poll
+//! `VaultManager::changes_since` with the last sequence number they saw to replicate the world
+//! or audit what happened to it, without having to stay subscribed.
+//!
+//! The log is an in-memory ring buffer: once it's full, the oldest changes are dropped. A
+//! reader that fell further behind than the log reaches gets an error, and has to resync from
+//! a full copy of the world before following the feed again. The log starts empty, at sequence
+//! number zero, whenever the manager is opened.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use your_crate::{VaultManager, VaultConfig, CustomData};
+//!
+//! let config = VaultConfig { change_log_capacity: 100_000, ..VaultConfig::default() };
+//! let vault_manager: VaultManager<CustomData> = VaultManager::with_config("path/to/database.db", config).unwrap();
+//! let mut last_seen = vault_manager.latest_change();
+//! // ... every second ...
+//! for change in vault_manager.changes_since(last_seen).unwrap() {
+//!     last_seen = change.seq;
+//!     /* forward change.event to the replica */
+//! }
+//! ```
+
+use std::collections::VecDeque;
+use std::time::SystemTime;
+use serde::{Serialize, Deserialize};
+use crate::events::VaultEvent;
+
+/// A change recorded in the change log of a `VaultManager`.
+#[derive(Clone, PartialEq)]
+pub struct Change<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> {
+    /// Sequence number of the change, one more than the change before it
+    pub seq: u64,
+    /// Time the change was recorded
+    pub recorded_at: SystemTime,
+    /// What changed
+    pub event: VaultEvent<T>,
+}
+
+/// The most recent changes made to a `VaultManager`, up to a fixed number of them.
+pub(crate) struct ChangeLog<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> {
+    /// Number of changes kept; zero disables the log
+    capacity: usize,
+    /// Sequence number of the last change recorded
+    latest: u64,
+    /// The retained changes, oldest first
+    changes: VecDeque<Change<T>>,
+}
+
+impl<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> ChangeLog<T> {
+    /// Creates an empty log keeping up to `capacity` changes.
+    pub(crate) fn new(capacity: usize) -> Self {
+        ChangeLog { capacity, latest: 0, changes: VecDeque::new() }
+    }
+
+    /// Returns `true` if changes are recorded at all.
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.capacity > 0
+    }
+
+    /// Returns the sequence number of the last change recorded, or zero if there was none.
+    pub(crate) fn latest(&self) -> u64 {
+        self.latest
+    }
+
+    /// Records a change, dropping the oldest one if the log is full.
+    pub(crate) fn record(&mut self, event: VaultEvent<T>, recorded_at: SystemTime) {
+        if !self.is_enabled() {
+            return;
+        }
+        if self.changes.len() == self.capacity {
+            self.changes.pop_front();
+        }
+        self.latest += 1;
+        self.changes.push_back(Change { seq: self.latest, recorded_at, event });
+    }
+
+    /// Returns every change with a sequence number above `seq`, oldest first.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<Change<T>>, String>` - The changes, or an error message if some of them were dropped or `seq` is unknown to the log.
+    pub(crate) fn since(&self, seq: u64) -> Result<Vec<Change<T>>, String> {
+        if !self.is_enabled() {
+            return Err("The change log is disabled".to_string());
+        }
+        if seq > self.latest {
+            return Err(format!("Sequence number {} is ahead of the change log, which ends at {}", seq, self.latest));
+        }
+        let oldest = self.changes.front().map_or(self.latest + 1, |change| change.seq);
+        if seq + 1 < oldest {
+            return Err(format!("Changes after {} are no longer retained; the oldest change kept is {}", seq, oldest));
+        }
+        let skip = (seq + 1 - oldest) as usize;
+        Ok(self.changes.iter().skip(skip).cloned().collect())
+    }
+}
@@ -0,0 +1,180 @@
+//! # VaultManager Configuration
+//!
+//! This module defines `VaultConfig`, the set of options used when opening a `VaultManager`.
+//! Every option has a conservative default, so `VaultConfig::default()` behaves exactly like
+//! `VaultManager::new`.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use your_crate::{VaultManager, VaultConfig, CustomData};
+//!
+//! let config = VaultConfig {
+//!     lenient_load: true,
+//!     ..VaultConfig::default()
+//! };
+//! let vault_manager: VaultManager<CustomData> = VaultManager::with_config("path/to/database.db", config).unwrap();
+//! ```
+
+use std::path::PathBuf;
+
+/// Options controlling how a `VaultManager` is opened and how it behaves.
+#[derive(Clone, Debug, Default)]
+pub struct VaultConfig {
+    /// Skip objects that fail to load at startup instead of aborting.
+    ///
+    /// Skipped objects are moved to quarantine and listed in the manager's `LoadReport`.
+    pub lenient_load: bool,
+    /// Store custom data payloads larger than this many bytes compressed.
+    ///
+    /// Smaller payloads are stored as plain JSON. `None` disables compression.
+    pub compression_threshold: Option<usize>,
+    /// Algorithm large custom data payloads are compressed with.
+    ///
+    /// Payloads record how they were compressed, so changing it never affects reading existing data.
+    pub compression: Compression,
+    /// Number of removed objects' custom data allocations to keep for reuse.
+    ///
+    /// Allocations are handed out again by `VaultManager::pooled_custom_data`. Zero disables pooling.
+    pub object_pool_capacity: usize,
+    /// Extent of the game world. Objects outside it are handled according to `out_of_bounds`.
+    ///
+    /// `None` accepts any finite position. Non-finite coordinates (NaN or infinity) are always rejected.
+    pub world_bounds: Option<WorldBounds>,
+    /// What to do with objects positioned outside `world_bounds`
+    pub out_of_bounds: BoundsPolicy,
+    /// Radius of the regions `VaultManager::add_object_auto` creates for positions no region contains.
+    ///
+    /// New regions are centered on a grid with a spacing of twice the radius, so they tile the
+    /// world without overlapping. `None` makes `add_object_auto` fail instead.
+    pub auto_region_radius: Option<f64>,
+    /// Number of objects above which a region is split into eight octants.
+    ///
+    /// Regions are checked by `VaultManager::split_oversized_regions` and after every
+    /// `VaultManager::add_object_auto`. `None` never splits regions.
+    pub max_objects_per_region: Option<usize>,
+    /// How object and region UUIDs are stored in the persistent database.
+    ///
+    /// If the database uses a different format, it's migrated when the manager is opened.
+    /// `None` keeps the format the database already uses; new databases use `KeyFormat::Text`.
+    pub key_format: Option<KeyFormat>,
+    /// Number of regions whose objects are kept in memory.
+    ///
+    /// When a region is loaded and the limit is exceeded, the least recently used regions are
+    /// flushed to the persistent database and unloaded. They're reloaded transparently the next
+    /// time they're accessed. `None` keeps every region loaded.
+    pub max_loaded_regions: Option<usize>,
+    /// Load only region metadata at startup and read each region's objects on first access.
+    ///
+    /// Makes opening a large world fast. Objects that fail to load later are quarantined like at
+    /// startup, but aren't listed in the `LoadReport`.
+    pub lazy_load: bool,
+    /// Reject objects whose type isn't registered with `VaultManager::register_object_type`.
+    ///
+    /// Objects of registered types are always checked against their `TypeDefinition`.
+    pub reject_unknown_types: bool,
+    /// Number of changes kept in the change log read by `VaultManager::changes_since`.
+    ///
+    /// Every mutation event is recorded with a sequence number; once the log is full, the oldest
+    /// changes are dropped. Zero disables the change log.
+    pub change_log_capacity: usize,
+    /// Order in which queries return their results.
+    ///
+    /// By default results come back in the order of the spatial index, which depends on the order
+    /// objects were inserted in and can differ between runs. Servers that must produce identical
+    /// results on every machine, such as lockstep simulations, should pick a sorted order.
+    pub query_order: QueryOrder,
+    /// Directory `VaultManager::archive_cold_objects` moves cold objects to.
+    ///
+    /// Archived objects are kept in compressed chunk files and faulted back in by
+    /// `VaultManager::get_object`. `None` disables archival.
+    pub archive_dir: Option<PathBuf>,
+    /// Journal changes made in memory, such as by `VaultManager::update_object`, before acknowledging them.
+    ///
+    /// Each change is appended to a journal in the persistent database, which `VaultManager::new`
+    /// replays on the next start, so a crash between persists loses nothing. Costs a small write
+    /// per change.
+    pub journal: bool,
+}
+
+/// An axis-aligned box enclosing the whole game world.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WorldBounds {
+    /// Minimum corner of the world [x, y, z]
+    pub min: [f64; 3],
+    /// Maximum corner of the world [x, y, z]
+    pub max: [f64; 3],
+}
+
+impl WorldBounds {
+    /// Creates world bounds from two corners.
+    pub fn new(min: [f64; 3], max: [f64; 3]) -> Self {
+        WorldBounds { min, max }
+    }
+
+    /// Returns `true` if `point` lies inside the bounds, including on their faces.
+    pub fn contains(&self, point: [f64; 3]) -> bool {
+        (0..3).all(|axis| point[axis] >= self.min[axis] && point[axis] <= self.max[axis])
+    }
+
+    /// Returns the point inside the bounds closest to `point`.
+    pub fn clamp(&self, point: [f64; 3]) -> [f64; 3] {
+        [
+            point[0].clamp(self.min[0], self.max[0]),
+            point[1].clamp(self.min[1], self.max[1]),
+            point[2].clamp(self.min[2], self.max[2]),
+        ]
+    }
+}
+
+/// How positions outside the configured world bounds are handled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BoundsPolicy {
+    /// Fail the operation with an error
+    #[default]
+    Reject,
+    /// Move the object to the nearest position inside the world
+    Clamp,
+}
+
+/// The order query results are returned in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum QueryOrder {
+    /// The order of the spatial index, which is the fastest but can differ between runs
+    #[default]
+    Index,
+    /// Sorted by object UUID
+    Uuid,
+    /// Sorted by distance from the query's center, ties broken by object UUID
+    Distance,
+}
+
+/// The algorithm custom data payloads above `VaultConfig::compression_threshold` are compressed with.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Compression {
+    /// zstd, which makes payloads smallest
+    #[default]
+    Zstd,
+    /// LZ4, which compresses less but is several times faster to compress and decompress
+    Lz4,
+}
+
+/// How UUID keys are stored in the persistent database.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum KeyFormat {
+    /// 36-character hyphenated text, readable in any SQL tool
+    #[default]
+    Text,
+    /// 16-byte blobs, which make the primary key indexes less than half the size
+    Binary,
+}
+
+impl KeyFormat {
+    /// Returns the name stored in the database's metadata.
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            KeyFormat::Text => "text",
+            KeyFormat::Binary => "binary",
+        }
+    }
+}
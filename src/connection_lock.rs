@@ -0,0 +1,132 @@
+//! # Connection Locking
+//!
+//! This module provides `ConnectionLock`, which lets a SQLite connection be shared between
+//! threads. A `rusqlite::Connection` can be moved between threads but not used by two at once,
+//! and a transaction spans several calls, during which no other thread may slip statements in.
+//!
+//! The lock is reentrant: a thread already holding the connection can lock it again, so
+//! database methods can call each other freely. A transaction holds the connection from
+//! `BEGIN` until it ends, blocking other threads for its whole duration.
+
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::sync::{Condvar, Mutex};
+use std::thread::{self, ThreadId};
+use rusqlite::Connection;
+
+/// The thread holding a `ConnectionLock`.
+struct Owner {
+    /// The holding thread
+    thread: ThreadId,
+    /// Number of live guards, plus one while a transaction is open
+    depth: usize,
+    /// Whether a transaction keeps the connection held
+    in_transaction: bool,
+}
+
+/// A connection usable by one thread at a time.
+pub(crate) struct ConnectionLock {
+    conn: Connection,
+    /// The thread holding the connection, if any
+    owner: Mutex<Option<Owner>>,
+    /// Signalled when the connection is released
+    released: Condvar,
+}
+
+// SAFETY: `Connection` is `Send` but not `Sync`. It's only reachable through a `ConnectionGuard`,
+// guards can't leave the thread that created them, and `acquire` only creates guards on the
+// thread recorded as the owner, so the connection is never used by two threads at once.
+unsafe impl Sync for ConnectionLock {}
+
+/// Access to a locked connection, releasing it when dropped.
+pub(crate) struct ConnectionGuard<'a> {
+    lock: &'a ConnectionLock,
+    /// Keeps the guard on the thread that holds the lock
+    _not_send: PhantomData<*const ()>,
+}
+
+impl ConnectionLock {
+    /// Wraps a connection.
+    pub(crate) fn new(conn: Connection) -> Self {
+        ConnectionLock { conn, owner: Mutex::new(None), released: Condvar::new() }
+    }
+
+    /// Locks the connection for the current thread, waiting while another thread holds it.
+    pub(crate) fn lock(&self) -> ConnectionGuard<'_> {
+        self.acquire();
+        ConnectionGuard { lock: self, _not_send: PhantomData }
+    }
+
+    /// Keeps the connection held by the current thread until `end_transaction`.
+    ///
+    /// Must be called while holding a guard, right after the transaction began.
+    pub(crate) fn begin_transaction(&self) {
+        self.acquire();
+        let mut owner = self.owner.lock().unwrap();
+        if let Some(owner) = owner.as_mut() {
+            owner.in_transaction = true;
+        }
+    }
+
+    /// Lets go of the hold taken by `begin_transaction`, if the current thread has one.
+    pub(crate) fn end_transaction(&self) {
+        let in_transaction = {
+            let mut owner = self.owner.lock().unwrap();
+            match owner.as_mut() {
+                Some(owner) if owner.thread == thread::current().id() && owner.in_transaction => {
+                    owner.in_transaction = false;
+                    true
+                }
+                _ => false,
+            }
+        };
+        if in_transaction {
+            self.release();
+        }
+    }
+
+    /// Records the current thread as the owner, waiting for other threads to release the connection.
+    fn acquire(&self) {
+        let current = thread::current().id();
+        let mut owner = self.owner.lock().unwrap();
+        loop {
+            match owner.as_mut() {
+                None => {
+                    *owner = Some(Owner { thread: current, depth: 1, in_transaction: false });
+                    return;
+                }
+                Some(held) if held.thread == current => {
+                    held.depth += 1;
+                    return;
+                }
+                Some(_) => owner = self.released.wait(owner).unwrap(),
+            }
+        }
+    }
+
+    /// Undoes one `acquire`, releasing the connection once it's no longer held.
+    fn release(&self) {
+        let mut owner = self.owner.lock().unwrap();
+        if let Some(held) = owner.as_mut() {
+            held.depth -= 1;
+            if held.depth == 0 {
+                *owner = None;
+                self.released.notify_one();
+            }
+        }
+    }
+}
+
+impl Deref for ConnectionGuard<'_> {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        &self.lock.conn
+    }
+}
+
+impl Drop for ConnectionGuard<'_> {
+    fn drop(&mut self) {
+        self.lock.release();
+    }
+}
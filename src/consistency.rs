@@ -0,0 +1,107 @@
+//! # Consistency Checking
+//!
+//! This module defines the results of `VaultManager::verify_tick`, an incremental check that the
+//! in-memory spatial indexes and the persistent database agree. Each call samples a few objects
+//! from memory and a few rows from the database and cross-checks them, so a production server
+//! can run the check continuously at a negligible cost per tick and catch bugs such as
+//! unpersisted transfers or missed deletes before they turn into data loss.
+//!
+//! Objects with unpersisted changes are expected to differ from their rows; for them only the
+//! existence and region of the row are checked.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use your_crate::{VaultManager, CustomData};
+//!
+//! let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+//! loop {
+//!     // ... simulate the tick ...
+//!     for divergence in vault_manager.verify_tick(50).unwrap() {
+//!         eprintln!("Index and database disagree: {:?}", divergence);
+//!     }
+//! }
+//! ```
+
+use uuid::Uuid;
+
+/// A way the in-memory index and the persistent database disagree about an object.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DivergenceKind {
+    /// The object is in memory but has no row in the database
+    MissingRow,
+    /// The object's row places it in another region
+    WrongRegion {
+        /// The region the row places the object in
+        stored_region: Uuid,
+    },
+    /// The object has no unpersisted changes, but its row holds different values
+    StaleRow,
+    /// A row places the object in a loaded region that doesn't hold it
+    MissingObject,
+    /// A row places the object in a region that doesn't exist
+    OrphanedRow,
+}
+
+/// A disagreement between the in-memory index and the persistent database.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Divergence {
+    /// The object the two disagree about
+    pub object_id: Uuid,
+    /// The region holding the object in memory, or the region its row names if it isn't in memory
+    pub region_id: Uuid,
+    /// How the two disagree
+    pub kind: DivergenceKind,
+}
+
+/// Running totals of the consistency checks made with `VaultManager::verify_tick`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ConsistencyStats {
+    /// In-memory objects checked against their rows
+    pub objects_checked: u64,
+    /// Database rows checked against the in-memory index
+    pub rows_checked: u64,
+    /// Complete passes over the objects of the loaded regions
+    pub passes: u64,
+    /// Objects in memory without a row
+    pub missing_rows: u64,
+    /// Objects whose row places them in another region
+    pub wrong_regions: u64,
+    /// Unchanged objects whose row holds different values
+    pub stale_rows: u64,
+    /// Rows in loaded regions without an object in memory
+    pub missing_objects: u64,
+    /// Rows naming a region that doesn't exist
+    pub orphaned_rows: u64,
+}
+
+impl ConsistencyStats {
+    /// Returns the number of divergences found so far.
+    pub fn divergences(&self) -> u64 {
+        self.missing_rows + self.wrong_regions + self.stale_rows + self.missing_objects + self.orphaned_rows
+    }
+
+    /// Counts a divergence in the totals.
+    pub(crate) fn record(&mut self, divergence: &Divergence) {
+        match divergence.kind {
+            DivergenceKind::MissingRow => self.missing_rows += 1,
+            DivergenceKind::WrongRegion { .. } => self.wrong_regions += 1,
+            DivergenceKind::StaleRow => self.stale_rows += 1,
+            DivergenceKind::MissingObject => self.missing_objects += 1,
+            DivergenceKind::OrphanedRow => self.orphaned_rows += 1,
+        }
+    }
+}
+
+/// Where the consistency check continues on the next tick.
+#[derive(Debug, Default)]
+pub(crate) struct VerifyCursor {
+    /// Regions left to check in the current pass, in reverse order
+    pub regions: Vec<Uuid>,
+    /// The region being checked and the number of its objects already checked
+    pub current: Option<(Uuid, usize)>,
+    /// The last database row checked, or `None` to start from the first
+    pub last_row: Option<Uuid>,
+    /// Totals so far
+    pub stats: ConsistencyStats,
+}
@@ -0,0 +1,105 @@
+//! # Mutation Events
+//!
+//! This module defines `VaultEvent`, a notification sent to subscribers of a `VaultManager`
+//! whenever an object or region changes. It is the integration point for propagating spatial
+//! changes to other systems, such as replicating them to networked clients.
+//!
+//! Events are delivered through channels returned by `VaultManager::subscribe`. They are sent
+//! once the change is committed to the persistent database, so a failed operation never emits
+//! events. A subscriber whose receiver was dropped is forgotten on the next event.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use your_crate::{VaultEvent, VaultManager, CustomData};
+//! use std::thread;
+//!
+//! let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+//! let events = vault_manager.subscribe();
+//! thread::spawn(move || {
+//!     for event in events {
+//!         match event {
+//!             VaultEvent::ObjectMoved { object_id, position, .. } => { /* replicate the move */ }
+//!             _ => {}
+//!         }
+//!     }
+//! });
+//! ```
+
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use crate::structs::SpatialObject;
+
+/// A change to the objects or regions of a `VaultManager`.
+#[derive(Clone, PartialEq)]
+pub enum VaultEvent<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> {
+    /// An object was added to a region, or restored to it
+    ObjectAdded {
+        /// The region the object was added to
+        region_id: Uuid,
+        /// The object as added
+        object: SpatialObject<T>,
+    },
+    /// An object was removed
+    ObjectRemoved {
+        /// The region the object was removed from
+        region_id: Uuid,
+        /// The UUID of the removed object
+        object_id: Uuid,
+    },
+    /// An object changed position, region or both
+    ObjectMoved {
+        /// The UUID of the moved object
+        object_id: Uuid,
+        /// The region the object was in before the move
+        from_region: Uuid,
+        /// The region the object is in after the move; the same as `from_region` for moves within a region
+        to_region: Uuid,
+        /// The object's new position [x, y, z]
+        position: [f64; 3],
+    },
+    /// An object's type, size or custom data changed
+    ObjectUpdated {
+        /// The region holding the object
+        region_id: Uuid,
+        /// The object as updated
+        object: SpatialObject<T>,
+    },
+    /// A region was created
+    RegionCreated {
+        /// The UUID of the new region
+        region_id: Uuid,
+        /// Center coordinates of the region [x, y, z]
+        center: [f64; 3],
+        /// Radius of the region
+        radius: f64,
+    },
+    /// A region was deleted, split or merged into another
+    RegionRemoved {
+        /// The UUID of the removed region
+        region_id: Uuid,
+    },
+}
+
+impl<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> VaultEvent<T> {
+    /// Returns the events describing how an object went from one state to another.
+    ///
+    /// Each state is the region holding the object and the object, or `None` if it didn't exist.
+    pub(crate) fn for_change(before: Option<(Uuid, &SpatialObject<T>)>, after: Option<(Uuid, &SpatialObject<T>)>) -> Vec<Self> {
+        match (before, after) {
+            (None, None) => Vec::new(),
+            (None, Some((region_id, object))) => vec![VaultEvent::ObjectAdded { region_id, object: object.clone() }],
+            (Some((region_id, object)), None) => vec![VaultEvent::ObjectRemoved { region_id, object_id: object.uuid }],
+            (Some((from_region, before)), Some((to_region, after))) => {
+                let mut events = Vec::new();
+                if from_region != to_region || before.point != after.point {
+                    events.push(VaultEvent::ObjectMoved { object_id: after.uuid, from_region, to_region, position: after.point });
+                }
+                if before.object_type != after.object_type || before.size != after.size || before.custom_data != after.custom_data {
+                    events.push(VaultEvent::ObjectUpdated { region_id: to_region, object: after.clone() });
+                }
+                events
+            }
+        }
+    }
+}
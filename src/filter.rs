@@ -0,0 +1,647 @@
+//! # Filter Expressions
+//!
+//! This module provides `Filter`, a small expression language for selecting spatial objects
+//! without writing Rust closures. Tools, admin consoles and subscription requests can send a
+//! filter as a string, and the server parses it once and evaluates it against each candidate.
+//!
+//! Expressions can't call arbitrary code, loop, or allocate unbounded memory, so it's safe to
+//! accept them from untrusted clients.
+//!
+//! ## Syntax
+//!
+//! - Literals: numbers (`10`, `-2.5`), strings (`"player"`), `true`, `false`, `null` and
+//!   vectors (`[0, 0, 0]`).
+//! - Fields: `uuid`, `type`, `point`, `size`, `x`, `y`, `z`, and `data.<path>` for fields of
+//!   the object's custom data (e.g., `data.stats.level`). Missing fields evaluate to `null`.
+//! - Vector components: `point.x`, `size.y`, ...
+//! - Functions: `dist(a, b)`, `abs(n)`, `min(a, b)`, `max(a, b)`.
+//! - Operators, from lowest to highest precedence: `||`, `&&`, `!`,
+//!   `==` `!=` `<` `<=` `>` `>=`, `+` `-`, `*` `/`, unary `-`.
+//!
+//! Comparisons between values of different kinds (e.g., a number and a string) are false, so
+//! `data.level >= 10` simply doesn't match objects without a numeric `level`.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use your_crate::{Filter, VaultManager, CustomData};
+//!
+//! let filter = Filter::parse(r#"type == "player" && data.level >= 10 && dist(point, [0,0,0]) < 100"#).unwrap();
+//! let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+//! # let region_id = uuid::Uuid::new_v4();
+//! let veterans = vault_manager.query_region_where(region_id, [-100.0; 3], [100.0; 3], &filter).unwrap();
+//! ```
+
+use std::error::Error;
+use std::fmt;
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use serde_json::Value as Json;
+use crate::structs::SpatialObject;
+
+/// Deepest nesting of parentheses, vectors and operators accepted by the parser
+const MAX_DEPTH: usize = 64;
+
+/// Why a filter expression couldn't be parsed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FilterError {
+    /// Byte offset in the expression where the problem was found
+    pub position: usize,
+    /// Description of the problem
+    pub message: String,
+}
+
+impl fmt::Display for FilterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid filter at position {}: {}", self.position, self.message)
+    }
+}
+
+impl Error for FilterError {}
+
+/// A parsed filter expression.
+///
+/// Parse once with `Filter::parse` and reuse it for every query.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Filter {
+    /// The expression tree
+    expr: Expr,
+    /// Whether the expression reads custom data, which has to be serialized to be inspected
+    reads_data: bool,
+    /// The text the filter was parsed from
+    source: String,
+}
+
+impl Filter {
+    /// Parses a filter expression.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The expression, e.g. `type == "player" && data.level >= 10`.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Filter, FilterError>` - The parsed filter, or an error describing where parsing failed.
+    pub fn parse(source: &str) -> Result<Self, FilterError> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser { tokens, pos: 0, depth: 0, end: source.len() };
+        let expr = parser.parse_or()?;
+        if let Some((position, token)) = parser.tokens.get(parser.pos) {
+            return Err(FilterError { position: *position, message: format!("Unexpected {}", token) });
+        }
+        Ok(Filter {
+            reads_data: expr.reads_data(),
+            expr,
+            source: source.to_string(),
+        })
+    }
+
+    /// Returns the text the filter was parsed from.
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// Returns `true` if `object` satisfies the filter.
+    ///
+    /// Custom data is only serialized when the expression refers to `data`.
+    pub fn matches<T>(&self, object: &SpatialObject<T>) -> bool
+    where
+        T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized,
+    {
+        let data = if self.reads_data {
+            serde_json::to_value(&*object.custom_data).unwrap_or(Json::Null)
+        } else {
+            Json::Null
+        };
+        let context = Context {
+            uuid: object.uuid,
+            object_type: object.object_type.as_str(),
+            point: object.point,
+            size: object.size,
+            data: &data,
+        };
+        matches!(self.expr.eval(&context), Value::Bool(true))
+    }
+}
+
+impl fmt::Display for Filter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl std::str::FromStr for Filter {
+    type Err = FilterError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        Filter::parse(source)
+    }
+}
+
+/// A value produced while evaluating an expression
+#[derive(Clone, Debug, PartialEq)]
+enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    Str(String),
+    Vector([f64; 3]),
+}
+
+impl Value {
+    /// Converts a custom data field, treating arrays of three numbers as vectors
+    fn from_json(json: &Json) -> Value {
+        match json {
+            Json::Bool(b) => Value::Bool(*b),
+            Json::Number(n) => n.as_f64().map(Value::Number).unwrap_or(Value::Null),
+            Json::String(s) => Value::Str(s.clone()),
+            Json::Array(items) if items.len() == 3 => {
+                match (items[0].as_f64(), items[1].as_f64(), items[2].as_f64()) {
+                    (Some(x), Some(y), Some(z)) => Value::Vector([x, y, z]),
+                    _ => Value::Null,
+                }
+            }
+            _ => Value::Null,
+        }
+    }
+}
+
+/// The object an expression is evaluated against
+struct Context<'a> {
+    uuid: Uuid,
+    object_type: &'a str,
+    point: [f64; 3],
+    size: [f64; 3],
+    data: &'a Json,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum BinaryOp {
+    Or,
+    And,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Function {
+    Dist,
+    Abs,
+    Min,
+    Max,
+}
+
+/// A node of a parsed expression
+#[derive(Clone, Debug, PartialEq)]
+enum Expr {
+    Literal(Value),
+    /// A vector literal whose components are expressions
+    Vector(Box<[Expr; 3]>),
+    Uuid,
+    Type,
+    Point,
+    Size,
+    /// A component of a vector-valued expression
+    Component(Box<Expr>, usize),
+    /// A path into the custom data; empty for the whole value
+    Data(Vec<String>),
+    Not(Box<Expr>),
+    Neg(Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+    Call(Function, Vec<Expr>),
+}
+
+impl Expr {
+    fn reads_data(&self) -> bool {
+        match self {
+            Expr::Data(_) => true,
+            Expr::Literal(_) | Expr::Uuid | Expr::Type | Expr::Point | Expr::Size => false,
+            Expr::Vector(items) => items.iter().any(Expr::reads_data),
+            Expr::Component(inner, _) | Expr::Not(inner) | Expr::Neg(inner) => inner.reads_data(),
+            Expr::Binary(_, lhs, rhs) => lhs.reads_data() || rhs.reads_data(),
+            Expr::Call(_, args) => args.iter().any(Expr::reads_data),
+        }
+    }
+
+    fn eval(&self, context: &Context<'_>) -> Value {
+        match self {
+            Expr::Literal(value) => value.clone(),
+            Expr::Vector(items) => match (items[0].eval(context), items[1].eval(context), items[2].eval(context)) {
+                (Value::Number(x), Value::Number(y), Value::Number(z)) => Value::Vector([x, y, z]),
+                _ => Value::Null,
+            },
+            Expr::Uuid => Value::Str(context.uuid.to_string()),
+            Expr::Type => Value::Str(context.object_type.to_string()),
+            Expr::Point => Value::Vector(context.point),
+            Expr::Size => Value::Vector(context.size),
+            Expr::Component(inner, axis) => match inner.eval(context) {
+                Value::Vector(v) => Value::Number(v[*axis]),
+                _ => Value::Null,
+            },
+            Expr::Data(path) => {
+                let mut current = context.data;
+                for key in path {
+                    match current.get(key) {
+                        Some(next) => current = next,
+                        None => return Value::Null,
+                    }
+                }
+                Value::from_json(current)
+            }
+            Expr::Not(inner) => match inner.eval(context) {
+                Value::Bool(b) => Value::Bool(!b),
+                _ => Value::Null,
+            },
+            Expr::Neg(inner) => match inner.eval(context) {
+                Value::Number(n) => Value::Number(-n),
+                Value::Vector(v) => Value::Vector([-v[0], -v[1], -v[2]]),
+                _ => Value::Null,
+            },
+            Expr::Binary(BinaryOp::Or, lhs, rhs) => {
+                if lhs.eval(context) == Value::Bool(true) {
+                    return Value::Bool(true);
+                }
+                Value::Bool(rhs.eval(context) == Value::Bool(true))
+            }
+            Expr::Binary(BinaryOp::And, lhs, rhs) => {
+                if lhs.eval(context) != Value::Bool(true) {
+                    return Value::Bool(false);
+                }
+                Value::Bool(rhs.eval(context) == Value::Bool(true))
+            }
+            Expr::Binary(op, lhs, rhs) => binary(*op, lhs.eval(context), rhs.eval(context)),
+            Expr::Call(function, args) => {
+                let args: Vec<Value> = args.iter().map(|arg| arg.eval(context)).collect();
+                call(*function, &args)
+            }
+        }
+    }
+}
+
+/// Applies a comparison or arithmetic operator
+fn binary(op: BinaryOp, lhs: Value, rhs: Value) -> Value {
+    use std::cmp::Ordering;
+
+    let ordering = match (&lhs, &rhs) {
+        (Value::Number(a), Value::Number(b)) => a.partial_cmp(b),
+        (Value::Str(a), Value::Str(b)) => Some(a.cmp(b)),
+        _ => None,
+    };
+    match op {
+        BinaryOp::Eq => Value::Bool(lhs == rhs),
+        BinaryOp::Ne => Value::Bool(lhs != rhs),
+        BinaryOp::Lt => Value::Bool(ordering == Some(Ordering::Less)),
+        BinaryOp::Le => Value::Bool(matches!(ordering, Some(Ordering::Less | Ordering::Equal))),
+        BinaryOp::Gt => Value::Bool(ordering == Some(Ordering::Greater)),
+        BinaryOp::Ge => Value::Bool(matches!(ordering, Some(Ordering::Greater | Ordering::Equal))),
+        BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul | BinaryOp::Div => {
+            let apply = |a: f64, b: f64| match op {
+                BinaryOp::Add => a + b,
+                BinaryOp::Sub => a - b,
+                BinaryOp::Mul => a * b,
+                _ => a / b,
+            };
+            match (lhs, rhs) {
+                (Value::Number(a), Value::Number(b)) => Value::Number(apply(a, b)),
+                (Value::Vector(a), Value::Vector(b)) if matches!(op, BinaryOp::Add | BinaryOp::Sub) => {
+                    Value::Vector([apply(a[0], b[0]), apply(a[1], b[1]), apply(a[2], b[2])])
+                }
+                (Value::Vector(a), Value::Number(b)) if matches!(op, BinaryOp::Mul | BinaryOp::Div) => {
+                    Value::Vector([apply(a[0], b), apply(a[1], b), apply(a[2], b)])
+                }
+                _ => Value::Null,
+            }
+        }
+        // Short-circuiting operators are handled by the caller
+        BinaryOp::Or | BinaryOp::And => Value::Null,
+    }
+}
+
+/// Calls a built-in function
+fn call(function: Function, args: &[Value]) -> Value {
+    match (function, args) {
+        (Function::Dist, [Value::Vector(a), Value::Vector(b)]) => {
+            let (dx, dy, dz) = (a[0] - b[0], a[1] - b[1], a[2] - b[2]);
+            Value::Number((dx * dx + dy * dy + dz * dz).sqrt())
+        }
+        (Function::Abs, [Value::Number(n)]) => Value::Number(n.abs()),
+        (Function::Min, [Value::Number(a), Value::Number(b)]) => Value::Number(a.min(*b)),
+        (Function::Max, [Value::Number(a), Value::Number(b)]) => Value::Number(a.max(*b)),
+        _ => Value::Null,
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Number(f64),
+    Str(String),
+    Ident(String),
+    Punct(&'static str),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Number(n) => write!(f, "number {}", n),
+            Token::Str(s) => write!(f, "string {:?}", s),
+            Token::Ident(name) => write!(f, "'{}'", name),
+            Token::Punct(p) => write!(f, "'{}'", p),
+        }
+    }
+}
+
+/// Operators and delimiters, longest first so `<=` wins over `<`
+const PUNCTUATION: [&str; 19] = [
+    "&&", "||", "==", "!=", "<=", ">=", "<", ">", "!", "+", "-", "*", "/", "(", ")", "[", "]", ",", ".",
+];
+
+/// Splits an expression into tokens, each paired with its byte offset
+fn tokenize(source: &str) -> Result<Vec<(usize, Token)>, FilterError> {
+    let mut tokens = Vec::new();
+    let bytes = source.as_bytes();
+    let mut pos = 0;
+    while pos < bytes.len() {
+        let c = bytes[pos];
+        if c.is_ascii_whitespace() {
+            pos += 1;
+        } else if c.is_ascii_digit() {
+            let start = pos;
+            while pos < bytes.len() && (bytes[pos].is_ascii_digit() || bytes[pos] == b'.') {
+                pos += 1;
+            }
+            let text = &source[start..pos];
+            let number = text.parse::<f64>()
+                .map_err(|_| FilterError { position: start, message: format!("Invalid number '{}'", text) })?;
+            tokens.push((start, Token::Number(number)));
+        } else if c.is_ascii_alphabetic() || c == b'_' {
+            let start = pos;
+            while pos < bytes.len() && (bytes[pos].is_ascii_alphanumeric() || bytes[pos] == b'_') {
+                pos += 1;
+            }
+            tokens.push((start, Token::Ident(source[start..pos].to_string())));
+        } else if c == b'"' {
+            let start = pos;
+            let mut text = String::new();
+            let mut chars = source[pos + 1..].char_indices();
+            loop {
+                match chars.next() {
+                    Some((offset, '"')) => {
+                        pos += offset + 2;
+                        break;
+                    }
+                    Some((_, '\\')) => match chars.next() {
+                        Some((_, escaped @ ('"' | '\\'))) => text.push(escaped),
+                        Some((_, 'n')) => text.push('\n'),
+                        _ => return Err(FilterError { position: start, message: "Invalid escape in string".to_string() }),
+                    },
+                    Some((_, other)) => text.push(other),
+                    None => return Err(FilterError { position: start, message: "Unterminated string".to_string() }),
+                }
+            }
+            tokens.push((start, Token::Str(text)));
+        } else if let Some(punct) = PUNCTUATION.iter().find(|p| source[pos..].starts_with(**p)) {
+            tokens.push((pos, Token::Punct(punct)));
+            pos += punct.len();
+        } else {
+            let found = source[pos..].chars().next().unwrap_or('?');
+            return Err(FilterError { position: pos, message: format!("Unexpected character '{}'", found) });
+        }
+    }
+    Ok(tokens)
+}
+
+/// A recursive descent parser over the token stream
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    pos: usize,
+    /// Current nesting depth, bounded by `MAX_DEPTH`
+    depth: usize,
+    /// Length of the source, reported as the position of errors at the end of input
+    end: usize,
+}
+
+impl Parser {
+    fn error(&self, message: String) -> FilterError {
+        let position = self.tokens.get(self.pos).map(|(position, _)| *position).unwrap_or(self.end);
+        FilterError { position, message }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(_, token)| token)
+    }
+
+    /// Consumes the next token if it's the given punctuation
+    fn eat(&mut self, punct: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Punct(p)) if *p == punct) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, punct: &str) -> Result<(), FilterError> {
+        if self.eat(punct) {
+            Ok(())
+        } else {
+            let found = self.peek().map(|token| token.to_string()).unwrap_or_else(|| "end of input".to_string());
+            Err(self.error(format!("Expected '{}' but found {}", punct, found)))
+        }
+    }
+
+    fn enter(&mut self) -> Result<(), FilterError> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err(self.error("Expression is nested too deeply".to_string()));
+        }
+        Ok(())
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, FilterError> {
+        self.enter()?;
+        let mut lhs = self.parse_and()?;
+        while self.eat("||") {
+            let rhs = self.parse_and()?;
+            lhs = Expr::Binary(BinaryOp::Or, Box::new(lhs), Box::new(rhs));
+        }
+        self.depth -= 1;
+        Ok(lhs)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, FilterError> {
+        let mut lhs = self.parse_not()?;
+        while self.eat("&&") {
+            let rhs = self.parse_not()?;
+            lhs = Expr::Binary(BinaryOp::And, Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_not(&mut self) -> Result<Expr, FilterError> {
+        if self.eat("!") {
+            self.enter()?;
+            let inner = self.parse_not()?;
+            self.depth -= 1;
+            return Ok(Expr::Not(Box::new(inner)));
+        }
+        self.parse_comparison()
+    }
+
+    fn parse_comparison(&mut self) -> Result<Expr, FilterError> {
+        let lhs = self.parse_sum()?;
+        let op = match self.peek() {
+            Some(Token::Punct("==")) => BinaryOp::Eq,
+            Some(Token::Punct("!=")) => BinaryOp::Ne,
+            Some(Token::Punct("<")) => BinaryOp::Lt,
+            Some(Token::Punct("<=")) => BinaryOp::Le,
+            Some(Token::Punct(">")) => BinaryOp::Gt,
+            Some(Token::Punct(">=")) => BinaryOp::Ge,
+            _ => return Ok(lhs),
+        };
+        self.pos += 1;
+        let rhs = self.parse_sum()?;
+        Ok(Expr::Binary(op, Box::new(lhs), Box::new(rhs)))
+    }
+
+    fn parse_sum(&mut self) -> Result<Expr, FilterError> {
+        let mut lhs = self.parse_product()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Punct("+")) => BinaryOp::Add,
+                Some(Token::Punct("-")) => BinaryOp::Sub,
+                _ => return Ok(lhs),
+            };
+            self.pos += 1;
+            let rhs = self.parse_product()?;
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(rhs));
+        }
+    }
+
+    fn parse_product(&mut self) -> Result<Expr, FilterError> {
+        let mut lhs = self.parse_unary()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Punct("*")) => BinaryOp::Mul,
+                Some(Token::Punct("/")) => BinaryOp::Div,
+                _ => return Ok(lhs),
+            };
+            self.pos += 1;
+            let rhs = self.parse_unary()?;
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(rhs));
+        }
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, FilterError> {
+        if self.eat("-") {
+            self.enter()?;
+            let inner = self.parse_unary()?;
+            self.depth -= 1;
+            return Ok(match inner {
+                Expr::Literal(Value::Number(n)) => Expr::Literal(Value::Number(-n)),
+                inner => Expr::Neg(Box::new(inner)),
+            });
+        }
+        let primary = self.parse_primary()?;
+        self.parse_components(primary)
+    }
+
+    /// Parses `.x`, `.y` and `.z` suffixes on vector-valued expressions
+    fn parse_components(&mut self, mut expr: Expr) -> Result<Expr, FilterError> {
+        while self.eat(".") {
+            let axis = match self.peek() {
+                Some(Token::Ident(name)) if name == "x" => 0,
+                Some(Token::Ident(name)) if name == "y" => 1,
+                Some(Token::Ident(name)) if name == "z" => 2,
+                _ => return Err(self.error("Expected 'x', 'y' or 'z' after '.'".to_string())),
+            };
+            self.pos += 1;
+            expr = Expr::Component(Box::new(expr), axis);
+        }
+        Ok(expr)
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, FilterError> {
+        let token = match self.tokens.get(self.pos) {
+            Some((_, token)) => token.clone(),
+            None => return Err(self.error("Unexpected end of input".to_string())),
+        };
+        self.pos += 1;
+        match token {
+            Token::Number(n) => Ok(Expr::Literal(Value::Number(n))),
+            Token::Str(s) => Ok(Expr::Literal(Value::Str(s))),
+            Token::Punct("(") => {
+                let inner = self.parse_or()?;
+                self.expect(")")?;
+                Ok(inner)
+            }
+            Token::Punct("[") => {
+                let x = self.parse_or()?;
+                self.expect(",")?;
+                let y = self.parse_or()?;
+                self.expect(",")?;
+                let z = self.parse_or()?;
+                self.expect("]")?;
+                Ok(Expr::Vector(Box::new([x, y, z])))
+            }
+            Token::Ident(name) => self.parse_identifier(&name),
+            other => {
+                self.pos -= 1;
+                Err(self.error(format!("Unexpected {}", other)))
+            }
+        }
+    }
+
+    fn parse_identifier(&mut self, name: &str) -> Result<Expr, FilterError> {
+        let function = match name {
+            "true" => return Ok(Expr::Literal(Value::Bool(true))),
+            "false" => return Ok(Expr::Literal(Value::Bool(false))),
+            "null" => return Ok(Expr::Literal(Value::Null)),
+            "uuid" => return Ok(Expr::Uuid),
+            "type" => return Ok(Expr::Type),
+            "point" => return Ok(Expr::Point),
+            "size" => return Ok(Expr::Size),
+            "x" => return Ok(Expr::Component(Box::new(Expr::Point), 0)),
+            "y" => return Ok(Expr::Component(Box::new(Expr::Point), 1)),
+            "z" => return Ok(Expr::Component(Box::new(Expr::Point), 2)),
+            "data" => {
+                let mut path = Vec::new();
+                while self.eat(".") {
+                    match self.peek() {
+                        Some(Token::Ident(key)) => path.push(key.clone()),
+                        _ => return Err(self.error("Expected a field name after '.'".to_string())),
+                    }
+                    self.pos += 1;
+                }
+                return Ok(Expr::Data(path));
+            }
+            "dist" => (Function::Dist, 2),
+            "abs" => (Function::Abs, 1),
+            "min" => (Function::Min, 2),
+            "max" => (Function::Max, 2),
+            _ => {
+                self.pos -= 1;
+                return Err(self.error(format!("Unknown identifier '{}'", name)));
+            }
+        };
+
+        let (function, arity) = function;
+        self.expect("(")?;
+        let mut args = Vec::with_capacity(arity);
+        for i in 0..arity {
+            if i > 0 {
+                self.expect(",")?;
+            }
+            args.push(self.parse_or()?);
+        }
+        self.expect(")")?;
+        Ok(Expr::Call(function, args))
+    }
+}
@@ -0,0 +1,133 @@
+//! # View Frustums
+//!
+//! This module defines `Frustum`, the volume a camera can see, used by `VaultManager::query_frustum`
+//! to compute what each client can see directly from the spatial index.
+//!
+//! ## Key Components
+//!
+//! - `Plane`: A plane with the inside of the frustum on the side its normal points to.
+//! - `Frustum`: Six planes bounding the visible volume.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use your_crate::Frustum;
+//!
+//! // An orthographic camera looking at the cube from -10 to 10 on every axis
+//! let view_projection = [
+//!     [0.1, 0.0, 0.0, 0.0],
+//!     [0.0, 0.1, 0.0, 0.0],
+//!     [0.0, 0.0, 0.1, 0.0],
+//!     [0.0, 0.0, 0.0, 1.0],
+//! ];
+//! let frustum = Frustum::from_view_projection(view_projection);
+//! assert!(frustum.contains_point([5.0, -5.0, 0.0]));
+//! assert!(!frustum.contains_point([15.0, 0.0, 0.0]));
+//! ```
+
+use rstar::{RTreeObject, SelectionFunction, AABB};
+
+/// A plane in 3D space, described by `normal · p + distance = 0`.
+///
+/// Points for which `normal · p + distance >= 0` are considered inside.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Plane {
+    /// Normal of the plane, pointing towards the inside of the frustum
+    pub normal: [f64; 3],
+    /// Signed offset of the plane from the origin along its normal
+    pub distance: f64,
+}
+
+impl Plane {
+    /// Creates a plane from its normal and offset.
+    ///
+    /// The normal does not need to be unit length, but distances returned by
+    /// `signed_distance` are only true distances if it is.
+    pub fn new(normal: [f64; 3], distance: f64) -> Self {
+        Plane { normal, distance }
+    }
+
+    /// Returns how far `point` lies on the inside of the plane. Negative values are outside.
+    pub fn signed_distance(&self, point: [f64; 3]) -> f64 {
+        self.normal[0] * point[0] + self.normal[1] * point[1] + self.normal[2] * point[2] + self.distance
+    }
+
+    /// Returns `true` if any part of `bounds` lies on the inside of the plane.
+    fn intersects_aabb(&self, bounds: &AABB<[f64; 3]>) -> bool {
+        let lower = bounds.lower();
+        let upper = bounds.upper();
+        // The corner furthest along the normal is the last one to leave the inside
+        let corner = [
+            if self.normal[0] >= 0.0 { upper[0] } else { lower[0] },
+            if self.normal[1] >= 0.0 { upper[1] } else { lower[1] },
+            if self.normal[2] >= 0.0 { upper[2] } else { lower[2] },
+        ];
+        self.signed_distance(corner) >= 0.0
+    }
+}
+
+/// A view frustum bounded by six planes.
+///
+/// The planes are ordered left, right, bottom, top, near, far, but any six planes whose
+/// insides enclose a convex volume work.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Frustum {
+    /// The bounding planes, with normals pointing inwards
+    pub planes: [Plane; 6],
+}
+
+impl Frustum {
+    /// Creates a frustum from six planes with inward-facing normals.
+    pub fn from_planes(planes: [Plane; 6]) -> Self {
+        Frustum { planes }
+    }
+
+    /// Extracts the frustum from a combined view-projection matrix.
+    ///
+    /// The matrix is row-major and transforms column vectors (`clip = m * [x, y, z, 1]`), with
+    /// clip space spanning `-w..w` on every axis as in OpenGL.
+    ///
+    /// # Arguments
+    ///
+    /// * `m` - The view-projection matrix of the camera.
+    pub fn from_view_projection(m: [[f64; 4]; 4]) -> Self {
+        let plane = |sign: f64, row: usize| {
+            Plane::new(
+                [m[3][0] + sign * m[row][0], m[3][1] + sign * m[row][1], m[3][2] + sign * m[row][2]],
+                m[3][3] + sign * m[row][3],
+            )
+        };
+        Frustum {
+            planes: [plane(1.0, 0), plane(-1.0, 0), plane(1.0, 1), plane(-1.0, 1), plane(1.0, 2), plane(-1.0, 2)],
+        }
+    }
+
+    /// Returns `true` if `point` lies inside the frustum.
+    pub fn contains_point(&self, point: [f64; 3]) -> bool {
+        self.planes.iter().all(|plane| plane.signed_distance(point) >= 0.0)
+    }
+
+    /// Returns `true` if `bounds` may overlap the frustum.
+    ///
+    /// The test is conservative: a box lying just outside a corner of the frustum can be
+    /// reported as overlapping, but a box that overlaps is never rejected.
+    pub fn intersects_aabb(&self, bounds: &AABB<[f64; 3]>) -> bool {
+        self.planes.iter().all(|plane| plane.intersects_aabb(bounds))
+    }
+}
+
+/// Selects R-tree entries whose envelopes may overlap a frustum, pruning whole subtrees outside it.
+pub(crate) struct FrustumSelection<'a>(pub(crate) &'a Frustum);
+
+impl<T> SelectionFunction<T> for FrustumSelection<'_>
+where
+    T: RTreeObject<Envelope = AABB<[f64; 3]>>,
+{
+    fn should_unpack_parent(&self, envelope: &AABB<[f64; 3]>) -> bool {
+        self.0.intersects_aabb(envelope)
+    }
+
+    fn should_unpack_leaf(&self, leaf: &T) -> bool {
+        self.0.intersects_aabb(&leaf.envelope())
+    }
+}
@@ -0,0 +1,152 @@
+//! # Validated Geometry
+//!
+//! This module provides the coordinate validation used throughout PebbleVault. A NaN or infinite
+//! coordinate can't be ordered, so once one reaches an R-tree its bounding boxes stop making sense
+//! and queries silently return wrong results.
+//!
+//! ## Key Components
+//!
+//! - `Finite`: An `f64` that is guaranteed to be neither NaN nor infinite, and therefore totally ordered.
+//! - `CoordinateError`: The typed error returned when a value fails validation.
+//! - `validate_point`: Checks all three coordinates of a position at once.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use your_crate::{validate_point, CoordinateError, Finite};
+//!
+//! let x = Finite::new(1.5).unwrap();
+//! assert_eq!(x.get(), 1.5);
+//! assert!(Finite::new(f64::NAN).is_err());
+//!
+//! assert!(validate_point([1.0, 2.0, 3.0]).is_ok());
+//! assert_eq!(
+//!     validate_point([1.0, f64::INFINITY, 3.0]),
+//!     Err(CoordinateError::NonFiniteCoordinate { axis: 1, value: f64::INFINITY })
+//! );
+//! ```
+
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+/// Names of the axes, indexed like the components of a point
+const AXIS_NAMES: [&str; 3] = ["x", "y", "z"];
+
+/// Why a coordinate or geometric parameter was rejected.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CoordinateError {
+    /// A single value was NaN or infinite
+    NonFinite {
+        /// The rejected value
+        value: f64,
+    },
+    /// A component of a point was NaN or infinite
+    NonFiniteCoordinate {
+        /// Index of the offending axis: 0 for x, 1 for y, 2 for z
+        axis: usize,
+        /// The rejected value
+        value: f64,
+    },
+    /// An orientation quaternion had all components zero, so it isn't a rotation
+    ZeroOrientation,
+}
+
+impl fmt::Display for CoordinateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CoordinateError::NonFinite { value } => write!(f, "Value is not finite: {}", value),
+            CoordinateError::NonFiniteCoordinate { axis, value } => {
+                let name = AXIS_NAMES.get(*axis).copied().unwrap_or("?");
+                write!(f, "Coordinate {} is not finite: {}", name, value)
+            }
+            CoordinateError::ZeroOrientation => write!(f, "Orientation quaternion has zero length"),
+        }
+    }
+}
+
+impl Error for CoordinateError {}
+
+/// A finite `f64`: never NaN and never infinite.
+///
+/// Because every value is finite, `Finite` implements `Eq`, `Ord` and `Hash`, so it can be sorted
+/// and used as a map key without the pitfalls of raw floats.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Finite(f64);
+
+impl Finite {
+    /// Validates `value`. Negative zero is stored as positive zero so that equality, ordering
+    /// and hashing all agree.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Finite, CoordinateError>` - The validated value, or an error if it's NaN or infinite.
+    pub fn new(value: f64) -> Result<Self, CoordinateError> {
+        if value.is_finite() {
+            Ok(Finite(if value == 0.0 { 0.0 } else { value }))
+        } else {
+            Err(CoordinateError::NonFinite { value })
+        }
+    }
+
+    /// Returns the wrapped value.
+    pub fn get(self) -> f64 {
+        self.0
+    }
+}
+
+impl Eq for Finite {}
+
+impl PartialOrd for Finite {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Finite {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl Hash for Finite {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.to_bits().hash(state);
+    }
+}
+
+impl TryFrom<f64> for Finite {
+    type Error = CoordinateError;
+
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        Finite::new(value)
+    }
+}
+
+impl From<Finite> for f64 {
+    fn from(value: Finite) -> Self {
+        value.0
+    }
+}
+
+impl fmt::Display for Finite {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+/// Checks that every coordinate of `point` is finite.
+///
+/// # Returns
+///
+/// * `Result<[f64; 3], CoordinateError>` - The point unchanged, or an error naming the first offending axis.
+pub fn validate_point(point: [f64; 3]) -> Result<[f64; 3], CoordinateError> {
+    for (axis, value) in point.iter().enumerate() {
+        if !value.is_finite() {
+            return Err(CoordinateError::NonFiniteCoordinate { axis, value: *value });
+        }
+    }
+    Ok(point)
+}
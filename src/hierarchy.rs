@@ -0,0 +1,112 @@
+//! # Object Attachment Hierarchy
+//!
+//! This module keeps track of which objects are attached to which, such as turrets mounted on a
+//! ship or a passenger riding a mount. Objects are attached with `VaultManager::attach_object`;
+//! an attached object follows its parent whenever `VaultManager::move_object` moves it, keeping
+//! its offset, and so do the objects attached to it in turn.
+//!
+//! Attachments are stored in the persistent database and loaded with the rest of the world.
+//! Removing an object detaches the objects attached to it, which stay where they are.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use your_crate::{VaultManager, CustomData};
+//!
+//! let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+//! # let (ship, turret) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+//! vault_manager.attach_object(turret, ship).unwrap();
+//! // The turret moves along with the ship
+//! vault_manager.move_object(ship, [120.0, 0.0, 40.0]).unwrap();
+//! assert_eq!(vault_manager.parent_of(turret), Some(ship));
+//! ```
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use uuid::Uuid;
+
+/// The parent of every attached object, and the objects attached to every parent.
+#[derive(Default)]
+pub(crate) struct Hierarchy {
+    /// The object each attached object is attached to
+    parents: HashMap<Uuid, Uuid>,
+    /// The objects attached to each parent, sorted so they're visited in a stable order
+    children: HashMap<Uuid, BTreeSet<Uuid>>,
+}
+
+impl Hierarchy {
+    /// Builds the hierarchy from `(child, parent)` pairs.
+    pub(crate) fn from_pairs(pairs: impl IntoIterator<Item = (Uuid, Uuid)>) -> Self {
+        let mut hierarchy = Hierarchy::default();
+        for (child, parent) in pairs {
+            hierarchy.attach(child, parent);
+        }
+        hierarchy
+    }
+
+    /// Returns the object `child` is attached to, if any.
+    pub(crate) fn parent(&self, child: Uuid) -> Option<Uuid> {
+        self.parents.get(&child).copied()
+    }
+
+    /// Returns the objects attached directly to `parent`, sorted by UUID.
+    pub(crate) fn children(&self, parent: Uuid) -> Vec<Uuid> {
+        self.children.get(&parent).map(|children| children.iter().copied().collect()).unwrap_or_default()
+    }
+
+    /// Returns every object attached to `parent` directly or through other objects, parents before their children.
+    ///
+    /// Each object is listed once, even if a damaged database attached objects in a cycle.
+    pub(crate) fn descendants(&self, parent: Uuid) -> Vec<Uuid> {
+        let mut seen = HashSet::from([parent]);
+        let mut descendants = self.children(parent);
+        seen.extend(descendants.iter().copied());
+        let mut next = 0;
+        while next < descendants.len() {
+            let children = self.children(descendants[next]);
+            descendants.extend(children.into_iter().filter(|child| seen.insert(*child)));
+            next += 1;
+        }
+        descendants
+    }
+
+    /// Returns `true` if `ancestor` is `object` or one of the objects it's attached to, directly or not.
+    pub(crate) fn is_ancestor(&self, ancestor: Uuid, object: Uuid) -> bool {
+        // A chain longer than the number of attachments loops, which a damaged database could cause
+        let mut current = Some(object);
+        for _ in 0..=self.parents.len() {
+            match current {
+                Some(object) if object == ancestor => return true,
+                Some(object) => current = self.parent(object),
+                None => return false,
+            }
+        }
+        false
+    }
+
+    /// Attaches `child` to `parent`, detaching it from its previous parent.
+    pub(crate) fn attach(&mut self, child: Uuid, parent: Uuid) {
+        self.detach(child);
+        self.parents.insert(child, parent);
+        self.children.entry(parent).or_default().insert(child);
+    }
+
+    /// Detaches `child` from its parent, returning the parent it had.
+    pub(crate) fn detach(&mut self, child: Uuid) -> Option<Uuid> {
+        let parent = self.parents.remove(&child)?;
+        if let Some(children) = self.children.get_mut(&parent) {
+            children.remove(&child);
+            if children.is_empty() {
+                self.children.remove(&parent);
+            }
+        }
+        Some(parent)
+    }
+
+    /// Forgets a removed object, detaching it from its parent and its children from it.
+    pub(crate) fn forget(&mut self, object: Uuid) {
+        self.detach(object);
+        for child in self.children.remove(&object).unwrap_or_default() {
+            self.parents.remove(&child);
+        }
+    }
+}
@@ -0,0 +1,101 @@
+//! # I/O Accounting
+//!
+//! This module provides `IoStats`, counters describing how much work the persistent database
+//! has done: SQL statements executed and data files read, written and deleted. They make it
+//! possible to measure the write amplification of an operation and to quantify improvements
+//! such as batched inserts.
+//!
+//! Counters only ever increase. To measure a single operation, take a snapshot before and after
+//! it and subtract them with `IoStats::since`.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use your_crate::{VaultManager, CustomData};
+//!
+//! let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+//! let before = vault_manager.io_stats();
+//! vault_manager.persist_to_disk().unwrap();
+//! let cost = vault_manager.io_stats().since(&before);
+//! println!("Persisting ran {} statements and wrote {} bytes", cost.statements, cost.bytes_written);
+//! ```
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// A snapshot of the persistent database's I/O counters.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct IoStats {
+    /// SQL statements executed, including transaction control
+    pub statements: u64,
+    /// Bytes read from data files
+    pub bytes_read: u64,
+    /// Bytes written to data files
+    pub bytes_written: u64,
+    /// Data files read
+    pub files_read: u64,
+    /// Data files written
+    pub files_written: u64,
+    /// Data files deleted
+    pub files_deleted: u64,
+}
+
+impl IoStats {
+    /// Returns the work done between `earlier` and this snapshot.
+    pub fn since(&self, earlier: &IoStats) -> IoStats {
+        IoStats {
+            statements: self.statements.saturating_sub(earlier.statements),
+            bytes_read: self.bytes_read.saturating_sub(earlier.bytes_read),
+            bytes_written: self.bytes_written.saturating_sub(earlier.bytes_written),
+            files_read: self.files_read.saturating_sub(earlier.files_read),
+            files_written: self.files_written.saturating_sub(earlier.files_written),
+            files_deleted: self.files_deleted.saturating_sub(earlier.files_deleted),
+        }
+    }
+}
+
+/// Live I/O counters, updated by the database as it works.
+#[derive(Debug, Default)]
+pub(crate) struct IoCounters {
+    statements: AtomicU64,
+    bytes_read: AtomicU64,
+    bytes_written: AtomicU64,
+    files_read: AtomicU64,
+    files_written: AtomicU64,
+    files_deleted: AtomicU64,
+}
+
+impl IoCounters {
+    /// Records an executed SQL statement.
+    pub(crate) fn statement(&self) {
+        self.statements.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records a data file of `bytes` bytes being read.
+    pub(crate) fn file_read(&self, bytes: usize) {
+        self.files_read.fetch_add(1, Ordering::Relaxed);
+        self.bytes_read.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    /// Records a data file of `bytes` bytes being written.
+    pub(crate) fn file_written(&self, bytes: usize) {
+        self.files_written.fetch_add(1, Ordering::Relaxed);
+        self.bytes_written.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    /// Records a data file being deleted.
+    pub(crate) fn file_deleted(&self) {
+        self.files_deleted.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Returns the current values of the counters.
+    pub(crate) fn snapshot(&self) -> IoStats {
+        IoStats {
+            statements: self.statements.load(Ordering::Relaxed),
+            bytes_read: self.bytes_read.load(Ordering::Relaxed),
+            bytes_written: self.bytes_written.load(Ordering::Relaxed),
+            files_read: self.files_read.load(Ordering::Relaxed),
+            files_written: self.files_written.load(Ordering::Relaxed),
+            files_deleted: self.files_deleted.load(Ordering::Relaxed),
+        }
+    }
+}
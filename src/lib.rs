@@ -13,10 +13,99 @@ mod MySQLGeo;
 mod structs;
 // Import the vault_manager module for managing spatial data
 mod vault_manager;
+// Import the cancellation module for query deadlines and cancellation
+mod cancellation;
+// Import the config module for VaultManager options
+mod config;
+// Import the object_type module for interned object type names
+mod object_type;
+// Import the frustum module for camera visibility queries
+mod frustum;
+// Import the pool module for reusing custom data allocations
+mod pool;
+// Import the geometry module for coordinate validation
+mod geometry;
+// Import the filter module for filter expressions
+mod filter;
+// Import the io_stats module for persistent storage I/O accounting
+mod io_stats;
+// Import the lock_stats module for region lock diagnostics
+mod lock_stats;
+// Import the batch module for atomic multi-object mutations
+mod batch;
+// Import the auto_persist module for background persistence
+mod auto_persist;
+// Import the operation module for progress tracking of long-running operations
+mod operation;
+// Import the async_manager module for the async API
+mod async_manager;
+// Import the simulation module for simulation level of detail
+mod simulation;
+// Import the capabilities module for backend feature discovery
+mod capabilities;
+// Import the persist_budget module for time-budgeted persistence
+mod persist_budget;
+// Import the template module for reusable region templates
+mod template;
+// Import the catch_up module for bringing reconnecting clients up to date
+mod catch_up;
+// Import the events module for notifying subscribers of changes
+mod events;
+// Import the consistency module for checking the index against the database
+mod consistency;
+// Import the type_registry module for registered object types
+mod type_registry;
+// Import the schema module for validating custom data
+mod schema;
+// Import the world_snapshot module for named snapshots of the world
+mod world_snapshot;
+// Import the connection_lock module for sharing the database connection between threads
+mod connection_lock;
+// Import the region_stats module for per-region statistics
+mod region_stats;
+// Import the region_file module for exporting regions to portable files
+mod region_file;
+// Import the change_log module for the feed of recorded changes
+mod change_log;
+// Import the hierarchy module for objects attached to other objects
+mod hierarchy;
+// Import the oriented_box module for rotated object bounds
+mod oriented_box;
+// Import the archive module for objects moved to cold storage
+mod archive;
 
 // Re-export structs and VaultManager for easier access
-pub use structs::*;
-pub use vault_manager::VaultManager;
+pub use structs::{LoadFailure, LoadReport, RaycastHit, RegionQuery, RegionSnapshot, SpatialObject, VaultRegion};
+pub use vault_manager::{VaultManager, COLUMN_TOLERANCE};
+pub use cancellation::{CancellationToken, QueryLimits};
+pub use config::{BoundsPolicy, Compression, KeyFormat, QueryOrder, VaultConfig, WorldBounds};
+pub use object_type::ObjectType;
+pub use frustum::{Frustum, Plane};
+pub use oriented_box::OrientedBox;
+pub use pool::{ObjectPool, PoolStats};
+pub use geometry::{validate_point, CoordinateError, Finite};
+pub use filter::{Filter, FilterError};
+pub use io_stats::IoStats;
+pub use lock_stats::{LockDiagnostics, LockOrderViolation, RegionLockStats};
+pub use batch::{Mutation, VaultTransaction};
+pub use auto_persist::AutoPersist;
+pub use operation::{OperationHandle, OperationStatus};
+pub use async_manager::{Reply, VaultManagerAsync};
+pub use simulation::{LodPolicy, RegionTick, SimulationLevel};
+pub use capabilities::BackendCapabilities;
+pub use persist_budget::PersistBudget;
+pub use template::{RegionTemplate, TemplateObject, Transform};
+pub use catch_up::{CatchUp, CatchUpToken};
+pub use events::VaultEvent;
+pub use change_log::Change;
+pub use consistency::{ConsistencyStats, Divergence, DivergenceKind};
+pub use type_registry::{DataValidator, TypeDefinition};
+pub use schema::DataSchema;
+pub use world_snapshot::WorldSnapshot;
+pub use region_stats::RegionStats;
+
+// Export the prelude module with the stable API
+pub mod prelude;
 
 // Make the tests module public
 pub mod tests;
@@ -26,6 +26,7 @@ use colored::*;
 use indicatif::{ProgressBar, ProgressStyle};
 use serde::{Serialize, Deserialize};
 use std::sync::Arc;
+use std::collections::HashMap;
 use std::fmt::Debug;
 use rand::distributions::{Distribution, Standard};
 
@@ -82,7 +83,7 @@ fn format_duration(duration: Duration) -> String {
 ///
 /// # Arguments
 ///
-/// * `vault_manager` - A mutable reference to the VaultManager instance.
+/// * `vault_manager` - A reference to the VaultManager instance.
 /// * `num_objects` - The number of objects to add in each test cycle.
 /// * `num_regions` - The number of regions to create or use.
 /// * `num_operations` - The number of additional operations to perform (delete/add cycles).
@@ -94,10 +95,10 @@ fn format_duration(duration: Duration) -> String {
 /// # Examples
 ///
 /// ```
-/// let mut vault_manager = VaultManager::new("test_db.sqlite").unwrap();
-/// run_load_test(&mut vault_manager, 10000, 5, 10).expect("Load test failed");
+/// let vault_manager = VaultManager::new("test_db.sqlite").unwrap();
+/// run_load_test(&vault_manager, 10000, 5, 10).expect("Load test failed");
 /// ```
-pub fn run_load_test(vault_manager: &mut VaultManager<LoadTestData>, num_objects: usize, num_regions: usize, num_operations: usize) -> Result<(), String> {
+pub fn run_load_test(vault_manager: &VaultManager<LoadTestData>, num_objects: usize, num_regions: usize, num_operations: usize) -> Result<(), String> {
     // Print the header for the load test
     println!("\n{}", "==== Running Enhanced PebbleVault Load Test ====".green().bold());
     println!("Number of objects to add: {}", num_objects.to_string().cyan());
@@ -110,7 +111,7 @@ pub fn run_load_test(vault_manager: &mut VaultManager<LoadTestData>, num_objects
     // Get existing regions or create new ones if needed
     let regions: Vec<Uuid> = {
         // Get the list of existing regions
-        let existing_regions: Vec<Uuid> = vault_manager.regions.keys().cloned().collect();
+        let existing_regions: Vec<Uuid> = vault_manager.region_ids();
         if existing_regions.len() < num_regions {
             // If we don't have enough regions, create new ones
             let mut regions = existing_regions;
@@ -138,7 +139,7 @@ pub fn run_load_test(vault_manager: &mut VaultManager<LoadTestData>, num_objects
 
     // Define a closure to add objects to the VaultManager
     println!("\n{}", "Adding new objects with custom data".blue());
-    let add_objects = |vm: &mut VaultManager<LoadTestData>, count: usize, regions: &[Uuid]| -> Result<Vec<Uuid>, String> {
+    let add_objects = |vm: &VaultManager<LoadTestData>, count: usize, regions: &[Uuid]| -> Result<Vec<Uuid>, String> {
         let mut rng = rand::thread_rng();
         let mut object_ids = Vec::with_capacity(count);
         let add_objects_start = Instant::now();
@@ -150,7 +151,8 @@ pub fn run_load_test(vault_manager: &mut VaultManager<LoadTestData>, num_objects
             .unwrap()
             .progress_chars("##-"));
 
-        // Add the specified number of objects
+        // Generate the specified number of objects, grouped by region
+        let mut batches: HashMap<Uuid, Vec<SpatialObject<LoadTestData>>> = HashMap::new();
         for _ in 0..count {
             let region_id = regions[rng.gen_range(0..regions.len())];
             let x = rng.gen_range(-500.0..500.0);
@@ -163,11 +165,25 @@ pub fn run_load_test(vault_manager: &mut VaultManager<LoadTestData>, num_objects
                 1 => "building",
                 _ => "resource",
             };
-            vm.add_object(region_id, object_uuid, object_type, x, y, z, custom_data)?;
+            batches.entry(region_id).or_default().push(SpatialObject {
+                uuid: object_uuid,
+                object_type: ObjectType::from(object_type),
+                point: [x, y, z],
+                custom_data,
+                size: [0.0, 0.0, 0.0],
+                version: 0,
+                velocity: None,
+                orientation: None,
+            });
             object_ids.push(object_uuid);
             pb.inc(1);
         }
 
+        // Add each region's objects in a single batch
+        for (region_id, batch) in batches {
+            vm.add_objects(region_id, batch)?;
+        }
+
         // Finish the progress bar
         pb.finish_with_message("Objects added");
         let add_objects_duration = add_objects_start.elapsed();
@@ -287,13 +303,13 @@ pub fn run_load_test(vault_manager: &mut VaultManager<LoadTestData>, num_objects
 ///
 /// # Arguments
 ///
-/// * `vault_manager` - A mutable reference to the VaultManager instance.
+/// * `vault_manager` - A reference to the VaultManager instance.
 /// * `object_ids` - A slice of object UUIDs to choose from.
 ///
 /// # Returns
 ///
 /// * `Result<(), String>` - Ok if the operations are successful, or an error message if they fail.
-fn test_custom_data_operations(vault_manager: &mut VaultManager<LoadTestData>, object_ids: &[Uuid]) -> Result<(), String> {
+fn test_custom_data_operations(vault_manager: &VaultManager<LoadTestData>, object_ids: &[Uuid]) -> Result<(), String> {
     let mut rng = rand::thread_rng();
     let num_tests = std::cmp::min(10, object_ids.len());
     
@@ -418,7 +434,7 @@ pub fn run_arbitrary_data_load_test(num_objects: usize, num_regions: usize) -> R
     println!("\n{}", "==== Running PebbleVault Load Test with Arbitrary Data ====".green().bold());
     
     let db_path = "arbitrary_test.db";
-    let mut vault_manager: VaultManager<ArbitraryTestData> = VaultManager::new(db_path)
+    let vault_manager: VaultManager<ArbitraryTestData> = VaultManager::new(db_path)
         .map_err(|e| format!("Failed to create VaultManager: {}", e))?;
 
     let start_time = Instant::now();
@@ -0,0 +1,200 @@
+//! # Region Lock Diagnostics
+//!
+//! This module provides `LockDiagnostics`, a report of how the region locks of a `VaultManager`
+//! are used: how often each region is locked, how long callers waited for it and how long it was
+//! held. Regions with high wait times are contention hot spots, worth splitting or accessing less.
+//!
+//! The report also lists lock-ordering hazards. When a thread holds two regions at once, it must
+//! lock them in ascending order of their UUIDs; a thread locking them the other way round can
+//! deadlock with one that follows the order. Every acquisition that breaks the order is recorded.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use your_crate::{VaultManager, CustomData};
+//!
+//! let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+//! let diagnostics = vault_manager.lock_diagnostics();
+//! for (region_id, stats) in diagnostics.hot_spots(5) {
+//!     println!("{}: waited {:?} over {} contended locks", region_id, stats.total_wait, stats.contended);
+//! }
+//! assert!(diagnostics.order_violations.is_empty());
+//! ```
+
+use std::cell::RefCell;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Mutex, MutexGuard, TryLockError};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+thread_local! {
+    /// Regions locked by the current thread, in acquisition order
+    static HELD_REGIONS: RefCell<Vec<Uuid>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Lock usage of a single region.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RegionLockStats {
+    /// Times the region was locked
+    pub acquisitions: u64,
+    /// Times the region was already locked and the caller had to wait
+    pub contended: u64,
+    /// Total time spent waiting for the lock
+    pub total_wait: Duration,
+    /// Total time the lock was held
+    pub total_hold: Duration,
+    /// Longest time the lock was held at once
+    pub max_hold: Duration,
+}
+
+/// A region locked while the same thread held a region with a greater UUID.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LockOrderViolation {
+    /// The region that was already held
+    pub held: Uuid,
+    /// The region that was locked out of order
+    pub acquired: Uuid,
+    /// Number of times this pair was locked out of order
+    pub occurrences: u64,
+}
+
+/// A snapshot of the region lock counters.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LockDiagnostics {
+    /// Lock usage per region, keyed by region UUID
+    pub regions: HashMap<Uuid, RegionLockStats>,
+    /// Acquisitions that broke the lock order, one entry per pair of regions
+    pub order_violations: Vec<LockOrderViolation>,
+}
+
+impl LockDiagnostics {
+    /// Returns the `count` regions callers waited on the longest, most contended first.
+    pub fn hot_spots(&self, count: usize) -> Vec<(Uuid, RegionLockStats)> {
+        let mut regions: Vec<(Uuid, RegionLockStats)> = self.regions.iter()
+            .filter(|(_, stats)| stats.contended > 0)
+            .map(|(region_id, stats)| (*region_id, *stats))
+            .collect();
+        regions.sort_by(|a, b| b.1.total_wait.cmp(&a.1.total_wait).then(b.1.contended.cmp(&a.1.contended)));
+        regions.truncate(count);
+        regions
+    }
+}
+
+/// Live lock counters, updated as regions are locked and released.
+#[derive(Debug, Default)]
+pub(crate) struct LockRecorder {
+    regions: Mutex<HashMap<Uuid, RegionLockStats>>,
+    violations: Mutex<HashMap<(Uuid, Uuid), u64>>,
+}
+
+impl LockRecorder {
+    /// Locks a region's mutex, blocking until it's available.
+    ///
+    /// Records the wait, and a lock-order violation if the current thread already holds a
+    /// region with a greater UUID.
+    pub(crate) fn acquire<'a, V>(&'a self, region_id: Uuid, mutex: &'a Mutex<V>) -> TimedGuard<'a, V> {
+        let out_of_order = HELD_REGIONS.with(|held| {
+            held.borrow().iter().copied().filter(|held| *held > region_id).max()
+        });
+        if let Some(held) = out_of_order {
+            *self.violations.lock().unwrap().entry((held, region_id)).or_default() += 1;
+        }
+
+        let start = Instant::now();
+        let (guard, contended) = match mutex.try_lock() {
+            Ok(guard) => (guard, false),
+            Err(TryLockError::WouldBlock) => (mutex.lock().unwrap(), true),
+            Err(TryLockError::Poisoned(e)) => panic!("Region {} lock poisoned: {}", region_id, e),
+        };
+        let wait = start.elapsed();
+
+        let mut regions = self.regions.lock().unwrap();
+        let stats = regions.entry(region_id).or_default();
+        stats.acquisitions += 1;
+        if contended {
+            stats.contended += 1;
+            stats.total_wait += wait;
+        }
+        drop(regions);
+        TimedGuard::new(self, region_id, guard)
+    }
+
+    /// Locks a region's mutex if it's available, without waiting.
+    ///
+    /// A lock that isn't waited for can't deadlock, so it's exempt from the lock order.
+    pub(crate) fn try_acquire<'a, V>(&'a self, region_id: Uuid, mutex: &'a Mutex<V>) -> Option<TimedGuard<'a, V>> {
+        let guard = mutex.try_lock().ok()?;
+        self.regions.lock().unwrap().entry(region_id).or_default().acquisitions += 1;
+        Some(TimedGuard::new(self, region_id, guard))
+    }
+
+    /// Records a region lock being released after `hold`.
+    fn release(&self, region_id: Uuid, hold: Duration) {
+        let mut regions = self.regions.lock().unwrap();
+        let stats = regions.entry(region_id).or_default();
+        stats.total_hold += hold;
+        stats.max_hold = stats.max_hold.max(hold);
+    }
+
+    /// Returns the current values of the counters.
+    pub(crate) fn snapshot(&self) -> LockDiagnostics {
+        let mut order_violations: Vec<LockOrderViolation> = self.violations.lock().unwrap()
+            .iter()
+            .map(|((held, acquired), occurrences)| LockOrderViolation { held: *held, acquired: *acquired, occurrences: *occurrences })
+            .collect();
+        order_violations.sort_by_key(|violation| Reverse(violation.occurrences));
+        LockDiagnostics {
+            regions: self.regions.lock().unwrap().clone(),
+            order_violations,
+        }
+    }
+
+    /// Resets every counter to zero.
+    pub(crate) fn reset(&self) {
+        self.regions.lock().unwrap().clear();
+        self.violations.lock().unwrap().clear();
+    }
+}
+
+/// A region lock that reports how long it was held when released.
+pub(crate) struct TimedGuard<'a, V> {
+    guard: MutexGuard<'a, V>,
+    recorder: &'a LockRecorder,
+    region_id: Uuid,
+    acquired: Instant,
+}
+
+impl<'a, V> TimedGuard<'a, V> {
+    fn new(recorder: &'a LockRecorder, region_id: Uuid, guard: MutexGuard<'a, V>) -> Self {
+        HELD_REGIONS.with(|held| held.borrow_mut().push(region_id));
+        TimedGuard { guard, recorder, region_id, acquired: Instant::now() }
+    }
+}
+
+impl<V> Deref for TimedGuard<'_, V> {
+    type Target = V;
+
+    fn deref(&self) -> &V {
+        &self.guard
+    }
+}
+
+impl<V> DerefMut for TimedGuard<'_, V> {
+    fn deref_mut(&mut self) -> &mut V {
+        &mut self.guard
+    }
+}
+
+impl<V> Drop for TimedGuard<'_, V> {
+    fn drop(&mut self) {
+        HELD_REGIONS.with(|held| {
+            let mut held = held.borrow_mut();
+            if let Some(position) = held.iter().rposition(|region_id| *region_id == self.region_id) {
+                held.remove(position);
+            }
+        });
+        self.recorder.release(self.region_id, self.acquired.elapsed());
+    }
+}
@@ -0,0 +1,152 @@
+//! # Interned Object Types
+//!
+//! This module provides `ObjectType`, the interned form of the object type strings
+//! ("player", "building", ...) attached to every `SpatialObject`.
+//!
+//! A world typically has millions of objects but only a handful of distinct types. Storing a
+//! separate `String` per object wastes memory and makes every query result clone a heap
+//! allocation. An `ObjectType` is instead a `Copy` handle to a single shared copy of the name.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use your_crate::ObjectType;
+//!
+//! let player = ObjectType::from("player");
+//! assert_eq!(player, "player");
+//! assert_eq!(player, ObjectType::from("player".to_string()));
+//! assert_eq!(player.as_str(), "player");
+//! ```
+//!
+//! ## Notes
+//!
+//! Interned names are never freed. Object types are expected to come from a small, fixed set,
+//! so don't build them from unbounded input such as player-chosen names.
+
+use std::borrow::Borrow;
+use std::collections::HashSet;
+use std::fmt;
+use std::ops::Deref;
+use std::sync::Mutex;
+use lazy_static::lazy_static;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+lazy_static! {
+    /// Every object type name interned so far
+    static ref REGISTRY: Mutex<HashSet<&'static str>> = Mutex::new(HashSet::new());
+}
+
+/// An interned object type name.
+///
+/// Two `ObjectType`s with the same name share the same storage, so copying one is as cheap as
+/// copying a reference. It dereferences to `str` and compares equal to string slices.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ObjectType(&'static str);
+
+impl ObjectType {
+    /// Returns the interned type for `name`, registering it on first use.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The object type name (e.g., "player", "building", "resource").
+    pub fn new(name: &str) -> Self {
+        let mut registry = REGISTRY.lock().unwrap();
+        if let Some(interned) = registry.get(name) {
+            return ObjectType(interned);
+        }
+        let interned: &'static str = Box::leak(name.to_owned().into_boxed_str());
+        registry.insert(interned);
+        ObjectType(interned)
+    }
+
+    /// Returns the type name.
+    pub fn as_str(&self) -> &'static str {
+        self.0
+    }
+
+    /// Returns the number of distinct object types interned so far.
+    pub fn registered_count() -> usize {
+        REGISTRY.lock().unwrap().len()
+    }
+}
+
+impl Deref for ObjectType {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.0
+    }
+}
+
+impl Borrow<str> for ObjectType {
+    fn borrow(&self) -> &str {
+        self.0
+    }
+}
+
+impl AsRef<str> for ObjectType {
+    fn as_ref(&self) -> &str {
+        self.0
+    }
+}
+
+impl From<&str> for ObjectType {
+    fn from(name: &str) -> Self {
+        ObjectType::new(name)
+    }
+}
+
+impl From<String> for ObjectType {
+    fn from(name: String) -> Self {
+        ObjectType::new(&name)
+    }
+}
+
+impl From<ObjectType> for String {
+    fn from(object_type: ObjectType) -> Self {
+        object_type.0.to_string()
+    }
+}
+
+impl PartialEq<str> for ObjectType {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for ObjectType {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialEq<String> for ObjectType {
+    fn eq(&self, other: &String) -> bool {
+        self.0 == other.as_str()
+    }
+}
+
+impl fmt::Display for ObjectType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+impl fmt::Debug for ObjectType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.0, f)
+    }
+}
+
+impl Serialize for ObjectType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for ObjectType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Ok(ObjectType::new(&name))
+    }
+}
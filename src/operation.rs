@@ -0,0 +1,215 @@
+//! # Long-Running Operations
+//!
+//! This module provides `OperationHandle`, a shared handle to a heavy operation such as a
+//! bulk import, a persist or a series of region splits. The thread running the operation
+//! reports its progress through the handle, while any other thread holding a clone can watch
+//! the progress and estimated time remaining, pause or cancel it, and wait for it to finish.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use your_crate::{OperationHandle, OperationStatus, VaultManager, CustomData};
+//! use std::thread;
+//!
+//! let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+//! let handle = OperationHandle::new();
+//!
+//! let watcher = handle.clone();
+//! thread::spawn(move || {
+//!     while !watcher.is_finished() {
+//!         println!("{:.0}% done, {:?} left", watcher.progress() * 100.0, watcher.eta());
+//!         thread::sleep(std::time::Duration::from_secs(1));
+//!     }
+//! });
+//!
+//! vault_manager.persist_to_disk_tracked(&handle).unwrap();
+//! assert_eq!(handle.status(), OperationStatus::Completed);
+//! ```
+
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// The lifecycle state of an operation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OperationStatus {
+    /// The operation hasn't started yet
+    #[default]
+    Pending,
+    /// The operation is making progress
+    Running,
+    /// The operation is waiting to be resumed
+    Paused,
+    /// The operation finished successfully
+    Completed,
+    /// The operation stopped with an error
+    Failed,
+    /// The operation was cancelled through its handle
+    Cancelled,
+}
+
+/// Progress and control state shared by the clones of a handle.
+#[derive(Debug, Default)]
+struct OperationState {
+    status: OperationStatus,
+    /// Units of work done so far
+    done: u64,
+    /// Units of work in the whole operation
+    total: u64,
+    started: Option<Instant>,
+    /// Time spent paused, excluded from the estimate
+    paused_for: Duration,
+    paused_since: Option<Instant>,
+    pause_requested: bool,
+    cancel_requested: bool,
+    error: Option<String>,
+}
+
+/// A shared handle for watching and controlling a long-running operation.
+///
+/// Clones share the same operation, so a handle can be passed to the operation while another
+/// thread keeps a clone to report its progress or cancel it. A handle tracks one operation.
+#[derive(Clone, Debug, Default)]
+pub struct OperationHandle {
+    shared: Arc<(Mutex<OperationState>, Condvar)>,
+}
+
+impl OperationHandle {
+    /// Creates a handle for an operation that hasn't started.
+    pub fn new() -> Self {
+        OperationHandle::default()
+    }
+
+    fn state(&self) -> MutexGuard<'_, OperationState> {
+        self.shared.0.lock().unwrap()
+    }
+
+    /// Returns the current state of the operation.
+    pub fn status(&self) -> OperationStatus {
+        self.state().status
+    }
+
+    /// Returns the fraction of the operation done, from 0.0 to 1.0.
+    pub fn progress(&self) -> f64 {
+        let state = self.state();
+        match (state.status, state.total) {
+            (OperationStatus::Completed, _) => 1.0,
+            (_, 0) => 0.0,
+            (_, total) => state.done as f64 / total as f64,
+        }
+    }
+
+    /// Returns the units of work done so far and the units in the whole operation.
+    pub fn units(&self) -> (u64, u64) {
+        let state = self.state();
+        (state.done, state.total)
+    }
+
+    /// Estimates the time until the operation finishes from its rate so far.
+    ///
+    /// Returns `None` before any progress was made and once the operation is finished.
+    pub fn eta(&self) -> Option<Duration> {
+        let state = self.state();
+        if !matches!(state.status, OperationStatus::Running | OperationStatus::Paused) || state.done == 0 {
+            return None;
+        }
+        let paused = state.paused_for + state.paused_since.map_or(Duration::ZERO, |since| since.elapsed());
+        let active = state.started?.elapsed().saturating_sub(paused);
+        let remaining = state.total.saturating_sub(state.done);
+        Some(active.mul_f64(remaining as f64 / state.done as f64))
+    }
+
+    /// Returns the error the operation failed with, if any.
+    pub fn error(&self) -> Option<String> {
+        self.state().error.clone()
+    }
+
+    /// Returns `true` once the operation has completed, failed or been cancelled.
+    pub fn is_finished(&self) -> bool {
+        matches!(self.status(), OperationStatus::Completed | OperationStatus::Failed | OperationStatus::Cancelled)
+    }
+
+    /// Asks the operation to pause at its next checkpoint.
+    pub fn pause(&self) {
+        self.state().pause_requested = true;
+    }
+
+    /// Resumes a paused operation.
+    pub fn resume(&self) {
+        self.state().pause_requested = false;
+        self.shared.1.notify_all();
+    }
+
+    /// Asks the operation to stop at its next checkpoint, including while it's paused.
+    pub fn cancel(&self) {
+        self.state().cancel_requested = true;
+        self.shared.1.notify_all();
+    }
+
+    /// Blocks until the operation finishes.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), String>` - Ok if the operation completed, or the error it failed with.
+    pub fn wait(&self) -> Result<(), String> {
+        let mut state = self.state();
+        loop {
+            match state.status {
+                OperationStatus::Completed => return Ok(()),
+                OperationStatus::Failed | OperationStatus::Cancelled => {
+                    return Err(state.error.clone().unwrap_or_else(|| "Operation cancelled".to_string()));
+                }
+                _ => state = self.shared.1.wait(state).unwrap(),
+            }
+        }
+    }
+
+    /// Marks the operation as running with `total` units of work.
+    pub(crate) fn start(&self, total: u64) {
+        let mut state = self.state();
+        state.status = OperationStatus::Running;
+        state.total = total;
+        state.done = 0;
+        state.started = Some(Instant::now());
+    }
+
+    /// Records `units` more units of work as done.
+    pub(crate) fn advance(&self, units: u64) {
+        let mut state = self.state();
+        state.done = (state.done + units).min(state.total);
+    }
+
+    /// Pauses while a pause is requested, and fails once the operation is cancelled.
+    ///
+    /// Operations call this between units of work, at points where stopping is safe.
+    pub(crate) fn checkpoint(&self) -> Result<(), String> {
+        let mut state = self.state();
+        while state.pause_requested && !state.cancel_requested {
+            if state.paused_since.is_none() {
+                state.status = OperationStatus::Paused;
+                state.paused_since = Some(Instant::now());
+                self.shared.1.notify_all();
+            }
+            state = self.shared.1.wait(state).unwrap();
+        }
+        if let Some(since) = state.paused_since.take() {
+            state.paused_for += since.elapsed();
+            state.status = OperationStatus::Running;
+        }
+        if state.cancel_requested {
+            return Err("Operation cancelled".to_string());
+        }
+        Ok(())
+    }
+
+    /// Records the outcome of the operation and wakes every waiter.
+    pub(crate) fn finish<R>(&self, result: &Result<R, String>) {
+        let mut state = self.state();
+        state.status = match result {
+            Ok(_) => OperationStatus::Completed,
+            Err(_) if state.cancel_requested => OperationStatus::Cancelled,
+            Err(_) => OperationStatus::Failed,
+        };
+        state.error = result.as_ref().err().cloned();
+        self.shared.1.notify_all();
+    }
+}
@@ -0,0 +1,150 @@
+//! # Oriented Bounding Boxes
+//!
+//! This module defines `OrientedBox`, the box an object with an orientation occupies, and the
+//! quaternion math behind it. Objects are indexed by the axis-aligned box enclosing their
+//! oriented box, so spatial queries never miss a rotated object. The overlap queries,
+//! `VaultManager::query_overlapping` and `VaultManager::find_intersections`, then test the
+//! oriented boxes themselves, so a long diagonal object doesn't overlap everything near it.
+//!
+//! Orientations are unit quaternions stored as `[x, y, z, w]`; `[0.0, 0.0, 0.0, 1.0]` is no rotation.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use your_crate::OrientedBox;
+//!
+//! // A 10 x 1 x 1 plank turned 45 degrees around the vertical axis
+//! let half_angle = std::f64::consts::FRAC_PI_8;
+//! let plank = OrientedBox::new([0.0, 0.0, 0.0], [10.0, 1.0, 1.0], [0.0, half_angle.sin(), 0.0, half_angle.cos()]);
+//! let beside = OrientedBox::axis_aligned([3.0, -0.5, 3.0], [4.0, 0.5, 4.0]);
+//! let corner = OrientedBox::axis_aligned([3.0, -0.5, -4.0], [4.0, 0.5, -3.0]);
+//! assert!(!plank.intersects(&beside));
+//! assert!(plank.intersects(&corner));
+//! ```
+
+use crate::geometry::CoordinateError;
+
+/// Tolerance added to the separating axis test, so boxes with parallel edges don't separate through rounding
+const EPSILON: f64 = 1e-9;
+
+/// A box rotated in space, described by its center, half extents and local axes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OrientedBox {
+    /// Center of the box [x, y, z]
+    pub center: [f64; 3],
+    /// Half of the box's extent along each of its local axes
+    pub half_extents: [f64; 3],
+    /// Unit vectors of the box's local x, y and z axes, in world coordinates
+    pub axes: [[f64; 3]; 3],
+}
+
+impl OrientedBox {
+    /// Creates the box of an object with the given size and orientation.
+    ///
+    /// # Arguments
+    ///
+    /// * `center` - Center of the box [x, y, z].
+    /// * `size` - Extent of the box along each of its local axes.
+    /// * `orientation` - Unit quaternion [x, y, z, w] rotating the box's local axes into the world.
+    pub fn new(center: [f64; 3], size: [f64; 3], orientation: [f64; 4]) -> Self {
+        let rotation = rotation_matrix(orientation);
+        OrientedBox {
+            center,
+            half_extents: size.map(|extent| extent / 2.0),
+            axes: std::array::from_fn(|axis| std::array::from_fn(|row| rotation[row][axis])),
+        }
+    }
+
+    /// Creates an unrotated box from its minimum and maximum corners.
+    pub fn axis_aligned(min: [f64; 3], max: [f64; 3]) -> Self {
+        OrientedBox {
+            center: std::array::from_fn(|axis| (min[axis] + max[axis]) / 2.0),
+            half_extents: std::array::from_fn(|axis| (max[axis] - min[axis]) / 2.0),
+            axes: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+        }
+    }
+
+    /// Returns `true` if the boxes overlap. Boxes that only touch count as overlapping.
+    ///
+    /// Uses the separating axis test: two boxes are disjoint exactly when one of their 15 face
+    /// and edge axes separates them.
+    pub fn intersects(&self, other: &OrientedBox) -> bool {
+        // Rotation of `other` expressed in the frame of `self`, and its absolute value
+        let rotation: [[f64; 3]; 3] = std::array::from_fn(|i| std::array::from_fn(|j| dot(self.axes[i], other.axes[j])));
+        let abs_rotation = rotation.map(|row| row.map(|value| value.abs() + EPSILON));
+        let offset = sub(other.center, self.center);
+        let t: [f64; 3] = std::array::from_fn(|i| dot(offset, self.axes[i]));
+        let (a, b) = (self.half_extents, other.half_extents);
+
+        // The face axes of both boxes
+        for i in 0..3 {
+            let radius_b = b[0] * abs_rotation[i][0] + b[1] * abs_rotation[i][1] + b[2] * abs_rotation[i][2];
+            if t[i].abs() > a[i] + radius_b {
+                return false;
+            }
+        }
+        for j in 0..3 {
+            let radius_a = a[0] * abs_rotation[0][j] + a[1] * abs_rotation[1][j] + a[2] * abs_rotation[2][j];
+            let distance = t[0] * rotation[0][j] + t[1] * rotation[1][j] + t[2] * rotation[2][j];
+            if distance.abs() > radius_a + b[j] {
+                return false;
+            }
+        }
+
+        // The cross products of an edge of each box
+        for i in 0..3 {
+            let (i1, i2) = ((i + 1) % 3, (i + 2) % 3);
+            for j in 0..3 {
+                let (j1, j2) = ((j + 1) % 3, (j + 2) % 3);
+                let radius_a = a[i1] * abs_rotation[i2][j] + a[i2] * abs_rotation[i1][j];
+                let radius_b = b[j1] * abs_rotation[i][j2] + b[j2] * abs_rotation[i][j1];
+                let distance = t[i2] * rotation[i1][j] - t[i1] * rotation[i2][j];
+                if distance.abs() > radius_a + radius_b {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+}
+
+/// Checks that an orientation is a usable rotation, and scales it to unit length.
+///
+/// # Returns
+///
+/// * `Result<[f64; 4], CoordinateError>` - The unit quaternion, or an error if a component isn't finite or all of them are zero.
+pub(crate) fn normalize_orientation(orientation: [f64; 4]) -> Result<[f64; 4], CoordinateError> {
+    if let Some(value) = orientation.iter().find(|value| !value.is_finite()) {
+        return Err(CoordinateError::NonFinite { value: *value });
+    }
+    let length = orientation.iter().map(|value| value * value).sum::<f64>().sqrt();
+    if length == 0.0 {
+        return Err(CoordinateError::ZeroOrientation);
+    }
+    Ok(orientation.map(|value| value / length))
+}
+
+/// Returns the extent along each world axis of the box enclosing a rotated box of the given size.
+pub(crate) fn enclosing_size(size: [f64; 3], orientation: [f64; 4]) -> [f64; 3] {
+    let rotation = rotation_matrix(orientation);
+    std::array::from_fn(|row| (0..3).map(|axis| rotation[row][axis].abs() * size[axis]).sum())
+}
+
+/// Returns the rotation matrix of a unit quaternion [x, y, z, w], whose columns are the rotated axes.
+fn rotation_matrix([x, y, z, w]: [f64; 4]) -> [[f64; 3]; 3] {
+    [
+        [1.0 - 2.0 * (y * y + z * z), 2.0 * (x * y - z * w), 2.0 * (x * z + y * w)],
+        [2.0 * (x * y + z * w), 1.0 - 2.0 * (x * x + z * z), 2.0 * (y * z - x * w)],
+        [2.0 * (x * z - y * w), 2.0 * (y * z + x * w), 1.0 - 2.0 * (x * x + y * y)],
+    ]
+}
+
+/// Returns the dot product of two vectors.
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+/// Returns the difference of two vectors.
+fn sub(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
@@ -0,0 +1,56 @@
+//! # Persistence Budgeting
+//!
+//! This module provides `PersistBudget`, the settings of `VaultManager::persist_tick`. Instead of
+//! writing every changed object at once, a budgeted persist writes as many as it can within a
+//! small time budget on each game tick, so saving never causes a frame hitch on the server.
+//!
+//! Objects are written in order of importance, and the most recently changed objects first
+//! among objects of equal importance. Whatever doesn't fit in a tick stays dirty for the next one.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use std::time::Duration;
+//! use your_crate::{PersistBudget, VaultManager, CustomData};
+//!
+//! let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+//! let budget = PersistBudget::per_tick(Duration::from_millis(2))
+//!     .with_priority("player", 10)
+//!     .with_priority("building", 5);
+//!
+//! loop {
+//!     // ... simulate the tick ...
+//!     vault_manager.persist_tick(&budget).unwrap();
+//! }
+//! ```
+
+use std::collections::HashMap;
+use std::time::Duration;
+use crate::object_type::ObjectType;
+
+/// How much time a budgeted persist may spend per tick, and which objects it writes first.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PersistBudget {
+    /// Time a single `persist_tick` may spend writing objects
+    pub per_tick: Duration,
+    /// Importance of each object type. Types that aren't listed have priority 0.
+    pub priorities: HashMap<ObjectType, i32>,
+}
+
+impl PersistBudget {
+    /// Spends at most `budget` per tick, treating every object type as equally important.
+    pub fn per_tick(budget: Duration) -> Self {
+        PersistBudget { per_tick: budget, priorities: HashMap::new() }
+    }
+
+    /// Writes objects of `object_type` before objects of types with a lower priority.
+    pub fn with_priority(mut self, object_type: impl Into<ObjectType>, priority: i32) -> Self {
+        self.priorities.insert(object_type.into(), priority);
+        self
+    }
+
+    /// Returns the priority of an object type.
+    pub fn priority(&self, object_type: ObjectType) -> i32 {
+        self.priorities.get(&object_type).copied().unwrap_or(0)
+    }
+}
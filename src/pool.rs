@@ -0,0 +1,128 @@
+//! # Custom Data Pooling
+//!
+//! This module provides `ObjectPool`, a free list of custom data allocations for worlds with
+//! high-churn entities such as projectiles, where objects are added and removed thousands of
+//! times per second.
+//!
+//! Every `SpatialObject` owns its custom data through an `Arc<T>`. When an object is removed and
+//! nothing else holds its data, the allocation is kept in the pool and handed out again for the
+//! next object instead of going back to the allocator.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use your_crate::{VaultManager, VaultConfig, CustomData};
+//! use uuid::Uuid;
+//!
+//! let config = VaultConfig { object_pool_capacity: 1024, ..VaultConfig::default() };
+//! let vault_manager: VaultManager<CustomData> = VaultManager::with_config("path/to/database.db", config).unwrap();
+//! let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0).unwrap();
+//!
+//! let projectile = Uuid::new_v4();
+//! let data = vault_manager.pooled_custom_data(CustomData { /* ... */ });
+//! vault_manager.add_object(region_id, projectile, "projectile", 0.0, 0.0, 0.0, data).unwrap();
+//! // The removed projectile's allocation goes back to the pool
+//! vault_manager.remove_object(projectile).unwrap();
+//! ```
+
+use std::sync::Arc;
+use serde::{Serialize, Deserialize};
+use crate::structs::SpatialObject;
+
+/// Counters describing how effective an `ObjectPool` has been.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PoolStats {
+    /// Allocations handed out from the pool instead of the allocator
+    pub reused: u64,
+    /// Allocations that had to come from the allocator because the pool was empty
+    pub allocated: u64,
+    /// Allocations currently waiting in the pool
+    pub pooled: usize,
+}
+
+/// A bounded free list of custom data allocations.
+///
+/// # Type Parameters
+///
+/// * `T`: The type of custom data associated with spatial objects.
+pub struct ObjectPool<T> {
+    /// Uniquely owned allocations ready to be reused
+    free: Vec<Arc<T>>,
+    /// Maximum number of allocations kept in `free`
+    capacity: usize,
+    /// Number of allocations handed out from `free`
+    reused: u64,
+    /// Number of allocations made because `free` was empty
+    allocated: u64,
+}
+
+impl<T> ObjectPool<T> {
+    /// Creates an empty pool that keeps at most `capacity` allocations.
+    ///
+    /// A capacity of zero disables pooling: every allocation comes from the allocator and
+    /// nothing is retained.
+    pub fn new(capacity: usize) -> Self {
+        ObjectPool {
+            free: Vec::with_capacity(capacity),
+            capacity,
+            reused: 0,
+            allocated: 0,
+        }
+    }
+
+    /// Wraps `value` in an `Arc`, reusing a pooled allocation if one is available.
+    pub fn acquire(&mut self, value: T) -> Arc<T> {
+        while let Some(mut recycled) = self.free.pop() {
+            // Only uniquely owned allocations are pooled, but a weak reference may have been
+            // upgraded since, so check again before writing through it
+            if let Some(slot) = Arc::get_mut(&mut recycled) {
+                *slot = value;
+                self.reused += 1;
+                return recycled;
+            }
+        }
+        self.allocated += 1;
+        Arc::new(value)
+    }
+
+    /// Returns a custom data allocation to the pool.
+    ///
+    /// The allocation is only kept if nothing else references it and the pool isn't full;
+    /// otherwise it's simply dropped.
+    pub fn release_data(&mut self, mut custom_data: Arc<T>) {
+        if self.free.len() < self.capacity && Arc::get_mut(&mut custom_data).is_some() {
+            self.free.push(custom_data);
+        }
+    }
+
+    /// Returns the number of allocations currently waiting in the pool.
+    pub fn len(&self) -> usize {
+        self.free.len()
+    }
+
+    /// Returns `true` if the pool holds no allocations.
+    pub fn is_empty(&self) -> bool {
+        self.free.is_empty()
+    }
+
+    /// Returns the maximum number of allocations the pool keeps.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns counters describing how effective the pool has been.
+    pub fn stats(&self) -> PoolStats {
+        PoolStats {
+            reused: self.reused,
+            allocated: self.allocated,
+            pooled: self.free.len(),
+        }
+    }
+}
+
+impl<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> ObjectPool<T> {
+    /// Returns the custom data allocation of a removed object to the pool.
+    pub fn release(&mut self, object: SpatialObject<T>) {
+        self.release_data(object.custom_data);
+    }
+}
@@ -0,0 +1,40 @@
+//! # PebbleVault Prelude
+//!
+//! This module re-exports the stable public API of PebbleVault, so most programs only need a
+//! single import. Everything in the prelude follows semantic versioning; items that are only
+//! reachable from the crate root may still change between minor releases.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use your_crate::prelude::*;
+//!
+//! let vault_manager: VaultManager<CustomData> = VaultManager::with_config("path/to/database.db", VaultConfig::default()).unwrap();
+//! let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0).unwrap();
+//! let objects: Vec<SpatialObject<CustomData>> = vault_manager.query_region(region_id, 0.0, 0.0, 0.0, 10.0, 10.0, 10.0).unwrap();
+//! ```
+
+pub use crate::vault_manager::VaultManager;
+pub use crate::async_manager::{Reply, VaultManagerAsync};
+pub use crate::batch::{Mutation, VaultTransaction};
+pub use crate::template::{RegionTemplate, TemplateObject, Transform};
+pub use crate::catch_up::{CatchUp, CatchUpToken};
+pub use crate::events::VaultEvent;
+pub use crate::change_log::Change;
+pub use crate::consistency::{ConsistencyStats, Divergence, DivergenceKind};
+pub use crate::structs::{LoadFailure, LoadReport, RaycastHit, RegionQuery, RegionSnapshot, SpatialObject, VaultRegion};
+pub use crate::auto_persist::AutoPersist;
+pub use crate::persist_budget::PersistBudget;
+pub use crate::capabilities::BackendCapabilities;
+pub use crate::config::{BoundsPolicy, Compression, KeyFormat, QueryOrder, VaultConfig, WorldBounds};
+pub use crate::object_type::ObjectType;
+pub use crate::type_registry::{DataValidator, TypeDefinition};
+pub use crate::schema::DataSchema;
+pub use crate::world_snapshot::WorldSnapshot;
+pub use crate::cancellation::{CancellationToken, QueryLimits};
+pub use crate::operation::{OperationHandle, OperationStatus};
+pub use crate::simulation::{LodPolicy, RegionTick, SimulationLevel};
+pub use crate::frustum::{Frustum, Plane};
+pub use crate::oriented_box::OrientedBox;
+pub use crate::filter::{Filter, FilterError};
+pub use crate::geometry::{validate_point, CoordinateError, Finite};
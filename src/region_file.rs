@@ -0,0 +1,124 @@
+//! # Portable Region Files
+//!
+//! This module defines the file format written by `VaultManager::export_region` and read by
+//! `VaultManager::import_region`. A region file is self-contained: it holds the region's
+//! record and every object in it, including custom data and expiry times, so it doesn't depend
+//! on the database the region came from. Regions can be shipped between servers that use
+//! different backends, or checked into version control.
+//!
+//! The file is pretty-printed JSON with objects sorted by UUID, so re-exporting an unchanged
+//! region produces an identical file and changes show up as small diffs. It starts with a
+//! header naming the format and its version, which is checked before anything is imported.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use your_crate::{VaultManager, CustomData};
+//!
+//! let staging: VaultManager<CustomData> = VaultManager::new("path/to/staging.db").unwrap();
+//! # let region_id = uuid::Uuid::new_v4();
+//! staging.export_region(region_id, "regions/castle.json").unwrap();
+//!
+//! let live: VaultManager<CustomData> = VaultManager::new("path/to/live.db").unwrap();
+//! let imported = live.import_region("regions/castle.json").unwrap();
+//! assert_eq!(imported, region_id);
+//! ```
+
+use std::fs;
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as Json;
+use uuid::Uuid;
+use crate::object_type::ObjectType;
+
+/// Name of the format, stored in the header of every region file
+const FORMAT_NAME: &str = "pebblevault-region";
+
+/// Version of the format written by this release
+const FORMAT_VERSION: u32 = 1;
+
+/// The contents of a region file.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct RegionFile {
+    /// Name of the format, always `FORMAT_NAME`
+    format: String,
+    /// Version of the format the file was written in
+    version: u32,
+    /// The exported region
+    pub(crate) region: RegionRecord,
+    /// Every object of the region, sorted by UUID
+    pub(crate) objects: Vec<ObjectRecord>,
+}
+
+/// The record of an exported region.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct RegionRecord {
+    /// Unique identifier for the region
+    pub(crate) id: Uuid,
+    /// Center coordinates of the region [x, y, z]
+    pub(crate) center: [f64; 3],
+    /// Radius of the region
+    pub(crate) radius: f64,
+}
+
+/// An exported object.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct ObjectRecord {
+    /// Unique identifier for the object
+    pub(crate) uuid: Uuid,
+    /// Type of the object
+    pub(crate) object_type: ObjectType,
+    /// Position of the object [x, y, z]
+    pub(crate) point: [f64; 3],
+    /// Extent of the object along each axis [x, y, z]
+    pub(crate) size: [f64; 3],
+    /// Number of times the object had been changed
+    pub(crate) version: u64,
+    /// Velocity of the object in units per second, if it moves on its own
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) velocity: Option<[f64; 3]>,
+    /// Rotation of the object as a unit quaternion [x, y, z, w], if it isn't aligned with the world axes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) orientation: Option<[f64; 4]>,
+    /// Custom data of the object, as JSON
+    pub(crate) custom_data: Json,
+    /// Time the object expires, in milliseconds since the Unix epoch, if it has a TTL
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) expires_at: Option<i64>,
+}
+
+impl RegionFile {
+    /// Creates the contents of a region file, sorting the objects by UUID.
+    pub(crate) fn new(region: RegionRecord, mut objects: Vec<ObjectRecord>) -> Self {
+        objects.sort_unstable_by_key(|object| object.uuid);
+        RegionFile { format: FORMAT_NAME.to_string(), version: FORMAT_VERSION, region, objects }
+    }
+
+    /// Writes the region file to `path`, replacing any existing file.
+    pub(crate) fn write(&self, path: &Path) -> Result<(), String> {
+        let contents = serde_json::to_vec_pretty(self)
+            .map_err(|e| format!("Failed to serialize region file: {}", e))?;
+        fs::write(path, contents)
+            .map_err(|e| format!("Failed to write region file {}: {}", path.display(), e))
+    }
+
+    /// Reads a region file from `path`, rejecting files of another format or a newer version.
+    pub(crate) fn read(path: &Path) -> Result<Self, String> {
+        let contents = fs::read(path)
+            .map_err(|e| format!("Failed to read region file {}: {}", path.display(), e))?;
+
+        // Check the header first, so that other files get a clear error instead of a parse error
+        let header: Json = serde_json::from_slice(&contents)
+            .map_err(|e| format!("Failed to parse region file {}: {}", path.display(), e))?;
+        if header["format"] != FORMAT_NAME {
+            return Err(format!("{} is not a PebbleVault region file", path.display()));
+        }
+        match header["version"].as_u64() {
+            Some(version) if version <= FORMAT_VERSION as u64 => {}
+            version => return Err(format!("Unsupported region file version {:?} in {}", version, path.display())),
+        }
+
+        serde_json::from_value(header)
+            .map_err(|e| format!("Failed to parse region file {}: {}", path.display(), e))
+    }
+}
@@ -0,0 +1,106 @@
+//! # Region Statistics
+//!
+//! This module provides `RegionStats`, a summary of a single region's contents and of the
+//! in-memory indexes holding them: how many objects it has and of which types, the space they
+//! occupy, how deep its R-tree is and roughly how much memory it takes. Operators use it for
+//! dashboards, and to decide which regions are worth splitting.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use your_crate::{VaultManager, CustomData};
+//!
+//! let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+//! for region_id in vault_manager.region_ids() {
+//!     let stats = vault_manager.region_stats(region_id).unwrap();
+//!     if stats.object_count > 10_000 {
+//!         println!("{} holds {} objects in {} bytes, consider splitting it", region_id, stats.object_count, stats.estimated_memory);
+//!     }
+//! }
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::mem::size_of;
+use std::sync::Arc;
+use rstar::{ParentNode, RTreeNode, RTreeObject};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::object_type::ObjectType;
+use crate::structs::VaultRegion;
+
+/// Statistics of a single region, returned by `VaultManager::region_stats`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RegionStats {
+    /// The region described
+    pub region_id: Uuid,
+    /// Number of objects in the region
+    pub object_count: usize,
+    /// Number of objects of each type in the region
+    pub counts_by_type: HashMap<ObjectType, usize>,
+    /// Minimum and maximum corners of the box enclosing every object, or `None` if the region is empty
+    pub occupied_bounds: Option<([f64; 3], [f64; 3])>,
+    /// Number of node levels in the region's R-tree, 1 while every object fits in the root node
+    pub tree_depth: usize,
+    /// Estimated bytes held by the region's indexes and custom data, excluding heap memory owned by the custom data
+    pub estimated_memory: usize,
+}
+
+/// Bytes of bookkeeping the region keeps per object, besides its spatial indexes.
+///
+/// Covers the entries of the revision and position maps, with the usual hash map overhead.
+const BOOKKEEPING_PER_OBJECT: usize = 2 * (size_of::<Uuid>() + size_of::<u64>() + size_of::<[f64; 3]>());
+
+impl RegionStats {
+    /// Computes the statistics of a region from its spatial indexes.
+    pub(crate) fn collect<T>(region: &VaultRegion<T>) -> Self
+    where
+        T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized,
+    {
+        let rtree = &region.rtree;
+        let type_index = &region.type_index;
+        let object_count = rtree.size();
+        let occupied_bounds = (object_count > 0).then(|| {
+            let envelope = rtree.root().envelope();
+            (envelope.lower(), envelope.upper())
+        });
+
+        // Objects sharing their custom data through the pool or deduplication hold it once
+        let custom_data: HashSet<*const T> = rtree.iter().map(|object| Arc::as_ptr(&object.custom_data)).collect();
+        let estimated_memory = tree_memory(rtree.root())
+            + type_index.values().map(|typed| tree_memory(typed.root())).sum::<usize>()
+            + custom_data.len() * size_of::<T>()
+            + object_count * BOOKKEEPING_PER_OBJECT;
+
+        RegionStats {
+            region_id: region.id,
+            object_count,
+            counts_by_type: region.type_counts(),
+            occupied_bounds,
+            tree_depth: tree_depth(rtree.root()),
+            estimated_memory,
+        }
+    }
+}
+
+/// Returns the number of levels below and including `node`.
+///
+/// R-trees are balanced, so following the first child down reaches the deepest level.
+fn tree_depth<O: RTreeObject>(node: &ParentNode<O>) -> usize {
+    let mut depth = 1;
+    let mut node = node;
+    while let Some(RTreeNode::Parent(child)) = node.children().first() {
+        depth += 1;
+        node = child;
+    }
+    depth
+}
+
+/// Returns the bytes held by `node` and everything below it.
+fn tree_memory<O: RTreeObject>(node: &ParentNode<O>) -> usize {
+    size_of::<ParentNode<O>>() + node.children().iter()
+        .map(|child| size_of::<RTreeNode<O>>() + match child {
+            RTreeNode::Parent(parent) => tree_memory(parent),
+            RTreeNode::Leaf(_) => 0,
+        })
+        .sum::<usize>()
+}
@@ -0,0 +1,306 @@
+//! # Custom Data Schemas
+//!
+//! This module provides `DataSchema`, a JSON schema the custom data of an object type has to
+//! match. Registering a schema with a `TypeDefinition` rejects malformed custom data when an
+//! object is added or updated, instead of storing it and failing to deserialize it later.
+//!
+//! A schema is written as a JSON Schema document. The commonly used subset is supported:
+//!
+//! - `type`: a type name or an array of names (`null`, `boolean`, `integer`, `number`,
+//!   `string`, `array`, `object`)
+//! - `enum`: the allowed values
+//! - `minimum` and `maximum` for numbers
+//! - `minLength` and `maxLength` for strings
+//! - `items` for the elements of arrays
+//! - `properties`, `required` and `additionalProperties` (a boolean) for objects
+//!
+//! Other keywords, such as `description`, are ignored.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use serde_json::json;
+//! use your_crate::{DataSchema, TypeDefinition};
+//!
+//! let schema = DataSchema::parse(json!({
+//!     "type": "object",
+//!     "required": ["name", "level"],
+//!     "properties": {
+//!         "name": { "type": "string", "minLength": 1 },
+//!         "level": { "type": "integer", "minimum": 1, "maximum": 100 }
+//!     }
+//! })).unwrap();
+//! let player = TypeDefinition::new("player").with_schema(schema);
+//! ```
+
+use std::fmt;
+use serde_json::Value as Json;
+
+/// Deepest nesting of schemas accepted by the parser
+const MAX_DEPTH: usize = 64;
+
+/// A JSON schema custom data is validated against.
+///
+/// Parse once with `DataSchema::parse` and register it with `TypeDefinition::with_schema`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DataSchema {
+    /// The compiled schema
+    root: Node,
+    /// The document the schema was parsed from
+    source: Json,
+}
+
+/// A kind of JSON value named by the `type` keyword.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Kind {
+    Null,
+    Boolean,
+    Integer,
+    Number,
+    String,
+    Array,
+    Object,
+}
+
+/// A compiled schema, or subschema of an array or object.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Node {
+    /// Kinds of values accepted, or `None` for any
+    kinds: Option<Vec<Kind>>,
+    /// Values accepted, or `None` for any
+    allowed: Option<Vec<Json>>,
+    /// Smallest number accepted
+    minimum: Option<f64>,
+    /// Largest number accepted
+    maximum: Option<f64>,
+    /// Fewest characters a string may have
+    min_length: Option<usize>,
+    /// Most characters a string may have
+    max_length: Option<usize>,
+    /// Schema of array elements
+    items: Option<Box<Node>>,
+    /// Schemas of object fields
+    properties: Vec<(String, Node)>,
+    /// Fields objects must have
+    required: Vec<String>,
+    /// Whether objects may have fields not listed in `properties`
+    additional_properties: bool,
+}
+
+impl DataSchema {
+    /// Parses a JSON schema document.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The schema, e.g. `{"type": "object", "required": ["name"]}`.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<DataSchema, String>` - The parsed schema, or a description of what's wrong with it.
+    pub fn parse(source: Json) -> Result<Self, String> {
+        let root = Node::parse(&source, "schema", 0)?;
+        Ok(DataSchema { root, source })
+    }
+
+    /// Returns the document the schema was parsed from.
+    pub fn source(&self) -> &Json {
+        &self.source
+    }
+
+    /// Checks whether a value matches the schema.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), String>` - An empty result if the value matches, or the path of the first mismatch and its reason.
+    pub fn validate(&self, value: &Json) -> Result<(), String> {
+        self.root.validate(value, "data")
+    }
+}
+
+impl Node {
+    /// Compiles a schema found at `path` within the document.
+    fn parse(schema: &Json, path: &str, depth: usize) -> Result<Self, String> {
+        if depth > MAX_DEPTH {
+            return Err(format!("{}: schema is nested too deeply", path));
+        }
+        let fields = match schema {
+            Json::Object(fields) => fields,
+            Json::Bool(true) => return Ok(Node { additional_properties: true, ..Node::default() }),
+            _ => return Err(format!("{}: expected an object", path)),
+        };
+        let number = |keyword: &str| match fields.get(keyword) {
+            None => Ok(None),
+            Some(value) => value.as_f64().map(Some).ok_or_else(|| format!("{}.{}: expected a number", path, keyword)),
+        };
+        let length = |keyword: &str| match fields.get(keyword) {
+            None => Ok(None),
+            Some(value) => value.as_u64().map(|n| Some(n as usize)).ok_or_else(|| format!("{}.{}: expected a non-negative integer", path, keyword)),
+        };
+
+        let kinds = match fields.get("type") {
+            None => None,
+            Some(Json::String(name)) => Some(vec![Kind::parse(name, path)?]),
+            Some(Json::Array(names)) => Some(names.iter()
+                .map(|name| name.as_str().ok_or_else(|| format!("{}.type: expected type names", path)).and_then(|name| Kind::parse(name, path)))
+                .collect::<Result<Vec<Kind>, String>>()?),
+            Some(_) => return Err(format!("{}.type: expected a type name or an array of them", path)),
+        };
+        let allowed = match fields.get("enum") {
+            None => None,
+            Some(Json::Array(values)) => Some(values.clone()),
+            Some(_) => return Err(format!("{}.enum: expected an array", path)),
+        };
+        let items = match fields.get("items") {
+            None => None,
+            Some(items) => Some(Box::new(Node::parse(items, &format!("{}.items", path), depth + 1)?)),
+        };
+        let properties = match fields.get("properties") {
+            None => Vec::new(),
+            Some(Json::Object(properties)) => properties.iter()
+                .map(|(name, schema)| Ok((name.clone(), Node::parse(schema, &format!("{}.properties.{}", path, name), depth + 1)?)))
+                .collect::<Result<Vec<(String, Node)>, String>>()?,
+            Some(_) => return Err(format!("{}.properties: expected an object", path)),
+        };
+        let required = match fields.get("required") {
+            None => Vec::new(),
+            Some(Json::Array(names)) => names.iter()
+                .map(|name| name.as_str().map(str::to_string).ok_or_else(|| format!("{}.required: expected field names", path)))
+                .collect::<Result<Vec<String>, String>>()?,
+            Some(_) => return Err(format!("{}.required: expected an array", path)),
+        };
+        let additional_properties = match fields.get("additionalProperties") {
+            None => true,
+            Some(Json::Bool(allowed)) => *allowed,
+            Some(_) => return Err(format!("{}.additionalProperties: expected a boolean", path)),
+        };
+
+        Ok(Node {
+            kinds,
+            allowed,
+            minimum: number("minimum")?,
+            maximum: number("maximum")?,
+            min_length: length("minLength")?,
+            max_length: length("maxLength")?,
+            items,
+            properties,
+            required,
+            additional_properties,
+        })
+    }
+
+    /// Checks a value found at `path` within the custom data.
+    fn validate(&self, value: &Json, path: &str) -> Result<(), String> {
+        if let Some(kinds) = &self.kinds {
+            if !kinds.iter().any(|kind| kind.matches(value)) {
+                return Err(format!("{}: expected {}, found {}", path, Kinds(kinds), Kind::of(value)));
+            }
+        }
+        if let Some(allowed) = &self.allowed {
+            if !allowed.contains(value) {
+                return Err(format!("{}: {} is not one of the allowed values", path, value));
+            }
+        }
+
+        match value {
+            Json::Number(number) => {
+                let number = number.as_f64().unwrap_or(f64::NAN);
+                if self.minimum.is_some_and(|minimum| number < minimum) || self.maximum.is_some_and(|maximum| number > maximum) {
+                    return Err(format!("{}: {} is out of range", path, number));
+                }
+            }
+            Json::String(string) => {
+                let length = string.chars().count();
+                if self.min_length.is_some_and(|min| length < min) || self.max_length.is_some_and(|max| length > max) {
+                    return Err(format!("{}: length {} is out of range", path, length));
+                }
+            }
+            Json::Array(elements) => {
+                if let Some(items) = &self.items {
+                    for (index, element) in elements.iter().enumerate() {
+                        items.validate(element, &format!("{}[{}]", path, index))?;
+                    }
+                }
+            }
+            Json::Object(fields) => {
+                if let Some(missing) = self.required.iter().find(|name| !fields.contains_key(*name)) {
+                    return Err(format!("{}: missing required field {}", path, missing));
+                }
+                for (name, field) in fields {
+                    let field_path = format!("{}.{}", path, name);
+                    match self.properties.iter().find(|(property, _)| property == name) {
+                        Some((_, schema)) => schema.validate(field, &field_path)?,
+                        None if !self.additional_properties => return Err(format!("{}: unexpected field", field_path)),
+                        None => {}
+                    }
+                }
+            }
+            Json::Null | Json::Bool(_) => {}
+        }
+        Ok(())
+    }
+}
+
+impl Kind {
+    /// Looks up a type name of the `type` keyword.
+    fn parse(name: &str, path: &str) -> Result<Self, String> {
+        match name {
+            "null" => Ok(Kind::Null),
+            "boolean" => Ok(Kind::Boolean),
+            "integer" => Ok(Kind::Integer),
+            "number" => Ok(Kind::Number),
+            "string" => Ok(Kind::String),
+            "array" => Ok(Kind::Array),
+            "object" => Ok(Kind::Object),
+            _ => Err(format!("{}.type: unknown type {}", path, name)),
+        }
+    }
+
+    /// Returns the most specific kind of a value.
+    fn of(value: &Json) -> Self {
+        match value {
+            Json::Null => Kind::Null,
+            Json::Bool(_) => Kind::Boolean,
+            Json::Number(number) if number.is_i64() || number.is_u64() => Kind::Integer,
+            Json::Number(_) => Kind::Number,
+            Json::String(_) => Kind::String,
+            Json::Array(_) => Kind::Array,
+            Json::Object(_) => Kind::Object,
+        }
+    }
+
+    /// Returns `true` if the value is of this kind. Integers are numbers too.
+    fn matches(&self, value: &Json) -> bool {
+        let kind = Kind::of(value);
+        kind == *self || (*self == Kind::Number && kind == Kind::Integer)
+    }
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Kind::Null => "null",
+            Kind::Boolean => "boolean",
+            Kind::Integer => "integer",
+            Kind::Number => "number",
+            Kind::String => "string",
+            Kind::Array => "array",
+            Kind::Object => "object",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Formats a list of kinds as "a", "a or b", ...
+struct Kinds<'a>(&'a [Kind]);
+
+impl fmt::Display for Kinds<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, kind) in self.0.iter().enumerate() {
+            if index > 0 {
+                f.write_str(" or ")?;
+            }
+            write!(f, "{}", kind)?;
+        }
+        Ok(())
+    }
+}
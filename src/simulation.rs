@@ -0,0 +1,72 @@
+//! # Simulation Level of Detail
+//!
+//! This module defines the types used by `VaultManager::schedule_tick` to decide how each
+//! region is simulated on a game tick. Regions near a player are simulated every tick; the
+//! others are simulated only every few ticks, at which point the game applies an aggregated
+//! update covering the whole gap (e.g., resources regrowing in bulk).
+//!
+//! The manager remembers the last tick each region was simulated at. When a player comes back
+//! to a region, its first full tick reports how many ticks were skipped, so the game can catch
+//! up before resuming full simulation.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use your_crate::{LodPolicy, SimulationLevel, VaultManager, CustomData};
+//!
+//! let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+//! let policy = LodPolicy::new(200.0, 20);
+//! for tick in 0..100 {
+//!     for region in vault_manager.schedule_tick(tick, &policy).unwrap() {
+//!         match region.level {
+//!             SimulationLevel::Full if region.elapsed_ticks == 1 => { /* simulate one tick */ }
+//!             _ => { /* apply an aggregated update covering region.elapsed_ticks */ }
+//!         }
+//!     }
+//! }
+//! ```
+
+use uuid::Uuid;
+use crate::object_type::ObjectType;
+
+/// Decides which regions are simulated fully and how often the others are simulated.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LodPolicy {
+    /// Distance from a region's bounding cube within which a player keeps the region fully simulated
+    pub active_distance: f64,
+    /// Inactive regions are simulated once every this many ticks
+    pub inactive_interval: u64,
+    /// Object type of the players
+    pub player_type: ObjectType,
+}
+
+impl LodPolicy {
+    /// Creates a policy treating objects of type "player" as players.
+    pub fn new(active_distance: f64, inactive_interval: u64) -> Self {
+        LodPolicy {
+            active_distance,
+            inactive_interval,
+            player_type: ObjectType::from("player"),
+        }
+    }
+}
+
+/// How a region is simulated on a tick.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SimulationLevel {
+    /// A player is nearby; the region is simulated every tick
+    Full,
+    /// No player is nearby; the region is simulated every `LodPolicy::inactive_interval` ticks
+    Reduced,
+}
+
+/// A region due to be simulated on a tick.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RegionTick {
+    /// The region to simulate
+    pub region_id: Uuid,
+    /// How the region is simulated
+    pub level: SimulationLevel,
+    /// Ticks since the region was last simulated, which the update should cover
+    pub elapsed_ticks: u64,
+}
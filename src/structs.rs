@@ -8,6 +8,9 @@
 //!
 //! - `SpatialObject`: Represents individual entities in the spatial database.
 //! - `VaultRegion`: Defines a spatial region containing multiple objects.
+//! - `RegionSnapshot`: A frozen view of a region's objects that can be iterated without locking.
+//! - `RaycastHit`: An object struck by a ray, with the distance along the ray.
+//! - `LoadReport`: Describes what was loaded from the persistent database at startup.
 //!
 //! ## Features
 //!
@@ -21,8 +24,7 @@
 //! use uuid::Uuid;
 //! use std::sync::Arc;
 //! use serde::{Serialize, Deserialize};
-//! use your_crate::{SpatialObject, VaultRegion};
-//! use rstar::RTree;
+//! use your_crate::{ObjectType, SpatialObject, VaultRegion};
 //!
 //! #[derive(Clone, Serialize, Deserialize, PartialEq)]
 //! struct PlayerData {
@@ -32,23 +34,33 @@
 //!
 //! let player = SpatialObject {
 //!     uuid: Uuid::new_v4(),
-//!     object_type: "player".to_string(),
+//!     object_type: ObjectType::from("player"),
 //!     point: [1.0, 2.0, 3.0],
 //!     custom_data: Arc::new(PlayerData { name: "Alice".to_string(), level: 5 }),
+//!     size: [1.0, 2.0, 1.0],
+//!     version: 0,
+//!     velocity: None,
+//!     orientation: None,
 //! };
 //!
-//! let region = VaultRegion {
-//!     id: Uuid::new_v4(),
-//!     center: [0.0, 0.0, 0.0],
-//!     radius: 100.0,
-//!     rtree: RTree::new(),
-//! };
+//! let mut region = VaultRegion::new(Uuid::new_v4(), [0.0, 0.0, 0.0], 100.0);
+//! region.insert_object(player);
 //! ```
 
 use rstar::*;
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
+use std::time::Instant;
 use serde::{Serialize, Deserialize};
 use uuid::Uuid;
+use crate::object_type::ObjectType;
+use crate::geometry::{validate_point, CoordinateError};
+use crate::oriented_box::{enclosing_size, normalize_orientation, OrientedBox};
+use crate::catch_up::CatchUpToken;
+use crate::region_stats::RegionStats;
+
+/// Number of recent removals a region remembers for catch-up deltas.
+const REMOVAL_HISTORY: usize = 1024;
 
 /// Represents a spatial object in the game world.
 ///
@@ -64,9 +76,11 @@ use uuid::Uuid;
 /// # Fields
 ///
 /// * `uuid`: Unique identifier for the object.
-/// * `object_type`: String describing the type of the object (e.g., "player", "building").
+/// * `object_type`: Interned name of the type of the object (e.g., "player", "building").
 /// * `point`: 3D coordinates of the object [x, y, z].
 /// * `custom_data`: Reference-counted pointer to associated custom data.
+/// * `size`: Extent of the object along each axis, centered on `point`. Zero for point-like objects.
+/// * `version`: Number of times the object has been changed. Updates carrying an older version are rejected.
 ///
 /// # Examples
 ///
@@ -74,7 +88,7 @@ use uuid::Uuid;
 /// use uuid::Uuid;
 /// use std::sync::Arc;
 /// use serde::{Serialize, Deserialize};
-/// use your_crate::SpatialObject;
+/// use your_crate::{ObjectType, SpatialObject};
 ///
 /// #[derive(Clone, Serialize, Deserialize, PartialEq)]
 /// struct PlayerData {
@@ -84,16 +98,24 @@ use uuid::Uuid;
 ///
 /// let player = SpatialObject {
 ///     uuid: Uuid::new_v4(),
-///     object_type: "player".to_string(),
+///     object_type: ObjectType::from("player"),
 ///     point: [1.0, 2.0, 3.0],
 ///     custom_data: Arc::new(PlayerData { name: "Alice".to_string(), level: 5 }),
+///     size: [1.0, 2.0, 1.0],
+///     version: 0,
+///     velocity: None,
+///     orientation: None,
 /// };
 ///
 /// let resource = SpatialObject {
 ///     uuid: Uuid::new_v4(),
-///     object_type: "resource".to_string(),
+///     object_type: ObjectType::from("resource"),
 ///     point: [4.0, 5.0, 6.0],
 ///     custom_data: Arc::new("Gold Ore".to_string()),
+///     size: [0.0, 0.0, 0.0],
+///     version: 0,
+///     velocity: None,
+///     orientation: None,
 /// };
 /// ```
 #[derive(Clone, PartialEq)]
@@ -101,11 +123,106 @@ pub struct SpatialObject<T: Clone + Serialize + for<'de> Deserialize<'de> + Part
     /// Unique identifier for the object
     pub uuid: Uuid,
     /// Type of the object (e.g., "player", "building", "resource")
-    pub object_type: String,
+    pub object_type: ObjectType,
     /// 3D coordinates of the object [x, y, z]
     pub point: [f64; 3],
     /// Reference-counted pointer to custom data associated with the object
     pub custom_data: Arc<T>,
+    /// Extent of the object along each axis [x, y, z], centered on `point`
+    pub size: [f64; 3],
+    /// Number of times the object has been changed, incremented by the `VaultManager` on every update
+    pub version: u64,
+    /// Velocity of the object in units per second [x, y, z], extrapolated by `VaultManager::advance`
+    ///
+    /// `None` for objects that only move when they're moved explicitly.
+    pub velocity: Option<[f64; 3]>,
+    /// Rotation of the object as a unit quaternion [x, y, z, w], turning `size` into an oriented box
+    ///
+    /// `None` for objects aligned with the world axes.
+    pub orientation: Option<[f64; 4]>,
+}
+
+impl<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> SpatialObject<T> {
+    /// Creates a point-like object, validating its position.
+    ///
+    /// # Arguments
+    ///
+    /// * `uuid` - Unique identifier for the object.
+    /// * `object_type` - Type of the object (e.g., "player").
+    /// * `point` - 3D coordinates of the object [x, y, z].
+    /// * `custom_data` - Custom data associated with the object.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Self, CoordinateError>` - The new object with a zero size and version, or an error if any coordinate is NaN or infinite.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use uuid::Uuid;
+    /// # use std::sync::Arc;
+    /// # use your_crate::SpatialObject;
+    /// let object = SpatialObject::new(Uuid::new_v4(), "player", [1.0, 2.0, 3.0], Arc::new("Example object".to_string()))
+    ///     .expect("Position should be finite");
+    /// assert!(SpatialObject::new(Uuid::new_v4(), "player", [f64::NAN, 0.0, 0.0], Arc::new(String::new())).is_err());
+    /// ```
+    pub fn new(uuid: Uuid, object_type: impl Into<ObjectType>, point: [f64; 3], custom_data: Arc<T>) -> Result<Self, CoordinateError> {
+        Ok(SpatialObject {
+            uuid,
+            object_type: object_type.into(),
+            point: validate_point(point)?,
+            custom_data,
+            size: [0.0; 3],
+            version: 0,
+            velocity: None,
+            orientation: None,
+        })
+    }
+
+    /// Sets the extent of the object along each axis, validating it.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Self, CoordinateError>` - The object with its new size, or an error if any extent is NaN or infinite.
+    pub fn with_size(mut self, size: [f64; 3]) -> Result<Self, CoordinateError> {
+        self.size = validate_point(size)?;
+        Ok(self)
+    }
+
+    /// Sets the velocity of the object in units per second, validating it.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Self, CoordinateError>` - The object with its new velocity, or an error if any component is NaN or infinite.
+    pub fn with_velocity(mut self, velocity: [f64; 3]) -> Result<Self, CoordinateError> {
+        self.velocity = Some(validate_point(velocity)?);
+        Ok(self)
+    }
+
+    /// Sets the rotation of the object, scaling the quaternion to unit length.
+    ///
+    /// # Arguments
+    ///
+    /// * `orientation` - Quaternion [x, y, z, w] rotating the object's size into the world.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Self, CoordinateError>` - The object with its new orientation, or an error if any component is NaN or infinite, or all are zero.
+    pub fn with_orientation(mut self, orientation: [f64; 4]) -> Result<Self, CoordinateError> {
+        self.orientation = Some(normalize_orientation(orientation)?);
+        Ok(self)
+    }
+
+    /// Returns the box the object occupies, rotated by its orientation.
+    pub fn oriented_box(&self) -> OrientedBox {
+        match self.orientation {
+            Some(orientation) => OrientedBox::new(self.point, self.size, orientation),
+            None => {
+                let envelope = self.envelope();
+                OrientedBox::axis_aligned(envelope.lower(), envelope.upper())
+            }
+        }
+    }
 }
 
 impl<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> PointDistance for SpatialObject<T> {
@@ -126,12 +243,16 @@ impl<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> Point
     /// ```rust
     /// # use uuid::Uuid;
     /// # use std::sync::Arc;
-    /// # use your_crate::{SpatialObject, PointDistance};
+    /// # use your_crate::{ObjectType, SpatialObject, PointDistance};
     /// let object = SpatialObject {
     ///     uuid: Uuid::new_v4(),
-    ///     object_type: "player".to_string(),
+    ///     object_type: ObjectType::from("player"),
     ///     point: [1.0, 2.0, 3.0],
     ///     custom_data: Arc::new("Example object".to_string()),
+    ///     size: [0.0, 0.0, 0.0],
+    ///     version: 0,
+    ///     velocity: None,
+    ///     orientation: None,
     /// };
     /// let distance = object.distance_2(&[4.0, 5.0, 6.0]);
     /// assert_eq!(distance, 27.0);
@@ -150,6 +271,9 @@ impl<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> RTree
     /// Creates an Axis-Aligned Bounding Box (AABB) envelope for this object.
     ///
     /// This method is used by the R-tree for efficient spatial indexing and querying.
+    /// The box is centered on the object's point and spans its `size`; objects with
+    /// a zero size have a degenerate box at their point. The box of an object with an
+    /// orientation encloses its rotated box.
     ///
     /// # Returns
     ///
@@ -160,19 +284,31 @@ impl<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> RTree
     /// ```rust
     /// # use uuid::Uuid;
     /// # use std::sync::Arc;
-    /// # use your_crate::{SpatialObject, RTreeObject};
+    /// # use your_crate::{ObjectType, SpatialObject, RTreeObject};
     /// let object = SpatialObject {
     ///     uuid: Uuid::new_v4(),
-    ///     object_type: "player".to_string(),
+    ///     object_type: ObjectType::from("player"),
     ///     point: [1.0, 2.0, 3.0],
     ///     custom_data: Arc::new("Example object".to_string()),
+    ///     size: [0.0, 0.0, 0.0],
+    ///     version: 0,
+    ///     velocity: None,
+    ///     orientation: None,
     /// };
     /// let envelope = object.envelope();
     /// assert_eq!(envelope.lower(), [1.0, 2.0, 3.0]);
     /// assert_eq!(envelope.upper(), [1.0, 2.0, 3.0]);
     /// ```
     fn envelope(&self) -> Self::Envelope {
-        AABB::from_point(self.point)
+        let size = match self.orientation {
+            Some(orientation) => enclosing_size(self.size, orientation),
+            None => self.size,
+        };
+        let half = [size[0] / 2.0, size[1] / 2.0, size[2] / 2.0];
+        AABB::from_corners(
+            [self.point[0] - half[0], self.point[1] - half[1], self.point[2] - half[2]],
+            [self.point[0] + half[0], self.point[1] + half[1], self.point[2] + half[2]],
+        )
     }
 }
 
@@ -192,12 +328,12 @@ impl<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> RTree
 /// * `center`: 3D coordinates of the region's center [x, y, z].
 /// * `radius`: Radius of the region.
 /// * `rtree`: Spatial index (RTree) for objects in this region.
+/// * `type_index`: Per-object-type spatial indexes mirroring `rtree`.
 ///
 /// # Examples
 ///
 /// ```rust
 /// use uuid::Uuid;
-/// use rstar::RTree;
 /// use pebblevault::{VaultRegion, SpatialObject};
 ///
 /// // Define a custom data type for your spatial objects
@@ -207,16 +343,14 @@ impl<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> RTree
 ///     value: i32,
 /// }
 ///
-/// let region: VaultRegion<CustomData> = VaultRegion {
-///     id: Uuid::new_v4(),
-///     center: [0.0, 0.0, 0.0],
-///     radius: 100.0,
-///     rtree: RTree::new(),
-/// };
+/// let region: VaultRegion<CustomData> = VaultRegion::new(Uuid::new_v4(), [0.0, 0.0, 0.0], 100.0);
 /// ```
 ///
 /// Note that the custom data type `T` is associated with the `SpatialObject`s
 /// that will be stored in this region, not with the region itself.
+///
+/// Objects should be added and removed with `insert_object` and `remove_object`, which keep
+/// `rtree` and `type_index` in sync. Mutating `rtree` directly leaves `type_index` stale.
 pub struct VaultRegion<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> {
     /// Unique identifier for the region
     pub id: Uuid,
@@ -224,6 +358,460 @@ pub struct VaultRegion<T: Clone + Serialize + for<'de> Deserialize<'de> + Partia
     pub center: [f64; 3],
     /// Radius of the region
     pub radius: f64,
-    /// Spatial index (RTree) for objects in this region
-    pub rtree: RTree<SpatialObject<T>>,
-}
\ No newline at end of file
+    /// Spatial index (RTree) for objects in this region.
+    ///
+    /// The tree is shared copy-on-write with any `RegionSnapshot` taken of the region.
+    pub rtree: Arc<RTree<SpatialObject<T>>>,
+    /// Spatial indexes for objects in this region, keyed by object type
+    pub type_index: HashMap<ObjectType, RTree<SpatialObject<T>>>,
+    /// Objects changed in memory since they were last written to the persistent database, with the time of their last change
+    dirty: HashMap<Uuid, Instant>,
+    /// Identifies this instance of the region in catch-up tokens
+    epoch: Uuid,
+    /// Advanced by every object added to or removed from the region
+    revision: u64,
+    /// Revision at which each object in the region was last added
+    revisions: HashMap<Uuid, u64>,
+    /// Position of each object in the region, to find it in `rtree` without a scan
+    positions: HashMap<Uuid, [f64; 3]>,
+    /// Recent removals and their revisions, oldest first
+    removals: VecDeque<(u64, Uuid)>,
+    /// Oldest revision catch-up deltas can still be computed from
+    history_floor: u64,
+    /// Set once the region is deleted or replaced, for threads that looked it up beforehand
+    retired: bool,
+}
+impl<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> VaultRegion<T> {
+    /// Creates an empty region.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - Unique identifier for the region.
+    /// * `center` - Center coordinates of the region [x, y, z].
+    /// * `radius` - Radius of the region.
+    pub fn new(id: Uuid, center: [f64; 3], radius: f64) -> Self {
+        VaultRegion {
+            id,
+            center,
+            radius,
+            rtree: Arc::new(RTree::new()),
+            type_index: HashMap::new(),
+            dirty: HashMap::new(),
+            epoch: Uuid::new_v4(),
+            revision: 0,
+            revisions: HashMap::new(),
+            positions: HashMap::new(),
+            removals: VecDeque::new(),
+            history_floor: 0,
+            retired: false,
+        }
+    }
+
+    /// Adds an object to the region's spatial index and to the index for its type.
+    ///
+    /// # Arguments
+    ///
+    /// * `object` - The object to add.
+    pub fn insert_object(&mut self, object: SpatialObject<T>) {
+        self.record_insertion(object.uuid);
+        self.positions.insert(object.uuid, object.point);
+        self.type_index
+            .entry(object.object_type)
+            .or_default()
+            .insert(object.clone());
+        Arc::make_mut(&mut self.rtree).insert(object);
+    }
+
+    /// Adds many objects to the region at once.
+    ///
+    /// If the region is empty its indexes are built with `RTree::bulk_load`, which is much faster
+    /// than inserting objects one at a time and produces a better balanced tree. Otherwise the
+    /// objects are inserted individually.
+    ///
+    /// # Arguments
+    ///
+    /// * `objects` - The objects to add.
+    pub fn insert_objects(&mut self, objects: Vec<SpatialObject<T>>) {
+        if self.rtree.size() > 0 {
+            for object in objects {
+                self.insert_object(object);
+            }
+            return;
+        }
+
+        let mut by_type: HashMap<ObjectType, Vec<SpatialObject<T>>> = HashMap::new();
+        for object in &objects {
+            self.record_insertion(object.uuid);
+            self.positions.insert(object.uuid, object.point);
+            by_type.entry(object.object_type).or_default().push(object.clone());
+        }
+        self.type_index = by_type.into_iter()
+            .map(|(object_type, typed)| (object_type, RTree::bulk_load(typed)))
+            .collect();
+        self.rtree = Arc::new(RTree::bulk_load(objects));
+    }
+
+    /// Removes an object from the region's spatial index and from the index for its type.
+    ///
+    /// # Arguments
+    ///
+    /// * `object` - The object to remove. It must be equal to the stored object.
+    ///
+    /// # Returns
+    ///
+    /// The removed object, or `None` if it wasn't in the region.
+    pub fn remove_object(&mut self, object: &SpatialObject<T>) -> Option<SpatialObject<T>> {
+        let removed = Arc::make_mut(&mut self.rtree).remove(object)?;
+        self.dirty.remove(&removed.uuid);
+        self.record_removal(removed.uuid);
+        self.positions.remove(&removed.uuid);
+        if let Some(typed) = self.type_index.get_mut(&removed.object_type) {
+            typed.remove(object);
+            if typed.size() == 0 {
+                self.type_index.remove(&removed.object_type);
+            }
+        }
+        Some(removed)
+    }
+
+    /// Empties the region's indexes without recording removals, for unloading it from memory.
+    ///
+    /// Objects loaded back in later count as changed for catch-up, so no change is missed.
+    pub(crate) fn clear_objects(&mut self) {
+        self.rtree = Arc::default();
+        self.type_index.clear();
+        self.revisions.clear();
+        self.positions.clear();
+    }
+
+    /// Empties a region that was deleted or replaced, and marks it as such.
+    ///
+    /// Threads still holding the region find nothing in it and nothing left to persist.
+    pub(crate) fn retire(&mut self) {
+        self.clear_objects();
+        self.dirty.clear();
+        self.retired = true;
+    }
+
+    /// Returns `true` if the region was deleted or replaced.
+    pub(crate) fn is_retired(&self) -> bool {
+        self.retired
+    }
+
+    fn record_insertion(&mut self, uuid: Uuid) {
+        self.revision += 1;
+        self.revisions.insert(uuid, self.revision);
+    }
+
+    fn record_removal(&mut self, uuid: Uuid) {
+        self.revision += 1;
+        self.revisions.remove(&uuid);
+        self.removals.push_back((self.revision, uuid));
+        if self.removals.len() > REMOVAL_HISTORY {
+            if let Some((revision, _)) = self.removals.pop_front() {
+                self.history_floor = revision;
+            }
+        }
+    }
+
+    /// Returns `true` if the region holds an object with the given UUID.
+    ///
+    /// Unlike searching `rtree`, this is a constant-time lookup.
+    pub fn contains_object(&self, uuid: Uuid) -> bool {
+        self.revisions.contains_key(&uuid)
+    }
+
+    /// Returns the object with the given UUID, if the region holds it.
+    ///
+    /// The object's position is looked up first, so only the part of `rtree` around it is
+    /// searched instead of every object in the region.
+    pub fn get_object(&self, uuid: Uuid) -> Option<&SpatialObject<T>> {
+        let position = self.positions.get(&uuid)?;
+        self.rtree.locate_all_at_point(position).find(|obj| obj.uuid == uuid)
+    }
+
+    /// Returns a token for the region's current state, to catch up from later.
+    pub fn catch_up_token(&self) -> CatchUpToken {
+        CatchUpToken { region_id: self.id, epoch: self.epoch, revision: self.revision }
+    }
+
+    /// Returns the objects added or changed and the objects removed since `token`.
+    ///
+    /// Returns `None` if the token wasn't issued by this instance of the region, or is older
+    /// than the removals the region remembers.
+    pub(crate) fn changes_since(&self, token: CatchUpToken) -> Option<(Vec<SpatialObject<T>>, Vec<Uuid>)> {
+        if token.region_id != self.id || token.epoch != self.epoch
+            || token.revision < self.history_floor || token.revision > self.revision {
+            return None;
+        }
+        let changed = self.rtree.iter()
+            .filter(|obj| self.revisions.get(&obj.uuid).is_some_and(|revision| *revision > token.revision))
+            .cloned()
+            .collect();
+        let mut removed: Vec<Uuid> = self.removals.iter()
+            .filter(|(revision, uuid)| *revision > token.revision && !self.revisions.contains_key(uuid))
+            .map(|(_, uuid)| *uuid)
+            .collect();
+        removed.sort();
+        removed.dedup();
+        Some((changed, removed))
+    }
+
+    /// Marks an object as changed in memory but not yet in the persistent database.
+    ///
+    /// Dirty objects are written by the next `VaultManager::persist_to_disk`.
+    ///
+    /// # Arguments
+    ///
+    /// * `uuid` - The UUID of the changed object.
+    pub fn mark_dirty(&mut self, uuid: Uuid) {
+        self.dirty.insert(uuid, Instant::now());
+    }
+
+    /// Marks an object as dirty, keeping the time of its last change from another region.
+    pub(crate) fn mark_dirty_at(&mut self, uuid: Uuid, changed_at: Instant) {
+        self.dirty.insert(uuid, changed_at);
+    }
+
+    /// Returns `true` if the object has changes that haven't been persisted yet.
+    pub fn is_dirty(&self, uuid: Uuid) -> bool {
+        self.dirty.contains_key(&uuid)
+    }
+
+    /// Returns when an object with unpersisted changes was last changed, or `None` if it has none.
+    pub fn dirty_since(&self, uuid: Uuid) -> Option<Instant> {
+        self.dirty.get(&uuid).copied()
+    }
+
+    /// Clears an object's dirty flag, unless it changed again after `changed_at`.
+    pub(crate) fn clear_dirty(&mut self, uuid: Uuid, changed_at: Instant) {
+        if self.dirty.get(&uuid) == Some(&changed_at) {
+            self.dirty.remove(&uuid);
+        }
+    }
+
+    /// Returns the number of objects with changes that haven't been persisted yet.
+    pub fn dirty_count(&self) -> usize {
+        self.dirty.len()
+    }
+
+    /// Returns the objects with changes that haven't been persisted yet.
+    pub(crate) fn dirty(&self) -> &HashMap<Uuid, Instant> {
+        &self.dirty
+    }
+
+    /// Clears the dirty set, returning the objects that were in it.
+    pub(crate) fn take_dirty(&mut self) -> HashMap<Uuid, Instant> {
+        std::mem::take(&mut self.dirty)
+    }
+
+    /// Returns the spatial index holding only objects of the given type.
+    ///
+    /// # Arguments
+    ///
+    /// * `object_type` - The object type to look up (e.g., "player").
+    ///
+    /// # Returns
+    ///
+    /// The index for `object_type`, or `None` if the region holds no objects of that type.
+    pub fn objects_of_type(&self, object_type: &str) -> Option<&RTree<SpatialObject<T>>> {
+        self.type_index.get(object_type)
+    }
+
+    /// Returns the number of objects of each type in the region.
+    pub fn type_counts(&self) -> HashMap<ObjectType, usize> {
+        self.type_index.iter()
+            .map(|(object_type, typed)| (*object_type, typed.size()))
+            .collect()
+    }
+
+    /// Returns the statistics of the region: object counts, occupied space, tree depth and memory use.
+    pub fn stats(&self) -> RegionStats {
+        RegionStats::collect(self)
+    }
+
+    /// Returns the axis-aligned bounding box of the region.
+    ///
+    /// The box spans `radius` in every direction from the region's center, so it encloses
+    /// everything that belongs to the region. It's used to decide which regions a spatial
+    /// query can possibly touch.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use uuid::Uuid;
+    /// # use your_crate::VaultRegion;
+    /// let region: VaultRegion<String> = VaultRegion::new(Uuid::new_v4(), [0.0, 0.0, 0.0], 100.0);
+    /// assert_eq!(region.bounds().lower(), [-100.0, -100.0, -100.0]);
+    /// assert_eq!(region.bounds().upper(), [100.0, 100.0, 100.0]);
+    /// ```
+    pub fn bounds(&self) -> AABB<[f64; 3]> {
+        AABB::from_corners(
+            [self.center[0] - self.radius, self.center[1] - self.radius, self.center[2] - self.radius],
+            [self.center[0] + self.radius, self.center[1] + self.radius, self.center[2] + self.radius],
+        )
+    }
+
+    /// Returns a frozen view of the region's objects.
+    ///
+    /// Taking a snapshot only clones an `Arc`. While the snapshot is alive, the first change to
+    /// the region copies its spatial index, so later changes don't show up in the snapshot.
+    pub fn snapshot(&self) -> RegionSnapshot<T> {
+        RegionSnapshot {
+            region_id: self.id,
+            tree: Arc::clone(&self.rtree),
+        }
+    }
+}
+
+/// A frozen, point-in-time view of the objects in a region.
+///
+/// Snapshots are taken with `VaultManager::iter_region_snapshot` or `VaultRegion::snapshot` and
+/// can be iterated for as long as needed without holding the region's lock.
+///
+/// # Fields
+///
+/// * `region_id`: The UUID of the region the snapshot was taken of.
+pub struct RegionSnapshot<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> {
+    /// The UUID of the region the snapshot was taken of
+    pub region_id: Uuid,
+    /// The region's spatial index at the time of the snapshot
+    tree: Arc<RTree<SpatialObject<T>>>,
+}
+
+impl<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> RegionSnapshot<T> {
+    /// Returns an iterator over the objects in the snapshot, in no particular order.
+    pub fn iter(&self) -> iterators::RTreeIterator<'_, SpatialObject<T>> {
+        self.tree.iter()
+    }
+
+    /// Returns the number of objects in the snapshot.
+    pub fn len(&self) -> usize {
+        self.tree.size()
+    }
+
+    /// Returns `true` if the snapshot holds no objects.
+    pub fn is_empty(&self) -> bool {
+        self.tree.size() == 0
+    }
+
+    /// Returns the snapshot's spatial index, for spatial queries against the frozen state.
+    pub fn rtree(&self) -> &RTree<SpatialObject<T>> {
+        &self.tree
+    }
+}
+
+impl<'a, T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> IntoIterator for &'a RegionSnapshot<T> {
+    type Item = &'a SpatialObject<T>;
+    type IntoIter = iterators::RTreeIterator<'a, SpatialObject<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.tree.iter()
+    }
+}
+
+/// The objects of a region within a bounding box, iterated without cloning them.
+///
+/// Created by `VaultManager::query_region_iter`. The query runs against a snapshot of the
+/// region, so the region isn't locked while the results are read and later changes don't
+/// show up in them.
+pub struct RegionQuery<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> {
+    snapshot: RegionSnapshot<T>,
+    envelope: AABB<[f64; 3]>,
+}
+
+impl<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> RegionQuery<T> {
+    pub(crate) fn new(snapshot: RegionSnapshot<T>, envelope: AABB<[f64; 3]>) -> Self {
+        RegionQuery { snapshot, envelope }
+    }
+
+    /// Returns the UUID of the queried region.
+    pub fn region_id(&self) -> Uuid {
+        self.snapshot.region_id
+    }
+
+    /// Returns an iterator over the objects within the bounding box, in no particular order.
+    pub fn iter(&self) -> iterators::LocateInEnvelope<'_, SpatialObject<T>> {
+        self.snapshot.rtree().locate_in_envelope(&self.envelope)
+    }
+}
+
+impl<'a, T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> IntoIterator for &'a RegionQuery<T> {
+    type Item = &'a SpatialObject<T>;
+    type IntoIter = iterators::LocateInEnvelope<'a, SpatialObject<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An object struck by a ray cast with `VaultManager::raycast`.
+///
+/// # Fields
+///
+/// * `object`: The object whose bounding box the ray entered.
+/// * `distance`: Distance from the ray's origin to the point where it enters the object's box.
+///   Zero if the origin is inside the box.
+#[derive(Clone, PartialEq)]
+pub struct RaycastHit<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> {
+    /// The object that was hit
+    pub object: SpatialObject<T>,
+    /// Distance along the ray to the hit
+    pub distance: f64,
+}
+
+/// Describes the outcome of loading the persistent database when a `VaultManager` starts.
+///
+/// The report is built by `VaultManager::new` / `VaultManager::with_config` and can be
+/// inspected afterwards through `VaultManager::load_report`.
+///
+/// # Fields
+///
+/// * `regions_loaded`: Number of regions loaded into memory.
+/// * `points_per_region`: Number of objects loaded for each region.
+/// * `failures`: Objects that couldn't be loaded and were moved to quarantine. Only populated
+///   in lenient mode, since strict mode aborts on the first failure.
+/// * `orphaned_points`: IDs of stored points whose region doesn't exist.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LoadReport {
+    /// Number of regions loaded into memory
+    pub regions_loaded: usize,
+    /// Number of objects loaded for each region, keyed by region UUID
+    pub points_per_region: HashMap<Uuid, usize>,
+    /// Objects that were quarantined because they couldn't be loaded
+    pub failures: Vec<LoadFailure>,
+    /// IDs of stored points that reference a missing region
+    pub orphaned_points: Vec<String>,
+}
+
+impl LoadReport {
+    /// Returns the total number of objects loaded across all regions.
+    pub fn total_points(&self) -> usize {
+        self.points_per_region.values().sum()
+    }
+
+    /// Returns `true` if nothing was skipped and no orphaned points were found.
+    pub fn is_clean(&self) -> bool {
+        self.failures.is_empty() && self.orphaned_points.is_empty()
+    }
+}
+
+/// An object that failed to load from the persistent database.
+///
+/// Failed objects are kept in quarantine together with their raw payload; see
+/// `VaultManager::quarantined_objects`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LoadFailure {
+    /// ID of the point as stored in the database
+    pub point_id: String,
+    /// UUID of the region the point belongs to
+    pub region_id: Uuid,
+    /// Stored object type
+    pub object_type: String,
+    /// Stored coordinates [x, y, z]
+    pub point: [f64; 3],
+    /// Raw custom data payload, if it could be read
+    pub raw_custom_data: Option<String>,
+    /// Why the object couldn't be loaded
+    pub error: String,
+}
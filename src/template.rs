@@ -0,0 +1,114 @@
+//! # Region Templates
+//!
+//! This module defines `RegionTemplate`, a reusable set of objects (a prefab) such as a dungeon
+//! room or an outpost. Object positions are stored relative to the template's origin, so the
+//! same layout can be stamped into any region with `VaultManager::instantiate_template`, moved
+//! and rotated by a `Transform`.
+//!
+//! Templates are saved in the persistent database alongside the world, so they're shared by
+//! every server using it.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use your_crate::{RegionTemplate, Transform, VaultManager, CustomData};
+//!
+//! let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+//! let outpost = RegionTemplate::new("outpost")
+//!     .with_object("building", [0.0, 0.0, 0.0], [10.0, 5.0, 10.0], CustomData { /* ... */ })
+//!     .with_object("npc", [3.0, 0.0, 2.0], [0.0, 0.0, 0.0], CustomData { /* ... */ });
+//! vault_manager.save_template(&outpost).unwrap();
+//!
+//! let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 500.0).unwrap();
+//! let placed = vault_manager.instantiate_template(outpost.id, region_id, Transform::translate([120.0, 0.0, -40.0])).unwrap();
+//! println!("Placed {} objects", placed.len());
+//! ```
+
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use crate::object_type::ObjectType;
+
+/// An object of a template, positioned relative to the template's origin.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+// The struct's own bounds already let T be (de)serialized
+#[serde(bound = "")]
+pub struct TemplateObject<T: Clone + Serialize + for<'a> Deserialize<'a> + PartialEq + Sized> {
+    /// Type of the object (e.g., "building", "npc")
+    pub object_type: ObjectType,
+    /// Position of the object relative to the template's origin [x, y, z]
+    pub offset: [f64; 3],
+    /// Extent of the object along each axis [x, y, z]
+    pub size: [f64; 3],
+    /// Custom data each instance of the object starts with
+    pub custom_data: T,
+}
+
+/// A reusable set of objects that can be placed into any region.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RegionTemplate<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> {
+    /// Unique identifier of the template
+    pub id: Uuid,
+    /// Human-readable name of the template
+    pub name: String,
+    /// The objects of the template
+    pub objects: Vec<TemplateObject<T>>,
+}
+
+impl<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> RegionTemplate<T> {
+    /// Creates an empty template with a new ID.
+    pub fn new(name: &str) -> Self {
+        RegionTemplate { id: Uuid::new_v4(), name: name.to_string(), objects: Vec::new() }
+    }
+
+    /// Adds an object to the template.
+    ///
+    /// # Arguments
+    ///
+    /// * `object_type` - Type of the object.
+    /// * `offset` - Position of the object relative to the template's origin.
+    /// * `size` - Extent of the object along each axis.
+    /// * `custom_data` - Custom data each instance of the object starts with.
+    pub fn with_object(mut self, object_type: impl Into<ObjectType>, offset: [f64; 3], size: [f64; 3], custom_data: T) -> Self {
+        self.objects.push(TemplateObject { object_type: object_type.into(), offset, size, custom_data });
+        self
+    }
+}
+
+/// Where and how a template is placed: a rotation around the vertical (Y) axis followed by a translation.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Transform {
+    /// Position of the template's origin in the world [x, y, z]
+    pub translation: [f64; 3],
+    /// Rotation around the Y axis, in radians
+    pub yaw: f64,
+}
+
+impl Transform {
+    /// Places the template's origin at `translation` without rotating it.
+    pub fn translate(translation: [f64; 3]) -> Self {
+        Transform { translation, yaw: 0.0 }
+    }
+
+    /// Additionally rotates the template by `yaw` radians around the Y axis.
+    pub fn with_yaw(mut self, yaw: f64) -> Self {
+        self.yaw = yaw;
+        self
+    }
+
+    /// Returns the world position of a point given relative to the template's origin.
+    pub fn apply(&self, offset: [f64; 3]) -> [f64; 3] {
+        let (sin, cos) = self.yaw.sin_cos();
+        [
+            self.translation[0] + offset[0] * cos + offset[2] * sin,
+            self.translation[1] + offset[1],
+            self.translation[2] - offset[0] * sin + offset[2] * cos,
+        ]
+    }
+
+    /// Returns the axis-aligned extent of a rotated object. Exact for quarter turns.
+    pub fn apply_size(&self, size: [f64; 3]) -> [f64; 3] {
+        let (sin, cos) = self.yaw.sin_cos();
+        let (sin, cos) = (sin.abs(), cos.abs());
+        [size[0] * cos + size[2] * sin, size[1], size[0] * sin + size[2] * cos]
+    }
+}
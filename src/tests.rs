@@ -9,9 +9,82 @@
 //! - VaultManager initialization
 //! - Region creation and management
 //! - Object addition and retrieval
+//! - Bulk insertion of objects
+//! - Automatic region resolution on insert
+//! - Splitting oversized regions into octants
+//! - Merging sparse regions
+//! - Deleting regions with and without their objects
+//! - Soft deletion, restoring and purging of objects
+//! - Bulk removal of objects by box or type
+//! - Region templates and placing them into regions
+//! - Expiring objects with a TTL
+//! - World bounds and coordinate sanity checks
+//! - Validated finite coordinates
 //! - Spatial querying
+//! - Radius, cone and column queries
+//! - Raycasts against sized objects
+//! - Broad-phase overlap detection between sized objects
+//! - Rotated objects and oriented-box overlap tests
+//! - Deterministic ordering of query results
+//! - Frustum culling queries
+//! - Pooling of custom data allocations
+//! - Object-type filtered queries
+//! - Registering object types and rejecting objects that break their rules
+//! - Validating custom data against JSON schemas and closures
+//! - Filter expressions
+//! - Interning of object type names
+//! - Nearest-neighbor queries across region boundaries
+//! - Query and persistence cancellation
+//! - Progress, pausing and cancellation of long-running operations
+//! - Graceful shutdown
+//! - Cross-region queries
+//! - Streaming and paginated region queries
+//! - Count and existence queries, including unloaded regions
+//! - Per-region statistics
+//! - Snapshot-consistent region iteration
+//! - Catch-up snapshots and deltas for reconnecting clients
+//! - Read-through queries over regions that aren't loaded
+//! - Unloading idle regions and reloading them on access
+//! - Lazy loading of regions at startup
+//! - Incremental persistence of changed objects
+//! - Background auto-persistence
+//! - Time-budgeted persistence across ticks
+//! - The async API
+//! - Startup consistency reports and lenient loading
+//! - Quarantine and repair of corrupt objects
+//! - Incremental consistency checks between the index and the database
+//! - Compression of large custom data payloads
+//! - Choosing between zstd and LZ4 compression
+//! - Archiving cold objects and faulting them back in
+//! - Deduplication of identical custom data payloads
+//! - I/O accounting of the persistent database
+//! - Region lock diagnostics and lock ordering
+//! - Sharing one VaultManager between threads
+//! - Object lookups through the object-to-region index
+//! - Moving objects within and between regions
+//! - Attached objects following their parents, persisted across restarts
+//! - Dead reckoning of objects with a velocity
+//! - Atomic batches of object mutations and transactions collecting them
+//! - Optimistic concurrency through object versions
+//! - Mutation events sent to subscribers
+//! - Change feed with sequence numbers for replication
+//! - Simulation level of detail for regions without players
 //! - Player transfer between regions
+//! - Player spawning, movement and proximity helpers
+//! - Object transfer between regions, optionally keeping its position
+//! - Object transfer between vaults
 //! - Data persistence and recovery
+//! - Vaults whose persistent storage lives in memory
+//! - Write-ahead logging and batched writes to the SQLite database
+//! - Multi-row batch writes and removals of points
+//! - Updating every field of a stored point in place
+//! - Journaling in-memory changes and replaying them after a crash
+//! - Loading regions a page of points at a time
+//! - Looking up objects of unloaded regions without loading them
+//! - Vacuuming data files no point refers to
+//! - Named world snapshots and rolling back to them
+//! - Exporting regions to portable files and importing them
+//! - Binary key storage and key format migration
 //! - Support for arbitrary custom data structures
 //!
 //! ## Test Structure
@@ -25,6 +98,7 @@ use tempfile::tempdir;
 use uuid::Uuid;
 use serde::{Serialize, Deserialize};
 use std::sync::Arc;
+use std::time::Duration;
 use crate::MySQLGeo::Point;
 use colored::*;
 use serde_json;
@@ -46,6 +120,26 @@ struct ArbitraryGameObject {
     inventory: Vec<String>,
 }
 
+/// Drives a future to completion on the current thread, so the async API can be tested without a runtime.
+fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    struct ThreadWaker(std::thread::Thread);
+    impl std::task::Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let waker = std::task::Waker::from(Arc::new(ThreadWaker(std::thread::current())));
+    let mut context = std::task::Context::from_waker(&waker);
+    let mut future = std::pin::pin!(future);
+    loop {
+        match future.as_mut().poll(&mut context) {
+            std::task::Poll::Ready(output) => return output,
+            std::task::Poll::Pending => std::thread::park(),
+        }
+    }
+}
+
 /// Runs the complete test suite for PebbleVault.
 pub fn run_tests() -> Result<(), String> {
     // Print the header for the test suite
@@ -66,16 +160,392 @@ pub fn run_tests() -> Result<(), String> {
     let db_path = temp_dir.path().join("test_db_query.sqlite");
     test_querying_and_player_transfer(db_path.to_str().unwrap())?;
 
+    // Test the player helpers
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_players.sqlite");
+    test_player_helpers(db_path.to_str().unwrap())?;
+
     // Test persistence
     let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
     let db_path = temp_dir.path().join("test_db_persistence.sqlite");
     test_persistence(db_path.to_str().unwrap())?;
 
+    // Test in-memory vaults, which need no database file
+    test_in_memory_vault()?;
+
+    // Test the SQLite journal mode and batched persistence
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_sqlite_mode.sqlite");
+    test_sqlite_performance_mode(db_path.to_str().unwrap())?;
+
+    // Test batch point writes and removals
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_batch_writes.sqlite");
+    test_batch_point_writes(db_path.to_str().unwrap())?;
+
+    // Test full point updates
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_update_point.sqlite");
+    test_update_point(db_path.to_str().unwrap())?;
+
+    // Test vacuuming orphaned data files
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_vacuum.sqlite");
+    test_vacuum(db_path.to_str().unwrap())?;
+
+    // Test journal replay after a crash
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_journal.sqlite");
+    test_journal_recovery(db_path.to_str().unwrap())?;
+
+    // Test paged region loads
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_paged_load.sqlite");
+    test_paged_region_load(db_path.to_str().unwrap())?;
+
+    // Test lookups in unloaded regions
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_unloaded_lookups.sqlite");
+    test_unloaded_lookups(db_path.to_str().unwrap())?;
+
+    // Test named snapshots and restoring them
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_snapshots.sqlite");
+    test_world_snapshots(db_path.to_str().unwrap())?;
+
+    // Test exporting and importing regions
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let src_path = temp_dir.path().join("test_db_export.sqlite");
+    let dst_path = temp_dir.path().join("test_db_import.sqlite");
+    let file_path = temp_dir.path().join("region.json");
+    test_region_export(src_path.to_str().unwrap(), dst_path.to_str().unwrap(), file_path.to_str().unwrap())?;
+
     // Test with arbitrary struct
     let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
     let db_path = temp_dir.path().join("test_db_arbitrary.sqlite");
     test_with_arbitrary_struct(db_path.to_str().unwrap())?;
 
+    // Test bulk insertion
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_bulk.sqlite");
+    test_bulk_insert(db_path.to_str().unwrap())?;
+
+    // Test automatic region resolution
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_auto_region.sqlite");
+    test_add_object_auto(db_path.to_str().unwrap())?;
+
+    // Test region splitting
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_split.sqlite");
+    test_region_splitting(db_path.to_str().unwrap())?;
+
+    // Test region merging
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_merge.sqlite");
+    test_merge_regions(db_path.to_str().unwrap())?;
+
+    // Test region deletion
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_delete_region.sqlite");
+    test_delete_region(db_path.to_str().unwrap())?;
+
+    // Test soft deletion
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_soft_delete.sqlite");
+    test_soft_delete(db_path.to_str().unwrap())?;
+
+    // Test bulk removal
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_bulk_remove.sqlite");
+    test_bulk_remove(db_path.to_str().unwrap())?;
+
+    // Test region templates
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_templates.sqlite");
+    test_region_templates(db_path.to_str().unwrap())?;
+
+    // Test expiring objects
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_ttl.sqlite");
+    test_object_ttl(db_path.to_str().unwrap())?;
+
+    // Test world bounds
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_world_bounds.sqlite");
+    test_world_bounds(db_path.to_str().unwrap())?;
+
+    // Test coordinate validation
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_finite.sqlite");
+    test_finite_coordinates(db_path.to_str().unwrap())?;
+
+    // Test I/O accounting
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_io_stats.sqlite");
+    test_io_stats(db_path.to_str().unwrap())?;
+
+    // Test lock diagnostics
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_locks.sqlite");
+    test_lock_diagnostics(db_path.to_str().unwrap())?;
+
+    // Test sharing a manager between threads
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_shared.sqlite");
+    test_shared_manager(db_path.to_str().unwrap())?;
+
+    // Test the object index
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_object_index.sqlite");
+    test_object_index(db_path.to_str().unwrap())?;
+
+    // Test key formats
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_key_format.sqlite");
+    test_key_format_migration(db_path.to_str().unwrap())?;
+
+    // Test moving objects
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_move.sqlite");
+    test_move_object(db_path.to_str().unwrap())?;
+
+    // Test object attachments
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_hierarchy.sqlite");
+    test_object_hierarchy(db_path.to_str().unwrap())?;
+
+    // Test velocity and dead reckoning
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_velocity.sqlite");
+    test_velocity(db_path.to_str().unwrap())?;
+
+    // Test batch mutations
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_batch.sqlite");
+    test_apply_batch(db_path.to_str().unwrap())?;
+
+    // Test object versioning
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_versioning.sqlite");
+    test_object_versioning(db_path.to_str().unwrap())?;
+
+    // Test mutation events
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_events.sqlite");
+    test_mutation_events(db_path.to_str().unwrap())?;
+
+    // Test the change feed
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_change_feed.sqlite");
+    test_change_feed(db_path.to_str().unwrap())?;
+
+    // Test simulation level of detail
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_lod.sqlite");
+    test_simulation_lod(db_path.to_str().unwrap())?;
+
+    // Test object transfers
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_transfer_object.sqlite");
+    test_transfer_object(db_path.to_str().unwrap())?;
+
+    // Test transfers between vaults
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let src_path = temp_dir.path().join("test_db_shard_a.sqlite");
+    let dst_path = temp_dir.path().join("test_db_shard_b.sqlite");
+    test_transfer_between_vaults(src_path.to_str().unwrap(), dst_path.to_str().unwrap())?;
+
+    // Test region snapshots
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_snapshot.sqlite");
+    test_region_snapshot(db_path.to_str().unwrap())?;
+
+    // Test late-join catch-up
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_catch_up.sqlite");
+    test_catch_up(db_path.to_str().unwrap())?;
+
+    // Test read-through queries
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_cold.sqlite");
+    test_query_world_cold(db_path.to_str().unwrap())?;
+
+    // Test region unloading
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_unloading.sqlite");
+    test_region_unloading(db_path.to_str().unwrap())?;
+
+    // Test lazy loading
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_lazy.sqlite");
+    test_lazy_loading(db_path.to_str().unwrap())?;
+
+    // Test incremental persistence
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_dirty.sqlite");
+    test_dirty_persistence(db_path.to_str().unwrap())?;
+
+    // Test background auto-persistence
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_auto_persist.sqlite");
+    test_auto_persist(db_path.to_str().unwrap())?;
+
+    // Test budgeted persistence
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_persist_budget.sqlite");
+    test_persist_budget(db_path.to_str().unwrap())?;
+
+    // Test the async API
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_async.sqlite");
+    test_async_api(db_path.to_str().unwrap())?;
+
+    // Test radius queries
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_radius.sqlite");
+    test_radius_query(db_path.to_str().unwrap())?;
+
+    // Test cone queries
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_cone.sqlite");
+    test_cone_query(db_path.to_str().unwrap())?;
+
+    // Test column queries
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_column.sqlite");
+    test_column_query(db_path.to_str().unwrap())?;
+
+    // Test frustum queries
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_frustum.sqlite");
+    test_frustum_query(db_path.to_str().unwrap())?;
+
+    // Test custom data pooling
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_pool.sqlite");
+    test_object_pool(db_path.to_str().unwrap())?;
+
+    // Test object type interning
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_interning.sqlite");
+    test_object_type_interning(db_path.to_str().unwrap())?;
+
+    // Test raycasts
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_raycast.sqlite");
+    test_raycast(db_path.to_str().unwrap())?;
+
+    // Test broad-phase overlap detection
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_broad_phase.sqlite");
+    test_broad_phase(db_path.to_str().unwrap())?;
+
+    // Test object orientations
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_orientation.sqlite");
+    test_object_orientation(db_path.to_str().unwrap())?;
+
+    // Test deterministic query ordering
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_query_order.sqlite");
+    test_query_order(db_path.to_str().unwrap())?;
+
+    // Test object-type filtered queries
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_type_filter.sqlite");
+    test_type_filtered_queries(db_path.to_str().unwrap())?;
+
+    // Test the object type registry
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_type_registry.sqlite");
+    test_object_type_registry(db_path.to_str().unwrap())?;
+
+    // Test custom data validation
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_data_schema.sqlite");
+    test_custom_data_validation(db_path.to_str().unwrap())?;
+
+    // Test filter expressions
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_filter_expr.sqlite");
+    test_filter_expressions(db_path.to_str().unwrap())?;
+
+    // Test nearest-neighbor queries across regions
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_nearest.sqlite");
+    test_nearest_neighbors_across_regions(db_path.to_str().unwrap())?;
+
+    // Test query limits and cancellation
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_limits.sqlite");
+    test_query_limits(db_path.to_str().unwrap())?;
+
+    // Test operation handles
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_operations.sqlite");
+    test_operation_handle(db_path.to_str().unwrap())?;
+
+    // Test graceful shutdown
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_shutdown.sqlite");
+    test_graceful_shutdown(db_path.to_str().unwrap())?;
+
+    // Test cross-region queries
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_query_all.sqlite");
+    test_query_all(db_path.to_str().unwrap())?;
+
+    // Test streaming and paginated queries
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_query_pages.sqlite");
+    test_query_streaming(db_path.to_str().unwrap())?;
+
+    // Test count and existence queries
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_count.sqlite");
+    test_count_and_contains(db_path.to_str().unwrap())?;
+
+    // Test region statistics
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_region_stats.sqlite");
+    test_region_stats(db_path.to_str().unwrap())?;
+
+    // Test startup consistency reports
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_load_report.sqlite");
+    test_load_report(db_path.to_str().unwrap())?;
+
+    // Test quarantine of corrupt objects
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_quarantine.sqlite");
+    test_quarantine(db_path.to_str().unwrap())?;
+
+    // Test consistency checks
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_consistency.sqlite");
+    test_consistency_check(db_path.to_str().unwrap())?;
+
+    // Test per-object compression
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_compression.sqlite");
+    test_compression_threshold(db_path.to_str().unwrap())?;
+
+    // Test LZ4 compression of custom data
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_lz4.sqlite");
+    test_lz4_compression(db_path.to_str().unwrap())?;
+
+    // Test cold-storage archival
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_archive.sqlite");
+    test_cold_archive(db_path.to_str().unwrap())?;
+
+    // Test payload deduplication
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let db_path = temp_dir.path().join("test_db_dedup.sqlite");
+    test_payload_deduplication(db_path.to_str().unwrap())?;
+
     // Print a footer indicating all tests passed
     println!("\n{}", "==== All PebbleVault tests passed successfully! ====".green().bold());
     Ok(())
@@ -92,9 +562,16 @@ fn test_vault_manager_creation(db_path: &str) -> Result<(), String> {
     println!("{}", "VaultManager created successfully".green());
 
     // Assert that the VaultManager starts with no regions
-    assert!(vault_manager.regions.is_empty(), "VaultManager should be created with empty regions");
+    assert_eq!(vault_manager.region_count(), 0, "VaultManager should be created with empty regions");
     println!("{}", "VaultManager's regions are empty as expected".green());
 
+    // The SQLite backend reports what it supports natively
+    let capabilities = vault_manager.backend_capabilities();
+    assert!(capabilities.transactions && capabilities.streaming, "SQLite should support transactions and streaming");
+    assert!(!capabilities.spatial_index, "Stored points aren't spatially indexed");
+    assert!(capabilities.batch_writes, "Points should be written in multi-row batches");
+    println!("{}", "Backend capabilities were reported".green());
+
     // Print test passed message
     println!("{}", "VaultManager creation test passed".green());
     Ok(())
@@ -106,10 +583,10 @@ fn test_region_and_object_operations(db_path: &str) -> Result<(), String> {
     println!("\n{}", "---- Testing Region Creation and Object Addition ----".blue());
 
     // Create a new VaultManager instance
-    let mut vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
 
     // Assert that the VaultManager starts with no regions
-    assert!(vault_manager.regions.is_empty(), "VaultManager should start with no regions");
+    assert_eq!(vault_manager.region_count(), 0, "VaultManager should start with no regions");
     println!("{}", "VaultManager starts with no regions as expected".green());
 
     // Create a new region
@@ -119,7 +596,7 @@ fn test_region_and_object_operations(db_path: &str) -> Result<(), String> {
     println!("Region created with ID: {}", region_id.to_string().cyan());
 
     // Assert that the VaultManager now has one region
-    assert_eq!(vault_manager.regions.len(), 1, "VaultManager should have one region after creation");
+    assert_eq!(vault_manager.region_count(), 1, "VaultManager should have one region after creation");
     println!("{}", "VaultManager has one region as expected".green());
 
     // Add the first object to the region
@@ -155,7 +632,7 @@ fn test_querying_and_player_transfer(db_path: &str) -> Result<(), String> {
     std::fs::remove_file(db_path).ok();
     
     // Create a new VaultManager instance
-    let mut vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
 
     // Create two regions
     let region1_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
@@ -204,112 +681,3982 @@ fn test_querying_and_player_transfer(db_path: &str) -> Result<(), String> {
     assert_eq!(transferred_player.point, [200.0, 200.0, 200.0], "Player should be at the center of region 2");
     println!("{}", "Player's position has been updated correctly".green());
 
+    // The transfer is written through, so a fresh manager sees it without persisting
+    assert!(!vault_manager.has_unflushed_changes(), "Transfers should not leave unflushed changes");
+    let reloaded: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let reloaded_region2 = reloaded.query_region(region2_id, 150.0, 150.0, 150.0, 250.0, 250.0, 250.0)?;
+    assert_eq!(reloaded_region2.len(), 1, "The transferred player should reload in region 2");
+    assert_eq!(reloaded_region2[0].point, [200.0, 200.0, 200.0], "The transferred position should be persisted");
+    assert!(reloaded.query_region(region1_id, -50.0, -50.0, -50.0, 50.0, 50.0, 50.0)?.is_empty(), "Region 1 should be empty after reload");
+    println!("{}", "Player transfer was persisted".green());
+
     // Print test passed message
     println!("{}", "Querying and player transfer test passed".green());
     Ok(())
 }
 
-
-/// Tests data persistence operations.
-fn test_persistence(db_path: &str) -> Result<(), String> {
+/// Tests spawning, moving, finding and transferring players through the player helpers.
+fn test_player_helpers(db_path: &str) -> Result<(), String> {
     // Print the test header
-    println!("\n{}", "---- Testing Persistence ----".blue());
-    
-    // Remove any existing database file
-    std::fs::remove_file(db_path).ok();
-    
-    {
-        // Create a new VaultManager instance
-        let mut vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
-        
-        // Create a region
-        let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
-        println!("Created region with ID: {}", region_id.to_string().cyan());
-        
-        // Add an object to the region
-        let object_uuid = Uuid::new_v4();
-        let custom_data = Arc::new(TestCustomData { name: "Persistent Object".to_string(), value: 200 });
-        vault_manager.add_object(region_id, object_uuid, "building", 10.0, 20.0, 30.0, custom_data)?;
-        println!("Added object with UUID: {}", object_uuid.to_string().cyan());
-        
-        // Persist data to disk
-        vault_manager.persist_to_disk()?;
-        println!("{}", "Data persisted successfully".green());
-    }
+    println!("\n{}", "---- Testing Player Helpers ----".blue());
 
-    // Create a new VaultManager instance to load persisted data
-    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
-    
-    // Retrieve persisted objects
-    let objects = vault_manager.persistent_db.get_points_within_radius(0.0, 0.0, 0.0, 100.0)
-        .map_err(|e| format!("Failed to load objects from persistent database: {}", e))?;
+    let (alice, bob, ore) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+    let data = Arc::new(TestCustomData { name: "Player".to_string(), value: 1 });
+    let east = {
+        let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+        let west = vault_manager.create_or_load_region([-100.0, 0.0, 0.0], 50.0)?;
+        let east = vault_manager.create_or_load_region([100.0, 0.0, 0.0], 50.0)?;
 
-    // Verify persisted objects
-    println!("Number of persisted objects: {}", objects.len().to_string().cyan());
-    for (i, obj) in objects.iter().enumerate() {
-        println!("Persisted object {}: UUID: {}, Type: {}, Custom Data: {:?}, Position: [{}, {}, {}]", 
-                 i + 1, obj.id.unwrap().to_string().cyan(), obj.object_type, obj.custom_data, obj.x, obj.y, obj.z);
-    }
-    assert_eq!(objects.len(), 1, "Persisted object should be loaded");
-    println!("{}", "Correct number of objects loaded from persistent storage".green());
+        assert_eq!(vault_manager.spawn_player(alice, [-100.0, 0.0, 0.0], data.clone())?, west, "Alice should spawn in the west");
+        assert_eq!(vault_manager.spawn_player(bob, [-95.0, 0.0, 0.0], data.clone())?, west, "Bob should spawn in the west");
+        assert!(vault_manager.spawn_player(alice, [-90.0, 0.0, 0.0], data.clone()).is_err(), "A player should only spawn once");
+        vault_manager.add_object(west, ore, "resource", -98.0, 0.0, 0.0, data.clone())?;
+        println!("{}", "Players were spawned".green());
 
-    // Print test passed message
-    println!("{}", "Persistence test passed".green());
+        let nearby = vault_manager.players_near([-100.0, 0.0, 0.0], 10.0)?;
+        assert_eq!(nearby.len(), 2, "Both players should be nearby");
+        assert!(nearby.iter().all(|obj| obj.object_type == "player"), "Only players should be returned");
+        assert!(vault_manager.players_near([-100.0, 0.0, 0.0], -1.0).is_err(), "A negative radius should be rejected");
+        println!("{}", "Nearby players were found".green());
+
+        // A player standing outside its region's bounds is still found
+        let carol = Uuid::new_v4();
+        vault_manager.add_object(east, carol, "player", 0.0, 0.0, 0.0, data.clone())?;
+        let nearby = vault_manager.players_near([0.0, 0.0, 0.0], 5.0)?;
+        assert_eq!(nearby.iter().map(|obj| obj.uuid).collect::<Vec<_>>(), vec![carol], "A player outside its region's bounds should be found");
+        vault_manager.remove_object(carol)?;
+        println!("{}", "Player outside its region's bounds was found".green());
+
+        assert_eq!(vault_manager.move_player(alice, [100.0, 0.0, 0.0])?, east, "Alice should move east");
+        let nearby = vault_manager.players_near([100.0, 0.0, 0.0], 10.0)?;
+        assert_eq!(nearby.iter().map(|obj| obj.uuid).collect::<Vec<_>>(), vec![alice], "Alice should be found in the east");
+        assert!(vault_manager.move_player(ore, [0.0, 0.0, 0.0]).is_err(), "Non-players can't be moved as players");
+        println!("{}", "Player moved between regions".green());
+
+        // Moving a player through the generic API leaves the index stale until it's used
+        vault_manager.move_object(alice, [-100.0, 0.0, 0.0])?;
+        assert_eq!(vault_manager.move_player(alice, [-99.0, 0.0, 0.0])?, west, "A stale index entry should be refreshed");
+        println!("{}", "Stale index entry was refreshed".green());
+
+        vault_manager.transfer_player(bob, west, east)?;
+        assert_eq!(vault_manager.move_player(bob, [101.0, 0.0, 0.0])?, east, "Bob should be indexed in the east");
+        east
+    };
+
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let nearby = vault_manager.query_region(east, 90.0, -10.0, -10.0, 110.0, 10.0, 10.0)?;
+    assert_eq!(nearby.iter().map(|obj| obj.uuid).collect::<Vec<_>>(), vec![bob], "The transfer should be persisted");
+    println!("{}", "Player transfer was persisted".green());
+
+    println!("{}", "Player helpers test passed".green());
     Ok(())
 }
 
 
-/// Tests VaultManager with an arbitrary struct as custom data.
-fn test_with_arbitrary_struct(db_path: &str) -> Result<(), String> {
+/// Tests a vault whose database and custom data files are kept in memory.
+fn test_in_memory_vault() -> Result<(), String> {
     // Print the test header
-    println!("\n{}", "---- Testing VaultManager with Arbitrary Struct ----".blue());
+    println!("\n{}", "---- Testing In-Memory Vault ----".blue());
 
-    // Remove any existing database file
-    std::fs::remove_file(db_path).ok();
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::in_memory()?;
+    let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+    let object_id = Uuid::new_v4();
+    let data = TestCustomData { name: "Instanced".to_string(), value: 11 };
+    vault_manager.add_object(region_id, object_id, "building", 4.0, 5.0, 6.0, Arc::new(data.clone()))?;
+    vault_manager.persist_to_disk()?;
 
-    // Create a new VaultManager instance with ArbitraryGameObject as custom data
-    let mut vault_manager: VaultManager<ArbitraryGameObject> = VaultManager::new(db_path)?;
+    // Custom data is stored, but not in a file
+    let data_file = vault_manager.database().get_data_file(object_id)
+        .map_err(|e| format!("Failed to look up data file: {}", e))?
+        .expect("The object should have a data file");
+    assert!(!std::path::Path::new(&data_file).exists(), "No data file should be written to disk");
+    println!("{}", "Objects persisted without touching the disk".green());
 
-    // Create a region
-    let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
-    println!("Created region with ID: {}", region_id.to_string().cyan());
+    // Objects are read back from the in-memory database when their region is reloaded
+    vault_manager.unload_region(region_id)?;
+    let reloaded = vault_manager.get_object(object_id)?.expect("The object should be reloaded");
+    assert_eq!(*reloaded.custom_data, data, "Custom data should survive unloading the region");
+    println!("{}", "Unloaded region reloaded from memory".green());
 
-    // Create an ArbitraryGameObject instance
-    let game_object = Arc::new(ArbitraryGameObject {
-        id: 1,
-        position: [10.0, 20.0, 30.0],
-        health: 100.0,
-        inventory: vec!["Sword".to_string(), "Health Potion".to_string()],
-    });
+    // Removed objects release their in-memory data files
+    vault_manager.remove_object(object_id)?;
+    assert_eq!(vault_manager.database().count_blobs().map_err(|e| format!("Failed to count blobs: {}", e))?, 0, "The data file should be released");
+    assert!(vault_manager.enable_auto_persist(AutoPersist::every(Duration::from_secs(1))).is_err(), "Auto-persist needs a database file");
+    println!("{}", "Data files released and auto-persist refused".green());
 
-    // Add the game object to the region
-    let object_uuid = Uuid::new_v4();
-    vault_manager.add_object(region_id, object_uuid, "game_object", 10.0, 20.0, 30.0, game_object.clone())?;
-    println!("Added game object with UUID: {}", object_uuid.to_string().cyan());
+    println!("{}", "In-memory vault test passed".green());
+    Ok(())
+}
 
-    let query_result = vault_manager.query_region(region_id, -50.0, -50.0, -50.0, 50.0, 50.0, 50.0)?;
-    println!("Query returned {} objects", query_result.len().to_string().cyan());
-    assert_eq!(query_result.len(), 1, "Query should return 1 object");
-    
-    let retrieved_object = &query_result[0];
-    assert_eq!(retrieved_object.uuid, object_uuid, "Retrieved object UUID should match");
-    assert_eq!(*retrieved_object.custom_data, *game_object, "Retrieved custom data should match original");
-    println!("{}", "Retrieved object matches the original".green());
+/// Tests that file databases use write-ahead logging and that persisting many objects round-trips.
+fn test_sqlite_performance_mode(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing SQLite Performance Mode ----".blue());
 
-    vault_manager.persist_to_disk()?;
-    println!("{}", "Data persisted successfully".green());
+    let object_count = 500;
+    {
+        let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+        let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 1000.0)?;
+        for i in 0..object_count {
+            let data = Arc::new(TestCustomData { name: format!("Prop {}", i), value: i });
+            vault_manager.add_object(region_id, Uuid::new_v4(), "resource", i as f64, 0.0, 0.0, data)?;
+        }
 
-    let new_vault_manager: VaultManager<ArbitraryGameObject> = VaultManager::new(db_path)?;
-    let loaded_objects = new_vault_manager.persistent_db.get_points_within_radius(0.0, 0.0, 0.0, 100.0)
-        .map_err(|e| format!("Failed to load objects from persistent database: {}", e))?;
+        // Move every object, then persist them all in one transaction
+        for object in vault_manager.query_region(region_id, -1.0, -1.0, -1.0, 1000.0, 1.0, 1.0)? {
+            vault_manager.move_object(object.uuid, [object.point[0], 0.5, 0.0])?;
+        }
+        vault_manager.persist_to_disk()?;
 
-    assert_eq!(loaded_objects.len(), 1, "Persisted object should be loaded");
-    let loaded_object = &loaded_objects[0];
-    let loaded_custom_data: ArbitraryGameObject = serde_json::from_value(loaded_object.custom_data.clone())
-        .map_err(|e| format!("Failed to deserialize loaded custom data: {}", e))?;
-    assert_eq!(loaded_custom_data, *game_object, "Loaded custom data should match original");
-    println!("{}", "Loaded object matches the original".green());
+        let journal_mode: String = vault_manager.database().path()
+            .map(|path| rusqlite::Connection::open(path).and_then(|conn| conn.query_row("PRAGMA journal_mode", [], |row| row.get(0))))
+            .transpose()
+            .map_err(|e| format!("Failed to read journal mode: {}", e))?
+            .unwrap_or_default();
+        assert_eq!(journal_mode.to_lowercase(), "wal", "File databases should use write-ahead logging");
+        println!("{}", "Database uses write-ahead logging".green());
+    }
 
-    println!("{}", "VaultManager with arbitrary struct test passed".green());
+    // Every object was persisted at its new position
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let moved = vault_manager.query_all(-1.0, 0.4, -1.0, 1000.0, 0.6, 1.0)?;
+    assert_eq!(moved.len(), object_count as usize, "Every moved object should be persisted");
+    println!("{}", "Persisted objects reloaded".green());
+
+    println!("{}", "SQLite performance mode test passed".green());
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Tests adding, upserting and removing points in batches spanning several statements.
+fn test_batch_point_writes(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Batch Point Writes ----".blue());
+
+    let db = MySQLGeo::Database::new(db_path).map_err(|e| format!("Failed to open database: {}", e))?;
+    db.create_table().map_err(|e| format!("Failed to create table: {}", e))?;
+    let region_id = Uuid::new_v4();
+    db.create_region(region_id, [0.0, 0.0, 0.0], 1000.0).map_err(|e| format!("Failed to create region: {}", e))?;
+
+    // More points than fit in one statement, sharing two custom data payloads
+    let ids: Vec<Uuid> = (0..600).map(|_| Uuid::new_v4()).collect();
+    let make_points = |team: &str| -> Vec<(Point, Uuid)> {
+        ids.iter().enumerate()
+            .map(|(i, id)| {
+                let data = serde_json::json!({ "team": if i % 2 == 0 { team } else { "neutral" } });
+                (Point::new(Some(*id), i as f64, 0.0, 0.0, "resource".to_string(), data), region_id)
+            })
+            .collect()
+    };
+    db.add_points(&make_points("red")).map_err(|e| format!("Failed to add points: {}", e))?;
+    let count = |db: &MySQLGeo::Database| db.count_points(region_id, [-1.0; 3], [1000.0; 3]).map_err(|e| format!("Failed to count points: {}", e));
+    assert_eq!(count(&db)?, 600, "Every point should be added");
+    assert_eq!(db.count_blobs().map_err(|e| e.to_string())?, 2, "Identical payloads should share blobs");
+    println!("{}", "Added 600 points in batches".green());
+
+    // Adding a stored point again, or the same point twice, fails without writing anything
+    assert!(db.add_points(&make_points("red")[..1]).is_err(), "Adding a stored point should fail");
+    let id = Uuid::new_v4();
+    let twice = vec![
+        (Point::new(Some(id), 0.0, 0.0, 0.0, "resource".to_string(), serde_json::json!({})), region_id),
+        (Point::new(Some(id), 1.0, 0.0, 0.0, "resource".to_string(), serde_json::json!({})), region_id),
+    ];
+    assert!(db.upsert_points(&twice).is_err(), "A point appearing twice in a batch should be rejected");
+    assert!(db.get_point(id).map_err(|e| e.to_string())?.is_none(), "The rejected batch shouldn't be written");
+    println!("{}", "Conflicting batches rejected".green());
+
+    // Upserting replaces the points and moves the blob references over
+    db.upsert_points(&make_points("blue")).map_err(|e| format!("Failed to upsert points: {}", e))?;
+    assert_eq!(count(&db)?, 600, "Upserting shouldn't add points");
+    assert_eq!(db.count_blobs().map_err(|e| e.to_string())?, 2, "The replaced payload's blob should be deleted");
+    let (_, point) = db.get_point(ids[0]).map_err(|e| e.to_string())?.expect("The point should exist");
+    assert_eq!(point.custom_data["team"], "blue", "The point should be replaced");
+    println!("{}", "Upserted 600 points".green());
+
+    // Removing in batches skips unknown points
+    let mut doomed: Vec<Uuid> = ids[..400].to_vec();
+    doomed.push(Uuid::new_v4());
+    assert_eq!(db.remove_points(&doomed).map_err(|e| e.to_string())?, 400, "Only stored points should be counted");
+    assert_eq!(count(&db)?, 200, "The removed points should be gone");
+    db.remove_points(&ids[400..]).map_err(|e| e.to_string())?;
+    assert_eq!(db.count_blobs().map_err(|e| e.to_string())?, 0, "Every blob should be released");
+    println!("{}", "Removed points in batches".green());
+
+    println!("{}", "Batch point writes test passed".green());
+    Ok(())
+}
+
+/// Tests that a point's size, type and custom data are updated in place.
+fn test_update_point(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Point Updates ----".blue());
+
+    let db = MySQLGeo::Database::new(db_path).map_err(|e| format!("Failed to open database: {}", e))?;
+    db.create_table().map_err(|e| format!("Failed to create table: {}", e))?;
+    let region_id = Uuid::new_v4();
+    let point_id = Uuid::new_v4();
+    let mut point = Point::new(Some(point_id), 1.0, 2.0, 3.0, "sapling".to_string(), serde_json::json!({ "height": 1 }));
+    db.add_point(&point, region_id).map_err(|e| format!("Failed to add point: {}", e))?;
+
+    // Every field changes, and the old custom data is released
+    point.object_type = "tree".to_string();
+    point.size = [2.0, 6.0, 2.0];
+    point.custom_data = serde_json::json!({ "height": 6 });
+    point.version = 1;
+    assert!(db.update_point(&point, region_id).map_err(|e| format!("Failed to update point: {}", e))?, "The stored point should be updated");
+    let (stored_region, stored) = db.get_point(point_id)
+        .map_err(|e| format!("Failed to get point: {}", e))?
+        .ok_or("Updated point not found")?;
+    assert_eq!(stored_region, region_id);
+    assert_eq!(stored, point, "Every field should be updated");
+    assert_eq!(db.count_blobs().map_err(|e| e.to_string())?, 1, "The previous custom data should be released");
+    println!("{}", "Point was updated in place".green());
+
+    // Missing points aren't created, and soft-deleted points stay deleted
+    let missing = Point::new(Some(Uuid::new_v4()), 0.0, 0.0, 0.0, "tree".to_string(), serde_json::json!({ "height": 9 }));
+    assert!(!db.update_point(&missing, region_id).map_err(|e| format!("Failed to update point: {}", e))?, "Missing points shouldn't be updated");
+    assert_eq!(db.count_blobs().map_err(|e| e.to_string())?, 1, "A failed update shouldn't store custom data");
+    db.soft_delete_point(point_id).map_err(|e| format!("Failed to delete point: {}", e))?;
+    point.version = 2;
+    db.update_point(&point, region_id).map_err(|e| format!("Failed to update point: {}", e))?;
+    assert!(db.get_point(point_id).map_err(|e| format!("Failed to get point: {}", e))?.is_none(), "The point should stay soft-deleted");
+    println!("{}", "Missing and deleted points were handled".green());
+
+    println!("{}", "Point updates test passed".green());
+    Ok(())
+}
+
+/// Tests that vacuuming deletes data files no point refers to and keeps the rest.
+fn test_vacuum(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Vacuum ----".blue());
+
+    let db = MySQLGeo::Database::new(db_path).map_err(|e| format!("Failed to open database: {}", e))?;
+    db.create_table().map_err(|e| format!("Failed to create table: {}", e))?;
+    let region_id = Uuid::new_v4();
+    let kept = Point::new(Some(Uuid::new_v4()), 1.0, 2.0, 3.0, "tree".to_string(), serde_json::json!({ "height": 6 }));
+    db.add_point(&kept, region_id).map_err(|e| format!("Failed to add point: {}", e))?;
+    let kept_file = db.get_data_file(kept.id.unwrap()).map_err(|e| e.to_string())?.ok_or("Data file not found")?;
+
+    // A rolled back write leaves its data file behind
+    let rolled_back = Point::new(Some(Uuid::new_v4()), 4.0, 5.0, 6.0, "rock".to_string(), serde_json::json!({ "mass": 80 }));
+    db.begin_transaction().map_err(|e| format!("Failed to begin transaction: {}", e))?;
+    db.add_point(&rolled_back, region_id).map_err(|e| format!("Failed to add point: {}", e))?;
+    let orphan_file = db.get_data_file(rolled_back.id.unwrap()).map_err(|e| e.to_string())?.ok_or("Data file not found")?;
+    assert!(db.vacuum().is_err(), "Vacuuming inside a transaction should fail");
+    db.rollback_transaction().map_err(|e| format!("Failed to roll back transaction: {}", e))?;
+    assert!(std::path::Path::new(&orphan_file).exists(), "The rolled back data file should be orphaned");
+
+    // Files written just now may belong to a write that hasn't recorded them yet
+    assert_eq!(db.vacuum().map_err(|e| format!("Failed to vacuum: {}", e))?, 0, "Freshly written files should be kept");
+    let backdate = |data_file: &str| std::fs::File::options().write(true).open(data_file)
+        .and_then(|file| file.set_modified(std::time::SystemTime::now() - Duration::from_secs(60)))
+        .map_err(|e| format!("Failed to backdate {}: {}", data_file, e));
+    backdate(&orphan_file)?;
+    let deleted = db.vacuum().map_err(|e| format!("Failed to vacuum: {}", e))?;
+    assert_eq!(deleted, 1, "Only the orphaned data file should be deleted");
+    assert!(!std::path::Path::new(&orphan_file).exists(), "The orphaned data file should be deleted");
+    assert!(std::path::Path::new(&kept_file).exists(), "Referenced data files should be kept");
+    let (_, stored) = db.get_point(kept.id.unwrap())
+        .map_err(|e| format!("Failed to get point: {}", e))?
+        .ok_or("Kept point not found")?;
+    assert_eq!(stored.custom_data, kept.custom_data, "Referenced custom data should still be readable");
+    println!("{}", "Orphaned data file was deleted".green());
+
+    // A file written by another connection's open transaction survives a vacuum waiting on it
+    let writer = MySQLGeo::Database::new(db_path).map_err(|e| format!("Failed to open database: {}", e))?;
+    let pending = Point::new(Some(Uuid::new_v4()), 7.0, 8.0, 9.0, "log".to_string(), serde_json::json!({ "length": 4 }));
+    writer.begin_transaction().map_err(|e| format!("Failed to begin transaction: {}", e))?;
+    writer.add_point(&pending, region_id).map_err(|e| format!("Failed to add point: {}", e))?;
+    let pending_file = writer.get_data_file(pending.id.unwrap()).map_err(|e| e.to_string())?.ok_or("Data file not found")?;
+    backdate(&pending_file)?;
+    let vacuumed = std::thread::scope(|scope| {
+        let vacuum = scope.spawn(|| db.vacuum());
+        std::thread::sleep(Duration::from_millis(100));
+        writer.commit_transaction().map_err(|e| format!("Failed to commit transaction: {}", e))?;
+        vacuum.join().unwrap().map_err(|e| format!("Failed to vacuum: {}", e))
+    })?;
+    assert_eq!(vacuumed, 0, "The committed point's data file should be kept");
+    assert!(std::path::Path::new(&pending_file).exists(), "The data file written in the transaction should survive");
+    assert!(db.get_point(pending.id.unwrap()).map_err(|e| format!("Failed to get point: {}", e))?.is_some(), "The committed point should be readable");
+    db.remove_point(pending.id.unwrap()).map_err(|e| format!("Failed to remove point: {}", e))?;
+    println!("{}", "Data file of an open transaction was kept".green());
+
+    // Removing the last point deletes its data file right away, leaving nothing to vacuum
+    db.remove_point(kept.id.unwrap()).map_err(|e| format!("Failed to remove point: {}", e))?;
+    assert!(!std::path::Path::new(&kept_file).exists(), "The removed point's data file should be deleted");
+    assert_eq!(db.vacuum().map_err(|e| format!("Failed to vacuum: {}", e))?, 0, "Nothing should be left to vacuum");
+    println!("{}", "Removed point's data file was deleted".green());
+
+    println!("{}", "Vacuum test passed".green());
+    Ok(())
+}
+
+/// Tests that journaled changes survive a crash before they're persisted.
+fn test_journal_recovery(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Journal Recovery ----".blue());
+
+    let (crate_id, barrel_id) = (Uuid::new_v4(), Uuid::new_v4());
+    let config = VaultConfig { journal: true, ..VaultConfig::default() };
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::with_config(db_path, config.clone())?;
+    let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+    vault_manager.add_object(region_id, crate_id, "resource", 1.0, 0.0, 0.0, Arc::new(TestCustomData { name: "crate".to_string(), value: 1 }))?;
+    vault_manager.add_object(region_id, barrel_id, "resource", 2.0, 0.0, 0.0, Arc::new(TestCustomData { name: "barrel".to_string(), value: 1 }))?;
+
+    // Change both objects in memory, and move one of them afterwards
+    for object_id in [crate_id, barrel_id] {
+        let mut object = vault_manager.get_object(object_id)?.ok_or("Object not found")?;
+        object.custom_data = Arc::new(TestCustomData { name: object.custom_data.name.clone(), value: 2 });
+        vault_manager.update_object(&object)?;
+    }
+    vault_manager.move_object(barrel_id, [5.0, 0.0, 0.0])?;
+    // Dropping without persisting stands in for a crash
+    drop(vault_manager);
+
+    // The journal is replayed on the next start
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let recovered_crate = vault_manager.get_object(crate_id)?.ok_or("Crate not found")?;
+    let recovered_barrel = vault_manager.get_object(barrel_id)?.ok_or("Barrel not found")?;
+    assert_eq!(recovered_crate.custom_data.value, 2, "The crate's change should be recovered");
+    assert_eq!(recovered_barrel.custom_data.value, 2, "The barrel's change should be recovered");
+    assert_eq!(recovered_barrel.point, [5.0, 0.0, 0.0], "The barrel should stay where it was moved");
+    assert!(!vault_manager.has_unflushed_changes(), "Recovered changes should already be persisted");
+    println!("{}", "Journaled changes were replayed".green());
+    drop(vault_manager);
+
+    // Persisting empties the journal, and unjournaled changes are lost in a crash
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::with_config(db_path, config)?;
+    let mut object = vault_manager.get_object(crate_id)?.ok_or("Crate not found")?;
+    object.custom_data = Arc::new(TestCustomData { name: "crate".to_string(), value: 3 });
+    vault_manager.update_object(&object)?;
+    vault_manager.persist_to_disk()?;
+    drop(vault_manager);
+    let db = MySQLGeo::Database::new(db_path).map_err(|e| format!("Failed to open database: {}", e))?;
+    assert_eq!(db.replay_journal().map_err(|e| format!("Failed to replay journal: {}", e))?, 0, "Persisting should empty the journal");
+    drop(db);
+
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let mut object = vault_manager.get_object(crate_id)?.ok_or("Crate not found")?;
+    assert_eq!(object.custom_data.value, 3);
+    object.custom_data = Arc::new(TestCustomData { name: "crate".to_string(), value: 4 });
+    vault_manager.update_object(&object)?;
+    drop(vault_manager);
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    assert_eq!(vault_manager.get_object(crate_id)?.ok_or("Crate not found")?.custom_data.value, 3, "Without the journal the change is lost");
+    println!("{}", "Journal is only kept while it's enabled".green());
+
+    println!("{}", "Journal recovery test passed".green());
+    Ok(())
+}
+
+/// Tests reading a region's points a page at a time, and loading a region larger than a page.
+fn test_paged_region_load(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Paged Region Loads ----".blue());
+
+    let db = MySQLGeo::Database::new(db_path).map_err(|e| format!("Failed to open database: {}", e))?;
+    db.create_table().map_err(|e| format!("Failed to create table: {}", e))?;
+    let (region_id, other_region) = (Uuid::new_v4(), Uuid::new_v4());
+    db.create_region(region_id, [0.0, 0.0, 0.0], 5000.0).map_err(|e| format!("Failed to create region: {}", e))?;
+    db.create_region(other_region, [0.0, 0.0, 0.0], 5000.0).map_err(|e| format!("Failed to create region: {}", e))?;
+    let points: Vec<(Point, Uuid)> = (0..2500)
+        .map(|i| {
+            let data = serde_json::json!({ "name": "ore", "value": i });
+            let region = if i % 5 == 0 { other_region } else { region_id };
+            (Point::new(Some(Uuid::new_v4()), i as f64, 0.0, 0.0, "resource".to_string(), data), region)
+        })
+        .collect();
+    db.add_points(&points).map_err(|e| format!("Failed to add points: {}", e))?;
+
+    // Pages cover the region exactly once, and the last one ends the walk
+    let mut seen = std::collections::HashSet::new();
+    let mut page_sizes = Vec::new();
+    let mut cursor = Some(0);
+    while let Some(after) = cursor {
+        let (page, next) = db.get_points_in_region_paged(region_id, after, 800)
+            .map_err(|e| format!("Failed to read page: {}", e))?;
+        page_sizes.push(page.len());
+        for point in page {
+            let point = point.map_err(|corrupt| format!("Corrupt point {}: {}", corrupt.id, corrupt.error))?;
+            assert!(seen.insert(point.id.unwrap()), "A point should appear on one page only");
+        }
+        cursor = next;
+    }
+    assert_eq!(seen.len(), 2000, "Every point of the region should be read");
+    assert_eq!(page_sizes, vec![800, 800, 400], "Pages should hold up to the limit");
+    println!("{}", "Read the region in pages".green());
+    drop(db);
+
+    // Regions larger than a page load completely
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let loaded = vault_manager.query_region(region_id, -1.0, -1.0, -1.0, 3000.0, 1.0, 1.0)?;
+    assert_eq!(loaded.len(), 2000, "Every object of the region should be loaded");
+    assert_eq!(vault_manager.query_region(other_region, -1.0, -1.0, -1.0, 3000.0, 1.0, 1.0)?.len(), 500);
+    println!("{}", "Loaded regions spanning several pages".green());
+
+    println!("{}", "Paged region loads test passed".green());
+    Ok(())
+}
+
+/// Tests single-object and per-type lookups that don't load the region.
+fn test_unloaded_lookups(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Unloaded Region Lookups ----".blue());
+
+    let (alice, bob, tower) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+    let region_id = {
+        let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+        let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+        vault_manager.add_object(region_id, alice, "player", 1.0, 0.0, 0.0, Arc::new(TestCustomData { name: "alice".to_string(), value: 1 }))?;
+        vault_manager.add_object(region_id, bob, "player", 2.0, 0.0, 0.0, Arc::new(TestCustomData { name: "bob".to_string(), value: 2 }))?;
+        vault_manager.add_object(region_id, tower, "building", 3.0, 0.0, 0.0, Arc::new(TestCustomData { name: "tower".to_string(), value: 3 }))?;
+        region_id
+    };
+
+    // Lookups read rows instead of loading the region
+    let config = VaultConfig { lazy_load: true, ..VaultConfig::default() };
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::with_config(db_path, config)?;
+    let peeked = vault_manager.peek_object(alice)?.ok_or("Alice not found")?;
+    assert_eq!(peeked.custom_data.name, "alice");
+    assert_eq!(peeked.point, [1.0, 0.0, 0.0]);
+    assert!(vault_manager.peek_object(Uuid::new_v4())?.is_none(), "Unknown objects shouldn't be found");
+    let mut players: Vec<Uuid> = vault_manager.get_objects_by_type(region_id, "player")?.iter().map(|obj| obj.uuid).collect();
+    players.sort_unstable();
+    let mut expected = vec![alice, bob];
+    expected.sort_unstable();
+    assert_eq!(players, expected, "Both players should be found");
+    assert!(!vault_manager.is_region_loaded(region_id), "Lookups shouldn't load the region");
+    println!("{}", "Unloaded region answered from the database".green());
+
+    // Loaded regions answer from memory, including unpersisted changes
+    let mut loaded = vault_manager.get_object(alice)?.ok_or("Alice not found")?;
+    assert!(vault_manager.is_region_loaded(region_id));
+    loaded.custom_data = Arc::new(TestCustomData { name: "alice".to_string(), value: 10 });
+    vault_manager.update_object(&loaded)?;
+    assert_eq!(vault_manager.peek_object(alice)?.ok_or("Alice not found")?.custom_data.value, 10);
+    let buildings = vault_manager.get_objects_by_type(region_id, "building")?;
+    assert_eq!(buildings.len(), 1);
+    assert_eq!(buildings[0].uuid, tower);
+    vault_manager.persist_to_disk()?;
+    println!("{}", "Loaded region answered from memory".green());
+
+    println!("{}", "Unloaded region lookups test passed".green());
+    Ok(())
+}
+
+/// Tests data persistence operations.
+fn test_persistence(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Persistence ----".blue());
+    
+    // Remove any existing database file
+    std::fs::remove_file(db_path).ok();
+    
+    {
+        // Create a new VaultManager instance
+        let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+        
+        // Create a region
+        let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+        println!("Created region with ID: {}", region_id.to_string().cyan());
+        
+        // Add an object to the region
+        let object_uuid = Uuid::new_v4();
+        let custom_data = Arc::new(TestCustomData { name: "Persistent Object".to_string(), value: 200 });
+        vault_manager.add_object(region_id, object_uuid, "building", 10.0, 20.0, 30.0, custom_data)?;
+        println!("Added object with UUID: {}", object_uuid.to_string().cyan());
+        
+        // Persist data to disk
+        vault_manager.persist_to_disk()?;
+        println!("{}", "Data persisted successfully".green());
+    }
+
+    // Create a new VaultManager instance to load persisted data
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    
+    // Retrieve persisted objects
+    let objects = vault_manager.database().get_points_within_radius(0.0, 0.0, 0.0, 100.0)
+        .map_err(|e| format!("Failed to load objects from persistent database: {}", e))?;
+
+    // Verify persisted objects
+    println!("Number of persisted objects: {}", objects.len().to_string().cyan());
+    for (i, obj) in objects.iter().enumerate() {
+        println!("Persisted object {}: UUID: {}, Type: {}, Custom Data: {:?}, Position: [{}, {}, {}]", 
+                 i + 1, obj.id.unwrap().to_string().cyan(), obj.object_type, obj.custom_data, obj.x, obj.y, obj.z);
+    }
+    assert_eq!(objects.len(), 1, "Persisted object should be loaded");
+    println!("{}", "Correct number of objects loaded from persistent storage".green());
+
+    // Print test passed message
+    println!("{}", "Persistence test passed".green());
+    Ok(())
+}
+
+
+/// Tests exporting a region to a file and importing it into another vault.
+fn test_region_export(src_path: &str, dst_path: &str, file_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Region Export ----".blue());
+
+    let src: VaultManager<TestCustomData> = VaultManager::new(src_path)?;
+    let region_id = src.create_or_load_region([50.0, 0.0, 0.0], 100.0)?;
+    let (tower, loot) = (Uuid::new_v4(), Uuid::new_v4());
+    src.add_spatial_object(region_id, SpatialObject {
+        uuid: tower,
+        object_type: ObjectType::from("building"),
+        point: [40.0, 0.0, 0.0],
+        custom_data: Arc::new(TestCustomData { name: "Tower".to_string(), value: 3 }),
+        size: [4.0, 20.0, 4.0],
+        version: 0,
+        velocity: None,
+        orientation: None,
+    })?;
+    src.add_object_with_ttl(region_id, SpatialObject {
+        uuid: loot,
+        object_type: ObjectType::from("resource"),
+        point: [60.0, 0.0, 0.0],
+        custom_data: Arc::new(TestCustomData { name: "Loot".to_string(), value: 7 }),
+        size: [0.0, 0.0, 0.0],
+        version: 0,
+        velocity: None,
+        orientation: None,
+    }, Duration::from_secs(3600))?;
+
+    let exported = src.export_region(region_id, file_path)?;
+    assert_eq!(exported, 2, "Both objects should be exported");
+    let first = std::fs::read(file_path).map_err(|e| e.to_string())?;
+    src.export_region(region_id, file_path)?;
+    assert!(std::fs::read(file_path).map_err(|e| e.to_string())? == first, "Exporting an unchanged region should produce the same file");
+    println!("{}", "Region exported deterministically".green());
+
+    // The region is recreated in another vault with the same IDs, data and TTLs
+    {
+        let dst: VaultManager<TestCustomData> = VaultManager::new(dst_path)?;
+        assert_eq!(dst.import_region(file_path)?, region_id, "The region should keep its UUID");
+        let imported = dst.get_object(tower)?.ok_or("Imported object should be found")?;
+        assert_eq!(imported.custom_data.name, "Tower");
+        assert_eq!((imported.point, imported.size), ([40.0, 0.0, 0.0], [4.0, 20.0, 4.0]));
+        assert!(dst.ttl_remaining(loot).is_some_and(|ttl| ttl > Duration::from_secs(3000)), "The TTL should be imported");
+        assert!(dst.ttl_remaining(tower).is_none(), "Permanent objects should stay permanent");
+        assert!(dst.import_region(file_path).is_err(), "Importing an existing region should fail");
+    }
+    let reopened: VaultManager<TestCustomData> = VaultManager::new(dst_path)?;
+    assert_eq!(reopened.load_report().total_points(), 2, "Imported objects should be persisted");
+    assert_eq!(reopened.get_object(loot)?.map(|obj| obj.custom_data.value), Some(7));
+    println!("{}", "Region imported into another vault".green());
+
+    // Files that aren't region files are rejected
+    std::fs::write(file_path, r#"{"format": "something-else"}"#).map_err(|e| e.to_string())?;
+    assert!(reopened.import_region(file_path).is_err(), "Other files should be rejected");
+    assert!(reopened.import_region("does/not/exist.json").is_err(), "Missing files should be rejected");
+    println!("{}", "Invalid files were rejected".green());
+
+    println!("{}", "Region export test passed".green());
+    Ok(())
+}
+
+/// Tests saving named snapshots of the world and restoring them.
+fn test_world_snapshots(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing World Snapshots ----".blue());
+
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let town = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+    let forest = vault_manager.create_or_load_region([300.0, 0.0, 0.0], 100.0)?;
+    let data = |name: &str, value| Arc::new(TestCustomData { name: name.to_string(), value });
+    let (mayor, tree, chest) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+    vault_manager.add_object(town, mayor, "player", 1.0, 0.0, 0.0, data("Mayor", 1))?;
+    vault_manager.add_object(forest, tree, "resource", 300.0, 0.0, 0.0, data("Oak", 5))?;
+    let loot = SpatialObject::new(chest, "loot", [2.0, 0.0, 0.0], data("Chest", 9)).map_err(|e| e.to_string())?;
+    vault_manager.add_object_with_ttl(town, loot, Duration::from_secs(3600))?;
+
+    // Unpersisted changes and unloaded regions are part of the snapshot
+    let mut moved = vault_manager.get_object(mayor)?.ok_or("The mayor should exist")?;
+    moved.point = [5.0, 0.0, 0.0];
+    vault_manager.update_object(&moved)?;
+    vault_manager.unload_region(forest)?;
+    let saved = vault_manager.snapshot("slot-1")?;
+    assert_eq!((saved.name.as_str(), saved.region_count, saved.object_count), ("slot-1", 2, 3), "The snapshot should hold the whole world");
+    println!("{}", "The world was saved".green());
+
+    // Change the world in every way, then roll it back
+    vault_manager.remove_object(tree)?;
+    vault_manager.remove_object(chest)?;
+    let mut moved = vault_manager.get_object(mayor)?.ok_or("The mayor should exist")?;
+    moved.point = [50.0, 0.0, 0.0];
+    vault_manager.update_object(&moved)?;
+    let ruins = vault_manager.create_or_load_region([600.0, 0.0, 0.0], 100.0)?;
+    vault_manager.add_object(ruins, Uuid::new_v4(), "building", 600.0, 0.0, 0.0, data("Ruin", 0))?;
+    let events = vault_manager.subscribe();
+    vault_manager.restore("slot-1")?;
+
+    let mut region_ids = vault_manager.region_ids();
+    let mut expected = vec![town, forest];
+    region_ids.sort();
+    expected.sort();
+    assert_eq!(region_ids, expected, "Only the saved regions should exist");
+    let mayor_now = vault_manager.get_object(mayor)?.ok_or("The mayor should be restored")?;
+    assert_eq!(mayor_now.point, [5.0, 0.0, 0.0], "Positions should be rolled back");
+    assert!(vault_manager.contains_object(tree)?, "Removed objects should be restored");
+    let chest_now = vault_manager.get_object(chest)?.ok_or("The chest should be restored")?;
+    assert_eq!(chest_now.custom_data.name, "Chest", "Custom data should be restored");
+    assert!(vault_manager.ttl_remaining(chest).is_some(), "TTLs should be restored");
+    let events: Vec<VaultEvent<TestCustomData>> = events.try_iter().collect();
+    assert!(events.iter().any(|event| matches!(event, VaultEvent::RegionRemoved { region_id } if *region_id == ruins)), "Subscribers should see removed regions");
+    assert_eq!(events.iter().filter(|event| matches!(event, VaultEvent::RegionCreated { .. })).count(), 2, "Subscribers should see restored regions");
+    println!("{}", "The world was rolled back".green());
+
+    // The restored world is what's persisted
+    drop(vault_manager);
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    assert_eq!(vault_manager.region_count(), 2, "The restore should be persisted");
+    assert_eq!(vault_manager.get_object(mayor)?.map(|obj| obj.point), Some([5.0, 0.0, 0.0]), "The restored position should be persisted");
+    println!("{}", "The restored world was persisted".green());
+
+    // Snapshots are listed, newest first, and can be deleted
+    vault_manager.snapshot("slot-2")?;
+    let names: Vec<String> = vault_manager.snapshots()?.into_iter().map(|snapshot| snapshot.name).collect();
+    assert_eq!(names.len(), 2, "Both snapshots should be listed");
+    assert!(names.contains(&"slot-1".to_string()) && names.contains(&"slot-2".to_string()), "Both snapshots should be listed by name");
+    assert!(vault_manager.delete_snapshot("slot-2")?, "The snapshot should be deleted");
+    assert!(vault_manager.restore("slot-2").is_err(), "Restoring a missing snapshot should fail");
+    assert!(vault_manager.contains_object(mayor)?, "A failed restore should leave the world intact");
+    println!("{}", "Snapshots were listed and deleted".green());
+
+    println!("{}", "World snapshots test passed".green());
+    Ok(())
+}
+
+/// Tests VaultManager with an arbitrary struct as custom data.
+fn test_with_arbitrary_struct(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing VaultManager with Arbitrary Struct ----".blue());
+
+    // Remove any existing database file
+    std::fs::remove_file(db_path).ok();
+
+    // Create a new VaultManager instance with ArbitraryGameObject as custom data
+    let vault_manager: VaultManager<ArbitraryGameObject> = VaultManager::new(db_path)?;
+
+    // Create a region
+    let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+    println!("Created region with ID: {}", region_id.to_string().cyan());
+
+    // Create an ArbitraryGameObject instance
+    let game_object = Arc::new(ArbitraryGameObject {
+        id: 1,
+        position: [10.0, 20.0, 30.0],
+        health: 100.0,
+        inventory: vec!["Sword".to_string(), "Health Potion".to_string()],
+    });
+
+    // Add the game object to the region
+    let object_uuid = Uuid::new_v4();
+    vault_manager.add_object(region_id, object_uuid, "game_object", 10.0, 20.0, 30.0, game_object.clone())?;
+    println!("Added game object with UUID: {}", object_uuid.to_string().cyan());
+
+    let query_result = vault_manager.query_region(region_id, -50.0, -50.0, -50.0, 50.0, 50.0, 50.0)?;
+    println!("Query returned {} objects", query_result.len().to_string().cyan());
+    assert_eq!(query_result.len(), 1, "Query should return 1 object");
+    
+    let retrieved_object = &query_result[0];
+    assert_eq!(retrieved_object.uuid, object_uuid, "Retrieved object UUID should match");
+    assert_eq!(*retrieved_object.custom_data, *game_object, "Retrieved custom data should match original");
+    println!("{}", "Retrieved object matches the original".green());
+
+    vault_manager.persist_to_disk()?;
+    println!("{}", "Data persisted successfully".green());
+
+    let new_vault_manager: VaultManager<ArbitraryGameObject> = VaultManager::new(db_path)?;
+    let loaded_objects = new_vault_manager.database().get_points_within_radius(0.0, 0.0, 0.0, 100.0)
+        .map_err(|e| format!("Failed to load objects from persistent database: {}", e))?;
+
+    assert_eq!(loaded_objects.len(), 1, "Persisted object should be loaded");
+    let loaded_object = &loaded_objects[0];
+    let loaded_custom_data: ArbitraryGameObject = serde_json::from_value(loaded_object.custom_data.clone())
+        .map_err(|e| format!("Failed to deserialize loaded custom data: {}", e))?;
+    assert_eq!(loaded_custom_data, *game_object, "Loaded custom data should match original");
+    println!("{}", "Loaded object matches the original".green());
+
+    println!("{}", "VaultManager with arbitrary struct test passed".green());
+    Ok(())
+}
+
+
+/// Tests cone queries used for vision checks.
+fn test_cone_query(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Cone Queries ----".blue());
+
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+
+    // One object straight ahead, one off to the side, one behind and one out of range
+    let ahead = Uuid::new_v4();
+    let side = Uuid::new_v4();
+    let behind = Uuid::new_v4();
+    let far = Uuid::new_v4();
+    let data = Arc::new(TestCustomData { name: "Target".to_string(), value: 1 });
+    vault_manager.add_object(region_id, ahead, "player", 10.0, 1.0, 0.0, data.clone())?;
+    vault_manager.add_object(region_id, side, "player", 1.0, 10.0, 0.0, data.clone())?;
+    vault_manager.add_object(region_id, behind, "player", -10.0, 0.0, 0.0, data.clone())?;
+    vault_manager.add_object(region_id, far, "player", 60.0, 0.0, 0.0, data)?;
+
+    // A 90 degree field of view looking down the x-axis
+    let visible = vault_manager.query_cone(region_id, [0.0, 0.0, 0.0], [1.0, 0.0, 0.0], std::f64::consts::FRAC_PI_4, 50.0)?;
+    println!("Cone query returned {} objects", visible.len().to_string().cyan());
+    assert_eq!(visible.len(), 1, "Only the object straight ahead should be visible");
+    assert_eq!(visible[0].uuid, ahead, "The visible object should be the one straight ahead");
+    println!("{}", "Cone query returned only the object inside the cone".green());
+
+    // A sized object is matched by its center even though its box pokes out of the range cube
+    let boulder = Uuid::new_v4();
+    vault_manager.add_spatial_object(region_id, SpatialObject {
+        uuid: boulder,
+        object_type: ObjectType::from("rock"),
+        point: [45.0, 0.0, 0.0],
+        custom_data: Arc::new(TestCustomData { name: "Boulder".to_string(), value: 2 }),
+        size: [12.0, 12.0, 12.0],
+        version: 0,
+        velocity: None,
+        orientation: None,
+    })?;
+    let visible = vault_manager.query_cone(region_id, [0.0, 0.0, 0.0], [1.0, 0.0, 0.0], std::f64::consts::FRAC_PI_4, 50.0)?;
+    assert!(visible.iter().any(|obj| obj.uuid == boulder), "A sized object whose center is in the cone should be visible");
+    println!("{}", "Sized object inside the cone was found".green());
+
+    // A zero-length direction is rejected
+    assert!(vault_manager.query_cone(region_id, [0.0, 0.0, 0.0], [0.0, 0.0, 0.0], 1.0, 50.0).is_err(), "Zero direction should be rejected");
+    println!("{}", "Invalid cone direction was rejected".green());
+
+    println!("{}", "Cone query test passed".green());
+    Ok(())
+}
+
+
+/// Tests vertical column queries, including objects with a size.
+fn test_column_query(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Column Queries ----".blue());
+
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+
+    // A point-sized marker and a 1x1x1 block stacked on the same tile, and a block on the next tile
+    let data = Arc::new(TestCustomData { name: "Block".to_string(), value: 1 });
+    let (marker, block, neighbor) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+    vault_manager.add_object(region_id, marker, "marker", 12.0, 1.0, 7.0, data.clone())?;
+    for (uuid, point) in [(block, [12.0, 5.0, 7.0]), (neighbor, [13.0, 5.0, 7.0])] {
+        vault_manager.add_spatial_object(region_id, SpatialObject {
+            uuid,
+            object_type: ObjectType::from("block"),
+            point,
+            custom_data: data.clone(),
+            size: [1.0, 1.0, 1.0],
+            version: 0,
+            velocity: None,
+            orientation: None,
+        })?;
+    }
+
+    let column = vault_manager.query_column(region_id, 12.0, 7.0, -100.0, 100.0)?;
+    let found: Vec<Uuid> = column.iter().map(|obj| obj.uuid).collect();
+    assert_eq!(found, vec![marker, block], "The column should hold both objects on the tile, bottom first");
+    println!("{}", "Sized and point objects in the column were found".green());
+
+    // Positions rounded off the grid still fall in the column, while the next tile doesn't
+    let drifted = Uuid::new_v4();
+    vault_manager.add_object(region_id, drifted, "marker", 12.0 + COLUMN_TOLERANCE / 2.0, 9.0, 7.0 - COLUMN_TOLERANCE / 2.0, data)?;
+    let column = vault_manager.query_column(region_id, 12.0, 7.0, -100.0, 100.0)?;
+    assert!(column.iter().any(|obj| obj.uuid == drifted), "Objects within the tolerance should be in the column");
+    assert!(column.iter().all(|obj| obj.uuid != neighbor), "Objects on the next tile shouldn't be in the column");
+    let column = vault_manager.query_column(region_id, 12.5, 7.0, -100.0, 100.0)?;
+    assert!(column.is_empty(), "A column between tiles should be empty, even where sized objects overlap it");
+    println!("{}", "Column tolerance was applied".green());
+
+    // The y range applies to positions
+    let column = vault_manager.query_column(region_id, 12.0, 7.0, 5.2, 100.0)?;
+    assert!(column.iter().all(|obj| obj.uuid != block), "A range starting above the block's center shouldn't return it");
+    println!("{}", "Column range was applied to positions".green());
+
+    println!("{}", "Column query test passed".green());
+    Ok(())
+}
+
+/// Tests nearest-neighbor queries near a region boundary.
+fn test_nearest_neighbors_across_regions(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Nearest-Neighbor Queries Across Regions ----".blue());
+
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let region1_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+    let region2_id = vault_manager.create_or_load_region([200.0, 0.0, 0.0], 100.0)?;
+
+    // The query point sits near the shared edge; the closest object lives in the other region
+    let near_home = Uuid::new_v4();
+    let across_edge = Uuid::new_v4();
+    let data = Arc::new(TestCustomData { name: "Neighbor".to_string(), value: 1 });
+    vault_manager.add_object(region1_id, near_home, "player", 80.0, 0.0, 0.0, data.clone())?;
+    vault_manager.add_object(region2_id, across_edge, "player", 102.0, 0.0, 0.0, data)?;
+
+    let local = vault_manager.query_nearest_neighbors(region1_id, [98.0, 0.0, 0.0], 1, false)?;
+    assert_eq!(local.len(), 1, "Local search should return one object");
+    assert_eq!(local[0].uuid, near_home, "Local search should only see its own region");
+    println!("{}", "Region-local search returned the closest local object".green());
+
+    let merged = vault_manager.query_nearest_neighbors(region1_id, [98.0, 0.0, 0.0], 2, true)?;
+    println!("Cross-region search returned {} objects", merged.len().to_string().cyan());
+    assert_eq!(merged.len(), 2, "Cross-region search should return both objects");
+    assert_eq!(merged[0].uuid, across_edge, "The object across the edge is the true nearest neighbor");
+    assert_eq!(merged[1].uuid, near_home, "Results should be ordered by distance");
+    println!("{}", "Cross-region search merged candidates by distance".green());
+
+    // An object lying outside its region's bounds is still considered when it's nearer
+    let far_region_id = vault_manager.create_or_load_region([600.0, 0.0, 0.0], 100.0)?;
+    let stray = Uuid::new_v4();
+    let data = Arc::new(TestCustomData { name: "Stray".to_string(), value: 2 });
+    vault_manager.add_object(far_region_id, stray, "player", 99.0, 0.0, 0.0, data)?;
+    let nearest = vault_manager.query_nearest_neighbors(region1_id, [98.0, 0.0, 0.0], 1, true)?;
+    assert_eq!(nearest.len(), 1, "Cross-region search should return one object");
+    assert_eq!(nearest[0].uuid, stray, "An object outside its region's bounds should still be found");
+    println!("{}", "Cross-region search found an object outside its region's bounds".green());
+
+    println!("{}", "Nearest-neighbor across regions test passed".green());
+    Ok(())
+}
+
+
+/// Tests deadlines and cancellation of queries and persistence.
+fn test_query_limits(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Query Limits and Cancellation ----".blue());
+
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+    let object_uuid = Uuid::new_v4();
+    let data = Arc::new(TestCustomData { name: "Limited".to_string(), value: 7 });
+    vault_manager.add_object(region_id, object_uuid, "resource", 1.0, 1.0, 1.0, data)?;
+
+    // A generous deadline lets the query complete
+    let limits = QueryLimits::with_timeout(Duration::from_secs(60));
+    let results = vault_manager.query_region_with_limits(region_id, [-10.0, -10.0, -10.0], [10.0, 10.0, 10.0], &limits)?;
+    assert_eq!(results.len(), 1, "Query within its deadline should return the object");
+    println!("{}", "Query within its deadline completed".green());
+
+    // A cancelled token aborts both queries and persistence
+    let token = CancellationToken::new();
+    let limits = QueryLimits::none().cancellable(token.clone());
+    token.cancel();
+    assert!(vault_manager.query_region_with_limits(region_id, [-10.0, -10.0, -10.0], [10.0, 10.0, 10.0], &limits).is_err(), "Cancelled query should fail");
+    assert!(vault_manager.persist_to_disk_with_limits(&limits).is_err(), "Cancelled persist should fail");
+    println!("{}", "Cancelled query and persist were aborted".green());
+
+    // The aborted persist must not have wiped the stored point
+    let stored = vault_manager.database().get_points_within_radius(0.0, 0.0, 0.0, 100.0)
+        .map_err(|e| format!("Failed to load objects from persistent database: {}", e))?;
+    assert_eq!(stored.len(), 1, "Aborted persist should leave stored points intact");
+    println!("{}", "Stored points survived the aborted persist".green());
+
+    println!("{}", "Query limits test passed".green());
+    Ok(())
+}
+
+
+/// Tests tracking, pausing and cancelling long-running operations through their handles.
+fn test_operation_handle(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Operation Handles ----".blue());
+
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 1000.0)?;
+    let data = Arc::new(TestCustomData { name: "Imported".to_string(), value: 1 });
+    let objects: Vec<SpatialObject<TestCustomData>> = (0..1000)
+        .map(|i| SpatialObject::new(Uuid::new_v4(), "resource", [i as f64 / 10.0, 0.0, 0.0], data.clone()))
+        .collect::<Result<_, _>>()
+        .map_err(|e| format!("Invalid object: {}", e))?;
+
+    // A tracked import reports its progress
+    let handle = OperationHandle::new();
+    assert_eq!(handle.status(), OperationStatus::Pending, "A new handle should be pending");
+    vault_manager.add_objects_tracked(region_id, objects.clone(), &handle)?;
+    assert_eq!(handle.status(), OperationStatus::Completed, "The import should be completed");
+    assert_eq!(handle.units(), (1000, 1000), "Every object should be counted");
+    assert_eq!(handle.progress(), 1.0, "A completed operation should be fully done");
+    assert!(handle.wait().is_ok(), "Waiting for a completed operation should succeed");
+    println!("{}", "Import progress was reported".green());
+
+    // A cancelled import adds nothing
+    let handle = OperationHandle::new();
+    handle.cancel();
+    let fresh: Vec<SpatialObject<TestCustomData>> = objects.iter().map(|obj| SpatialObject { uuid: Uuid::new_v4(), ..obj.clone() }).collect();
+    assert!(vault_manager.add_objects_tracked(region_id, fresh, &handle).is_err(), "A cancelled import should fail");
+    assert_eq!(handle.status(), OperationStatus::Cancelled, "The import should be marked cancelled");
+    assert!(handle.wait().is_err(), "Waiting for a cancelled operation should report the cancellation");
+    assert_eq!(vault_manager.query_region(region_id, -1.0, -1.0, -1.0, 101.0, 1.0, 1.0)?.len(), 1000, "A cancelled import should add nothing");
+    println!("{}", "Cancelled import was rolled back".green());
+
+    // A paused persist waits until another thread resumes it
+    for object in objects.iter().take(10) {
+        let mut object = object.clone();
+        object.custom_data = Arc::new(TestCustomData { name: "Changed".to_string(), value: 2 });
+        vault_manager.update_object(&object)?;
+    }
+    let handle = OperationHandle::new();
+    handle.pause();
+    let watcher = handle.clone();
+    let resumer = std::thread::spawn(move || {
+        while watcher.status() != OperationStatus::Paused {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        watcher.resume();
+    });
+    vault_manager.persist_to_disk_tracked(&handle)?;
+    resumer.join().map_err(|_| "Resuming thread panicked".to_string())?;
+    assert_eq!(handle.status(), OperationStatus::Completed, "The resumed persist should complete");
+    assert_eq!(handle.units(), (10, 10), "Only the changed objects should be counted");
+    assert!(!vault_manager.has_unflushed_changes(), "The resumed persist should flush every change");
+    println!("{}", "Paused persist was resumed".green());
+
+    println!("{}", "Operation handle test passed".green());
+    Ok(())
+}
+
+
+/// Tests that shutdown flushes changes that only lived in memory.
+fn test_graceful_shutdown(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Graceful Shutdown ----".blue());
+
+    let object_uuid = Uuid::new_v4();
+    {
+        let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+        let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+        let data = Arc::new(TestCustomData { name: "Before".to_string(), value: 1 });
+        vault_manager.add_object(region_id, object_uuid, "building", 5.0, 5.0, 5.0, data)?;
+        assert!(!vault_manager.has_unflushed_changes(), "add_object writes through to the database");
+
+        // update_object only changes memory until the next flush
+        let mut object = vault_manager.get_object(object_uuid)?
+            .ok_or_else(|| format!("Object not found: {}", object_uuid))?;
+        object.custom_data = Arc::new(TestCustomData { name: "After".to_string(), value: 2 });
+        vault_manager.update_object(&object)?;
+        assert!(vault_manager.has_unflushed_changes(), "update_object should leave unflushed changes");
+
+        vault_manager.shutdown()?;
+        println!("{}", "VaultManager shut down".green());
+    }
+
+    // The update must have been flushed by shutdown
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let object = vault_manager.get_object(object_uuid)?
+        .ok_or_else(|| format!("Object not found after restart: {}", object_uuid))?;
+    assert_eq!(object.custom_data.name, "After", "Shutdown should flush the updated custom data");
+    println!("{}", "Updated data survived the restart".green());
+
+    println!("{}", "Graceful shutdown test passed".green());
+    Ok(())
+}
+
+
+/// Tests streaming query results and reading them page by page.
+fn test_query_streaming(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Streaming Queries ----".blue());
+
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+    let data = Arc::new(TestCustomData { name: "Stream".to_string(), value: 1 });
+    for i in 0..25 {
+        vault_manager.add_object(region_id, Uuid::new_v4(), "resource", i as f64, 0.0, 0.0, data.clone())?;
+    }
+
+    // The iterator sees the same objects as query_region, and isn't affected by later changes
+    let query = vault_manager.query_region_iter(region_id, [0.0, -1.0, -1.0], [19.5, 1.0, 1.0])?;
+    assert_eq!(query.region_id(), region_id, "The query should know its region");
+    vault_manager.add_object(region_id, Uuid::new_v4(), "resource", 5.5, 0.0, 0.0, data.clone())?;
+    let mut streamed: Vec<Uuid> = query.iter().map(|obj| obj.uuid).collect();
+    let mut expected: Vec<Uuid> = vault_manager.query_region(region_id, 0.0, -1.0, -1.0, 19.5, 1.0, 1.0)?
+        .iter()
+        .filter(|obj| obj.point[0] != 5.5)
+        .map(|obj| obj.uuid)
+        .collect();
+    streamed.sort();
+    expected.sort();
+    assert_eq!(streamed.len(), 20, "The query should stream every matching object");
+    assert_eq!(streamed, expected, "Streaming should match query_region");
+    drop(query);
+    println!("{}", "Query results were streamed".green());
+
+    // Pages cover every object exactly once
+    let mut paged = Vec::new();
+    let mut offset = 0;
+    loop {
+        let page = vault_manager.query_region_page(region_id, [-1.0; 3], [30.0; 3], offset, 7)?;
+        assert!(page.len() <= 7, "A page should respect the limit");
+        if page.is_empty() {
+            break;
+        }
+        offset += page.len();
+        paged.extend(page.into_iter().map(|obj| obj.uuid));
+    }
+    let total = paged.len();
+    paged.sort();
+    paged.dedup();
+    assert_eq!((total, paged.len()), (26, 26), "Every object should be on exactly one page");
+    assert!(vault_manager.query_region_page(region_id, [-1.0; 3], [30.0; 3], 100, 7)?.is_empty(), "Pages past the end should be empty");
+    println!("{}", "Query results were paginated".green());
+
+    println!("{}", "Streaming queries test passed".green());
+    Ok(())
+}
+
+/// Tests counting objects and checking their existence without cloning them.
+fn test_count_and_contains(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Count and Existence Queries ----".blue());
+
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+    let data = Arc::new(TestCustomData { name: "Count".to_string(), value: 2 });
+    let mut ids = Vec::new();
+    for i in 0..10 {
+        let id = Uuid::new_v4();
+        vault_manager.add_object(region_id, id, "resource", i as f64 * 2.0, 0.0, 0.0, data.clone())?;
+        ids.push(id);
+    }
+    // Sticks out of the counted box, so it only counts once the box covers its whole extent
+    let wide = SpatialObject::new(Uuid::new_v4(), "building", [5.0, 0.0, 0.0], data.clone())
+        .and_then(|obj| obj.with_size([4.0, 4.0, 4.0]))
+        .map_err(|e| e.to_string())?;
+    vault_manager.add_spatial_object(region_id, wide)?;
+
+    // Counts match the length of query_region
+    let boxes = [([-1.0, -1.0, -1.0], [9.0, 1.0, 1.0]), ([-5.0; 3], [30.0; 3]), ([100.0; 3], [200.0; 3])];
+    let mut loaded_counts = Vec::new();
+    for (min, max) in boxes {
+        let count = vault_manager.count_in_region(region_id, min, max)?;
+        let queried = vault_manager.query_region(region_id, min[0], min[1], min[2], max[0], max[1], max[2])?.len();
+        assert_eq!(count, queried, "The count should match query_region");
+        loaded_counts.push(count);
+    }
+    assert_eq!(loaded_counts, vec![5, 11, 0], "Counts should only include objects inside the box");
+    println!("{}", "Loaded regions were counted".green());
+
+    // Unloaded regions are counted by the database without being loaded
+    vault_manager.unload_region(region_id)?;
+    for ((min, max), expected) in boxes.into_iter().zip(loaded_counts) {
+        assert_eq!(vault_manager.count_in_region(region_id, min, max)?, expected, "The stored count should match the loaded one");
+    }
+    assert!(!vault_manager.is_region_loaded(region_id), "Counting shouldn't load the region");
+    assert!(vault_manager.count_in_region(Uuid::new_v4(), [0.0; 3], [1.0; 3]).is_err(), "Counting an unknown region should fail");
+    println!("{}", "Unloaded regions were counted".green());
+
+    // Existence is answered for unloaded and loaded regions alike
+    assert!(vault_manager.contains_object(ids[0])?, "A stored object should exist");
+    assert!(!vault_manager.is_region_loaded(region_id), "Checking existence shouldn't load the region");
+    assert!(!vault_manager.contains_object(Uuid::new_v4())?, "An unknown object shouldn't exist");
+    vault_manager.remove_object(ids[0])?;
+    vault_manager.soft_remove_object(ids[1])?;
+    assert!(vault_manager.contains_object(ids[2])?, "A loaded object should exist");
+    assert!(!vault_manager.contains_object(ids[0])?, "A removed object shouldn't exist");
+    assert!(!vault_manager.contains_object(ids[1])?, "A soft-deleted object shouldn't exist");
+    assert_eq!(vault_manager.count_in_region(region_id, [-5.0; 3], [30.0; 3])?, 9, "Removed objects shouldn't be counted");
+    println!("{}", "Object existence was checked".green());
+
+    println!("{}", "Count and existence test passed".green());
+    Ok(())
+}
+
+/// Tests the statistics reported for a region.
+fn test_region_stats(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Region Statistics ----".blue());
+
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+    let empty = vault_manager.region_stats(region_id)?;
+    assert_eq!((empty.object_count, empty.occupied_bounds, empty.tree_depth), (0, None, 1), "An empty region has no objects or bounds");
+
+    let data = Arc::new(TestCustomData { name: "Stats".to_string(), value: 1 });
+    for i in 0..500 {
+        let object_type = if i % 5 == 0 { "building" } else { "resource" };
+        vault_manager.add_object(region_id, Uuid::new_v4(), object_type, (i % 50) as f64, (i / 50) as f64, -10.0, data.clone())?;
+    }
+    let stats = vault_manager.region_stats(region_id)?;
+    println!("Region holds {} objects in about {} bytes", stats.object_count.to_string().cyan(), stats.estimated_memory.to_string().cyan());
+    assert_eq!(stats.region_id, region_id);
+    assert_eq!(stats.object_count, 500);
+    assert_eq!(stats.counts_by_type.get("building").copied(), Some(100));
+    assert_eq!(stats.counts_by_type.get("resource").copied(), Some(400));
+    assert_eq!(stats.occupied_bounds, Some(([0.0, 0.0, -10.0], [49.0, 9.0, -10.0])), "Bounds should enclose every object");
+    assert!(stats.tree_depth > 1, "500 objects don't fit in the root node");
+    assert!(stats.estimated_memory > empty.estimated_memory, "More objects should take more memory");
+    println!("{}", "Counts, bounds and depth were reported".green());
+
+    println!("{}", "Region statistics test passed".green());
+    Ok(())
+}
+
+/// Tests bounding-box queries spanning several regions.
+fn test_query_all(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Cross-Region Queries ----".blue());
+
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let west_id = vault_manager.create_or_load_region([-100.0, 0.0, 0.0], 100.0)?;
+    let east_id = vault_manager.create_or_load_region([100.0, 0.0, 0.0], 100.0)?;
+    let far_id = vault_manager.create_or_load_region([1000.0, 0.0, 0.0], 100.0)?;
+
+    let data = Arc::new(TestCustomData { name: "Spread".to_string(), value: 3 });
+    vault_manager.add_object(west_id, Uuid::new_v4(), "resource", -5.0, 0.0, 0.0, data.clone())?;
+    vault_manager.add_object(east_id, Uuid::new_v4(), "resource", 5.0, 0.0, 0.0, data.clone())?;
+    vault_manager.add_object(far_id, Uuid::new_v4(), "resource", 1000.0, 0.0, 0.0, data)?;
+
+    // A box straddling the border between west and east
+    let results = vault_manager.query_all(-10.0, -10.0, -10.0, 10.0, 10.0, 10.0)?;
+    println!("Cross-region query returned {} objects", results.len().to_string().cyan());
+    assert_eq!(results.len(), 2, "Query should merge objects from both neighbouring regions");
+    println!("{}", "Results from both regions were merged".green());
+
+    println!("{}", "Cross-region query test passed".green());
+    Ok(())
+}
+
+
+/// Tests the startup consistency report and lenient loading of corrupt objects.
+fn test_load_report(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Startup Consistency Report ----".blue());
+
+    let region_id;
+    let bad_uuid = Uuid::new_v4();
+    {
+        let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+        region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+        let data = Arc::new(TestCustomData { name: "Healthy".to_string(), value: 1 });
+        vault_manager.add_object(region_id, Uuid::new_v4(), "player", 1.0, 1.0, 1.0, data)?;
+
+        // A point whose custom data doesn't match TestCustomData, and one pointing at a missing region
+        let bad_point = Point::new(Some(bad_uuid), 2.0, 2.0, 2.0, "player".to_string(), serde_json::json!({"bogus": true}));
+        vault_manager.database().add_point(&bad_point, region_id)
+            .map_err(|e| format!("Failed to add corrupt point: {}", e))?;
+        let orphan = Point::new(Some(Uuid::new_v4()), 3.0, 3.0, 3.0, "player".to_string(), serde_json::json!({"name": "Orphan", "value": 0}));
+        vault_manager.database().add_point(&orphan, Uuid::new_v4())
+            .map_err(|e| format!("Failed to add orphaned point: {}", e))?;
+    }
+
+    // Strict mode refuses to start with a corrupt object
+    assert!(VaultManager::<TestCustomData>::new(db_path).is_err(), "Strict loading should fail on corrupt data");
+    println!("{}", "Strict loading rejected the corrupt object".green());
+
+    // Lenient mode starts and reports what was skipped
+    let config = VaultConfig { lenient_load: true, ..VaultConfig::default() };
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::with_config(db_path, config)?;
+    let report = vault_manager.load_report();
+    println!("Report: {} regions, {} points, {} failures, {} orphans", report.regions_loaded, report.total_points(), report.failures.len(), report.orphaned_points.len());
+    assert_eq!(report.regions_loaded, 1, "One region should be loaded");
+    assert_eq!(report.points_per_region.get(&region_id), Some(&1), "Only the healthy object should be loaded");
+    assert_eq!(report.failures.len(), 1, "The corrupt object should be reported");
+    assert_eq!(report.failures[0].point_id, bad_uuid.to_string(), "The failure should identify the corrupt object");
+    assert!(report.failures[0].raw_custom_data.as_deref().unwrap_or_default().contains("bogus"), "The raw payload should be kept");
+    assert_eq!(report.orphaned_points.len(), 1, "The orphaned point should be reported");
+    assert!(!report.is_clean(), "The report should not be clean");
+    println!("{}", "Lenient loading reported the corrupt and orphaned objects".green());
+
+    println!("{}", "Startup consistency report test passed".green());
+    Ok(())
+}
+
+
+/// Tests that corrupt objects are quarantined and can be repaired.
+fn test_quarantine(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Quarantine of Corrupt Objects ----".blue());
+
+    let bad_uuid = Uuid::new_v4();
+    {
+        let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+        let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+        let bad_point = Point::new(Some(bad_uuid), 4.0, 5.0, 6.0, "resource".to_string(), serde_json::json!({"name": 12}));
+        vault_manager.database().add_point(&bad_point, region_id)
+            .map_err(|e| format!("Failed to add corrupt point: {}", e))?;
+    }
+
+    // Lenient startup moves the corrupt object into quarantine
+    let config = VaultConfig { lenient_load: true, ..VaultConfig::default() };
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::with_config(db_path, config)?;
+    let quarantined = vault_manager.quarantined_objects()?;
+    assert_eq!(quarantined.len(), 1, "The corrupt object should be quarantined");
+    assert_eq!(quarantined[0].point_id, bad_uuid.to_string(), "The quarantined object should keep its ID");
+    assert_eq!(quarantined[0].point, [4.0, 5.0, 6.0], "The quarantined object should keep its position");
+    println!("{}", "Corrupt object was quarantined".green());
+
+    // Repairing puts it back into its region and empties the quarantine
+    let repaired = Arc::new(TestCustomData { name: "Repaired".to_string(), value: 12 });
+    vault_manager.repair_quarantined_object(&bad_uuid.to_string(), repaired.clone())?;
+    let object = vault_manager.get_object(bad_uuid)?
+        .ok_or_else(|| format!("Repaired object not found: {}", bad_uuid))?;
+    assert_eq!(object.custom_data, repaired, "Repaired object should carry the new data");
+    assert!(vault_manager.quarantined_objects()?.is_empty(), "Quarantine should be empty after repair");
+    println!("{}", "Quarantined object was repaired".green());
+    drop(vault_manager);
+
+    // Strict startup succeeds again now that the data is healthy
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    assert!(vault_manager.load_report().is_clean(), "Load should be clean after repair");
+    println!("{}", "Strict loading succeeds after repair".green());
+
+    println!("{}", "Quarantine test passed".green());
+    Ok(())
+}
+
+
+/// Tests that only payloads above the compression threshold are compressed.
+fn test_compression_threshold(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Compression Threshold ----".blue());
+
+    let small_uuid = Uuid::new_v4();
+    let large_uuid = Uuid::new_v4();
+    let large_data = Arc::new(TestCustomData { name: "inventory item ".repeat(64), value: 9 });
+    {
+        let config = VaultConfig { compression_threshold: Some(256), ..VaultConfig::default() };
+        let vault_manager: VaultManager<TestCustomData> = VaultManager::with_config(db_path, config)?;
+        let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+        vault_manager.add_object(region_id, small_uuid, "resource", 1.0, 0.0, 0.0, Arc::new(TestCustomData { name: "Small".to_string(), value: 1 }))?;
+        vault_manager.add_object(region_id, large_uuid, "resource", 2.0, 0.0, 0.0, large_data.clone())?;
+    }
+
+    // Small payloads stay plain JSON, large ones are compressed on disk
+    let data_file = |uuid: Uuid| {
+        let db = MySQLGeo::Database::new(db_path).map_err(|e| format!("Failed to open database: {}", e))?;
+        let path = db.get_data_file(uuid)
+            .map_err(|e| format!("Failed to look up data file: {}", e))?
+            .ok_or_else(|| format!("No data file recorded for {}", uuid))?;
+        std::fs::read(path).map_err(|e| format!("Failed to read data file: {}", e))
+    };
+    assert!(data_file(small_uuid)?.starts_with(b"{"), "Small payload should be stored uncompressed");
+    assert!(!data_file(large_uuid)?.starts_with(b"{"), "Large payload should be stored compressed");
+    println!("{}", "Only the large payload was compressed".green());
+
+    // Reading decodes transparently, even with compression turned off
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let object = vault_manager.get_object(large_uuid)?
+        .ok_or_else(|| format!("Compressed object not found: {}", large_uuid))?;
+    assert_eq!(object.custom_data, large_data, "Compressed payload should round-trip");
+    println!("{}", "Compressed payload was decoded transparently".green());
+
+    println!("{}", "Compression threshold test passed".green());
+    Ok(())
+}
+
+
+/// Tests compressing custom data with LZ4, and reading it back after switching algorithms.
+fn test_lz4_compression(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing LZ4 Compression ----".blue());
+
+    let lz4_uuid = Uuid::new_v4();
+    let zstd_uuid = Uuid::new_v4();
+    let lz4_data = Arc::new(TestCustomData { name: "sword of lightning ".repeat(64), value: 1 });
+    let zstd_data = Arc::new(TestCustomData { name: "shield of thunder ".repeat(64), value: 2 });
+    let region_id = {
+        let config = VaultConfig { compression_threshold: Some(256), compression: Compression::Lz4, ..VaultConfig::default() };
+        let vault_manager: VaultManager<TestCustomData> = VaultManager::with_config(db_path, config)?;
+        let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+        vault_manager.add_object(region_id, lz4_uuid, "resource", 1.0, 0.0, 0.0, lz4_data.clone())?;
+        region_id
+    };
+
+    // The payload is stored as LZ4 and smaller than the JSON it holds
+    let db = MySQLGeo::Database::new(db_path).map_err(|e| format!("Failed to open database: {}", e))?;
+    let path = db.get_data_file(lz4_uuid)
+        .map_err(|e| format!("Failed to look up data file: {}", e))?
+        .ok_or_else(|| format!("No data file recorded for {}", lz4_uuid))?;
+    drop(db);
+    let contents = std::fs::read(path).map_err(|e| format!("Failed to read data file: {}", e))?;
+    let json = lz4_flex::decompress_size_prepended(&contents).map_err(|e| format!("Payload isn't LZ4: {}", e))?;
+    assert!(contents.len() < json.len(), "The LZ4 payload should be smaller than its JSON");
+    println!("{}", "Large payload was compressed with LZ4".green());
+
+    // Switching back to zstd still reads the LZ4 payload
+    let config = VaultConfig { compression_threshold: Some(256), ..VaultConfig::default() };
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::with_config(db_path, config)?;
+    vault_manager.add_object(region_id, zstd_uuid, "resource", 2.0, 0.0, 0.0, zstd_data.clone())?;
+    let lz4_object = vault_manager.get_object(lz4_uuid)?.ok_or("LZ4 object not found")?;
+    let zstd_object = vault_manager.get_object(zstd_uuid)?.ok_or("zstd object not found")?;
+    assert_eq!(lz4_object.custom_data, lz4_data, "LZ4 payload should round-trip");
+    assert_eq!(zstd_object.custom_data, zstd_data, "zstd payload should round-trip");
+    println!("{}", "Payloads of both algorithms decoded".green());
+
+    println!("{}", "LZ4 compression test passed".green());
+    Ok(())
+}
+
+/// Tests that cold objects are archived out of the database and restored by `get_object`.
+fn test_cold_archive(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Cold-Storage Archival ----".blue());
+
+    // Archival needs a directory to archive to
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    assert!(vault_manager.archive_cold_objects(Duration::ZERO).is_err(), "Archival without a directory should fail");
+    drop(vault_manager);
+
+    let archive_dir = std::path::Path::new(db_path).with_file_name("archive");
+    let config = VaultConfig { archive_dir: Some(archive_dir.clone()), ..VaultConfig::default() };
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::with_config(db_path, config)?;
+    let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+    let (rock, tree, flare) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+    vault_manager.add_object(region_id, rock, "resource", 1.0, 0.0, 0.0, Arc::new(TestCustomData { name: "rock".to_string(), value: 1 }))?;
+    vault_manager.add_object(region_id, tree, "resource", 2.0, 0.0, 0.0, Arc::new(TestCustomData { name: "tree".to_string(), value: 2 }))?;
+    let flare_object = SpatialObject {
+        uuid: flare,
+        object_type: "effect".into(),
+        point: [3.0, 0.0, 0.0],
+        custom_data: Arc::new(TestCustomData { name: "flare".to_string(), value: 3 }),
+        size: [0.0; 3],
+        version: 0,
+        velocity: None,
+        orientation: None,
+    };
+    vault_manager.add_object_with_ttl(region_id, flare_object, Duration::from_secs(3600))?;
+    let original_rock = vault_manager.get_object(rock)?.ok_or("Rock not found")?;
+
+    // Recently written objects aren't cold
+    assert_eq!(vault_manager.archive_cold_objects(Duration::from_secs(3600))?, 0, "Fresh objects shouldn't be archived");
+    std::thread::sleep(Duration::from_millis(10));
+
+    // Objects with a TTL stay in the database
+    assert_eq!(vault_manager.archive_cold_objects(Duration::ZERO)?, 2, "Both resources should be archived");
+    let remaining = vault_manager.query_region(region_id, -10.0, -10.0, -10.0, 10.0, 10.0, 10.0)?;
+    assert_eq!(remaining.len(), 1, "Only the flare should stay in the region");
+    assert_eq!(remaining[0].uuid, flare);
+    let chunks = std::fs::read_dir(&archive_dir).map_err(|e| format!("Failed to list archive: {}", e))?.count();
+    assert_eq!(chunks, 1, "The region's objects should share one chunk");
+    println!("{}", "Cold objects were moved to the archive".green());
+
+    // Archived objects are faulted back in, and the chunk goes away with the last of them
+    let restored_rock = vault_manager.get_object(rock)?.ok_or("Rock wasn't restored")?;
+    assert!(restored_rock == original_rock, "The restored object should match the archived one");
+    assert_eq!(vault_manager.query_region(region_id, -10.0, -10.0, -10.0, 10.0, 10.0, 10.0)?.len(), 2);
+    assert!(vault_manager.get_object(tree)?.is_some(), "Tree wasn't restored");
+    let chunks = std::fs::read_dir(&archive_dir).map_err(|e| format!("Failed to list archive: {}", e))?.count();
+    assert_eq!(chunks, 0, "The chunk should be deleted once it's empty");
+    println!("{}", "Archived objects were restored on access".green());
+
+    // Restored objects are back in the database
+    drop(vault_manager);
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    assert_eq!(vault_manager.query_region(region_id, -10.0, -10.0, -10.0, 10.0, 10.0, 10.0)?.len(), 3);
+    println!("{}", "Restored objects were persisted".green());
+
+    println!("{}", "Cold-storage archival test passed".green());
+    Ok(())
+}
+
+/// Tests that the consistency check finds rows and objects that disagree.
+fn test_consistency_check(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Consistency Checks ----".blue());
+
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+    let other_region = vault_manager.create_or_load_region([300.0, 0.0, 0.0], 100.0)?;
+    let data = Arc::new(TestCustomData { name: "Checked".to_string(), value: 4 });
+    let ids: Vec<Uuid> = (0..12).map(|_| Uuid::new_v4()).collect();
+    for (i, id) in ids.iter().enumerate() {
+        vault_manager.add_object(region_id, *id, "resource", i as f64, 0.0, 0.0, data.clone())?;
+    }
+
+    // A healthy vault passes, a few samples at a time
+    for _ in 0..4 {
+        assert!(vault_manager.verify_tick(5)?.is_empty(), "A healthy vault should have no divergences");
+    }
+    let stats = vault_manager.consistency_stats();
+    assert_eq!(stats.objects_checked, 20, "Each tick should check up to the sample size, wrapping around");
+    assert!((12..=20).contains(&stats.rows_checked), "Every row should be checked, at most the sample size per tick");
+    assert!(stats.passes >= 1, "Twelve objects should take less than four ticks");
+    println!("{}", "Healthy vault passed".green());
+
+    // Unpersisted changes are expected to differ from their rows
+    let mut changed = vault_manager.get_object(ids[0])?.ok_or("Object should exist")?;
+    changed.custom_data = Arc::new(TestCustomData { name: "Unsaved".to_string(), value: 5 });
+    vault_manager.update_object(&changed)?;
+    assert!(vault_manager.verify_tick(100)?.is_empty(), "Dirty objects should not be reported");
+
+    // Break the database behind the manager's back
+    let db = MySQLGeo::Database::new(db_path).map_err(|e| format!("Failed to open database: {}", e))?;
+    let (orphan, stray) = (Uuid::new_v4(), Uuid::new_v4());
+    let point = |id: Uuid| Point::new(Some(id), 1.0, 1.0, 1.0, "resource".to_string(), serde_json::to_value(&*data).unwrap());
+    db.remove_point(ids[1]).map_err(|e| e.to_string())?;
+    db.update_point_position(ids[2], 50.0, 0.0, 0.0).map_err(|e| e.to_string())?;
+    db.update_point_region(ids[3], other_region).map_err(|e| e.to_string())?;
+    db.add_point(&point(stray), region_id).map_err(|e| e.to_string())?;
+    db.add_point(&point(orphan), Uuid::new_v4()).map_err(|e| e.to_string())?;
+
+    let mut found: Vec<(Uuid, DivergenceKind)> = Vec::new();
+    for _ in 0..6 {
+        found.extend(vault_manager.verify_tick(5)?.into_iter().map(|divergence| (divergence.object_id, divergence.kind)));
+    }
+    assert!(found.contains(&(ids[1], DivergenceKind::MissingRow)), "A missing row should be found");
+    assert!(found.contains(&(ids[2], DivergenceKind::StaleRow)), "A stale row should be found");
+    assert!(found.contains(&(ids[3], DivergenceKind::WrongRegion { stored_region: other_region })), "A row in the wrong region should be found");
+    assert!(found.contains(&(stray, DivergenceKind::MissingObject)), "A row without an object should be found");
+    assert!(found.contains(&(orphan, DivergenceKind::OrphanedRow)), "A row without a region should be found");
+    assert!(found.iter().all(|(object_id, _)| *object_id != ids[0]), "The dirty object should not be reported");
+    assert!(vault_manager.consistency_stats().divergences() >= 5, "Divergences should be counted");
+    println!("{}", "Divergences were found".green());
+
+    println!("{}", "Consistency check test passed".green());
+    Ok(())
+}
+
+/// Tests that identical custom data payloads share one data file.
+fn test_payload_deduplication(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Payload Deduplication ----".blue());
+
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+
+    // Three objects share one payload, a fourth has its own
+    let shared = Arc::new(TestCustomData { name: "Stone".to_string(), value: 1 });
+    let uuids: Vec<Uuid> = (0..3).map(|_| Uuid::new_v4()).collect();
+    for (i, uuid) in uuids.iter().enumerate() {
+        vault_manager.add_object(region_id, *uuid, "resource", i as f64, 0.0, 0.0, shared.clone())?;
+    }
+    let unique = Uuid::new_v4();
+    vault_manager.add_object(region_id, unique, "resource", 5.0, 0.0, 0.0, Arc::new(TestCustomData { name: "Gem".to_string(), value: 2 }))?;
+
+    let blob_count = |vault_manager: &VaultManager<TestCustomData>| {
+        vault_manager.database().count_blobs().map_err(|e| format!("Failed to count blobs: {}", e))
+    };
+    assert_eq!(blob_count(&vault_manager)?, 2, "Identical payloads should be stored once");
+    let first_file = vault_manager.database().get_data_file(uuids[0]).map_err(|e| format!("Failed to look up data file: {}", e))?;
+    let last_file = vault_manager.database().get_data_file(uuids[2]).map_err(|e| format!("Failed to look up data file: {}", e))?;
+    assert_eq!(first_file, last_file, "Objects with identical payloads should share a data file");
+    println!("{}", "Identical payloads share a single data file".green());
+
+    // The shared file survives until its last reference is removed
+    vault_manager.remove_object(uuids[0])?;
+    vault_manager.remove_object(uuids[1])?;
+    let shared_file = first_file.ok_or("Shared data file should be recorded")?;
+    assert!(std::path::Path::new(&shared_file).exists(), "Shared data file should still be referenced");
+    vault_manager.remove_object(uuids[2])?;
+    assert!(!std::path::Path::new(&shared_file).exists(), "Unreferenced data file should be deleted");
+    assert_eq!(blob_count(&vault_manager)?, 1, "Only the unique payload should remain");
+    println!("{}", "Unreferenced data files are deleted".green());
+
+    // Persisting rewrites every point without losing shared files
+    vault_manager.persist_to_disk()?;
+    assert_eq!(blob_count(&vault_manager)?, 1, "Persisting should keep referenced payloads");
+    let reloaded: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    assert!(reloaded.get_object(unique)?.is_some(), "Unique object should reload after persisting");
+    println!("{}", "Payloads survive a full persist".green());
+
+    // Another database storing the same payload keeps its own copy
+    let other_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let other_path = other_dir.path().join("test_db_dedup_other.sqlite");
+    let other: VaultManager<TestCustomData> = VaultManager::new(other_path.to_str().unwrap())?;
+    let other_region = other.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+    let other_uuid = Uuid::new_v4();
+    other.add_object(other_region, other_uuid, "resource", 0.0, 0.0, 0.0, Arc::new(TestCustomData { name: "Gem".to_string(), value: 2 }))?;
+    vault_manager.remove_object(unique)?;
+    let other_file = other.database().get_data_file(other_uuid)
+        .map_err(|e| format!("Failed to look up data file: {}", e))?
+        .ok_or("Other database should record a data file")?;
+    assert!(std::path::Path::new(&other_file).exists(), "Removing a payload must not delete another database's file");
+    drop(other);
+    assert!(VaultManager::<TestCustomData>::new(other_path.to_str().unwrap())?.get_object(other_uuid)?.is_some(), "The other database should still load");
+    println!("{}", "Databases don't share payload files".green());
+
+    println!("{}", "Payload deduplication test passed".green());
+    Ok(())
+}
+
+
+/// Tests frustum queries built from planes and from a view-projection matrix.
+fn test_frustum_query(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Frustum Queries ----".blue());
+
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+    let data = Arc::new(TestCustomData { name: "Frustum".to_string(), value: 2 });
+
+    // A camera at the origin looking down +x with a 90 degree field of view, from 1 to 50 units away
+    let frustum = Frustum::from_planes([
+        Plane::new([1.0, 0.0, 1.0], 0.0),
+        Plane::new([1.0, 0.0, -1.0], 0.0),
+        Plane::new([1.0, 1.0, 0.0], 0.0),
+        Plane::new([1.0, -1.0, 0.0], 0.0),
+        Plane::new([1.0, 0.0, 0.0], -1.0),
+        Plane::new([-1.0, 0.0, 0.0], 50.0),
+    ]);
+    let visible = Uuid::new_v4();
+    let wide_wall = Uuid::new_v4();
+    vault_manager.add_object(region_id, visible, "player", 20.0, 5.0, -5.0, data.clone())?;
+    vault_manager.add_object(region_id, Uuid::new_v4(), "player", -20.0, 0.0, 0.0, data.clone())?;
+    vault_manager.add_object(region_id, Uuid::new_v4(), "player", 10.0, 15.0, 0.0, data.clone())?;
+    vault_manager.add_object(region_id, Uuid::new_v4(), "player", 60.0, 0.0, 0.0, data.clone())?;
+    // The wall's center is outside the view but part of it reaches inside
+    vault_manager.add_spatial_object(region_id, SpatialObject {
+        uuid: wide_wall,
+        object_type: ObjectType::from("building"),
+        point: [10.0, 14.0, 0.0],
+        custom_data: data,
+        size: [2.0, 10.0, 2.0],
+        version: 0,
+        velocity: None,
+        orientation: None,
+    })?;
+
+    let mut seen: Vec<Uuid> = vault_manager.query_frustum(region_id, &frustum)?.iter().map(|obj| obj.uuid).collect();
+    seen.sort();
+    let mut expected = vec![visible, wide_wall];
+    expected.sort();
+    println!("Frustum query returned {} objects", seen.len().to_string().cyan());
+    assert_eq!(seen, expected, "Only objects inside the view should be returned");
+    println!("{}", "Frustum built from planes culled correctly".green());
+
+    // An orthographic view-projection matrix covering -10..10 on every axis
+    let ortho = Frustum::from_view_projection([
+        [0.1, 0.0, 0.0, 0.0],
+        [0.0, 0.1, 0.0, 0.0],
+        [0.0, 0.0, 0.1, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ]);
+    assert!(ortho.contains_point([9.0, -9.0, 9.0]), "Points inside the box should be visible");
+    assert!(!ortho.contains_point([0.0, 0.0, 11.0]), "Points past the far plane should be culled");
+    println!("{}", "Frustum extracted from a view-projection matrix".green());
+
+    println!("{}", "Frustum query test passed".green());
+    Ok(())
+}
+
+/// Tests that removed objects' custom data allocations are reused.
+fn test_object_pool(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Object Pool ----".blue());
+
+    let config = VaultConfig { object_pool_capacity: 2, ..VaultConfig::default() };
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::with_config(db_path, config)?;
+    let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+
+    // A removed projectile's allocation is handed to the next one
+    let first = Uuid::new_v4();
+    let data = vault_manager.pooled_custom_data(TestCustomData { name: "Bullet".to_string(), value: 1 });
+    let first_ptr = Arc::as_ptr(&data);
+    vault_manager.add_object(region_id, first, "projectile", 1.0, 0.0, 0.0, data)?;
+    vault_manager.remove_object(first)?;
+    assert_eq!(vault_manager.object_pool_stats().pooled, 1, "The removed allocation should be pooled");
+
+    let second = Uuid::new_v4();
+    let data = vault_manager.pooled_custom_data(TestCustomData { name: "Rocket".to_string(), value: 2 });
+    assert_eq!(Arc::as_ptr(&data), first_ptr, "The pooled allocation should be reused");
+    vault_manager.add_object(region_id, second, "projectile", 2.0, 0.0, 0.0, data)?;
+    let object = vault_manager.get_object(second)?.ok_or("Second projectile should exist")?;
+    assert_eq!(object.custom_data.name, "Rocket", "Reused allocations should hold the new data");
+    println!("{}", "Removed allocations are reused".green());
+
+    // Allocations still referenced elsewhere are never pooled
+    drop(object);
+    let shared = vault_manager.pooled_custom_data(TestCustomData { name: "Shared".to_string(), value: 3 });
+    let third = Uuid::new_v4();
+    vault_manager.add_object(region_id, third, "projectile", 3.0, 0.0, 0.0, shared.clone())?;
+    vault_manager.remove_object(third)?;
+    let stats = vault_manager.object_pool_stats();
+    assert_eq!(stats.pooled, 0, "Shared allocations should not be pooled");
+    assert_eq!(stats.reused, 1, "Exactly one allocation should have been reused");
+    assert_eq!(shared.name, "Shared", "Shared data should be untouched");
+    println!("{}", "Shared allocations are left alone".green());
+
+    println!("{}", "Object pool test passed".green());
+    Ok(())
+}
+
+/// Tests that object type names are interned and survive a reload.
+fn test_object_type_interning(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Object Type Interning ----".blue());
+
+    let first = ObjectType::from("interned_type");
+    let second = ObjectType::from("interned_type".to_string());
+    assert_eq!(first, second, "Equal names should produce equal types");
+    assert_eq!(first.as_str().as_ptr(), second.as_str().as_ptr(), "Equal names should share storage");
+    assert_eq!(first, "interned_type", "Types should compare equal to string slices");
+    println!("{}", "Equal names share one interned copy".green());
+
+    let uuids: Vec<Uuid> = (0..3).map(|_| Uuid::new_v4()).collect();
+    let region_id = {
+        let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+        let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+        let data = Arc::new(TestCustomData { name: "Interned".to_string(), value: 1 });
+        for (i, uuid) in uuids.iter().enumerate() {
+            vault_manager.add_object(region_id, *uuid, "interned_type", i as f64, 0.0, 0.0, data.clone())?;
+        }
+        region_id
+    };
+
+    // Objects loaded from disk share the same interned name
+    let registered = ObjectType::registered_count();
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let objects = vault_manager.query_region(region_id, -10.0, -10.0, -10.0, 10.0, 10.0, 10.0)?;
+    assert_eq!(objects.len(), 3, "All objects should reload");
+    assert!(objects.iter().all(|obj| obj.object_type.as_str().as_ptr() == first.as_str().as_ptr()), "Reloaded objects should share the interned name");
+    assert_eq!(ObjectType::registered_count(), registered, "Reloading should not register new types");
+    println!("{}", "Reloaded objects reuse the interned name".green());
+
+    println!("{}", "Object type interning test passed".green());
+    Ok(())
+}
+
+/// Tests raycasts against object bounding boxes.
+fn test_raycast(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Raycasts ----".blue());
+
+    let near_wall = Uuid::new_v4();
+    let far_wall = Uuid::new_v4();
+    let region_id = {
+        let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+        let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+        let data = Arc::new(TestCustomData { name: "Wall".to_string(), value: 1 });
+
+        // Two walls across the x-axis, added out of order, and one crate off to the side
+        for (uuid, x) in [(far_wall, 30.0), (near_wall, 10.0)] {
+            vault_manager.add_spatial_object(region_id, SpatialObject {
+                uuid,
+                object_type: ObjectType::from("building"),
+                point: [x, 0.0, 0.0],
+                custom_data: data.clone(),
+                size: [2.0, 10.0, 10.0],
+                version: 0,
+                velocity: None,
+                orientation: None,
+            })?;
+        }
+        vault_manager.add_spatial_object(region_id, SpatialObject {
+            uuid: Uuid::new_v4(),
+            object_type: ObjectType::from("resource"),
+            point: [20.0, 8.0, 0.0],
+            custom_data: data,
+            size: [1.0, 1.0, 1.0],
+            version: 0,
+            velocity: None,
+            orientation: None,
+        })?;
+        region_id
+    };
+
+    // Sizes are persisted, so a reloaded vault sees the same walls
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let hits = vault_manager.raycast(region_id, [0.0, 0.0, 0.0], [2.0, 0.0, 0.0], 100.0)?;
+    println!("Raycast hit {} objects", hits.len().to_string().cyan());
+    assert_eq!(hits.len(), 2, "The ray should pass both walls and miss the crate");
+    assert_eq!(hits[0].object.uuid, near_wall, "The nearest wall should be hit first");
+    assert!((hits[0].distance - 9.0).abs() < 1e-9, "The ray enters the near wall at its face");
+    assert_eq!(hits[1].object.uuid, far_wall, "The far wall should be hit second");
+    println!("{}", "Hits are ordered by distance".green());
+
+    // Hits beyond the maximum distance are ignored
+    let hits = vault_manager.raycast(region_id, [0.0, 0.0, 0.0], [1.0, 0.0, 0.0], 15.0)?;
+    assert_eq!(hits.len(), 1, "Only the near wall is within range");
+    let hits = vault_manager.raycast(region_id, [0.0, 0.0, 0.0], [-1.0, 0.0, 0.0], 100.0)?;
+    assert!(hits.is_empty(), "A ray pointing away should hit nothing");
+    assert!(vault_manager.raycast(region_id, [0.0, 0.0, 0.0], [0.0, 0.0, 0.0], 100.0).is_err(), "A zero direction should be rejected");
+    println!("{}", "Range and direction are respected".green());
+
+    println!("{}", "Raycast test passed".green());
+    Ok(())
+}
+
+/// Tests finding overlapping objects by their bounding boxes.
+fn test_broad_phase(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Broad Phase ----".blue());
+
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+    let data = Arc::new(TestCustomData { name: "Crate".to_string(), value: 1 });
+
+    // Two overlapping crates, a third touching the second, and a lone one far away
+    let crates: Vec<Uuid> = (0..4).map(|_| Uuid::new_v4()).collect();
+    for (uuid, x) in crates.iter().zip([0.0, 1.5, 3.5, 50.0]) {
+        vault_manager.add_spatial_object(region_id, SpatialObject {
+            uuid: *uuid,
+            object_type: ObjectType::from("building"),
+            point: [x, 0.0, 0.0],
+            custom_data: data.clone(),
+            size: [2.0, 2.0, 2.0],
+            version: 0,
+            velocity: None,
+            orientation: None,
+        })?;
+    }
+
+    let pairs = vault_manager.find_intersections(region_id)?;
+    println!("Found {} overlapping pairs", pairs.len().to_string().cyan());
+    let mut found: Vec<(Uuid, Uuid)> = pairs.iter().map(|(a, b)| (a.uuid, b.uuid)).collect();
+    assert!(found.iter().all(|(a, b)| a < b), "Pairs should be ordered by UUID");
+    found.sort();
+    let mut expected: Vec<(Uuid, Uuid)> = [(crates[0], crates[1]), (crates[1], crates[2])].iter()
+        .map(|&(a, b)| (a.min(b), a.max(b)))
+        .collect();
+    expected.sort();
+    assert_eq!(found, expected, "Only neighbouring crates should overlap");
+    println!("{}", "Each overlapping pair was found once".green());
+
+    // Box queries return objects the box only partly covers, unlike query_region
+    let overlapping = vault_manager.query_overlapping(region_id, [2.0, -1.0, -1.0], [3.0, 1.0, 1.0])?;
+    let mut overlapping: Vec<Uuid> = overlapping.iter().map(|obj| obj.uuid).collect();
+    overlapping.sort();
+    let mut expected = vec![crates[1], crates[2]];
+    expected.sort();
+    assert_eq!(overlapping, expected, "Both crates reaching into the box should be returned");
+    assert!(vault_manager.query_region(region_id, 2.0, -1.0, -1.0, 3.0, 1.0, 1.0)?.is_empty(), "Neither crate lies inside the box");
+    assert!(vault_manager.query_overlapping(region_id, [f64::NAN, 0.0, 0.0], [1.0, 1.0, 1.0]).is_err(), "A NaN box should be rejected");
+    println!("{}", "Box overlap query returned partly covered objects".green());
+
+    println!("{}", "Broad phase test passed".green());
+    Ok(())
+}
+
+/// Tests objects with an orientation, and the overlap queries using their oriented boxes.
+fn test_object_orientation(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Object Orientation ----".blue());
+
+    // A 10 x 1 x 1 plank turned 45 degrees around the vertical axis, and a crate in the corner of its bounding box
+    let half_angle = std::f64::consts::FRAC_PI_8;
+    let rotation = [0.0, half_angle.sin(), 0.0, half_angle.cos()];
+    let plank_id = Uuid::new_v4();
+    let crate_id = Uuid::new_v4();
+    let data = Arc::new(TestCustomData { name: "Plank".to_string(), value: 1 });
+    let plank = SpatialObject {
+        uuid: plank_id,
+        object_type: ObjectType::from("building"),
+        point: [0.0, 0.0, 0.0],
+        custom_data: data.clone(),
+        size: [10.0, 1.0, 1.0],
+        version: 0,
+        velocity: None,
+        orientation: None,
+    }.with_orientation(rotation.map(|value| value * 3.0)).map_err(|e| e.to_string())?;
+    let orientation = plank.orientation.expect("The orientation should be set");
+    assert!(orientation.iter().zip(rotation).all(|(a, b)| (a - b).abs() < 1e-12), "The orientation should be normalized");
+    assert!(plank.clone().with_orientation([0.0; 4]).is_err(), "A zero quaternion should be rejected");
+    assert!(plank.clone().with_orientation([f64::NAN, 0.0, 0.0, 1.0]).is_err(), "A NaN quaternion should be rejected");
+    println!("{}", "Orientations are normalized and validated".green());
+
+    let region_id = {
+        let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+        let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+        vault_manager.add_spatial_object(region_id, plank.clone())?;
+        vault_manager.add_spatial_object(region_id, SpatialObject {
+            uuid: crate_id,
+            object_type: ObjectType::from("building"),
+            point: [3.5, 0.0, 3.5],
+            custom_data: data.clone(),
+            size: [1.0, 1.0, 1.0],
+            version: 0,
+            velocity: None,
+            orientation: None,
+        })?;
+
+        // The crate lies inside the plank's bounding box, but clear of the plank itself
+        assert!(vault_manager.find_intersections(region_id)?.is_empty(), "The crate shouldn't overlap the rotated plank");
+        assert!(vault_manager.query_overlapping(region_id, [3.0, -0.5, 3.0], [4.0, 0.5, 4.0])?.iter().all(|obj| obj.uuid != plank_id), "The box beside the plank shouldn't touch it");
+        let corner = vault_manager.query_overlapping(region_id, [3.0, -0.5, -4.0], [4.0, 0.5, -3.0])?;
+        assert!(corner.iter().any(|obj| obj.uuid == plank_id), "The box at the end of the plank should touch it");
+        println!("{}", "Overlap queries test the rotated box".green());
+
+        // The envelope encloses the rotated plank, which reaches about 3.9 units along x and z
+        let reach = 5.5 * std::f64::consts::FRAC_1_SQRT_2 + 0.01;
+        assert!(vault_manager.query_region(region_id, -3.0, -1.0, -3.0, 3.0, 1.0, 3.0)?.iter().all(|obj| obj.uuid != plank_id), "The rotated plank reaches past the small box");
+        assert!(vault_manager.query_region(region_id, -reach, -0.5, -reach, reach, 0.5, reach)?.iter().any(|obj| obj.uuid == plank_id), "The box enclosing the rotated plank should contain it");
+        println!("{}", "Region queries use the box enclosing the rotated object".green());
+
+        vault_manager.persist_to_disk()?;
+        region_id
+    };
+
+    // The orientation survives a restart, and the database agrees with memory on the enclosing box
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let reloaded = vault_manager.get_object(plank_id)?.expect("The plank should be reloaded");
+    assert!(reloaded == plank, "The plank should keep its orientation across a restart");
+    assert_eq!(vault_manager.remove_in_bbox(region_id, [-3.0, -1.0, -3.0], [3.0, 1.0, 3.0])?, 0, "The rotated plank doesn't fit in the small box");
+    assert_eq!(vault_manager.remove_in_bbox(region_id, [-4.0, -1.0, -4.0], [4.0, 1.0, 4.0])?, 2, "The larger box should hold the plank and the crate");
+    println!("{}", "Orientation persisted and matched by the database".green());
+
+    println!("{}", "Object orientation test passed".green());
+    Ok(())
+}
+
+/// Tests that sorted query orders return the same results however the objects were inserted.
+fn test_query_order(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Query Order ----".blue());
+
+    // A ring of objects at the same distance from the origin, plus a line of objects along x
+    let data = Arc::new(TestCustomData { name: "Ordered".to_string(), value: 5 });
+    let mut objects: Vec<SpatialObject<TestCustomData>> = Vec::new();
+    for position in [[5.0, 0.0, 0.0], [-5.0, 0.0, 0.0], [0.0, 0.0, 5.0], [0.0, 0.0, -5.0], [1.0, 0.0, 0.0], [2.0, 0.0, 0.0], [8.0, 0.0, 0.0]] {
+        objects.push(SpatialObject {
+            uuid: Uuid::new_v4(),
+            object_type: ObjectType::from("resource"),
+            point: position,
+            custom_data: data.clone(),
+            size: [0.0; 3],
+            version: 0,
+            velocity: None,
+            orientation: None,
+        });
+    }
+
+    // Fill a vault in the given order and run the same queries against it
+    let run = |order: QueryOrder, objects: &[SpatialObject<TestCustomData>], name: &str| -> Result<[Vec<Uuid>; 3], String> {
+        let path = format!("{}.{}", db_path, name);
+        let config = VaultConfig { query_order: order, ..VaultConfig::default() };
+        let vault_manager: VaultManager<TestCustomData> = VaultManager::with_config(&path, config)?;
+        let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+        for object in objects {
+            vault_manager.add_spatial_object(region_id, object.clone())?;
+        }
+        let ids = |objects: Vec<SpatialObject<TestCustomData>>| objects.iter().map(|obj| obj.uuid).collect::<Vec<Uuid>>();
+        let in_box = ids(vault_manager.query_region(region_id, -10.0, -10.0, -10.0, 10.0, 10.0, 10.0)?);
+        let in_radius = ids(vault_manager.query_radius(region_id, [0.0, 0.0, 0.0], 6.0)?);
+        let nearest = ids(vault_manager.query_k_nearest(region_id, [0.0, 0.0, 0.0], 4)?);
+        let mut paged = Vec::new();
+        for offset in (0..objects.len()).step_by(3) {
+            paged.extend(ids(vault_manager.query_region_page(region_id, [-10.0; 3], [10.0; 3], offset, 3)?));
+        }
+        assert_eq!(paged, in_box, "Pages should follow the order of the full query");
+        Ok([in_box, in_radius, nearest])
+    };
+    let reversed: Vec<SpatialObject<TestCustomData>> = objects.iter().rev().cloned().collect();
+
+    // Sorted by UUID
+    let forward = run(QueryOrder::Uuid, &objects, "uuid_forward")?;
+    let backward = run(QueryOrder::Uuid, &reversed, "uuid_backward")?;
+    assert!(forward == backward, "Insertion order shouldn't change UUID-ordered results");
+    let mut sorted: Vec<Uuid> = objects.iter().map(|obj| obj.uuid).collect();
+    sorted.sort();
+    assert_eq!(forward[0], sorted, "Box query results should be sorted by UUID");
+    println!("{}", "UUID order is independent of insertion order".green());
+
+    // Sorted by distance, ties broken by UUID
+    let forward = run(QueryOrder::Distance, &objects, "distance_forward")?;
+    let backward = run(QueryOrder::Distance, &reversed, "distance_backward")?;
+    assert!(forward == backward, "Insertion order shouldn't change distance-ordered results");
+    let mut ring: Vec<Uuid> = objects[..4].iter().map(|obj| obj.uuid).collect();
+    ring.sort();
+    let mut expected = vec![objects[4].uuid, objects[5].uuid];
+    expected.extend(ring.iter().copied());
+    assert_eq!(forward[1], expected, "Radius query results should be sorted by distance, then UUID");
+    assert_eq!(forward[2], expected[..4].to_vec(), "Ties at the k-th nearest object should be broken by UUID");
+    expected.push(objects[6].uuid);
+    assert_eq!(forward[0], expected, "Box query results should be sorted by distance from the box center");
+    println!("{}", "Distance order breaks ties by UUID".green());
+
+    println!("{}", "Query order test passed".green());
+    Ok(())
+}
+
+/// Tests object-type and predicate filtered region queries.
+fn test_type_filtered_queries(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Type Filtered Queries ----".blue());
+
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+
+    let player = Uuid::new_v4();
+    let moved_player = Uuid::new_v4();
+    let data = Arc::new(TestCustomData { name: "Filter".to_string(), value: 3 });
+    vault_manager.add_object(region_id, player, "player", 1.0, 1.0, 1.0, data.clone())?;
+    vault_manager.add_object(region_id, moved_player, "player", 2.0, 2.0, 2.0, data.clone())?;
+    vault_manager.add_object(region_id, Uuid::new_v4(), "building", 3.0, 3.0, 3.0, data.clone())?;
+    vault_manager.add_object(region_id, Uuid::new_v4(), "resource", 4.0, 4.0, 4.0, Arc::new(TestCustomData { name: "Ore".to_string(), value: 7 }))?;
+
+    let players = vault_manager.query_region_by_type(region_id, [0.0, 0.0, 0.0], [10.0, 10.0, 10.0], "player")?;
+    assert_eq!(players.len(), 2, "Both players should be returned");
+    assert!(players.iter().all(|obj| obj.object_type == "player"), "Only players should be returned");
+    let vehicles = vault_manager.query_region_by_type(region_id, [0.0, 0.0, 0.0], [10.0, 10.0, 10.0], "vehicle")?;
+    assert!(vehicles.is_empty(), "Unknown types should return nothing");
+    println!("{}", "Type query returned only matching objects".green());
+
+    // The type index follows updates and removals
+    let mut object = vault_manager.get_object(moved_player)?.ok_or("Player should exist")?;
+    object.point = [50.0, 50.0, 50.0];
+    vault_manager.update_object(&object)?;
+    vault_manager.remove_object(player)?;
+    let players = vault_manager.query_region_by_type(region_id, [0.0, 0.0, 0.0], [10.0, 10.0, 10.0], "player")?;
+    assert!(players.is_empty(), "Moved and removed players should leave the type index");
+    println!("{}", "Type index follows updates and removals".green());
+
+    // General filters run before objects are cloned
+    let valuable = vault_manager.query_region_filtered(region_id, [0.0, 0.0, 0.0], [10.0, 10.0, 10.0], |obj| obj.custom_data.value > 5)?;
+    assert_eq!(valuable.len(), 1, "Only the resource should pass the filter");
+    assert_eq!(valuable[0].object_type, "resource", "The filtered object should be the resource");
+    let ore = vault_manager.query_region_by_data(region_id, [0.0, 0.0, 0.0], [10.0, 10.0, 10.0], |data| data.name == "Ore")?;
+    assert_eq!(ore.len(), 1, "Only the ore should match the custom data predicate");
+    assert_eq!(ore[0].custom_data.value, 7, "The matching object should be the ore");
+    println!("{}", "Filtered query applied the predicate".green());
+
+    println!("{}", "Type filtered query test passed".green());
+    Ok(())
+}
+
+/// Tests registering object types and validating objects against them.
+fn test_object_type_registry(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Object Type Registry ----".blue());
+
+    let config = VaultConfig { reject_unknown_types: true, ..VaultConfig::default() };
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::with_config(db_path, config)?;
+    let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+    let data = Arc::new(TestCustomData { name: "Typed".to_string(), value: 4 });
+
+    // The built-in types are registered, others are rejected until they're registered
+    assert!(["player", "building", "resource"].iter().all(|name| vault_manager.object_type(name).is_some()), "The built-in types should be registered");
+    assert!(vault_manager.add_object(region_id, Uuid::new_v4(), "npc", 1.0, 0.0, 0.0, data.clone()).is_err(), "Unknown types should be rejected");
+    vault_manager.register_object_type("npc");
+    vault_manager.add_object(region_id, Uuid::new_v4(), "npc", 1.0, 0.0, 0.0, data.clone())?;
+    println!("{}", "Unknown types were rejected until registered".green());
+
+    // Every path adding or changing objects checks the definition
+    vault_manager.register_object_type(TypeDefinition::new("vehicle").with_max_size([4.0, 2.0, 6.0]));
+    let vehicle = |size| SpatialObject::new(Uuid::new_v4(), "vehicle", [5.0, 0.0, 0.0], data.clone())
+        .and_then(|obj| obj.with_size(size))
+        .map_err(|e| e.to_string());
+    assert!(vault_manager.add_spatial_object(region_id, vehicle([4.0, 3.0, 6.0])?).is_err(), "Oversized objects should be rejected");
+    assert!(vault_manager.add_objects(region_id, vec![vehicle([1.0; 3])?, vehicle([5.0, 1.0, 1.0])?]).is_err(), "Bulk inserts should be checked");
+    let car = vehicle([2.0, 1.5, 4.0])?;
+    vault_manager.add_spatial_object(region_id, car.clone())?;
+    let mut grown = vault_manager.get_object(car.uuid)?.ok_or("The car should exist")?;
+    grown.size = [2.0, 1.5, 8.0];
+    assert!(vault_manager.update_object(&grown).is_err(), "Updates should be checked");
+    grown.object_type = ObjectType::from("spaceship");
+    assert!(vault_manager.apply_batch(vec![Mutation::Update { object: grown }]).is_err(), "Batches should be checked");
+    println!("{}", "Objects breaking their type's rules were rejected".green());
+
+    // Per-type helpers
+    vault_manager.add_object(region_id, Uuid::new_v4(), "npc", 2.0, 0.0, 0.0, data.clone())?;
+    let counts = vault_manager.count_by_type(region_id)?;
+    assert_eq!((counts.get("npc").copied(), counts.get("vehicle").copied()), (Some(2), Some(1)), "Counts should be kept per type");
+    assert!(!counts.contains_key("player"), "Absent types shouldn't be counted");
+    let npcs = vault_manager.query_all_by_type([-10.0; 3], [10.0; 3], "npc")?;
+    assert_eq!(npcs.len(), 2, "Both NPCs should be found across regions");
+
+    // A sized object outside its region's bounds is matched like `query_all` matches it
+    let parked = SpatialObject::new(Uuid::new_v4(), "vehicle", [150.0, 0.0, 0.0], data.clone())
+        .and_then(|obj| obj.with_size([2.0, 1.5, 4.0]))
+        .map_err(|e| e.to_string())?;
+    vault_manager.add_spatial_object(region_id, parked.clone())?;
+    for (min, max, expected) in [([140.0, -5.0, -5.0], [160.0, 5.0, 5.0], true), ([150.5, -5.0, -5.0], [160.0, 5.0, 5.0], false)] {
+        let typed = vault_manager.query_all_by_type(min, max, "vehicle")?;
+        let all = vault_manager.query_all(min[0], min[1], min[2], max[0], max[1], max[2])?;
+        assert_eq!(typed.iter().any(|obj| obj.uuid == parked.uuid), expected, "The typed query should match the parked vehicle's extent");
+        assert_eq!(all.iter().any(|obj| obj.uuid == parked.uuid), expected, "query_all should agree with the typed query");
+    }
+    println!("{}", "Objects were counted and queried by type".green());
+
+    // Unregistered types keep their objects but can't be added again
+    assert!(vault_manager.unregister_object_type("npc").is_some(), "The type should be unregistered");
+    assert!(vault_manager.add_object(region_id, Uuid::new_v4(), "npc", 3.0, 0.0, 0.0, data).is_err(), "The unregistered type should be rejected");
+    assert_eq!(vault_manager.count_by_type(region_id)?.get("npc").copied(), Some(2), "Existing objects should stay");
+    println!("{}", "Unregistering a type kept its objects".green());
+
+    println!("{}", "Object type registry test passed".green());
+    Ok(())
+}
+
+/// Tests rejecting malformed custom data with schemas and validation closures.
+fn test_custom_data_validation(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Custom Data Validation ----".blue());
+
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+
+    // Malformed schemas are refused
+    assert!(DataSchema::parse(serde_json::json!({"type": "integerish"})).is_err(), "Unknown type names should be refused");
+    assert!(DataSchema::parse(serde_json::json!({"required": "name"})).is_err(), "Malformed keywords should be refused");
+
+    // Custom data must match the schema of its type
+    let schema = DataSchema::parse(serde_json::json!({
+        "type": "object",
+        "required": ["name", "value"],
+        "additionalProperties": false,
+        "properties": {
+            "name": { "type": "string", "minLength": 1, "maxLength": 16 },
+            "value": { "type": "integer", "minimum": 0, "maximum": 100 }
+        }
+    }))?;
+    vault_manager.register_object_type(TypeDefinition::new("player").with_schema(schema.clone()));
+    let player = |name: &str, value| Arc::new(TestCustomData { name: name.to_string(), value });
+    vault_manager.add_object(region_id, Uuid::new_v4(), "player", 1.0, 0.0, 0.0, player("Alice", 10))?;
+    let error = vault_manager.add_object(region_id, Uuid::new_v4(), "player", 2.0, 0.0, 0.0, player("Bob", 250)).unwrap_err();
+    assert!(error.contains("data.value"), "The error should name the offending field: {}", error);
+    assert!(vault_manager.add_object(region_id, Uuid::new_v4(), "player", 3.0, 0.0, 0.0, player("", 5)).is_err(), "Empty names should be rejected");
+    assert!(schema.validate(&serde_json::json!({"name": "Eve", "value": 1, "extra": true})).is_err(), "Unexpected fields should be rejected");
+    assert!(schema.validate(&serde_json::json!({"name": "Eve"})).is_err(), "Missing fields should be rejected");
+    println!("{}", "Custom data was validated against the schema".green());
+
+    // Closures see the data as JSON and can enforce anything else
+    vault_manager.register_object_type(TypeDefinition::new("resource").with_validator(|data| {
+        match data["name"].as_str() {
+            Some("Ore" | "Wood") => Ok(()),
+            _ => Err("unknown resource".to_string()),
+        }
+    }));
+    let ore = Uuid::new_v4();
+    vault_manager.add_object(region_id, ore, "resource", 4.0, 0.0, 0.0, player("Ore", 3))?;
+    let error = vault_manager.add_object(region_id, Uuid::new_v4(), "resource", 5.0, 0.0, 0.0, player("Gold", 3)).unwrap_err();
+    assert!(error.contains("unknown resource"), "The validator's reason should be reported: {}", error);
+
+    // Updates are validated too, and rejected ones leave the stored object untouched
+    let mut object = vault_manager.get_object(ore)?.ok_or("The ore should exist")?;
+    object.custom_data = player("Gold", 3);
+    assert!(vault_manager.update_object(&object).is_err(), "Invalid updates should be rejected");
+    let stored = vault_manager.get_object(ore)?.ok_or("The ore should still exist")?;
+    assert_eq!(stored.custom_data.name, "Ore", "The rejected update shouldn't be applied");
+    let players = vault_manager.query_region_by_type(region_id, [-10.0; 3], [10.0; 3], "player")?;
+    assert_eq!(players.len(), 1, "Only the valid player should be stored");
+    println!("{}", "Custom data was validated by the closure".green());
+
+    println!("{}", "Custom data validation test passed".green());
+    Ok(())
+}
+
+
+/// Tests parsing filter expressions and using them in queries.
+fn test_filter_expressions(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Filter Expressions ----".blue());
+
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+    let veteran = Uuid::new_v4();
+    vault_manager.add_object(region_id, veteran, "player", 10.0, 0.0, 0.0, Arc::new(TestCustomData { name: "Veteran".to_string(), value: 12 }))?;
+    vault_manager.add_object(region_id, Uuid::new_v4(), "player", 90.0, 0.0, 0.0, Arc::new(TestCustomData { name: "Far".to_string(), value: 15 }))?;
+    vault_manager.add_object(region_id, Uuid::new_v4(), "player", 5.0, 5.0, 0.0, Arc::new(TestCustomData { name: "Novice".to_string(), value: 2 }))?;
+    vault_manager.add_object(region_id, Uuid::new_v4(), "building", 0.0, 0.0, 0.0, Arc::new(TestCustomData { name: "Hall".to_string(), value: 50 }))?;
+
+    let query = |source: &str| -> Result<Vec<SpatialObject<TestCustomData>>, String> {
+        let filter = Filter::parse(source).map_err(|e| e.to_string())?;
+        vault_manager.query_region_where(region_id, [-100.0; 3], [100.0; 3], &filter)
+    };
+
+    let found = query(r#"type == "player" && data.value >= 10 && dist(point, [0, 0, 0]) < 50"#)?;
+    assert_eq!(found.len(), 1, "Only the nearby veteran should match");
+    assert_eq!(found[0].uuid, veteran, "The veteran should match");
+    assert_eq!(query(r#"type != "player" || data.name == "Novice""#)?.len(), 2, "Alternatives should be combined");
+    assert_eq!(query("!(x > 1) && point.y == 0")?.len(), 1, "Negation and components should apply");
+    assert_eq!(query("abs(-x) + max(y, 1) * 2 >= 12")?.len(), 3, "Arithmetic should follow precedence");
+    println!("{}", "Filters selected the expected objects".green());
+
+    // Missing fields and mismatched kinds never match
+    assert!(query("data.missing == 1")?.is_empty(), "Missing fields should not match");
+    assert!(query(r#"data.name > 3"#)?.is_empty(), "Comparing a string with a number should not match");
+    assert_eq!(query("data.missing == null")?.len(), 4, "Missing fields should equal null");
+    println!("{}", "Mismatched values were rejected".green());
+
+    // Malformed expressions are rejected with a position
+    for source in ["type ==", "data.", "level > 1", "dist(point)", r#"type == "player"#, "x > 1 )", "x $ 1"] {
+        assert!(Filter::parse(source).is_err(), "'{}' should not parse", source);
+    }
+    let error = Filter::parse("x > 1 )").unwrap_err();
+    assert_eq!(error.position, 6, "The error should point at the stray parenthesis");
+    let deep = format!("{}x{}", "(".repeat(1000), ")".repeat(1000));
+    assert!(Filter::parse(&deep).is_err(), "Deeply nested expressions should be rejected");
+    println!("{}", "Malformed filters were rejected".green());
+
+    println!("{}", "Filter expression test passed".green());
+    Ok(())
+}
+
+/// Tests adding many objects in a single call.
+fn test_bulk_insert(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Bulk Insert ----".blue());
+
+    let make_objects = |count: usize, object_type: &str, offset: f64| -> Vec<SpatialObject<TestCustomData>> {
+        (0..count)
+            .map(|i| SpatialObject {
+                uuid: Uuid::new_v4(),
+                object_type: ObjectType::from(object_type),
+                point: [offset + i as f64 * 0.01, 0.0, 0.0],
+                custom_data: Arc::new(TestCustomData { name: format!("Bulk {}", i), value: i as i32 }),
+                size: [0.0, 0.0, 0.0],
+                version: 0,
+                velocity: None,
+                orientation: None,
+            })
+            .collect()
+    };
+
+    let region_id = {
+        let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+        let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+
+        // The first batch goes into an empty region and is bulk loaded
+        vault_manager.add_objects(region_id, make_objects(1000, "resource", 0.0))?;
+        // The second batch is merged into the existing index
+        vault_manager.add_objects(region_id, make_objects(200, "player", 50.0))?;
+
+        let all = vault_manager.query_region(region_id, -100.0, -100.0, -100.0, 100.0, 100.0, 100.0)?;
+        assert_eq!(all.len(), 1200, "Every object should be indexed");
+        let players = vault_manager.query_region_by_type(region_id, [-100.0, -100.0, -100.0], [100.0, 100.0, 100.0], "player")?;
+        assert_eq!(players.len(), 200, "The type index should cover bulk inserts");
+        println!("{}", "Bulk inserted objects are indexed".green());
+        region_id
+    };
+
+    // Every object was written to the persistent database
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    assert_eq!(vault_manager.load_report().total_points(), 1200, "Every object should reload");
+    let resources = vault_manager.query_region_by_type(region_id, [-100.0, -100.0, -100.0], [100.0, 100.0, 100.0], "resource")?;
+    assert_eq!(resources.len(), 1000, "Reloaded resources should be indexed by type");
+    println!("{}", "Bulk inserted objects were persisted".green());
+
+    println!("{}", "Bulk insert test passed".green());
+    Ok(())
+}
+
+/// Tests adding objects without naming their region.
+fn test_add_object_auto(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Automatic Region Resolution ----".blue());
+
+    let data = Arc::new(TestCustomData { name: "Auto".to_string(), value: 8 });
+    let config = VaultConfig { auto_region_radius: Some(50.0), ..VaultConfig::default() };
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::with_config(db_path, config)?;
+    let home = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 50.0)?;
+
+    // Positions inside an existing region use it
+    let tree = Uuid::new_v4();
+    let region_id = vault_manager.add_object_auto(tree, "tree", [10.0, 0.0, -20.0], [1.0, 8.0, 1.0], data.clone())?;
+    assert_eq!(region_id, home, "The existing region should be used");
+    let found = vault_manager.get_object(tree)?.ok_or("The object should be stored")?;
+    assert_eq!(found.size, [1.0, 8.0, 1.0], "The size should be kept");
+    println!("{}", "Object was added to the containing region".green());
+
+    // Positions outside every region get a new one on the grid
+    let far = vault_manager.add_object_auto(Uuid::new_v4(), "tree", [230.0, 10.0, 0.0], [0.0; 3], data.clone())?;
+    let region = vault_manager.get_region(far).ok_or("The new region should exist")?;
+    assert_eq!(region.lock().unwrap().center, [200.0, 0.0, 0.0], "The new region should be centered on the grid");
+    let again = vault_manager.add_object_auto(Uuid::new_v4(), "tree", [180.0, -40.0, 40.0], [0.0; 3], data.clone())?;
+    assert_eq!(again, far, "Nearby positions should reuse the created region");
+    println!("{}", "Regions were created on demand".green());
+
+    assert!(vault_manager.add_object_auto(Uuid::new_v4(), "tree", [f64::NAN, 0.0, 0.0], [0.0; 3], data.clone()).is_err(), "Invalid positions should be rejected");
+
+    // Without an automatic radius, uncovered positions are rejected
+    drop(vault_manager);
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    assert!(vault_manager.add_object_auto(Uuid::new_v4(), "tree", [5000.0, 0.0, 0.0], [0.0; 3], data.clone()).is_err(), "Uncovered positions should be rejected");
+    assert_eq!(vault_manager.add_object_auto(Uuid::new_v4(), "tree", [1.0, 1.0, 1.0], [0.0; 3], data)?, home, "Loaded regions should be found");
+    println!("{}", "Uncovered positions were rejected without an automatic radius".green());
+
+    println!("{}", "Automatic region resolution test passed".green());
+    Ok(())
+}
+
+/// Tests splitting regions that hold too many objects.
+fn test_region_splitting(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Region Splitting ----".blue());
+
+    let data = Arc::new(TestCustomData { name: "Split".to_string(), value: 6 });
+    let config = VaultConfig { max_objects_per_region: Some(4), ..VaultConfig::default() };
+    let west_object = Uuid::new_v4();
+    {
+        let vault_manager: VaultManager<TestCustomData> = VaultManager::with_config(db_path, config.clone())?;
+        let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+        vault_manager.add_object(region_id, west_object, "tree", -50.0, -50.0, -50.0, data.clone())?;
+        for x in [10.0, 20.0, 30.0] {
+            vault_manager.add_object(region_id, Uuid::new_v4(), "tree", x, 10.0, 10.0, data.clone())?;
+        }
+        assert!(vault_manager.split_oversized_regions()?.is_empty(), "Regions at the limit should not be split");
+
+        // The fifth object goes over the limit and splits the region
+        let region_id = vault_manager.add_object_auto(Uuid::new_v4(), "tree", [40.0, 10.0, 10.0], [0.0; 3], data.clone())?;
+        assert_eq!(vault_manager.region_count(), 8, "The region should be replaced by eight octants");
+        let region = vault_manager.get_region(region_id).ok_or("The returned region should exist")?;
+        let region = region.lock().unwrap();
+        assert_eq!((region.center, region.radius), ([50.0, 50.0, 50.0], 50.0), "The object should land in the upper octant");
+        assert_eq!(region.rtree.size(), 4, "The upper octant should hold the four nearby objects");
+        println!("{}", "Oversized region was split into octants".green());
+    }
+
+    // The split was written to the persistent database
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::with_config(db_path, config)?;
+    assert_eq!(vault_manager.region_count(), 8, "The octants should be reloaded");
+    assert!(vault_manager.load_report().orphaned_points.is_empty(), "No object should be left without a region");
+    let lower = vault_manager.region_ids().into_iter()
+        .find(|region_id| vault_manager.get_region(*region_id).is_some_and(|region| region.lock().unwrap().center == [-50.0, -50.0, -50.0]))
+        .ok_or("The lower octant should exist")?;
+    assert_eq!(vault_manager.query_radius(lower, [-50.0, -50.0, -50.0], 0.1)?[0].uuid, west_object, "The lower object should be in the lower octant");
+    println!("{}", "Split was persisted".green());
+
+    let octants = vault_manager.split_region(lower)?;
+    assert_eq!(octants.len(), 8, "Splitting should produce eight octants");
+    assert!(vault_manager.get_region(lower).is_none(), "The split region should be gone");
+    assert!(vault_manager.split_region(lower).is_err(), "A removed region can't be split again");
+    println!("{}", "Regions can be split explicitly".green());
+
+    println!("{}", "Region splitting test passed".green());
+    Ok(())
+}
+
+/// Tests merging several regions into one.
+fn test_merge_regions(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Region Merging ----".blue());
+
+    let data = Arc::new(TestCustomData { name: "Merge".to_string(), value: 2 });
+    let (merged, kept) = {
+        let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+        let west = vault_manager.create_or_load_region([-50.0, 0.0, 0.0], 50.0)?;
+        let east = vault_manager.create_or_load_region([50.0, 0.0, 0.0], 50.0)?;
+        let kept = vault_manager.create_or_load_region([500.0, 0.0, 0.0], 50.0)?;
+        vault_manager.add_object(west, Uuid::new_v4(), "tree", -40.0, 0.0, 0.0, data.clone())?;
+        vault_manager.add_object(east, Uuid::new_v4(), "tree", 40.0, 0.0, 0.0, data.clone())?;
+        vault_manager.add_object(kept, Uuid::new_v4(), "tree", 500.0, 0.0, 0.0, data.clone())?;
+
+        assert!(vault_manager.merge_regions(&[], [0.0; 3], 100.0).is_err(), "Merging nothing should fail");
+        assert!(vault_manager.merge_regions(&[west, west], [0.0; 3], 100.0).is_err(), "Duplicate regions should be rejected");
+        assert!(vault_manager.merge_regions(&[west, Uuid::new_v4()], [0.0; 3], 100.0).is_err(), "Unknown regions should be rejected");
+        assert_eq!(vault_manager.region_count(), 3, "Failed merges should change nothing");
+
+        let merged = vault_manager.merge_regions(&[west, east], [0.0, 0.0, 0.0], 100.0)?;
+        assert!(vault_manager.get_region(west).is_none() && vault_manager.get_region(east).is_none(), "Merged regions should be removed");
+        assert_eq!(vault_manager.query_radius(merged, [0.0, 0.0, 0.0], 100.0)?.len(), 2, "The new region should hold both objects");
+        println!("{}", "Regions were merged in memory".green());
+        (merged, kept)
+    };
+
+    // The merge was written to the persistent database
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    assert_eq!(vault_manager.region_count(), 2, "Only the merged and untouched regions should be reloaded");
+    assert_eq!(vault_manager.query_radius(merged, [0.0, 0.0, 0.0], 100.0)?.len(), 2, "The merged objects should be reloaded");
+    assert_eq!(vault_manager.query_radius(kept, [500.0, 0.0, 0.0], 1.0)?.len(), 1, "Other regions should be untouched");
+    assert!(vault_manager.load_report().orphaned_points.is_empty(), "No object should be left without a region");
+    println!("{}", "Merge was persisted".green());
+
+    println!("{}", "Region merging test passed".green());
+    Ok(())
+}
+
+/// Tests deleting regions, restricted and cascading.
+fn test_delete_region(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Region Deletion ----".blue());
+
+    let data = Arc::new(TestCustomData { name: "Doomed".to_string(), value: 0 });
+    let (kept, kept_object) = {
+        let config = VaultConfig { object_pool_capacity: 8, ..VaultConfig::default() };
+        let vault_manager: VaultManager<TestCustomData> = VaultManager::with_config(db_path, config)?;
+        let empty = vault_manager.create_or_load_region([500.0, 0.0, 0.0], 50.0)?;
+        let full = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 50.0)?;
+        let kept = vault_manager.create_or_load_region([-500.0, 0.0, 0.0], 50.0)?;
+        let kept_object = Uuid::new_v4();
+        vault_manager.add_object(kept, kept_object, "tree", -500.0, 0.0, 0.0, data.clone())?;
+        for x in [1.0, 2.0] {
+            let custom_data = vault_manager.pooled_custom_data(TestCustomData { name: "Doomed".to_string(), value: x as i32 });
+            vault_manager.add_object(full, Uuid::new_v4(), "tree", x, 0.0, 0.0, custom_data)?;
+        }
+
+        assert_eq!(vault_manager.delete_region(empty, false)?, 0, "Empty regions can be deleted without cascading");
+        assert!(vault_manager.delete_region(full, false).is_err(), "Regions with objects need a cascading delete");
+        assert_eq!(vault_manager.query_radius(full, [0.0, 0.0, 0.0], 10.0)?.len(), 2, "A refused delete should change nothing");
+        assert_eq!(vault_manager.delete_region(full, true)?, 2, "Cascading should delete the objects");
+        assert!(vault_manager.get_region(full).is_none(), "The region should be gone");
+        assert!(vault_manager.delete_region(full, true).is_err(), "Deleting twice should fail");
+        assert_eq!(vault_manager.object_pool_stats().pooled, 2, "Deleted objects' custom data should be pooled");
+        println!("{}", "Regions were deleted in memory".green());
+        (kept, kept_object)
+    };
+
+    // The deletes were written to the persistent database
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    assert_eq!(vault_manager.region_count(), 1, "Only the untouched region should be reloaded");
+    assert_eq!(vault_manager.query_radius(kept, [-500.0, 0.0, 0.0], 1.0)?[0].uuid, kept_object, "Other regions should keep their objects");
+    assert!(vault_manager.load_report().orphaned_points.is_empty(), "Cascading should leave no orphaned points");
+    assert_eq!(vault_manager.database().count_blobs().map_err(|e| e.to_string())?, 1, "Deleted objects' data files should be released");
+    println!("{}", "Deletes were persisted".green());
+
+    println!("{}", "Region deletion test passed".green());
+    Ok(())
+}
+
+/// Tests removing every object in a box, of a type or of a region at once.
+fn test_bulk_remove(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Bulk Removal ----".blue());
+
+    let region_id = {
+        let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+        let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+        let data = Arc::new(TestCustomData { name: "Arena".to_string(), value: 1 });
+        for i in 0..100 {
+            vault_manager.add_object(region_id, Uuid::new_v4(), "resource", (i % 20) as f64, 0.0, (i / 20) as f64, data.clone())?;
+        }
+        for i in 0..10 {
+            vault_manager.add_object(region_id, Uuid::new_v4(), "building", 50.0 + i as f64, 0.0, 0.0, data.clone())?;
+        }
+
+        // An unpersisted update moves a building into the arena, where it's removed with the rest
+        let building = vault_manager.query_region_by_type(region_id, [-100.0; 3], [100.0; 3], "building")?[0].clone();
+        let mut moved = building.clone();
+        moved.point = [5.0, 0.0, 2.5];
+        vault_manager.update_object(&moved)?;
+
+        let before = vault_manager.io_stats();
+        let removed = vault_manager.remove_in_bbox(region_id, [-1.0, -1.0, -1.0], [9.5, 1.0, 10.0])?;
+        let statements = vault_manager.io_stats().since(&before).statements;
+        println!("Removed {} objects with {} statements", removed.to_string().cyan(), statements.to_string().cyan());
+        assert_eq!(removed, 51, "Half the resources and the moved building should be removed");
+        assert!(statements < 15, "The objects should be removed in bulk, not one by one");
+        assert!(vault_manager.get_object(building.uuid)?.is_none(), "The moved building should be gone");
+        assert_eq!(vault_manager.count_in_region(region_id, [-1.0; 3], [9.5, 1.0, 10.0])?, 0, "The box should be empty");
+        println!("{}", "Objects in the box were removed".green());
+
+        let removed = vault_manager.remove_by_type(region_id, "building")?;
+        assert_eq!(removed, 9, "The remaining buildings should be removed");
+        assert_eq!(vault_manager.remove_by_type(region_id, "vehicle")?, 0, "Types without objects remove nothing");
+        assert!(!vault_manager.count_by_type(region_id)?.contains_key("building"), "No building should be left");
+        println!("{}", "Objects of the type were removed".green());
+        region_id
+    };
+
+    // The database agrees once the vault is reopened
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    assert_eq!(vault_manager.load_report().total_points(), 50, "Only the resources outside the box should be stored");
+    assert_eq!(vault_manager.count_by_type(region_id)?.get("resource").copied(), Some(50));
+    println!("{}", "Bulk removals were persisted".green());
+
+    // Clearing the region removes everything live in bulk, and keeps soft-deleted objects restorable
+    let hidden = vault_manager.query_region(region_id, -100.0, -100.0, -100.0, 100.0, 100.0, 100.0)?[0].uuid;
+    vault_manager.soft_remove_object(hidden)?;
+    let before = vault_manager.io_stats();
+    assert_eq!(vault_manager.clear_region(region_id)?, 49, "Every live object should be removed");
+    assert!(vault_manager.io_stats().since(&before).statements < 15, "The region should be cleared in bulk, not one by one");
+    assert!(vault_manager.region_ids().contains(&region_id), "The region itself should be kept");
+    assert_eq!(vault_manager.clear_region(region_id)?, 0, "An empty region has nothing to clear");
+    vault_manager.restore_object(hidden)?;
+    assert_eq!(vault_manager.query_region(region_id, -100.0, -100.0, -100.0, 100.0, 100.0, 100.0)?.len(), 1, "The soft-deleted object should be restorable");
+    println!("{}", "Region was cleared".green());
+
+    println!("{}", "Bulk removal test passed".green());
+    Ok(())
+}
+
+/// Tests that soft-deleted objects are hidden, can be restored across restarts, and are purged for good.
+fn test_soft_delete(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Soft Delete ----".blue());
+
+    let (chest, statue) = (Uuid::new_v4(), Uuid::new_v4());
+    {
+        let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+        let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+        let data = Arc::new(TestCustomData { name: "Original".to_string(), value: 1 });
+        vault_manager.add_object(region_id, chest, "building", 1.0, 1.0, 1.0, data.clone())?;
+        vault_manager.add_object(region_id, statue, "building", 2.0, 2.0, 2.0, data)?;
+
+        // An unpersisted change is kept with the deleted object
+        let mut object = vault_manager.get_object(chest)?.ok_or("The chest should exist")?;
+        object.custom_data = Arc::new(TestCustomData { name: "Changed".to_string(), value: 2 });
+        vault_manager.update_object(&object)?;
+
+        vault_manager.soft_remove_object(chest)?;
+        assert!(vault_manager.get_object(chest)?.is_none(), "A soft-deleted object should be hidden");
+        assert_eq!(vault_manager.query_region(region_id, 0.0, 0.0, 0.0, 3.0, 3.0, 3.0)?.len(), 1, "Queries should skip soft-deleted objects");
+        assert!(vault_manager.soft_remove_object(chest).is_err(), "An object can only be soft-deleted once");
+        println!("{}", "Soft-deleted object was hidden".green());
+
+        assert_eq!(vault_manager.restore_object(chest)?, region_id, "The chest should return to its region");
+        let restored = vault_manager.get_object(chest)?.ok_or("The restored chest should exist")?;
+        assert_eq!(restored.custom_data.name, "Changed", "The latest change should be restored");
+        assert!(vault_manager.restore_object(chest).is_err(), "A live object can't be restored");
+        println!("{}", "Soft-deleted object was restored".green());
+
+        vault_manager.soft_remove_object(statue)?;
+    }
+
+    // Soft deletion survives a restart and can still be undone
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    assert!(vault_manager.get_object(statue)?.is_none(), "A soft-deleted object should not be loaded");
+    vault_manager.restore_object(statue)?;
+    assert!(vault_manager.get_object(statue)?.is_some(), "The statue should be restored after a restart");
+    println!("{}", "Soft deletion survived a restart".green());
+
+    vault_manager.soft_remove_object(chest)?;
+    assert_eq!(vault_manager.purge_deleted_objects(Duration::from_secs(3600))?, 0, "Recently deleted objects should be kept");
+    assert_eq!(vault_manager.purge_deleted_objects(Duration::ZERO)?, 1, "The chest should be purged");
+    assert!(vault_manager.restore_object(chest).is_err(), "A purged object can't be restored");
+    println!("{}", "Purged object was removed for good".green());
+
+    println!("{}", "Soft delete test passed".green());
+    Ok(())
+}
+
+/// Tests saving region templates and placing them into regions.
+fn test_region_templates(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Region Templates ----".blue());
+
+    let data = TestCustomData { name: "Outpost".to_string(), value: 7 };
+    let outpost = RegionTemplate::new("outpost")
+        .with_object("building", [0.0, 0.0, 0.0], [10.0, 5.0, 4.0], data.clone())
+        .with_object("npc", [3.0, 0.0, 2.0], [0.0, 0.0, 0.0], data.clone());
+    {
+        let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+        vault_manager.save_template(&outpost)?;
+        assert_eq!(vault_manager.templates()?, vec![(outpost.id, "outpost".to_string())], "The template should be listed");
+        assert_eq!(vault_manager.load_template(outpost.id)?, Some(outpost.clone()), "The template should load unchanged");
+        println!("{}", "Template was saved and loaded".green());
+
+        let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 500.0)?;
+        let placed = vault_manager.instantiate_template(outpost.id, region_id, Transform::translate([100.0, 10.0, -50.0]))?;
+        assert_eq!(placed.len(), 2, "Every object of the template should be placed");
+        let npc = vault_manager.get_object(placed[1])?.ok_or("The placed NPC should exist")?;
+        assert_eq!(npc.point, [103.0, 10.0, -48.0], "Offsets should be translated");
+        assert_eq!(npc.object_type, "npc", "The object type should be kept");
+        assert_eq!(*npc.custom_data, data, "The custom data should be kept");
+        println!("{}", "Translated template was placed".green());
+
+        // A quarter turn swaps the footprint of the building and rotates the offsets
+        let rotated = vault_manager.instantiate_template(outpost.id, region_id, Transform::translate([0.0, 0.0, 0.0]).with_yaw(std::f64::consts::FRAC_PI_2))?;
+        assert!(rotated.iter().all(|id| !placed.contains(id)), "Each placement should get new object IDs");
+        let building = vault_manager.get_object(rotated[0])?.ok_or("The rotated building should exist")?;
+        assert!((building.size[0] - 4.0).abs() < 1e-9 && (building.size[2] - 10.0).abs() < 1e-9, "The size should follow the rotation");
+        let npc = vault_manager.get_object(rotated[1])?.ok_or("The rotated NPC should exist")?;
+        assert!((npc.point[0] - 2.0).abs() < 1e-9 && (npc.point[2] + 3.0).abs() < 1e-9, "The offsets should be rotated");
+        println!("{}", "Rotated template was placed".green());
+
+        // Templates can also be captured from the objects of a region
+        let captured = vault_manager.capture_template(region_id, [100.0, 10.0, -50.0], "captured")?;
+        assert_eq!(captured.objects.len(), 4, "Every object of the region should be captured");
+        assert!(captured.objects.iter().any(|obj| obj.offset == [3.0, 0.0, 2.0]), "Offsets should be relative to the origin");
+        assert!(vault_manager.instantiate_template(Uuid::new_v4(), region_id, Transform::default()).is_err(), "Unknown templates should be rejected");
+    }
+
+    // Templates are stored with the world
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    assert!(vault_manager.load_template(outpost.id)?.is_some(), "The template should survive a restart");
+    assert!(vault_manager.delete_template(outpost.id)?, "The template should be deleted");
+    assert!(vault_manager.templates()?.is_empty(), "No templates should remain");
+    assert!(!vault_manager.delete_template(outpost.id)?, "A template can only be deleted once");
+    println!("{}", "Template survived a restart and was deleted".green());
+
+    println!("{}", "Region templates test passed".green());
+    Ok(())
+}
+
+/// Tests that objects with a TTL are swept once they expire, across restarts.
+fn test_object_ttl(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Object TTL ----".blue());
+
+    let data = Arc::new(TestCustomData { name: "Loot".to_string(), value: 3 });
+    let (arrow, loot, kept, picked_up, chest) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+    {
+        let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+        let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+        let object = |uuid, x| SpatialObject::new(uuid, "loot", [x, 0.0, 0.0], data.clone()).map_err(|e| e.to_string());
+        vault_manager.add_object_with_ttl(region_id, object(arrow, 1.0)?, Duration::from_millis(20))?;
+        vault_manager.add_object_with_ttl(region_id, object(loot, 2.0)?, Duration::from_secs(3600))?;
+        vault_manager.add_object_with_ttl(region_id, object(kept, 3.0)?, Duration::from_millis(20))?;
+        vault_manager.add_object_with_ttl(region_id, object(picked_up, 4.0)?, Duration::from_millis(20))?;
+        vault_manager.add_object(region_id, chest, "building", 5.0, 0.0, 0.0, data.clone())?;
+
+        assert!(vault_manager.ttl_remaining(loot).is_some_and(|left| left > Duration::from_secs(3000)), "The loot should have most of its TTL left");
+        assert!(vault_manager.ttl_remaining(chest).is_none(), "Objects added normally should not expire");
+        vault_manager.set_ttl(kept, None)?;
+        assert!(vault_manager.set_ttl(Uuid::new_v4(), Some(Duration::ZERO)).is_err(), "Unknown objects should be rejected");
+        vault_manager.remove_object(picked_up)?;
+        assert!(vault_manager.ttl_remaining(picked_up).is_none(), "A removed object should lose its TTL");
+
+        std::thread::sleep(Duration::from_millis(50));
+        assert_eq!(vault_manager.ttl_remaining(arrow), Some(Duration::ZERO), "An expired object should have no time left");
+        assert_eq!(vault_manager.sweep_expired()?, vec![arrow], "Only the expired arrow should be swept");
+        assert!(vault_manager.get_object(arrow)?.is_none(), "The arrow should be removed");
+        assert!(vault_manager.get_object(kept)?.is_some(), "An object made permanent should stay");
+        assert!(vault_manager.sweep_expired()?.is_empty(), "Nothing else should have expired");
+        println!("{}", "Expired objects were swept".green());
+
+        vault_manager.set_ttl(chest, Some(Duration::from_millis(20)))?;
+        vault_manager.persist_to_disk()?;
+    }
+
+    // Expiry times are stored with the objects, so they survive a restart
+    std::thread::sleep(Duration::from_millis(50));
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    assert!(vault_manager.get_object(arrow)?.is_none(), "A swept object should stay removed");
+    assert!(vault_manager.ttl_remaining(loot).is_some(), "The loot's TTL should survive a restart");
+    assert_eq!(vault_manager.sweep_expired()?, vec![chest], "The chest expired while the vault was closed");
+    assert!(vault_manager.get_object(loot)?.is_some(), "The loot should still be there");
+    println!("{}", "Expiry times survived a restart".green());
+
+    println!("{}", "Object TTL test passed".green());
+    Ok(())
+}
+
+/// Tests that invalid and out-of-world positions are rejected or clamped.
+fn test_world_bounds(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing World Bounds ----".blue());
+
+    let data = Arc::new(TestCustomData { name: "Bounds".to_string(), value: 5 });
+    let bounds = WorldBounds::new([-1000.0, -100.0, -1000.0], [1000.0, 100.0, 1000.0]);
+    {
+        let config = VaultConfig { world_bounds: Some(bounds), ..VaultConfig::default() };
+        let vault_manager: VaultManager<TestCustomData> = VaultManager::with_config(db_path, config)?;
+        let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+
+        // Non-finite coordinates never make it into the index
+        assert!(vault_manager.add_object(region_id, Uuid::new_v4(), "player", f64::NAN, 0.0, 0.0, data.clone()).is_err(), "NaN should be rejected");
+        assert!(vault_manager.add_object(region_id, Uuid::new_v4(), "player", 0.0, f64::INFINITY, 0.0, data.clone()).is_err(), "Infinity should be rejected");
+        assert!(vault_manager.create_or_load_region([f64::NAN, 0.0, 0.0], 10.0).is_err(), "NaN region centers should be rejected");
+        println!("{}", "Non-finite coordinates were rejected".green());
+
+        // Positions outside the world are rejected by default
+        assert!(vault_manager.add_object(region_id, Uuid::new_v4(), "player", 9.46e15, 0.0, 0.0, data.clone()).is_err(), "Far away positions should be rejected");
+        let inside = Uuid::new_v4();
+        vault_manager.add_object(region_id, inside, "player", 10.0, 10.0, 10.0, data.clone())?;
+        let mut object = vault_manager.get_object(inside)?.ok_or("Object should exist")?;
+        object.point = [10.0, 500.0, 10.0];
+        assert!(vault_manager.update_object(&object).is_err(), "Updates outside the world should be rejected");
+        let stored = vault_manager.get_object(inside)?.ok_or("Object should still exist")?;
+        assert_eq!(stored.point, [10.0, 10.0, 10.0], "A rejected update should leave the object untouched");
+        println!("{}", "Out-of-world positions were rejected".green());
+    }
+
+    // With the clamp policy, objects are moved onto the world's edge instead
+    let config = VaultConfig { world_bounds: Some(bounds), out_of_bounds: BoundsPolicy::Clamp, ..VaultConfig::default() };
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::with_config(db_path, config)?;
+    let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+    let clamped = Uuid::new_v4();
+    vault_manager.add_object(region_id, clamped, "player", 5000.0, -500.0, 0.0, data)?;
+    let object = vault_manager.get_object(clamped)?.ok_or("Clamped object should exist")?;
+    assert_eq!(object.point, [1000.0, -100.0, 0.0], "The object should be clamped into the world");
+    println!("{}", "Out-of-world positions were clamped".green());
+
+    println!("{}", "World bounds test passed".green());
+    Ok(())
+}
+
+/// Tests that non-finite coordinates are rejected by constructors, queries and the loader.
+fn test_finite_coordinates(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Finite Coordinates ----".blue());
+
+    // Constructors report which axis was invalid
+    assert!(Finite::new(f64::NAN).is_err(), "NaN should not be finite");
+    assert!(Finite::new(-0.0).is_ok(), "Negative zero is finite");
+    let mut values = [Finite::new(3.0).unwrap(), Finite::new(-1.0).unwrap(), Finite::new(2.0).unwrap()];
+    values.sort();
+    assert_eq!(values.iter().map(|v| v.get()).collect::<Vec<_>>(), vec![-1.0, 2.0, 3.0], "Finite values should be totally ordered");
+    let data = Arc::new(TestCustomData { name: "Finite".to_string(), value: 1 });
+    match SpatialObject::new(Uuid::new_v4(), "player", [0.0, 0.0, f64::NAN], data.clone()) {
+        Err(CoordinateError::NonFiniteCoordinate { axis: 2, .. }) => {}
+        _ => return Err("The z coordinate should be reported as invalid".to_string()),
+    }
+    assert!(Point::try_new(None, f64::INFINITY, 0.0, 0.0, "player".to_string(), serde_json::json!({})).is_err(), "Points should reject infinity");
+    println!("{}", "Constructors rejected non-finite coordinates".green());
+
+    // Queries refuse NaN inputs instead of returning garbage
+    let region_id = {
+        let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+        let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+        vault_manager.add_spatial_object(region_id, SpatialObject::new(Uuid::new_v4(), "player", [1.0, 1.0, 1.0], data.clone()).map_err(|e| e.to_string())?)?;
+        assert!(vault_manager.query_radius(region_id, [f64::NAN, 0.0, 0.0], 10.0).is_err(), "NaN centers should be rejected");
+        assert!(vault_manager.query_k_nearest(region_id, [0.0, f64::NAN, 0.0], 1).is_err(), "NaN query points should be rejected");
+        assert!(vault_manager.query_region(region_id, f64::NAN, 0.0, 0.0, 1.0, 1.0, 1.0).is_err(), "NaN query boxes should be rejected");
+        let everything = vault_manager.query_region(region_id, f64::NEG_INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY, f64::INFINITY, f64::INFINITY, f64::INFINITY)?;
+        assert_eq!(everything.len(), 1, "Unbounded query boxes should still work");
+        println!("{}", "Queries rejected NaN inputs".green());
+
+        // A non-finite position written behind the manager's back
+        let mut bad_point = Point::new(Some(Uuid::new_v4()), 0.0, 0.0, 0.0, "player".to_string(), serde_json::to_value(&*data).unwrap());
+        bad_point.x = f64::INFINITY;
+        vault_manager.database().add_point(&bad_point, region_id)
+            .map_err(|e| format!("Failed to add point: {}", e))?;
+        region_id
+    };
+
+    // The loader treats it as corrupt rather than inserting it into the R-tree
+    assert!(VaultManager::<TestCustomData>::new(db_path).is_err(), "Strict loading should fail on non-finite positions");
+    let config = VaultConfig { lenient_load: true, ..VaultConfig::default() };
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::with_config(db_path, config)?;
+    assert_eq!(vault_manager.load_report().failures.len(), 1, "The non-finite object should be quarantined");
+    assert_eq!(vault_manager.query_radius(region_id, [0.0, 0.0, 0.0], 100.0)?.len(), 1, "Only the valid object should load");
+    println!("{}", "Non-finite stored positions were quarantined".green());
+
+    println!("{}", "Finite coordinates test passed".green());
+    Ok(())
+}
+
+/// Tests region lock diagnostics and lock-order hazard detection.
+fn test_lock_diagnostics(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Lock Diagnostics ----".blue());
+
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let first = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 50.0)?;
+    let second = vault_manager.create_or_load_region([100.0, 0.0, 0.0], 50.0)?;
+    let object_id = Uuid::new_v4();
+    let data = Arc::new(TestCustomData { name: "Locked".to_string(), value: 1 });
+    vault_manager.add_object(first, object_id, "cart", 0.0, 0.0, 0.0, data)?;
+
+    // Transfers in both directions lock the pair in the same order
+    vault_manager.reset_lock_diagnostics();
+    vault_manager.transfer_object(object_id, first, second, false)?;
+    vault_manager.transfer_object(object_id, second, first, false)?;
+    let diagnostics = vault_manager.lock_diagnostics();
+    assert!(diagnostics.order_violations.is_empty(), "Transfers should lock regions in order");
+    assert_eq!(diagnostics.regions.get(&first).map(|stats| stats.acquisitions), Some(2), "Each transfer should lock the source once");
+    println!("{}", "Transfers respected the lock order".green());
+
+    // Waiting for a region held elsewhere is reported as contention
+    let region = vault_manager.get_region(first).ok_or("Region should exist")?;
+    let (locked_tx, locked_rx) = std::sync::mpsc::channel();
+    let holder = std::thread::spawn(move || {
+        let _guard = region.lock().unwrap();
+        locked_tx.send(()).unwrap();
+        std::thread::sleep(Duration::from_millis(50));
+    });
+    locked_rx.recv().map_err(|e| format!("Holder thread failed: {}", e))?;
+    vault_manager.query_region(first, -10.0, -10.0, -10.0, 10.0, 10.0, 10.0)?;
+    holder.join().map_err(|_| "Holder thread panicked".to_string())?;
+    let hot_spots = vault_manager.lock_diagnostics().hot_spots(1);
+    println!("Hot spots: {:?}", hot_spots);
+    assert_eq!(hot_spots.len(), 1, "The contended region should be a hot spot");
+    assert_eq!(hot_spots[0].0, first, "The contended region should be reported");
+    assert!(hot_spots[0].1.total_wait >= Duration::from_millis(20), "The wait should be measured");
+    println!("{}", "Contention was reported".green());
+
+    // Locking out of order is detected
+    let recorder = crate::lock_stats::LockRecorder::default();
+    let (low, high) = if first < second { (first, second) } else { (second, first) };
+    let (low_mutex, high_mutex) = (std::sync::Mutex::new(()), std::sync::Mutex::new(()));
+    {
+        let _high = recorder.acquire(high, &high_mutex);
+        let _low = recorder.acquire(low, &low_mutex);
+    }
+    {
+        let _low = recorder.acquire(low, &low_mutex);
+        let _high = recorder.acquire(high, &high_mutex);
+    }
+    let violations = recorder.snapshot().order_violations;
+    assert_eq!(violations.len(), 1, "Only the reversed acquisition should be flagged");
+    assert_eq!((violations[0].held, violations[0].acquired), (high, low), "The offending pair should be named");
+    println!("{}", "Lock-order violation was detected".green());
+
+    vault_manager.reset_lock_diagnostics();
+    assert!(vault_manager.lock_diagnostics().regions.is_empty(), "Resetting should clear the counters");
+
+    println!("{}", "Lock diagnostics test passed".green());
+    Ok(())
+}
+
+/// Checks at compile time that a type can be shared between threads.
+fn assert_send_sync<S: Send + Sync>() {}
+
+/// Tests sharing a single VaultManager between threads through an `Arc`.
+fn test_shared_manager(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Shared VaultManager ----".blue());
+
+    assert_send_sync::<VaultManager<TestCustomData>>();
+    let vault_manager: Arc<VaultManager<TestCustomData>> = Arc::new(VaultManager::new(db_path)?);
+
+    // Every thread asks for the same region, then fills it while another thread persists
+    let writers: Vec<_> = (0..4)
+        .map(|thread| {
+            let vault_manager = Arc::clone(&vault_manager);
+            std::thread::spawn(move || -> Result<Uuid, String> {
+                let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+                for i in 0..25 {
+                    let object_id = Uuid::new_v4();
+                    let data = Arc::new(TestCustomData { name: format!("Worker {}", thread), value: i });
+                    vault_manager.add_object(region_id, object_id, "resource", thread as f64, i as f64, 0.0, data)?;
+                    let mut object = vault_manager.get_object(object_id)?.ok_or("Added object should be found")?;
+                    object.custom_data = Arc::new(TestCustomData { name: format!("Worker {}", thread), value: i + 100 });
+                    vault_manager.update_object(&object)?;
+                }
+                Ok(region_id)
+            })
+        })
+        .collect();
+    let persister = {
+        let vault_manager = Arc::clone(&vault_manager);
+        std::thread::spawn(move || -> Result<(), String> {
+            for _ in 0..10 {
+                vault_manager.persist_to_disk()?;
+            }
+            Ok(())
+        })
+    };
+
+    let mut region_ids = Vec::new();
+    for writer in writers {
+        region_ids.push(writer.join().map_err(|_| "Writer thread panicked".to_string())??);
+    }
+    persister.join().map_err(|_| "Persister thread panicked".to_string())??;
+    region_ids.dedup();
+    assert_eq!(region_ids.len(), 1, "Concurrent calls should create the region only once");
+    assert_eq!(vault_manager.region_count(), 1, "Only one region should exist");
+    let objects = vault_manager.query_region(region_ids[0], -10.0, -10.0, -10.0, 10.0, 30.0, 10.0)?;
+    assert_eq!(objects.len(), 100, "Every thread's objects should be stored");
+    assert!(objects.iter().all(|object| object.custom_data.value >= 100), "Every update should be applied");
+    println!("{}", "Four threads shared one manager".green());
+
+    // Everything reaches the database once the last changes are persisted
+    vault_manager.persist_to_disk()?;
+    drop(vault_manager);
+    let reopened: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    assert_eq!(reopened.load_report().total_points(), 100, "Every object should be persisted");
+    println!("{}", "Concurrent changes were persisted".green());
+
+    println!("{}", "Shared VaultManager test passed".green());
+    Ok(())
+}
+
+/// Tests that objects are found through the object index without searching every region.
+fn test_object_index(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Object Index ----".blue());
+
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let mut region_ids = Vec::new();
+    for i in 0..10 {
+        region_ids.push(vault_manager.create_or_load_region([i as f64 * 100.0, 0.0, 0.0], 40.0)?);
+    }
+    let mut object_ids = Vec::new();
+    for (i, region_id) in region_ids.iter().enumerate() {
+        for j in 0..20 {
+            let object_id = Uuid::new_v4();
+            let data = Arc::new(TestCustomData { name: format!("Object {}-{}", i, j), value: j });
+            vault_manager.add_object(*region_id, object_id, "resource", i as f64 * 100.0 + j as f64, 0.0, 0.0, data)?;
+            object_ids.push(object_id);
+        }
+    }
+
+    // A lookup only locks the region holding the object
+    let object_id = object_ids[150];
+    vault_manager.reset_lock_diagnostics();
+    let object = vault_manager.get_object(object_id)?.ok_or("Indexed object should be found")?;
+    assert_eq!(object.custom_data.name, "Object 7-10");
+    let acquisitions: u64 = vault_manager.lock_diagnostics().regions.values().map(|stats| stats.acquisitions).sum();
+    assert_eq!(acquisitions, 1, "Only the region holding the object should be locked");
+    println!("{}", "Object found by locking a single region".green());
+
+    // The index follows objects that move to other regions, and forgets removed ones
+    let new_region = vault_manager.move_object(object_id, [205.0, 0.0, 0.0])?;
+    assert_eq!(new_region, region_ids[2], "The object should move to the third region");
+    vault_manager.reset_lock_diagnostics();
+    let moved = vault_manager.get_object(object_id)?.ok_or("Moved object should be found")?;
+    assert_eq!(moved.point, [205.0, 0.0, 0.0]);
+    let diagnostics = vault_manager.lock_diagnostics();
+    assert_eq!(diagnostics.regions.len(), 1, "Only the new region should be locked");
+    assert!(diagnostics.regions.contains_key(&region_ids[2]));
+    vault_manager.remove_object(object_id)?;
+    assert!(vault_manager.get_object(object_id)?.is_none(), "Removed object should not be found");
+    println!("{}", "Index followed moved and removed objects".green());
+
+    // Objects of unloaded or split regions are still found
+    vault_manager.unload_region(region_ids[4])?;
+    let unloaded = vault_manager.get_object(object_ids[85])?.ok_or("Object of an unloaded region should be found")?;
+    assert_eq!(unloaded.custom_data.name, "Object 4-5");
+    let octants = vault_manager.split_region(region_ids[5])?;
+    let mut updated = vault_manager.get_object(object_ids[105])?.ok_or("Object of a split region should be found")?;
+    updated.custom_data = Arc::new(TestCustomData { name: "Updated".to_string(), value: 0 });
+    vault_manager.update_object(&updated)?;
+    let found = vault_manager.get_object(object_ids[105])?.ok_or("Updated object should be found")?;
+    assert_eq!(found.custom_data.name, "Updated");
+    assert!(octants.iter().any(|octant| vault_manager.query_region(*octant, 495.0, -5.0, -5.0, 530.0, 5.0, 5.0)
+        .map(|objects| objects.iter().any(|object| object.uuid == object_ids[105]))
+        .unwrap_or(false)), "The object should be in one of the octants");
+    println!("{}", "Objects of unloaded and split regions were found".green());
+
+    println!("{}", "Object index test passed".green());
+    Ok(())
+}
+
+/// Tests that persistent database I/O is counted.
+fn test_io_stats(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing I/O Accounting ----".blue());
+
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+    let data = Arc::new(TestCustomData { name: "Counted".to_string(), value: 9 });
+    let payload = serde_json::to_string(&*data).map_err(|e| e.to_string())?.len() as u64;
+
+    let before = vault_manager.io_stats();
+    let first = Uuid::new_v4();
+    vault_manager.add_object(region_id, first, "tree", 1.0, 0.0, 0.0, data.clone())?;
+    let cost = vault_manager.io_stats().since(&before);
+    assert!(cost.statements > 0, "Adding an object should execute statements");
+    assert_eq!((cost.files_written, cost.bytes_written), (1, payload), "The payload should be written once");
+    println!("Adding an object cost {:?}", cost);
+
+    // Identical payloads are deduplicated, so no further data files are written
+    let before = vault_manager.io_stats();
+    vault_manager.add_object(region_id, Uuid::new_v4(), "tree", 2.0, 0.0, 0.0, data.clone())?;
+    assert_eq!(vault_manager.io_stats().since(&before).files_written, 0, "A duplicate payload should not be written again");
+    println!("{}", "Writes were counted".green());
+
+    // Reading objects back and deleting data files are counted too
+    let before = vault_manager.io_stats();
+    let points = vault_manager.database().get_points_in_region(region_id).map_err(|e| e.to_string())?;
+    let cost = vault_manager.io_stats().since(&before);
+    assert_eq!((cost.statements, cost.files_read, cost.bytes_read), (1, 2, 2 * payload), "Each point's data file should be read");
+    assert_eq!(points.len(), 2, "Both points should be read");
+    vault_manager.remove_object(first)?;
+    let before = vault_manager.io_stats();
+    vault_manager.remove_object(points[1].id.ok_or("Point should have an ID")?)?;
+    assert_eq!(vault_manager.io_stats().since(&before).files_deleted, 1, "The last reference should delete the data file");
+    println!("{}", "Reads and deletions were counted".green());
+
+    println!("{}", "I/O accounting test passed".green());
+    Ok(())
+}
+
+/// Tests storing keys as binary and migrating between key formats.
+fn test_key_format_migration(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Key Format Migration ----".blue());
+
+    let data = Arc::new(TestCustomData { name: "Keyed".to_string(), value: 16 });
+    let object_id = Uuid::new_v4();
+    let region_id = {
+        let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+        assert_eq!(vault_manager.database().key_format(), KeyFormat::Text, "New databases should use text keys");
+        let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+        vault_manager.add_object(region_id, object_id, "player", 1.0, 2.0, 3.0, data.clone())?;
+        region_id
+    };
+
+    // Opening with binary keys migrates the existing rows
+    {
+        let config = VaultConfig { key_format: Some(KeyFormat::Binary), ..VaultConfig::default() };
+        let vault_manager: VaultManager<TestCustomData> = VaultManager::with_config(db_path, config)?;
+        assert_eq!(vault_manager.database().key_format(), KeyFormat::Binary, "Keys should be binary after migration");
+        let object = vault_manager.get_object(object_id)?.ok_or("The object should survive the migration")?;
+        assert_eq!(object.custom_data, data, "Custom data should survive the migration");
+        assert!(vault_manager.load_report().orphaned_points.is_empty(), "Points should still reference their region");
+
+        // Writes, moves and removals work against binary keys
+        let other = Uuid::new_v4();
+        vault_manager.add_object(region_id, other, "player", 5.0, 5.0, 5.0, data.clone())?;
+        vault_manager.move_object(object_id, [4.0, 4.0, 4.0])?;
+        vault_manager.remove_object(other)?;
+        println!("{}", "Existing keys were migrated to binary".green());
+    }
+
+    // Without a requested format the stored one is kept
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    assert_eq!(vault_manager.database().key_format(), KeyFormat::Binary, "The stored format should be kept");
+    let points = vault_manager.database().get_points_in_region(region_id).map_err(|e| e.to_string())?;
+    assert_eq!(points.len(), 1, "Only the moved object should remain");
+    assert_eq!((points[0].id, [points[0].x, points[0].y, points[0].z]), (Some(object_id), [4.0, 4.0, 4.0]), "The move should be stored");
+    drop(vault_manager);
+
+    // And the migration can be reversed
+    let config = VaultConfig { key_format: Some(KeyFormat::Text), ..VaultConfig::default() };
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::with_config(db_path, config)?;
+    assert_eq!(vault_manager.database().key_format(), KeyFormat::Text, "Keys should be text again");
+    assert!(vault_manager.get_object(object_id)?.is_some(), "The object should survive migrating back");
+    println!("{}", "Key format was kept and could be reverted".green());
+
+    println!("{}", "Key format migration test passed".green());
+    Ok(())
+}
+
+/// Tests that attached objects follow their parents, and that attachments persist and are dropped with their objects.
+fn test_object_hierarchy(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Object Hierarchy ----".blue());
+
+    let data = Arc::new(TestCustomData { name: "Ship".to_string(), value: 1 });
+    let (ship, turret, gunner, cargo) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+    let west = {
+        let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+        let west = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+        let east = vault_manager.create_or_load_region([300.0, 0.0, 0.0], 100.0)?;
+        vault_manager.add_object(west, ship, "building", 0.0, 0.0, 0.0, data.clone())?;
+        vault_manager.add_object(west, turret, "building", 0.0, 5.0, 0.0, data.clone())?;
+        vault_manager.add_object(west, gunner, "player", 1.0, 6.0, 0.0, data.clone())?;
+        vault_manager.add_object(west, cargo, "resource", -2.0, 0.0, 0.0, data.clone())?;
+        vault_manager.attach_object(turret, ship)?;
+        vault_manager.attach_object(gunner, turret)?;
+        vault_manager.attach_object(cargo, ship)?;
+        assert_eq!(vault_manager.parent_of(gunner), Some(turret), "The gunner should follow the turret");
+        let mut expected_children = vec![turret, cargo];
+        expected_children.sort();
+        assert_eq!(vault_manager.children_of(ship), expected_children, "The turret and cargo should follow the ship");
+
+        // Cycles and unknown objects are rejected
+        assert!(vault_manager.attach_object(ship, gunner).is_err(), "A ship can't follow its own gunner");
+        assert!(vault_manager.attach_object(ship, ship).is_err(), "An object can't follow itself");
+        assert!(vault_manager.attach_object(Uuid::new_v4(), ship).is_err(), "Unknown objects should be rejected");
+        assert_eq!(vault_manager.parent_of(ship), None, "Rejected attachments should change nothing");
+        println!("{}", "Objects were attached".green());
+
+        // Moving the ship moves everything on it by the same offset
+        vault_manager.move_object(ship, [10.0, 0.0, 0.0])?;
+        let position = |object_id| vault_manager.get_object(object_id).map(|object| object.map(|object| object.point));
+        assert_eq!(position(turret)?, Some([10.0, 5.0, 0.0]), "The turret should follow the ship");
+        assert_eq!(position(gunner)?, Some([11.0, 6.0, 0.0]), "The gunner should follow the turret");
+        assert_eq!(position(cargo)?, Some([8.0, 0.0, 0.0]), "The cargo should follow the ship");
+
+        // Attached objects move on their own without moving their parent
+        vault_manager.move_object(gunner, [10.0, 6.0, 1.0])?;
+        assert_eq!(position(turret)?, Some([10.0, 5.0, 0.0]), "Moving the gunner shouldn't move the turret");
+        println!("{}", "Attached objects followed their parent".green());
+
+        // The whole hierarchy changes regions together
+        let region_id = vault_manager.move_object(ship, [300.0, 0.0, 0.0])?;
+        assert_eq!(region_id, east, "The ship should move to the eastern region");
+        assert_eq!(vault_manager.query_region(east, 200.0, -100.0, -100.0, 400.0, 100.0, 100.0)?.len(), 4, "Everything on the ship should move east");
+        assert!(vault_manager.query_region(west, -100.0, -100.0, -100.0, 100.0, 100.0, 100.0)?.is_empty(), "Nothing should be left behind");
+
+        // A stale versioned move fails without moving anything
+        let stale = vault_manager.get_object(ship)?.ok_or("Ship should exist")?.version - 1;
+        assert!(vault_manager.move_object_versioned(ship, [310.0, 0.0, 0.0], stale).is_err(), "A stale move should be rejected");
+        assert_eq!(position(turret)?, Some([300.0, 5.0, 0.0]), "A rejected move shouldn't move attached objects");
+        println!("{}", "The hierarchy moved between regions atomically".green());
+        west
+    };
+
+    // Attachments are persisted
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    assert_eq!(vault_manager.parent_of(gunner), Some(turret), "The gunner's attachment should be reloaded");
+    vault_manager.move_object(ship, [290.0, 0.0, 0.0])?;
+    assert_eq!(vault_manager.get_object(gunner)?.map(|object| object.point), Some([290.0, 6.0, 1.0]), "Reloaded attachments should be followed");
+    println!("{}", "Attachments were persisted".green());
+
+    // Detached objects stay put, and removing a parent detaches its children
+    assert_eq!(vault_manager.detach_object(cargo)?, Some(ship), "The cargo should be detached from the ship");
+    assert_eq!(vault_manager.detach_object(cargo)?, None, "The cargo is no longer attached");
+    vault_manager.remove_object(turret)?;
+    assert_eq!(vault_manager.parent_of(gunner), None, "Removing the turret should detach the gunner");
+    vault_manager.move_object(ship, [280.0, 0.0, 0.0])?;
+    assert_eq!(vault_manager.get_object(cargo)?.map(|object| object.point), Some([288.0, 0.0, 0.0]), "Detached cargo shouldn't follow the ship");
+    assert_eq!(vault_manager.get_object(gunner)?.map(|object| object.point), Some([290.0, 6.0, 1.0]), "The gunner shouldn't follow the ship anymore");
+    drop(vault_manager);
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    assert!(vault_manager.children_of(ship).is_empty(), "Removed attachments should stay removed");
+    assert_eq!(vault_manager.parent_of(gunner), None, "The gunner should stay detached after reload");
+    assert!(vault_manager.region_ids().contains(&west), "Both regions should reload");
+    println!("{}", "Detaching and removal dropped attachments".green());
+
+    println!("{}", "Object hierarchy test passed".green());
+    Ok(())
+}
+
+/// Tests that objects with a velocity are extrapolated by `advance`, and that velocities persist.
+fn test_velocity(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Velocity ----".blue());
+
+    let data = Arc::new(TestCustomData { name: "Arrow".to_string(), value: 1 });
+    let (arrow, rock, idle) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+    let east = {
+        let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+        let west = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+        let east = vault_manager.create_or_load_region([300.0, 0.0, 0.0], 100.0)?;
+        let moving = SpatialObject::new(arrow, "resource", [0.0, 0.0, 0.0], data.clone())
+            .and_then(|object| object.with_velocity([10.0, 0.0, -2.0]))
+            .map_err(|e| e.to_string())?;
+        vault_manager.add_spatial_object(west, moving)?;
+        vault_manager.add_object(west, rock, "resource", 5.0, 0.0, 0.0, data.clone())?;
+        let stopped = SpatialObject::new(idle, "resource", [-5.0, 0.0, 0.0], data.clone())
+            .and_then(|object| object.with_velocity([0.0; 3]))
+            .map_err(|e| e.to_string())?;
+        vault_manager.add_spatial_object(west, stopped)?;
+        assert!(SpatialObject::new(Uuid::new_v4(), "resource", [0.0; 3], data.clone()).and_then(|object| object.with_velocity([f64::NAN, 0.0, 0.0])).is_err(), "Non-finite velocities should be rejected");
+
+        // Only objects with a non-zero velocity move, by their velocity times the step
+        let events = vault_manager.subscribe();
+        assert_eq!(vault_manager.advance(Duration::from_millis(1500))?, 1, "Only the arrow should move");
+        let object = vault_manager.get_object(arrow)?.ok_or("Arrow should exist")?;
+        assert_eq!(object.point, [15.0, 0.0, -3.0], "The arrow should be extrapolated");
+        assert_eq!(object.version, 1, "Advancing should count as a change");
+        assert_eq!(vault_manager.query_radius(west, [15.0, 0.0, -3.0], 0.1)?.len(), 1, "The arrow should be reindexed at its new position");
+        assert!(vault_manager.query_radius(west, [0.0, 0.0, 0.0], 0.1)?.is_empty(), "The old position should be empty");
+        assert_eq!(vault_manager.get_object(rock)?.map(|object| object.point), Some([5.0, 0.0, 0.0]), "Objects without a velocity should stay put");
+        assert_eq!(vault_manager.get_object(idle)?.map(|object| object.version), Some(0), "Stopped objects shouldn't be changed");
+        assert_eq!(events.try_iter().count(), 1, "The move should be announced");
+        println!("{}", "Objects were extrapolated by their velocity".green());
+
+        // Leaving the region reassigns the object
+        vault_manager.advance(Duration::from_secs(20))?;
+        let object = vault_manager.get_object(arrow)?.ok_or("Arrow should exist")?;
+        assert_eq!(object.point, [215.0, 0.0, -43.0], "The arrow should keep flying");
+        assert_eq!(vault_manager.query_radius(east, [215.0, 0.0, -43.0], 0.1)?.len(), 1, "The arrow should move to the eastern region");
+        println!("{}", "Objects leaving their region were reassigned".green());
+
+        // The last in-place step is only in memory until persisted
+        vault_manager.advance(Duration::from_secs(1))?;
+        vault_manager.persist_to_disk()?;
+        east
+    };
+
+    // Positions and velocities are persisted
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let object = vault_manager.get_object(arrow)?.ok_or("Arrow should reload")?;
+    assert_eq!(object.point, [225.0, 0.0, -45.0], "The advanced position should be persisted");
+    assert_eq!(object.velocity, Some([10.0, 0.0, -2.0]), "The velocity should be persisted");
+    assert_eq!(vault_manager.get_object(rock)?.ok_or("Rock should reload")?.velocity, None, "Objects without a velocity should reload without one");
+    assert_eq!(vault_manager.query_radius(east, [225.0, 0.0, -45.0], 0.1)?.len(), 1, "The arrow should reload in the eastern region");
+    println!("{}", "Velocities were persisted".green());
+
+    println!("{}", "Velocity test passed".green());
+    Ok(())
+}
+
+/// Tests moving objects within a region and across region boundaries.
+fn test_move_object(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Move Object ----".blue());
+
+    let object_id = Uuid::new_v4();
+    let (west, east) = {
+        let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+        let west = vault_manager.create_or_load_region([-100.0, 0.0, 0.0], 50.0)?;
+        let east = vault_manager.create_or_load_region([100.0, 0.0, 0.0], 50.0)?;
+        vault_manager.add_object(west, object_id, "player", -100.0, 0.0, 0.0, Arc::new(TestCustomData { name: "Walker".to_string(), value: 1 }))?;
+
+        // A short move stays in the same region
+        let region_id = vault_manager.move_object(object_id, [-90.0, 5.0, 0.0])?;
+        assert_eq!(region_id, west, "The object should stay in its region");
+        let found = vault_manager.query_radius(west, [-90.0, 5.0, 0.0], 0.1)?;
+        assert_eq!(found.len(), 1, "The object should be found at its new position");
+        assert!(vault_manager.query_radius(west, [-100.0, 0.0, 0.0], 0.1)?.is_empty(), "The old position should be empty");
+        println!("{}", "Object moved within its region".green());
+
+        // Leaving the region's cube reassigns it
+        let region_id = vault_manager.move_object(object_id, [120.0, 0.0, 0.0])?;
+        assert_eq!(region_id, east, "The object should move to the eastern region");
+        assert!(vault_manager.query_radius(west, [-90.0, 5.0, 0.0], 1000.0)?.is_empty(), "The western region should be empty");
+        assert_eq!(vault_manager.query_radius(east, [120.0, 0.0, 0.0], 0.1)?.len(), 1, "The eastern region should hold the object");
+        println!("{}", "Object was reassigned to the region containing it".green());
+
+        assert!(vault_manager.move_object(Uuid::new_v4(), [0.0, 0.0, 0.0]).is_err(), "Moving an unknown object should fail");
+        (west, east)
+    };
+
+    // The move was written straight to the persistent database
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let object = vault_manager.get_object(object_id)?.ok_or("Moved object should reload")?;
+    assert_eq!(object.point, [120.0, 0.0, 0.0], "The new position should be persisted");
+    assert_eq!(vault_manager.query_radius(east, [120.0, 0.0, 0.0], 0.1)?.len(), 1, "The new region should be persisted");
+    assert!(vault_manager.query_radius(west, [-100.0, 0.0, 0.0], 1000.0)?.is_empty(), "The old region should be empty after reload");
+    println!("{}", "Moves were persisted".green());
+
+    println!("{}", "Move object test passed".green());
+    Ok(())
+}
+
+/// Tests that batches of mutations are applied and persisted all together or not at all.
+fn test_apply_batch(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Batch Mutations ----".blue());
+
+    let resource = Arc::new(TestCustomData { name: "Wood".to_string(), value: 1 });
+    let (wood, stone, player) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+    let building = Uuid::new_v4();
+    let (west, east) = {
+        let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+        let west = vault_manager.create_or_load_region([-100.0, 0.0, 0.0], 50.0)?;
+        let east = vault_manager.create_or_load_region([100.0, 0.0, 0.0], 50.0)?;
+        vault_manager.add_object(west, wood, "resource", -100.0, 0.0, 0.0, resource.clone())?;
+        vault_manager.add_object(west, stone, "resource", -101.0, 0.0, 0.0, resource.clone())?;
+        vault_manager.add_object(west, player, "player", -102.0, 0.0, 0.0, resource.clone())?;
+
+        // A batch that fails part way changes nothing
+        let failing = vec![
+            Mutation::Remove { object_id: wood },
+            Mutation::Remove { object_id: Uuid::new_v4() },
+        ];
+        assert!(vault_manager.apply_batch(failing).is_err(), "A batch with an unknown object should fail");
+        assert!(vault_manager.get_object(wood)?.is_some(), "A failed batch should leave every object in place");
+        println!("{}", "Failed batch left the world untouched".green());
+
+        // Crafting consumes the resources, places the building and walks the player east
+        let mut structure = SpatialObject::new(building, "building", [-95.0, 0.0, 0.0], Arc::new(TestCustomData { name: "Hut".to_string(), value: 1 }))
+            .map_err(|e| format!("Invalid object: {}", e))?;
+        vault_manager.apply_batch(vec![
+            Mutation::Remove { object_id: wood },
+            Mutation::Remove { object_id: stone },
+            Mutation::Add { region_id: west, object: structure.clone() },
+            Mutation::Move { object_id: player, position: [100.0, 0.0, 0.0] },
+        ])?;
+        structure.custom_data = Arc::new(TestCustomData { name: "Hut".to_string(), value: 2 });
+        vault_manager.apply_batch(vec![Mutation::Update { object: structure }])?;
+
+        // Transactions collect mutations and only apply them on commit
+        let torch = SpatialObject::new(Uuid::new_v4(), "resource", [-98.0, 0.0, 0.0], resource.clone())
+            .map_err(|e| format!("Invalid object: {}", e))?;
+        let mut transaction = vault_manager.begin();
+        transaction.add(west, torch.clone()).move_object(player, [105.0, 0.0, 0.0]);
+        assert_eq!(transaction.len(), 2, "Both mutations should be collected");
+        assert!(vault_manager.get_object(torch.uuid)?.is_none(), "Nothing should change before the commit");
+        transaction.commit()?;
+        assert!(vault_manager.get_object(torch.uuid)?.is_some(), "The commit should add the torch");
+        let mut transaction = vault_manager.begin();
+        transaction.remove(torch.uuid).remove(Uuid::new_v4());
+        assert!(transaction.commit().is_err(), "A transaction with an unknown object should fail");
+        let mut transaction = vault_manager.begin();
+        transaction.remove(torch.uuid);
+        transaction.rollback();
+        assert!(vault_manager.get_object(torch.uuid)?.is_some(), "Failed and rolled back transactions should change nothing");
+        let mut transaction = vault_manager.begin();
+        transaction.remove(torch.uuid).move_object(player, [100.0, 0.0, 0.0]);
+        transaction.commit()?;
+        println!("{}", "Transactions applied on commit only".green());
+
+        assert!(vault_manager.get_object(wood)?.is_none() && vault_manager.get_object(stone)?.is_none(), "The resources should be consumed");
+        assert_eq!(vault_manager.query_region(west, -150.0, -50.0, -50.0, -50.0, 50.0, 50.0)?.len(), 1, "Only the building should remain in the west");
+        assert_eq!(vault_manager.query_region(east, 50.0, -50.0, -50.0, 150.0, 50.0, 50.0)?.len(), 1, "The player should have moved east");
+        assert!(!vault_manager.has_unflushed_changes(), "Batches should be written immediately");
+        println!("{}", "Batch was applied in memory".green());
+        (west, east)
+    };
+
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let hut = vault_manager.get_object(building)?.ok_or("The building should be persisted")?;
+    assert_eq!(hut.custom_data.value, 2, "The batched update should be persisted");
+    assert!(vault_manager.get_object(wood)?.is_none(), "The removal should be persisted");
+    let moved = vault_manager.get_object(player)?.ok_or("The player should be persisted")?;
+    assert_eq!(moved.point, [100.0, 0.0, 0.0], "The move should be persisted");
+    assert_eq!(vault_manager.query_region(east, 50.0, -50.0, -50.0, 150.0, 50.0, 50.0)?.len(), 1, "The player's new region should be persisted");
+    assert_eq!(vault_manager.query_region(west, -150.0, -50.0, -50.0, -50.0, 50.0, 50.0)?.len(), 1, "The west should only hold the building after a restart");
+    println!("{}", "Batch was persisted".green());
+
+    println!("{}", "Batch mutations test passed".green());
+    Ok(())
+}
+
+/// Tests that subscribers are told about every committed change.
+fn test_mutation_events(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Mutation Events ----".blue());
+
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let events = vault_manager.subscribe();
+    let data = Arc::new(TestCustomData { name: "Event".to_string(), value: 1 });
+
+    let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+    let other_region = vault_manager.create_or_load_region([300.0, 0.0, 0.0], 100.0)?;
+    let object_id = Uuid::new_v4();
+    vault_manager.add_object(region_id, object_id, "player", 1.0, 2.0, 3.0, data.clone())?;
+    let received: Vec<VaultEvent<TestCustomData>> = events.try_iter().collect();
+    assert_eq!(received.len(), 3, "Two regions and an object were created");
+    assert!(matches!(received[0], VaultEvent::RegionCreated { region_id: created, radius, .. } if created == region_id && radius == 100.0), "The first region should be announced");
+    assert!(matches!(&received[2], VaultEvent::ObjectAdded { region_id: added_to, object } if *added_to == region_id && object.uuid == object_id), "The object should be announced");
+    println!("{}", "Creations were announced".green());
+
+    vault_manager.move_object(object_id, [10.0, 0.0, 0.0])?;
+    vault_manager.move_object(object_id, [300.0, 0.0, 0.0])?;
+    let mut object = vault_manager.get_object(object_id)?.ok_or("Object should exist")?;
+    object.custom_data = Arc::new(TestCustomData { name: "Changed".to_string(), value: 2 });
+    vault_manager.update_object(&object)?;
+    let received: Vec<VaultEvent<TestCustomData>> = events.try_iter().collect();
+    assert_eq!(received.len(), 3, "Two moves and an update were made");
+    assert!(matches!(received[0], VaultEvent::ObjectMoved { from_region, to_region, position, .. } if from_region == region_id && to_region == region_id && position == [10.0, 0.0, 0.0]), "A move within the region should be announced");
+    assert!(matches!(received[1], VaultEvent::ObjectMoved { from_region, to_region, .. } if from_region == region_id && to_region == other_region), "A move to another region should be announced");
+    assert!(matches!(&received[2], VaultEvent::ObjectUpdated { object, .. } if object.custom_data.name == "Changed"), "The update should be announced");
+    println!("{}", "Moves and updates were announced".green());
+
+    // Failed operations don't emit anything
+    object.version = 0;
+    assert!(vault_manager.update_object(&object).is_err(), "A stale update should be rejected");
+    assert!(vault_manager.add_object(Uuid::new_v4(), Uuid::new_v4(), "player", 0.0, 0.0, 0.0, data.clone()).is_err(), "Unknown regions should be rejected");
+    assert_eq!(events.try_iter().count(), 0, "Failed operations should not emit events");
+
+    let second = Uuid::new_v4();
+    vault_manager.apply_batch(vec![
+        Mutation::Remove { object_id },
+        Mutation::Add { region_id, object: SpatialObject::new(second, "resource", [5.0, 5.0, 5.0], data.clone()).map_err(|e| e.to_string())? },
+    ])?;
+    let received: Vec<VaultEvent<TestCustomData>> = events.try_iter().collect();
+    assert!(received.contains(&VaultEvent::ObjectRemoved { region_id: other_region, object_id }), "The batch removal should be announced");
+    assert!(received.iter().any(|event| matches!(event, VaultEvent::ObjectAdded { object, .. } if object.uuid == second)), "The batch addition should be announced");
+    println!("{}", "Batch changes were announced".green());
+
+    // Concurrent removals of the same object announce it once
+    let raced = Uuid::new_v4();
+    vault_manager.add_object(region_id, raced, "resource", 6.0, 6.0, 6.0, data.clone())?;
+    events.try_iter().count();
+    let removals: Vec<Result<(), String>> = std::thread::scope(|scope| {
+        let removers: Vec<_> = (0..4).map(|_| scope.spawn(|| vault_manager.remove_object(raced))).collect();
+        removers.into_iter().map(|remover| remover.join().unwrap()).collect()
+    });
+    assert_eq!(removals.iter().filter(|removal| removal.is_ok()).count(), 1, "Only one removal should succeed");
+    let received: Vec<VaultEvent<TestCustomData>> = events.try_iter().collect();
+    assert!(received == vec![VaultEvent::ObjectRemoved { region_id, object_id: raced }], "The removal should be announced once");
+    println!("{}", "Concurrent removals were announced once".green());
+
+    // Dropped receivers are forgotten, other subscribers keep receiving
+    let late = vault_manager.subscribe();
+    drop(events);
+    vault_manager.delete_region(region_id, true)?;
+    let received: Vec<VaultEvent<TestCustomData>> = late.try_iter().collect();
+    assert!(received == vec![
+        VaultEvent::ObjectRemoved { region_id, object_id: second },
+        VaultEvent::RegionRemoved { region_id },
+    ], "The deletion should be announced");
+    println!("{}", "Region deletion was announced".green());
+
+    println!("{}", "Mutation events test passed".green());
+    Ok(())
+}
+
+/// Tests that changes are recorded with sequence numbers and can be read back from any point the log retains.
+fn test_change_feed(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Change Feed ----".blue());
+
+    let config = VaultConfig { change_log_capacity: 4, ..VaultConfig::default() };
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::with_config(db_path, config)?;
+    let data = Arc::new(TestCustomData { name: "Feed".to_string(), value: 1 });
+    assert_eq!(vault_manager.latest_change(), 0, "Nothing should be recorded yet");
+    assert!(vault_manager.changes_since(0)?.is_empty(), "The log should start empty");
+
+    let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+    let object_id = Uuid::new_v4();
+    vault_manager.add_object(region_id, object_id, "player", 1.0, 2.0, 3.0, data.clone())?;
+    vault_manager.move_object(object_id, [5.0, 0.0, 0.0])?;
+    let changes = vault_manager.changes_since(0)?;
+    assert_eq!(changes.iter().map(|change| change.seq).collect::<Vec<_>>(), vec![1, 2, 3], "Changes should be numbered in order");
+    assert!(matches!(changes[0].event, VaultEvent::RegionCreated { region_id: created, .. } if created == region_id), "The region creation should be recorded first");
+    assert!(matches!(&changes[1].event, VaultEvent::ObjectAdded { object, .. } if object.uuid == object_id), "The addition should be recorded");
+    assert!(matches!(changes[2].event, VaultEvent::ObjectMoved { position, .. } if position == [5.0, 0.0, 0.0]), "The move should be recorded");
+    assert!(changes.windows(2).all(|pair| pair[0].recorded_at <= pair[1].recorded_at), "Changes should be timestamped in order");
+    println!("{}", "Changes were recorded in order".green());
+
+    // Readers only get what they haven't seen, and failed operations record nothing
+    let last_seen = vault_manager.latest_change();
+    assert!(vault_manager.add_object(Uuid::new_v4(), Uuid::new_v4(), "player", 0.0, 0.0, 0.0, data.clone()).is_err(), "Unknown regions should be rejected");
+    assert!(vault_manager.changes_since(last_seen)?.is_empty(), "Failed operations should not be recorded");
+    vault_manager.remove_object(object_id)?;
+    let changes = vault_manager.changes_since(last_seen)?;
+    assert!(changes.len() == 1 && changes[0].seq == 4, "Only the removal should be new");
+    assert!(changes[0].event == VaultEvent::ObjectRemoved { region_id, object_id }, "The removal should be recorded");
+    println!("{}", "Readers got the changes since their last one".green());
+
+    // Once the log is full the oldest changes are dropped, and readers that need them are told to resync
+    vault_manager.add_object(region_id, Uuid::new_v4(), "resource", 0.0, 0.0, 0.0, data.clone())?;
+    assert_eq!(vault_manager.latest_change(), 5, "The addition should be change 5");
+    assert!(vault_manager.changes_since(0).is_err(), "The first change should no longer be retained");
+    assert_eq!(vault_manager.changes_since(1)?.len(), 4, "The four most recent changes should be retained");
+    assert!(vault_manager.changes_since(6).is_err(), "Sequence numbers ahead of the log should be rejected");
+    println!("{}", "Dropped changes were reported".green());
+
+    // Without a capacity the change log is disabled
+    drop(vault_manager);
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    vault_manager.add_object(region_id, Uuid::new_v4(), "resource", 0.0, 0.0, 0.0, data)?;
+    assert_eq!(vault_manager.latest_change(), 0, "Nothing should be recorded without a capacity");
+    assert!(vault_manager.changes_since(0).is_err(), "Reading a disabled change log should fail");
+    println!("{}", "The change log is disabled by default".green());
+
+    println!("{}", "Change feed test passed".green());
+    Ok(())
+}
+
+/// Tests that stale updates and moves are rejected instead of overwriting newer changes.
+fn test_object_versioning(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Object Versioning ----".blue());
+
+    let object_id = Uuid::new_v4();
+    {
+        let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+        let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+        vault_manager.add_object(region_id, object_id, "player", 1.0, 1.0, 1.0, Arc::new(TestCustomData { name: "Start".to_string(), value: 0 }))?;
+
+        // Two systems read the same version of the object
+        let mut combat = vault_manager.get_object(object_id)?.ok_or("The object should exist")?;
+        let mut inventory = combat.clone();
+        assert_eq!(combat.version, 0, "A new object should start at version 0");
+
+        combat.custom_data = Arc::new(TestCustomData { name: "Combat".to_string(), value: 1 });
+        assert_eq!(vault_manager.update_object(&combat)?, 1, "An update should increment the version");
+        inventory.custom_data = Arc::new(TestCustomData { name: "Inventory".to_string(), value: 2 });
+        let conflict = vault_manager.update_object(&inventory).expect_err("A stale update should be rejected");
+        assert!(conflict.contains("Version conflict"), "The error should report a version conflict: {}", conflict);
+        let stored = vault_manager.get_object(object_id)?.ok_or("The object should exist")?;
+        assert_eq!(stored.custom_data.name, "Combat", "A stale update should not overwrite the newer one");
+        println!("{}", "Stale update was rejected".green());
+
+        // Moves are checked and increment the version too
+        assert!(vault_manager.move_object_versioned(object_id, [5.0, 5.0, 5.0], 0).is_err(), "A stale move should be rejected");
+        vault_manager.move_object_versioned(object_id, [5.0, 5.0, 5.0], stored.version)?;
+        let moved = vault_manager.get_object(object_id)?.ok_or("The object should exist")?;
+        assert_eq!((moved.point, moved.version), ([5.0, 5.0, 5.0], 2), "The move should be applied and increment the version");
+        println!("{}", "Stale move was rejected".green());
+
+        // A batch with a stale update applies nothing
+        let stale = vault_manager.apply_batch(vec![
+            Mutation::Move { object_id, position: [6.0, 6.0, 6.0] },
+            Mutation::Update { object: stored },
+        ]);
+        assert!(stale.is_err(), "A batch with a stale update should fail");
+        let unchanged = vault_manager.get_object(object_id)?.ok_or("The object should exist")?;
+        assert_eq!((unchanged.point, unchanged.version), ([5.0, 5.0, 5.0], 2), "A failed batch should leave the object untouched");
+        println!("{}", "Stale batch was rejected".green());
+
+        vault_manager.persist_to_disk()?;
+    }
+
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let object = vault_manager.get_object(object_id)?.ok_or("The object should survive a restart")?;
+    assert_eq!(object.version, 2, "The version should be persisted");
+    println!("{}", "Version survived a restart".green());
+
+    println!("{}", "Object versioning test passed".green());
+    Ok(())
+}
+
+/// Tests that regions without nearby players are simulated less often and catch up when players return.
+fn test_simulation_lod(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Simulation LOD ----".blue());
+
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let town = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 50.0)?;
+    let wilds = vault_manager.create_or_load_region([1000.0, 0.0, 0.0], 50.0)?;
+    let player = Uuid::new_v4();
+    vault_manager.add_object(town, player, "player", 0.0, 0.0, 0.0, Arc::new(TestCustomData { name: "Hero".to_string(), value: 1 }))?;
+    vault_manager.add_object(wilds, Uuid::new_v4(), "resource", 1000.0, 0.0, 0.0, Arc::new(TestCustomData { name: "Berries".to_string(), value: 1 }))?;
+
+    let policy = LodPolicy::new(10.0, 5);
+    let mut wild_ticks = Vec::new();
+    for tick in 1..=12 {
+        let scheduled = vault_manager.schedule_tick(tick, &policy)?;
+        let town_tick = scheduled.iter().find(|region| region.region_id == town).ok_or("The town should be simulated every tick")?;
+        assert_eq!((town_tick.level, town_tick.elapsed_ticks), (SimulationLevel::Full, 1), "The town should be fully simulated");
+        wild_ticks.extend(scheduled.iter().filter(|region| region.region_id == wilds).map(|region| (tick, region.level, region.elapsed_ticks)));
+    }
+    assert_eq!(wild_ticks, vec![(5, SimulationLevel::Reduced, 5), (10, SimulationLevel::Reduced, 5)], "The wilds should be simulated every 5 ticks");
+    println!("{}", "Regions without players were simulated at reduced frequency".green());
+
+    // The player walks into the wilds; its first full tick covers the ticks it skipped
+    vault_manager.move_object(player, [1000.0, 0.0, 0.0])?;
+    let scheduled = vault_manager.schedule_tick(13, &policy)?;
+    let wild_tick = scheduled.iter().find(|region| region.region_id == wilds).ok_or("The wilds should be simulated")?;
+    assert_eq!((wild_tick.level, wild_tick.elapsed_ticks), (SimulationLevel::Full, 3), "The wilds should catch up on the skipped ticks");
+    assert!(scheduled.iter().all(|region| region.region_id != town), "The town should drop to reduced frequency");
+    assert_eq!(vault_manager.last_simulated_tick(town), Some(12), "The town's last tick should be recorded");
+    println!("{}", "Returning players resumed full simulation".green());
+
+    println!("{}", "Simulation LOD test passed".green());
+    Ok(())
+}
+
+/// Tests transferring objects between regions with and without keeping their position.
+fn test_transfer_object(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Transfer Object ----".blue());
+
+    let cart = Uuid::new_v4();
+    let crate_id = Uuid::new_v4();
+    let (west, east) = {
+        let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+        // Overlapping cubes, so a cart near the border lies within both
+        let west = vault_manager.create_or_load_region([-40.0, 0.0, 0.0], 50.0)?;
+        let east = vault_manager.create_or_load_region([40.0, 0.0, 0.0], 50.0)?;
+        let data = Arc::new(TestCustomData { name: "Cargo".to_string(), value: 7 });
+        vault_manager.add_object(west, cart, "vehicle", 5.0, 1.0, 2.0, data.clone())?;
+        vault_manager.add_object(west, crate_id, "item", -80.0, 0.0, 0.0, data)?;
+
+        vault_manager.transfer_object(cart, west, east, true)?;
+        assert!(vault_manager.query_radius(west, [5.0, 1.0, 2.0], 0.1)?.is_empty(), "The cart should leave the western region");
+        let found = vault_manager.query_radius(east, [5.0, 1.0, 2.0], 0.1)?;
+        assert_eq!(found.len(), 1, "The cart should keep its coordinates in the eastern region");
+        println!("{}", "Object kept its position across regions".green());
+
+        // The crate lies outside the eastern cube, so its coordinates can't be kept
+        assert!(vault_manager.transfer_object(crate_id, west, east, true).is_err(), "Coordinates outside the destination should be rejected");
+        assert_eq!(vault_manager.query_radius(west, [-80.0, 0.0, 0.0], 0.1)?.len(), 1, "A rejected transfer should leave the object in place");
+        vault_manager.transfer_object(crate_id, west, east, false)?;
+        assert_eq!(vault_manager.query_radius(east, [40.0, 0.0, 0.0], 0.1)?.len(), 1, "The crate should move to the destination center");
+        println!("{}", "Object outside the destination was moved to its center".green());
+
+        // Transferring within a region must not deadlock
+        vault_manager.transfer_object(cart, east, east, false)?;
+        assert_eq!(vault_manager.query_radius(east, [40.0, 0.0, 0.0], 0.1)?.len(), 2, "Both objects should be at the center");
+        vault_manager.transfer_object(cart, east, east, true)?;
+        assert!(vault_manager.transfer_object(cart, west, east, true).is_err(), "Objects missing from the source should be rejected");
+        println!("{}", "Transfers within a region succeeded".green());
+        (west, east)
+    };
+
+    // The transfers were written straight to the persistent database
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    assert!(vault_manager.query_radius(west, [0.0, 0.0, 0.0], 1000.0)?.is_empty(), "The western region should be empty after reload");
+    assert_eq!(vault_manager.query_radius(east, [40.0, 0.0, 0.0], 0.1)?.len(), 2, "The eastern region should hold both objects after reload");
+    println!("{}", "Transfers were persisted".green());
+
+    println!("{}", "Transfer object test passed".green());
+    Ok(())
+}
+
+/// Tests moving an object between two vaults backed by separate databases.
+fn test_transfer_between_vaults(src_path: &str, dst_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Transfer Between Vaults ----".blue());
+
+    let traveller = Uuid::new_v4();
+    let data = Arc::new(TestCustomData { name: "Traveller".to_string(), value: 42 });
+    let dst_region = {
+        let src: VaultManager<TestCustomData> = VaultManager::new(src_path)?;
+        let dst: VaultManager<TestCustomData> = VaultManager::new(dst_path)?;
+        let src_region = src.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+        let dst_region = dst.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+        src.add_object(src_region, traveller, "player", 3.0, 4.0, 5.0, data.clone())?;
+
+        VaultManager::transfer_between_vaults(&src, &dst, traveller, dst_region)?;
+        assert!(src.get_object(traveller)?.is_none(), "The object should leave the source vault");
+        let arrived = dst.get_object(traveller)?.ok_or("The object should arrive in the destination vault")?;
+        assert_eq!(arrived.point, [3.0, 4.0, 5.0], "The object should keep its position");
+        assert_eq!(arrived.custom_data, data, "The object should keep its custom data");
+        println!("{}", "Object moved between vaults".green());
+
+        // Transfers that can't complete leave both vaults untouched
+        assert!(VaultManager::transfer_between_vaults(&src, &dst, traveller, dst_region).is_err(), "Unknown objects can't be transferred");
+        let other = Uuid::new_v4();
+        src.add_object(src_region, other, "player", 1.0, 1.0, 1.0, data.clone())?;
+        assert!(VaultManager::transfer_between_vaults(&src, &dst, other, Uuid::new_v4()).is_err(), "Unknown destination regions should be rejected");
+        assert!(src.get_object(other)?.is_some(), "A failed transfer should leave the object in the source vault");
+        println!("{}", "Failed transfers left both vaults untouched".green());
+        dst_region
+    };
+
+    // Both databases reflect the transfer
+    let src: VaultManager<TestCustomData> = VaultManager::new(src_path)?;
+    let dst: VaultManager<TestCustomData> = VaultManager::new(dst_path)?;
+    assert!(src.get_object(traveller)?.is_none(), "The source database should no longer hold the object");
+    assert_eq!(dst.query_radius(dst_region, [3.0, 4.0, 5.0], 0.1)?.len(), 1, "The destination database should hold the object");
+    println!("{}", "Transfer was persisted in both vaults".green());
+
+    println!("{}", "Transfer between vaults test passed".green());
+    Ok(())
+}
+
+/// Tests iterating a frozen snapshot of a region while it changes.
+fn test_region_snapshot(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Region Snapshots ----".blue());
+
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+    let data = Arc::new(TestCustomData { name: "Snapshot".to_string(), value: 1 });
+    let removed = Uuid::new_v4();
+    vault_manager.add_object(region_id, removed, "tree", 1.0, 0.0, 0.0, data.clone())?;
+    vault_manager.add_object(region_id, Uuid::new_v4(), "tree", 2.0, 0.0, 0.0, data.clone())?;
+
+    let snapshot = vault_manager.iter_region_snapshot(region_id)?;
+    assert_eq!(snapshot.region_id, region_id, "The snapshot should name its region");
+
+    // The region isn't locked while the snapshot is held, and changes don't leak into it
+    vault_manager.add_object(region_id, Uuid::new_v4(), "tree", 3.0, 0.0, 0.0, data.clone())?;
+    vault_manager.remove_object(removed)?;
+    assert_eq!(snapshot.len(), 2, "The snapshot should keep its original objects");
+    assert!(snapshot.iter().any(|obj| obj.uuid == removed), "Removed objects should remain in the snapshot");
+    assert_eq!((&snapshot).into_iter().count(), 2, "Iteration should yield every object once");
+    assert_eq!(snapshot.rtree().locate_within_distance([3.0, 0.0, 0.0], 0.1).count(), 0, "Later additions should not be visible");
+    println!("{}", "Snapshot stayed consistent while the region changed".green());
+
+    let fresh = vault_manager.iter_region_snapshot(region_id)?;
+    assert_eq!(fresh.len(), 2, "A new snapshot should see the current state");
+    assert!(!fresh.iter().any(|obj| obj.uuid == removed), "A new snapshot should not see removed objects");
+    assert!(vault_manager.iter_region_snapshot(Uuid::new_v4()).is_err(), "Unknown regions should be rejected");
+    println!("{}", "New snapshots reflect the current state".green());
+
+    println!("{}", "Region snapshot test passed".green());
+    Ok(())
+}
+
+/// Tests catching reconnecting clients up with snapshots and deltas.
+fn test_catch_up(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Catch-Up ----".blue());
+
+    let data = Arc::new(TestCustomData { name: "Crate".to_string(), value: 1 });
+    let token = {
+        let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+        let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+        let other_region = vault_manager.create_or_load_region([300.0, 0.0, 0.0], 100.0)?;
+        let ids: Vec<Uuid> = (0..10).map(|_| Uuid::new_v4()).collect();
+        for (i, id) in ids.iter().enumerate() {
+            vault_manager.add_object(region_id, *id, "resource", i as f64, 0.0, 0.0, data.clone())?;
+        }
+
+        // A client joining for the first time gets the whole region
+        let catch_up = vault_manager.catch_up(region_id, None)?;
+        let CatchUp::Snapshot { objects, token } = catch_up else {
+            return Err("A first join should get a snapshot".to_string());
+        };
+        assert_eq!(objects.len(), 10, "The snapshot should hold every object");
+        println!("{}", "First join got a snapshot".green());
+
+        // A client that missed a few changes only gets those
+        let mut updated = vault_manager.get_object(ids[0])?.ok_or("Object should exist")?;
+        updated.custom_data = Arc::new(TestCustomData { name: "Opened".to_string(), value: 2 });
+        vault_manager.update_object(&updated)?;
+        vault_manager.move_object(ids[1], [50.0, 0.0, 0.0])?;
+        vault_manager.remove_object(ids[2])?;
+        vault_manager.transfer_object(ids[3], region_id, other_region, false)?;
+        let added = Uuid::new_v4();
+        vault_manager.add_object(region_id, added, "resource", 20.0, 0.0, 0.0, data.clone())?;
+
+        let catch_up = vault_manager.catch_up(region_id, Some(token))?;
+        let CatchUp::Delta { changed, mut removed, token } = catch_up else {
+            return Err("A recent token should get a delta".to_string());
+        };
+        let mut changed: Vec<Uuid> = changed.iter().map(|obj| obj.uuid).collect();
+        changed.sort();
+        let mut expected = vec![ids[0], ids[1], added];
+        expected.sort();
+        assert_eq!(changed, expected, "Updated, moved and added objects should be in the delta");
+        removed.sort();
+        let mut expected = vec![ids[2], ids[3]];
+        expected.sort();
+        assert_eq!(removed, expected, "Removed and transferred objects should be in the delta");
+
+        let catch_up = vault_manager.catch_up(region_id, Some(token))?;
+        assert!(matches!(&catch_up, CatchUp::Delta { changed, removed, .. } if changed.is_empty() && removed.is_empty()), "An up-to-date client should get an empty delta");
+        println!("{}", "Reconnecting client got a delta".green());
+
+        // Tokens of other regions can't be used for deltas
+        let other_token = vault_manager.catch_up(other_region, None)?.token();
+        assert!(vault_manager.catch_up(region_id, Some(other_token))?.is_snapshot(), "A foreign token should get a snapshot");
+        catch_up.token()
+    };
+
+    // Reopening the vault invalidates old tokens
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let catch_up = vault_manager.catch_up(token.region_id, Some(token))?;
+    assert!(catch_up.is_snapshot(), "A token from before a restart should get a snapshot");
+    println!("{}", "Stale tokens got a snapshot".green());
+
+    println!("{}", "Catch-up test passed".green());
+    Ok(())
+}
+
+/// Tests world queries that read regions not loaded in memory from the database.
+fn test_query_world_cold(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Cold Region Queries ----".blue());
+
+    let data = Arc::new(TestCustomData { name: "Cold".to_string(), value: 5 });
+    let hot: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let hot_region = hot.create_or_load_region([0.0, 0.0, 0.0], 50.0)?;
+    hot.add_object(hot_region, Uuid::new_v4(), "tree", 10.0, 0.0, 0.0, data.clone())?;
+
+    // A second manager adds a region the first one never loaded
+    let cold_object = Uuid::new_v4();
+    {
+        let other: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+        let cold_region = other.create_or_load_region([200.0, 0.0, 0.0], 50.0)?;
+        other.add_object(cold_region, cold_object, "rock", 210.0, 0.0, 0.0, data.clone())?;
+        other.add_object(cold_region, Uuid::new_v4(), "rock", 240.0, 30.0, 0.0, data.clone())?;
+    }
+    assert_eq!(hot.query_all(-500.0, -500.0, -500.0, 500.0, 500.0, 500.0)?.len(), 1, "Only the loaded region is searched in memory");
+
+    let found = hot.query_world_cold([-500.0, -500.0, -500.0], [500.0, 500.0, 500.0])?;
+    assert_eq!(found.len(), 3, "Objects in unloaded regions should be read from the database");
+    let found = hot.query_world_cold([0.0, -5.0, -5.0], [220.0, 5.0, 5.0])?;
+    assert_eq!(found.len(), 2, "The query box should apply to both sources");
+    let cold = found.iter().find(|obj| obj.uuid == cold_object).ok_or("The cold object should be returned")?;
+    assert_eq!(cold.custom_data, data, "Custom data should be read from the database");
+    println!("{}", "Loaded and unloaded regions were merged".green());
+
+    // In-memory state wins for loaded regions
+    hot.add_object(hot_region, Uuid::new_v4(), "tree", 20.0, 0.0, 0.0, data)?;
+    let found = hot.query_world_cold([0.0, -5.0, -5.0], [100.0, 5.0, 5.0])?;
+    assert_eq!(found.len(), 2, "Loaded regions should not be returned twice");
+    println!("{}", "Loaded regions were not duplicated".green());
+
+    // A sized object is matched the same way whether its region is loaded or not, even outside the region's bounds
+    let beam = Uuid::new_v4();
+    hot.add_spatial_object(hot_region, SpatialObject {
+        uuid: beam,
+        object_type: ObjectType::from("beam"),
+        point: [80.0, 0.0, 0.0],
+        custom_data: Arc::new(TestCustomData { name: "Beam".to_string(), value: 7 }),
+        size: [10.0, 1.0, 1.0],
+        version: 0,
+        velocity: None,
+        orientation: None,
+    })?;
+    let finds_beam = |min: [f64; 3], max: [f64; 3]| hot.query_world_cold(min, max)
+        .map(|found| found.iter().any(|obj| obj.uuid == beam));
+    for loaded in [true, false] {
+        if !loaded {
+            hot.persist_to_disk()?;
+            hot.unload_region(hot_region)?;
+        }
+        assert!(finds_beam([70.0, -5.0, -5.0], [90.0, 5.0, 5.0])?, "A box holding the beam should find it (loaded: {})", loaded);
+        assert!(!finds_beam([78.0, -5.0, -5.0], [90.0, 5.0, 5.0])?, "A box cutting through the beam shouldn't find it (loaded: {})", loaded);
+    }
+    assert!(!hot.is_region_loaded(hot_region), "The query shouldn't load the region");
+    println!("{}", "Loaded and unloaded regions matched sized objects alike".green());
+
+    // The database matches points by their whole extent, not just their position
+    let db = MySQLGeo::Database::new(db_path).map_err(|e| format!("Failed to open database: {}", e))?;
+    let mut wall = Point::new(Some(Uuid::new_v4()), 300.0, 0.0, 0.0, "wall".to_string(), serde_json::json!({ "name": "Wall", "value": 1 }));
+    wall.size = [20.0, 1.0, 1.0];
+    db.add_point(&wall, Uuid::new_v4()).map_err(|e| format!("Failed to add point: {}", e))?;
+    let in_box = |min: [f64; 3], max: [f64; 3]| db.get_points_in_aabb(min, max)
+        .map(|points| points.iter().any(|(_, point)| point.id == wall.id))
+        .map_err(|e| format!("Failed to query points: {}", e));
+    assert!(in_box([289.0, -1.0, -1.0], [311.0, 1.0, 1.0])?, "A box holding the wall's extent should find it");
+    assert!(!in_box([295.0, -1.0, -1.0], [311.0, 1.0, 1.0])?, "A box cutting through the wall shouldn't find it");
+    assert!(!in_box([289.0, -1.0, -1.0], [299.0, 1.0, 1.0])?, "A box missing the wall's center shouldn't find it");
+    println!("{}", "Database box queries matched extents".green());
+
+    println!("{}", "Cold region query test passed".green());
+    Ok(())
+}
+
+/// Tests unloading regions, LRU eviction and transparent reloading.
+fn test_region_unloading(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Region Unloading ----".blue());
+
+    let config = VaultConfig { max_loaded_regions: Some(2), ..VaultConfig::default() };
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::with_config(db_path, config.clone())?;
+    let data = Arc::new(TestCustomData { name: "Resident".to_string(), value: 1 });
+    let mut regions = Vec::new();
+    let mut objects = Vec::new();
+    for i in 0..3 {
+        let region_id = vault_manager.create_or_load_region([i as f64 * 100.0, 0.0, 0.0], 50.0)?;
+        let object_id = Uuid::new_v4();
+        vault_manager.add_object(region_id, object_id, "tree", i as f64 * 100.0, 0.0, 0.0, data.clone())?;
+        regions.push(region_id);
+        objects.push(object_id);
+    }
+    assert!(!vault_manager.is_region_loaded(regions[0]), "The least recently used region should be unloaded");
+    assert!(vault_manager.is_region_loaded(regions[1]) && vault_manager.is_region_loaded(regions[2]), "Recent regions should stay loaded");
+    println!("{}", "Least recently used region was unloaded".green());
+
+    // Accessing an unloaded region reloads it and evicts the next least recently used one
+    let found = vault_manager.query_region(regions[0], -10.0, -10.0, -10.0, 10.0, 10.0, 10.0)?;
+    assert_eq!(found.len(), 1, "The reloaded region should hold its object");
+    assert!(!vault_manager.is_region_loaded(regions[1]), "Reloading should evict another region");
+    let object = vault_manager.get_object(objects[1])?.ok_or("Objects of unloaded regions should be found")?;
+    assert!(vault_manager.is_region_loaded(regions[1]), "Finding an object should load its region");
+    println!("{}", "Unloaded regions were reloaded on access".green());
+
+    // In-memory changes are flushed when a region is unloaded
+    let updated = SpatialObject { custom_data: Arc::new(TestCustomData { name: "Updated".to_string(), value: 2 }), ..object };
+    vault_manager.update_object(&updated)?;
+    vault_manager.unload_region(regions[1])?;
+    assert!(!vault_manager.is_region_loaded(regions[1]), "The region should be unloaded explicitly");
+    let reloaded = vault_manager.get_object(objects[1])?.ok_or("The updated object should reload")?;
+    assert_eq!(reloaded.custom_data.value, 2, "Changes should survive unloading");
+    println!("{}", "Unloading flushed in-memory changes".green());
+
+    // Persisting leaves unloaded regions' points alone
+    vault_manager.unload_region(regions[2])?;
+    vault_manager.persist_to_disk()?;
+    vault_manager.remove_object(objects[2])?;
+    drop(vault_manager);
+    let reopened: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    assert_eq!(reopened.load_report().total_points(), 2, "Only the removed object should be gone");
+    assert!(reopened.get_object(objects[0])?.is_some(), "Objects of unloaded regions should survive persisting");
+    drop(reopened);
+    println!("{}", "Persisting kept unloaded regions' objects".green());
+
+    // Startup only loads up to the limit
+    let limited: VaultManager<TestCustomData> = VaultManager::with_config(db_path, config)?;
+    assert_eq!(limited.load_report().regions_loaded, 2, "Startup should respect the residency limit");
+    assert_eq!(limited.region_count(), 3, "Every region should still be known");
+    println!("{}", "Startup respected the residency limit".green());
+
+    println!("{}", "Region unloading test passed".green());
+    Ok(())
+}
+
+/// Tests opening a vault without loading any objects up front.
+fn test_lazy_loading(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Lazy Loading ----".blue());
+
+    let data = Arc::new(TestCustomData { name: "Lazy".to_string(), value: 3 });
+    let object_id = Uuid::new_v4();
+    let (near, far) = {
+        let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+        let near = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 50.0)?;
+        let far = vault_manager.create_or_load_region([500.0, 0.0, 0.0], 50.0)?;
+        vault_manager.add_object(near, Uuid::new_v4(), "tree", 1.0, 0.0, 0.0, data.clone())?;
+        vault_manager.add_object(far, object_id, "rock", 510.0, 0.0, 0.0, data.clone())?;
+        (near, far)
+    };
+
+    let config = VaultConfig { lazy_load: true, ..VaultConfig::default() };
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::with_config(db_path, config)?;
+    assert_eq!(vault_manager.load_report().regions_loaded, 0, "No region should be loaded at startup");
+    assert_eq!(vault_manager.region_count(), 2, "Region metadata should be loaded");
+    assert!(!vault_manager.is_region_loaded(near) && !vault_manager.is_region_loaded(far), "Regions should start unloaded");
+    println!("{}", "Only region metadata was loaded".green());
+
+    let found = vault_manager.query_region(near, -10.0, -10.0, -10.0, 10.0, 10.0, 10.0)?;
+    assert_eq!(found.len(), 1, "The first query should load the region's objects");
+    assert!(vault_manager.is_region_loaded(near), "The queried region should be loaded");
+    assert!(!vault_manager.is_region_loaded(far), "Other regions should stay unloaded");
+
+    let object = vault_manager.get_object(object_id)?.ok_or("Objects should be found in unloaded regions")?;
+    assert_eq!(object.custom_data, data, "The object should load with its custom data");
+    assert!(vault_manager.is_region_loaded(far), "Finding an object should load its region");
+    println!("{}", "Regions were loaded on first access".green());
+
+    println!("{}", "Lazy loading test passed".green());
+    Ok(())
+}
+
+/// Tests that persisting writes only the objects changed since the last flush.
+fn test_dirty_persistence(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Incremental Persistence ----".blue());
+
+    let data = Arc::new(TestCustomData { name: "Unchanged".to_string(), value: 1 });
+    let updated_id = Uuid::new_v4();
+    let moved_id = Uuid::new_v4();
+    let (west, east) = {
+        let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+        let west = vault_manager.create_or_load_region([-100.0, 0.0, 0.0], 100.0)?;
+        let east = vault_manager.create_or_load_region([100.0, 0.0, 0.0], 100.0)?;
+        for i in 0..20 {
+            vault_manager.add_object(west, Uuid::new_v4(), "tree", -(i as f64) - 1.0, 0.0, 0.0, data.clone())?;
+        }
+        vault_manager.add_object(west, updated_id, "building", -50.0, 0.0, 0.0, data.clone())?;
+        vault_manager.add_object(west, moved_id, "building", -60.0, 0.0, 0.0, data.clone())?;
+
+        // Nothing is written when nothing changed
+        let before = vault_manager.io_stats();
+        vault_manager.persist_to_disk()?;
+        assert_eq!(vault_manager.io_stats().since(&before).files_written, 0, "A clean persist should write nothing");
+        println!("{}", "Clean persist wrote nothing".green());
+
+        for (object_id, name) in [(updated_id, "Updated"), (moved_id, "Moved")] {
+            let mut object = vault_manager.get_object(object_id)?
+                .ok_or_else(|| format!("Object not found: {}", object_id))?;
+            object.custom_data = Arc::new(TestCustomData { name: name.to_string(), value: 2 });
+            vault_manager.update_object(&object)?;
+        }
+        let region = vault_manager.get_region(west).ok_or("The region should exist")?;
+        assert_eq!(region.lock().unwrap().dirty_count(), 2, "Both updated objects should be dirty");
+
+        // Moving an object carries its unflushed changes to the new region
+        vault_manager.move_object(moved_id, [60.0, 0.0, 0.0])?;
+        assert!(!region.lock().unwrap().is_dirty(moved_id), "The source region should no longer track the moved object");
+        let east_region = vault_manager.get_region(east).ok_or("The region should exist")?;
+        assert!(east_region.lock().unwrap().is_dirty(moved_id), "The destination region should track the moved object");
+        println!("{}", "Changes were tracked per region".green());
+
+        let before = vault_manager.io_stats();
+        vault_manager.persist_to_disk()?;
+        let cost = vault_manager.io_stats().since(&before);
+        assert_eq!(cost.files_written, 2, "Only the two changed objects should be written");
+        assert!(!vault_manager.has_unflushed_changes(), "Persisting should clear the dirty sets");
+        println!("Delta persist wrote {} data files", cost.files_written.to_string().cyan());
+        (west, east)
+    };
+
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let updated = vault_manager.get_object(updated_id)?.ok_or("The updated object should survive a restart")?;
+    assert_eq!(updated.custom_data.name, "Updated", "The update should have been persisted");
+    let moved = vault_manager.get_object(moved_id)?.ok_or("The moved object should survive a restart")?;
+    assert_eq!(moved.custom_data.name, "Moved", "The moved object's update should have been persisted");
+    assert_eq!(moved.point, [60.0, 0.0, 0.0], "The move should have been persisted");
+    let unchanged = vault_manager.query_region(west, -25.0, -10.0, -10.0, 0.0, 10.0, 10.0)?;
+    assert_eq!(unchanged.len(), 20, "Unchanged objects should be left intact");
+    assert_eq!(vault_manager.query_region(east, 50.0, -10.0, -10.0, 70.0, 10.0, 10.0)?.len(), 1, "The moved object should be stored in its new region");
+    println!("{}", "Only the changes were flushed and everything else survived".green());
+
+    println!("{}", "Incremental persistence test passed".green());
+    Ok(())
+}
+
+/// Tests that budgeted persists write the most important changes first and spread the rest over ticks.
+fn test_persist_budget(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Persistence Budget ----".blue());
+
+    let player = Uuid::new_v4();
+    let resources: Vec<Uuid> = (0..200).map(|_| Uuid::new_v4()).collect();
+    {
+        let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+        let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+        let data = Arc::new(TestCustomData { name: "Start".to_string(), value: 0 });
+        vault_manager.add_object(region_id, player, "player", 0.0, 0.0, 0.0, data.clone())?;
+        for (i, uuid) in resources.iter().enumerate() {
+            vault_manager.add_object(region_id, *uuid, "resource", i as f64 / 10.0, 1.0, 0.0, data.clone())?;
+        }
+        assert_eq!(vault_manager.persist_tick(&PersistBudget::per_tick(Duration::ZERO))?, 0, "Nothing should be written when nothing changed");
+
+        // The player changes first, so only its priority puts it ahead of the resources
+        for uuid in std::iter::once(&player).chain(&resources) {
+            let mut object = vault_manager.get_object(*uuid)?.ok_or("The object should exist")?;
+            object.custom_data = Arc::new(TestCustomData { name: "Saved".to_string(), value: 1 });
+            vault_manager.update_object(&object)?;
+        }
+
+        let budget = PersistBudget::per_tick(Duration::ZERO).with_priority("player", 10);
+        assert_eq!(budget.priority(ObjectType::from("resource")), 0, "Unlisted types should have priority 0");
+        assert_eq!(vault_manager.persist_tick(&budget)?, 1, "An exhausted budget should still write one object");
+        let region = vault_manager.get_region(region_id).ok_or("The region should exist")?;
+        assert!(!region.lock().unwrap().is_dirty(player), "The player should be written first");
+        assert!(region.lock().unwrap().is_dirty(*resources.last().unwrap()), "Resources should wait for a later tick");
+        println!("{}", "Highest priority object was written first".green());
+
+        // Without priorities the most recent change goes first
+        assert_eq!(vault_manager.persist_tick(&PersistBudget::per_tick(Duration::ZERO))?, 1, "One object should be written");
+        assert!(!region.lock().unwrap().is_dirty(*resources.last().unwrap()), "The most recent change should be written first");
+        println!("{}", "Most recent change was written next".green());
+
+        let budget = PersistBudget::per_tick(Duration::from_millis(2));
+        let mut ticks = 0;
+        let mut written = 2;
+        while vault_manager.has_unflushed_changes() {
+            written += vault_manager.persist_tick(&budget)?;
+            ticks += 1;
+        }
+        assert_eq!(written, resources.len() + 1, "Every changed object should be written exactly once");
+        println!("Remaining changes were written over {} ticks", ticks.to_string().cyan());
+    }
+
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    for uuid in std::iter::once(&player).chain(&resources) {
+        let object = vault_manager.get_object(*uuid)?.ok_or("The object should survive a restart")?;
+        assert_eq!(object.custom_data.name, "Saved", "Budgeted writes should be persisted");
+    }
+    println!("{}", "Budgeted writes survived a restart".green());
+
+    println!("{}", "Persistence budget test passed".green());
+    Ok(())
+}
+
+/// Tests the background worker flushing changes and being drained on shutdown.
+fn test_auto_persist(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Auto-Persist ----".blue());
+
+    let object_id = Uuid::new_v4();
+    {
+        let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+        let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+        vault_manager.add_object(region_id, object_id, "player", 1.0, 2.0, 3.0, Arc::new(TestCustomData { name: "Start".to_string(), value: 0 }))?;
+
+        // The interval is far off, so only the mutation count can trigger the flush
+        vault_manager.enable_auto_persist(AutoPersist::every(Duration::from_secs(3600)).or_after(2))?;
+        assert!(vault_manager.enable_auto_persist(AutoPersist::every(Duration::from_secs(1))).is_err(), "Auto-persist should only be enabled once");
+
+        let mut object = vault_manager.get_object(object_id)?.ok_or("The object should exist")?;
+        for value in 1..=2 {
+            object.custom_data = Arc::new(TestCustomData { name: "Flushed".to_string(), value });
+            object.version = vault_manager.update_object(&object)?;
+        }
+        let deadline = std::time::Instant::now() + Duration::from_secs(10);
+        while vault_manager.has_unflushed_changes() && std::time::Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert!(!vault_manager.has_unflushed_changes(), "The worker should flush after two mutations");
+        let stored = vault_manager.database().get_points_within_radius(1.0, 2.0, 3.0, 0.5)
+            .map_err(|e| format!("Failed to load objects from persistent database: {}", e))?;
+        assert_eq!(stored[0].custom_data["value"], 2, "The worker should have written the latest data");
+        println!("{}", "Worker flushed after the mutation threshold".green());
+
+        // A single change stays below the threshold until shutdown drains the worker
+        object.custom_data = Arc::new(TestCustomData { name: "Drained".to_string(), value: 3 });
+        vault_manager.update_object(&object)?;
+        vault_manager.shutdown()?;
+    }
+
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let object = vault_manager.get_object(object_id)?.ok_or("The object should survive a restart")?;
+    assert_eq!(object.custom_data.name, "Drained", "Shutdown should drain the worker");
+    println!("{}", "Shutdown drained the worker".green());
+
+    println!("{}", "Auto-persist test passed".green());
+    Ok(())
+}
+
+/// Tests calling a VaultManager through its async front end.
+fn test_async_api(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Async API ----".blue());
+
+    let object_id = Uuid::new_v4();
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let vault = VaultManagerAsync::new(vault_manager)?;
+    let region_id = block_on(vault.run(|vm| vm.create_or_load_region([0.0, 0.0, 0.0], 100.0)))??;
+
+    let object = SpatialObject::new(object_id, "player", [1.0, 2.0, 3.0], Arc::new(TestCustomData { name: "Async".to_string(), value: 1 }))
+        .map_err(|e| format!("Invalid object: {}", e))?;
+    block_on(vault.add_spatial_object(region_id, object))?;
+
+    // Calls run in the order they were made, whatever order they're awaited in
+    let moved = vault.move_object(object_id, [5.0, 5.0, 5.0]);
+    let found = vault.get_object(object_id);
+    let found = block_on(found)?.ok_or("The object should be found")?;
+    assert_eq!(found.point, [5.0, 5.0, 5.0], "The earlier move should have been applied first");
+    assert_eq!(block_on(moved)?, region_id, "The object should stay in its region");
+    println!("{}", "Calls were answered in order".green());
+
+    let mut updated = found.clone();
+    updated.custom_data = Arc::new(TestCustomData { name: "Async".to_string(), value: 2 });
+    block_on(vault.update_object(updated))?;
+    assert!(block_on(vault.get_object(Uuid::new_v4()))?.is_none(), "Unknown objects should resolve to None");
+    assert!(block_on(vault.remove_object(Uuid::new_v4())).is_err(), "Errors should be passed through");
+    assert_eq!(block_on(vault.query_region(region_id, [0.0; 3], [10.0; 3]))?.len(), 1, "The query should find the object");
+    block_on(vault.shutdown())?;
+    println!("{}", "Async front end shut down".green());
+
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let object = vault_manager.get_object(object_id)?.ok_or("The object should survive a restart")?;
+    assert_eq!(object.custom_data.value, 2, "Shutdown should flush the async update");
+    println!("{}", "Async changes were persisted".green());
+
+    println!("{}", "Async API test passed".green());
+    Ok(())
+}
+
+/// Tests sphere queries against the in-memory index.
+fn test_radius_query(db_path: &str) -> Result<(), String> {
+    // Print the test header
+    println!("\n{}", "---- Testing Radius Queries ----".blue());
+
+    let vault_manager: VaultManager<TestCustomData> = VaultManager::new(db_path)?;
+    let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0)?;
+
+    // One object inside the sphere, one in the corner of its bounding cube
+    let inside = Uuid::new_v4();
+    let data = Arc::new(TestCustomData { name: "Radius".to_string(), value: 4 });
+    vault_manager.add_object(region_id, inside, "resource", 6.0, 0.0, 0.0, data.clone())?;
+    vault_manager.add_object(region_id, Uuid::new_v4(), "resource", 9.0, 9.0, 9.0, data)?;
+
+    let cube = vault_manager.query_region(region_id, -10.0, -10.0, -10.0, 10.0, 10.0, 10.0)?;
+    assert_eq!(cube.len(), 2, "The bounding cube contains both objects");
+    let sphere = vault_manager.query_radius(region_id, [0.0, 0.0, 0.0], 10.0)?;
+    println!("Radius query returned {} objects", sphere.len().to_string().cyan());
+    assert_eq!(sphere.len(), 1, "The corner object lies outside the sphere");
+    assert_eq!(sphere[0].uuid, inside, "Only the object inside the sphere should be returned");
+    println!("{}", "Radius query excluded the corner object".green());
+
+    println!("{}", "Radius query test passed".green());
+    Ok(())
+}
@@ -0,0 +1,140 @@
+//! # Object Type Registry
+//!
+//! This module defines `TypeDefinition`, the entry of a `VaultManager`'s object type registry.
+//! Registering a type declares that objects of it belong in the vault and describes what they
+//! may look like. Objects breaking the rules of their type are rejected when they're added or
+//! updated, instead of ending up in the store.
+//!
+//! The custom data of a type's objects can be checked with a `DataSchema`, a validation
+//! closure, or both. Either only sees the custom data serialized to JSON, as it's stored.
+//!
+//! Every manager starts with "player", "building" and "resource" registered. Objects of types
+//! that aren't registered are accepted unless `VaultConfig::reject_unknown_types` is set.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use your_crate::{TypeDefinition, VaultManager, VaultConfig, CustomData};
+//!
+//! let config = VaultConfig { reject_unknown_types: true, ..VaultConfig::default() };
+//! let vault_manager: VaultManager<CustomData> = VaultManager::with_config("path/to/database.db", config).unwrap();
+//! vault_manager.register_object_type("npc");
+//! vault_manager.register_object_type(TypeDefinition::new("vehicle").with_max_size([10.0, 5.0, 20.0]));
+//! vault_manager.register_object_type(TypeDefinition::new("chest").with_validator(|data| {
+//!     match data["capacity"].as_u64() {
+//!         Some(1..=64) => Ok(()),
+//!         _ => Err("capacity must be between 1 and 64".to_string()),
+//!     }
+//! }));
+//! ```
+
+use std::fmt;
+use std::sync::Arc;
+use serde::Serialize;
+use serde_json::Value as Json;
+use crate::object_type::ObjectType;
+use crate::schema::DataSchema;
+
+/// The closure wrapped by a `DataValidator`
+type ValidateFn = dyn Fn(&Json) -> Result<(), String> + Send + Sync;
+
+/// A closure checking the custom data of objects, given as JSON.
+///
+/// Created by `TypeDefinition::with_validator`. Two validators are equal if they're the same closure.
+#[derive(Clone)]
+pub struct DataValidator(Arc<ValidateFn>);
+
+impl fmt::Debug for DataValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("DataValidator(..)")
+    }
+}
+
+impl PartialEq for DataValidator {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// An object type registered with a `VaultManager`, and the rules its objects must follow.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TypeDefinition {
+    /// The registered type
+    pub object_type: ObjectType,
+    /// Largest extent objects of the type may have along each axis, or `None` for any size
+    pub max_size: Option<[f64; 3]>,
+    /// Schema the custom data of the type's objects must match
+    pub schema: Option<DataSchema>,
+    /// Closure the custom data of the type's objects must pass
+    pub validator: Option<DataValidator>,
+}
+
+impl TypeDefinition {
+    /// Creates a definition without any rules beyond the type being known.
+    pub fn new(object_type: impl Into<ObjectType>) -> Self {
+        TypeDefinition { object_type: object_type.into(), max_size: None, schema: None, validator: None }
+    }
+
+    /// Rejects objects of the type that are larger than `max_size` along any axis.
+    pub fn with_max_size(mut self, max_size: [f64; 3]) -> Self {
+        self.max_size = Some(max_size);
+        self
+    }
+
+    /// Rejects objects of the type whose custom data doesn't match `schema`.
+    pub fn with_schema(mut self, schema: DataSchema) -> Self {
+        self.schema = Some(schema);
+        self
+    }
+
+    /// Rejects objects of the type whose custom data the closure returns an error for.
+    ///
+    /// # Arguments
+    ///
+    /// * `validator` - Receives the custom data serialized to JSON, and returns why it's invalid if it is.
+    pub fn with_validator(mut self, validator: impl Fn(&Json) -> Result<(), String> + Send + Sync + 'static) -> Self {
+        self.validator = Some(DataValidator(Arc::new(validator)));
+        self
+    }
+
+    /// Checks whether an object of the type with the given size and custom data follows the rules.
+    ///
+    /// The custom data is only serialized if a schema or validator is registered.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), String>` - An empty result if the object is valid, or a description of the broken rule.
+    pub(crate) fn check<T: Serialize>(&self, size: [f64; 3], custom_data: &T) -> Result<(), String> {
+        if let Some(max_size) = self.max_size {
+            if (0..3).any(|axis| size[axis] > max_size[axis]) {
+                return Err(format!("size {:?} exceeds the maximum of {:?} for type {}", size, max_size, self.object_type));
+            }
+        }
+        if self.schema.is_none() && self.validator.is_none() {
+            return Ok(());
+        }
+
+        let data = serde_json::to_value(custom_data).map_err(|e| format!("failed to serialize custom data: {}", e))?;
+        if let Some(schema) = &self.schema {
+            schema.validate(&data)
+                .map_err(|e| format!("custom data doesn't match the schema of type {}: {}", self.object_type, e))?;
+        }
+        if let Some(DataValidator(validator)) = &self.validator {
+            validator(&data)
+                .map_err(|e| format!("custom data rejected for type {}: {}", self.object_type, e))?;
+        }
+        Ok(())
+    }
+}
+
+impl From<&str> for TypeDefinition {
+    fn from(object_type: &str) -> Self {
+        TypeDefinition::new(object_type)
+    }
+}
+
+impl From<ObjectType> for TypeDefinition {
+    fn from(object_type: ObjectType) -> Self {
+        TypeDefinition::new(object_type)
+    }
+}
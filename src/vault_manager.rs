@@ -11,6 +11,7 @@
 //! - **Persistent Storage**: Saves and loads spatial data to/from a database.
 //! - **Efficient Querying**: Uses R-trees for fast spatial lookups within regions.
 //! - **Object Transfer**: Supports moving objects between regions.
+//! - **Thread Safety**: One manager can be shared between threads; each region is locked on its own.
 //!
 //! ## Usage Example
 //!
@@ -20,7 +21,7 @@
 //! use std::sync::Arc;
 //!
 //! // Initialize VaultManager
-//! let mut vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+//! let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
 //!
 //! // Create a new region
 //! let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0).unwrap();
@@ -46,18 +47,75 @@
 //!
 //! - The `VaultManager` uses R-trees for spatial indexing, providing O(log n) complexity for insertions and queries.
 //! - Regions are stored in-memory for fast access, with periodic persistence to disk.
+//! - Set `VaultConfig::max_loaded_regions` to keep only recently used regions in memory; idle regions are
+//!   unloaded and reloaded from disk on their next access.
 //! - Consider the trade-off between region size and number: larger regions mean fewer region transfers but potentially slower queries.
 //! - Custom data is stored as `Arc<T>`, allowing for efficient sharing of data between objects and reducing memory usage.
 
-use crate::structs::{VaultRegion, SpatialObject};
+use crate::structs::{VaultRegion, SpatialObject, LoadReport, LoadFailure, RaycastHit, RegionSnapshot, RegionQuery};
+use crate::config::{BoundsPolicy, QueryOrder, VaultConfig};
+use crate::object_type::ObjectType;
+use crate::frustum::{Frustum, FrustumSelection};
+use crate::pool::{ObjectPool, PoolStats};
+use crate::geometry::validate_point;
+use crate::filter::Filter;
+use crate::batch::{Mutation, VaultTransaction};
+use crate::operation::OperationHandle;
+use crate::capabilities::BackendCapabilities;
+use crate::simulation::{LodPolicy, RegionTick, SimulationLevel};
+use crate::auto_persist::{AutoPersist, AutoPersistWorker};
+use crate::persist_budget::PersistBudget;
+use crate::template::{RegionTemplate, TemplateObject, Transform};
+use crate::catch_up::{CatchUp, CatchUpToken};
+use crate::events::VaultEvent;
+use crate::change_log::{Change, ChangeLog};
+use crate::consistency::{ConsistencyStats, Divergence, DivergenceKind, VerifyCursor};
+use crate::type_registry::TypeDefinition;
+use crate::world_snapshot::WorldSnapshot;
+use crate::region_stats::RegionStats;
+use crate::hierarchy::Hierarchy;
+use crate::oriented_box::OrientedBox;
+use crate::region_file::{ObjectRecord, RegionFile, RegionRecord};
+use crate::archive::ArchiveChunk;
+use crate::io_stats::IoStats;
+use crate::lock_stats::{LockDiagnostics, LockRecorder, TimedGuard};
 use crate::MySQLGeo;
 use uuid::Uuid;
 use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
-use rstar::{RTree, AABB};
+use std::collections::hash_map::Entry;
+use std::path::Path;
+use std::sync::{Arc, Mutex, RwLock};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use rstar::{Envelope, PointDistance, RTree, RTreeObject, AABB};
 use indicatif::{ProgressBar, ProgressStyle};
 use serde::{Serialize, Deserialize};
-use crate::MySQLGeo::Point;
+use crate::MySQLGeo::{CorruptPoint, Point};
+use crate::cancellation::QueryLimits;
+
+/// Number of objects a limited query collects between checks of its `QueryLimits`, and a
+/// tracked import writes between checkpoints of its `OperationHandle`.
+const LIMIT_CHECK_INTERVAL: usize = 256;
+
+/// Number of points read from the persistent database at a time when a region is loaded
+const LOAD_PAGE_SIZE: usize = 1024;
+
+/// Distance along x and z within which an object is considered to be in a column queried with
+/// `query_column`, absorbing rounding in positions computed from grid coordinates
+pub const COLUMN_TOLERANCE: f64 = 1e-6;
+
+/// Object type of the players managed by the player API.
+const PLAYER_TYPE: &str = "player";
+
+/// A region, shared between the manager, threads using it and the auto-persist worker.
+type RegionHandle<T> = Arc<Mutex<VaultRegion<T>>>;
+
+/// A locked region, timed for the lock diagnostics.
+type RegionGuard<'a, T> = TimedGuard<'a, VaultRegion<T>>;
+
+/// Two objects whose bounding boxes overlap, found by `find_intersections`.
+type ObjectPair<T> = (SpatialObject<T>, SpatialObject<T>);
 
 /// Manages spatial regions and objects within a persistent database.
 ///
@@ -69,17 +127,53 @@ use crate::MySQLGeo::Point;
 /// flexibility for various use cases. The custom data is stored as `Arc<T>` to allow efficient sharing and
 /// reduce memory usage.
 ///
+/// Every method takes `&self`, and the manager is `Send` and `Sync` when `T` is, so a single
+/// `Arc<VaultManager<T>>` can be shared by all of a server's threads. Each region has its own
+/// lock, so threads working in different regions don't wait for each other.
+///
 /// # Type Parameters
 ///
 /// * `T`: The type of custom data associated with spatial objects. Must implement `Clone`, `Serialize`,
 ///        `Deserialize`, and `PartialEq`.
 pub struct VaultManager<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> {
     /// HashMap storing regions, keyed by their UUID
-    pub regions: HashMap<Uuid, Arc<Mutex<VaultRegion<T>>>>,
+    regions: RwLock<HashMap<Uuid, RegionHandle<T>>>,
+    /// Held while looking for a region to reuse and creating it, so a region isn't created twice
+    region_creation: Mutex<()>,
     /// Persistent database connection
-    pub persistent_db: MySQLGeo::Database,
-    /// HashMap storing object types
-    pub object_types: HashMap<String, String>,
+    persistent_db: MySQLGeo::Database,
+    /// Object types registered with the manager, and the rules of each
+    object_types: RwLock<HashMap<ObjectType, TypeDefinition>>,
+    /// Options the manager was opened with
+    config: VaultConfig,
+    /// Outcome of loading the persistent database at startup
+    load_report: RwLock<LoadReport>,
+    /// Custom data allocations of removed objects, kept for reuse
+    object_pool: Mutex<ObjectPool<T>>,
+    /// Regions whose objects are in memory, with the tick at which each was last accessed
+    loaded_regions: Mutex<HashMap<Uuid, u64>>,
+    /// Source of the access ticks in `loaded_regions`
+    access_clock: AtomicU64,
+    /// Usage counters of the region locks, shared with the auto-persist worker
+    locks: Arc<LockRecorder>,
+    /// Background thread flushing changed objects, if enabled
+    auto_persist: Mutex<Option<AutoPersistWorker<T>>>,
+    /// Tick at which each region was last simulated, for `schedule_tick`
+    simulation_ticks: Mutex<HashMap<Uuid, u64>>,
+    /// Region last known to hold each player, checked on use
+    players: Mutex<HashMap<Uuid, Uuid>>,
+    /// Region last known to hold each loaded object, checked on use
+    object_regions: Mutex<HashMap<Uuid, Uuid>>,
+    /// Time at which each object with a TTL expires, mirrored in the persistent database
+    expiries: Mutex<HashMap<Uuid, SystemTime>>,
+    /// Objects attached to other objects, mirrored in the persistent database
+    hierarchy: Mutex<Hierarchy>,
+    /// Channels of the subscribers to mutation events
+    subscribers: Mutex<Vec<Sender<VaultEvent<T>>>>,
+    /// Most recent mutation events with their sequence numbers, if enabled
+    change_log: Mutex<ChangeLog<T>>,
+    /// Progress and totals of the incremental consistency check
+    verifier: Mutex<VerifyCursor>,
 }
 
 impl<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> VaultManager<T> {
@@ -111,32 +205,173 @@ impl<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> Vault
     /// - The necessary tables cannot be created in the database
     /// - Existing regions cannot be loaded from the database
     pub fn new(db_path: &str) -> Result<Self, String> {
+        Self::with_config(db_path, VaultConfig::default())
+    }
+
+    /// Creates a new instance of `VaultManager` with the given configuration.
+    ///
+    /// This behaves like `new`, but lets callers tune how the manager is opened. The outcome of
+    /// loading the existing data is available afterwards through `load_report`.
+    ///
+    /// # Arguments
+    ///
+    /// * `db_path` - A string slice that holds the path to the database file.
+    /// * `config` - The options to open the manager with.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Self, String>` - A new `VaultManager` instance if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use your_crate::{VaultManager, VaultConfig, CustomData};
+    ///
+    /// let config = VaultConfig { lenient_load: true, ..VaultConfig::default() };
+    /// let vault_manager: VaultManager<CustomData> = VaultManager::with_config("path/to/database.db", config)
+    ///     .expect("Failed to create VaultManager");
+    /// for failure in &vault_manager.load_report().failures {
+    ///     println!("Skipped object {}: {}", failure.point_id, failure.error);
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The database connection cannot be established
+    /// - The necessary tables cannot be created in the database
+    /// - Existing regions cannot be loaded from the database
+    /// - An object cannot be loaded and `config.lenient_load` is not set
+    /// - The stored keys cannot be migrated to `config.key_format`
+    pub fn with_config(db_path: &str, config: VaultConfig) -> Result<Self, String> {
         // Create a new persistent database connection
         let persistent_db = MySQLGeo::Database::new(db_path)
             .map_err(|e| format!("Failed to create persistent database: {}", e))?;
+        Self::open(persistent_db, config)
+    }
+
+    /// Creates a new instance of `VaultManager` whose persistent storage lives in memory.
+    ///
+    /// Nothing is written to disk: neither a database file nor the `./data` directory holding
+    /// custom data files is created. This suits unit tests and short-lived worlds, such as
+    /// instanced maps, that are thrown away when the manager is dropped.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Self, String>` - A new, empty `VaultManager` instance if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use your_crate::{VaultManager, CustomData};
+    ///
+    /// let instance: VaultManager<CustomData> = VaultManager::in_memory().expect("Failed to create VaultManager");
+    /// let region_id = instance.create_or_load_region([0.0, 0.0, 0.0], 200.0).expect("Failed to create region");
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - Everything else works as with a database file, including `persist_to_disk`, snapshots
+    ///   and restoring them, except `enable_auto_persist`, which needs a file to open a second
+    ///   connection to.
+    pub fn in_memory() -> Result<Self, String> {
+        Self::in_memory_with_config(VaultConfig::default())
+    }
+
+    /// Creates a new instance of `VaultManager` whose persistent storage lives in memory, with the given configuration.
+    ///
+    /// See `in_memory` and `with_config`.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Self, String>` - A new, empty `VaultManager` instance if successful, or an error message if not.
+    pub fn in_memory_with_config(config: VaultConfig) -> Result<Self, String> {
+        let persistent_db = MySQLGeo::Database::in_memory()
+            .map_err(|e| format!("Failed to create in-memory database: {}", e))?;
+        Self::open(persistent_db, config)
+    }
+
+    /// Opens a `VaultManager` on top of a persistent database, loading what it holds.
+    fn open(mut persistent_db: MySQLGeo::Database, config: VaultConfig) -> Result<Self, String> {
+        persistent_db.set_compression_threshold(config.compression_threshold);
+        persistent_db.set_compression(config.compression);
         
         // Create the necessary tables in the database
         persistent_db.create_table()
             .map_err(|e| format!("Failed to create table: {}", e))?;
+
+        // Convert the stored keys if a different format was requested
+        if let Some(key_format) = config.key_format {
+            persistent_db.migrate_key_format(key_format)
+                .map_err(|e| format!("Failed to migrate key format: {}", e))?;
+        }
+
+        // Changes journaled before a crash are written back before anything is loaded
+        persistent_db.begin_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        if let Err(e) = persistent_db.replay_journal() {
+            let _ = persistent_db.rollback_transaction();
+            return Err(format!("Failed to replay journal: {}", e));
+        }
+        persistent_db.commit_transaction()
+            .map_err(|e| format!("Failed to commit transaction: {}", e))?;
         
         // Initialize the VaultManager struct
-        let mut vault_manager = VaultManager {
-            regions: HashMap::new(),
+        let vault_manager = VaultManager {
+            regions: RwLock::new(HashMap::new()),
+            region_creation: Mutex::new(()),
             persistent_db,
-            object_types: HashMap::new(),
+            object_types: RwLock::new(HashMap::new()),
+            object_pool: Mutex::new(ObjectPool::new(config.object_pool_capacity)),
+            change_log: Mutex::new(ChangeLog::new(config.change_log_capacity)),
+            config,
+            load_report: RwLock::new(LoadReport::default()),
+            loaded_regions: Mutex::new(HashMap::new()),
+            access_clock: AtomicU64::new(0),
+            locks: Arc::default(),
+            auto_persist: Mutex::new(None),
+            simulation_ticks: Mutex::new(HashMap::new()),
+            players: Mutex::new(HashMap::new()),
+            object_regions: Mutex::new(HashMap::new()),
+            expiries: Mutex::new(HashMap::new()),
+            hierarchy: Mutex::default(),
+            subscribers: Mutex::new(Vec::new()),
+            verifier: Mutex::new(VerifyCursor::default()),
         };
 
         // Initialize object types
-        vault_manager.object_types.insert("player".to_string(), "player".to_string());
-        vault_manager.object_types.insert("building".to_string(), "building".to_string());
-        vault_manager.object_types.insert("resource".to_string(), "resource".to_string());
+        vault_manager.register_object_type("player");
+        vault_manager.register_object_type("building");
+        vault_manager.register_object_type("resource");
 
         // Load existing regions from the persistent database
         vault_manager.load_regions_from_db()?;
 
+        // Objects that expired while the server was down are removed by the first sweep
+        vault_manager.load_expiries()?;
+        vault_manager.load_hierarchy()?;
+
         Ok(vault_manager)
     }
 
+    /// Loads the expiry times of objects with a TTL from the persistent database.
+    fn load_expiries(&self) -> Result<(), String> {
+        let expiries = self.persistent_db.get_point_expiries()
+            .map_err(|e| format!("Failed to load object expiries: {}", e))?;
+        *self.expiries.lock().unwrap() = expiries.into_iter()
+            .map(|(object_id, expires_at)| (object_id, UNIX_EPOCH + Duration::from_millis(expires_at.max(0) as u64)))
+            .collect();
+        Ok(())
+    }
+
+    /// Loads the attachments between objects from the persistent database.
+    fn load_hierarchy(&self) -> Result<(), String> {
+        let parents = self.persistent_db.get_point_parents()
+            .map_err(|e| format!("Failed to load object attachments: {}", e))?;
+        *self.hierarchy.lock().unwrap() = Hierarchy::from_pairs(parents);
+        Ok(())
+    }
+
     /// Loads existing regions and their objects from the persistent database.
     ///
     /// This function is called during VaultManager initialization to populate
@@ -150,44 +385,217 @@ impl<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> Vault
     /// # Notes
     ///
     /// This method is private and is automatically called by `new()`. It shouldn't be called directly by users.
-    fn load_regions_from_db(&mut self) -> Result<(), String> {
+    /// The outcome is recorded in `self.load_report`.
+    /// With `config.lazy_load` set, only the regions themselves are read; their objects are
+    /// loaded by `lock_region` on first access.
+    fn load_regions_from_db(&self) -> Result<(), String> {
         let regions = self.persistent_db.get_all_regions()
             .map_err(|e| format!("Failed to load regions from database: {}", e))?;
 
-        println!("Loaded {} regions from the database", regions.len());
+        let mut report = LoadReport::default();
 
         for region in regions {
-            println!("Loading region: ID: {}, Center: {:?}, Radius: {}", region.id, region.center, region.radius);
-            let vault_region = VaultRegion {
-                id: region.id,
-                center: region.center,
-                radius: region.radius,
-                rtree: RTree::new(),
-            };
+            let mut vault_region = VaultRegion::new(region.id, region.center, region.radius);
+
+            // Lazily loaded regions and those beyond the residency limit are loaded on first access instead
+            let load_now = !self.config.lazy_load && self.config.max_loaded_regions
+                .is_none_or(|limit| report.regions_loaded < limit);
+            if load_now {
+                let (objects, failures) = self.read_region_objects(region.id)?;
+                report.points_per_region.insert(region.id, objects.len());
+                report.failures.extend(failures);
+                report.regions_loaded += 1;
+                self.index_objects(region.id, objects.iter().map(|obj| obj.uuid));
+                vault_region.insert_objects(objects);
+                self.touch_region(region.id);
+            }
 
-            self.regions.insert(region.id, Arc::new(Mutex::new(vault_region)));
+            self.regions.write().unwrap().insert(region.id, Arc::new(Mutex::new(vault_region)));
+        }
+
+        report.orphaned_points = self.persistent_db.get_orphaned_point_ids()
+            .map_err(|e| format!("Failed to check for orphaned points: {}", e))?;
+
+        *self.load_report.write().unwrap() = report;
+        Ok(())
+    }
 
-            let points = self.persistent_db.get_points_in_region(region.id)
-                .map_err(|e| format!("Failed to load points for region {}: {}", region.id, e))?;
+    /// Reads the objects of a region from the persistent database.
+    ///
+    /// Objects that fail to load abort the read unless `config.lenient_load` is set, in which
+    /// case they're moved to quarantine and returned as failures. Points are read a page at a
+    /// time, so only one page of custom data is decoded but not yet converted at any moment.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(Vec<SpatialObject<T>>, Vec<LoadFailure>), String>` - The loaded objects and the
+    ///   quarantined ones if successful, or an error message if not.
+    fn read_region_objects(&self, region_id: Uuid) -> Result<(Vec<SpatialObject<T>>, Vec<LoadFailure>), String> {
+        let mut objects = Vec::new();
+        let mut failures = Vec::new();
+        let mut cursor = Some(0);
+        while let Some(after) = cursor {
+            let (points, next) = self.persistent_db.get_points_in_region_paged(region_id, after, LOAD_PAGE_SIZE)
+                .map_err(|e| format!("Failed to load points for region {}: {}", region_id, e))?;
+            cursor = next;
+            objects.reserve(points.len());
+            self.convert_points(region_id, points, &mut objects, &mut failures)?;
+        }
 
-            println!("Loaded {} points for region {}", points.len(), region.id);
+        Ok((objects, failures))
+    }
 
-            if let Some(region_arc) = self.regions.get(&region.id) {
-                let mut region = region_arc.lock().unwrap();
-                for point in points {
-                    let custom_data: T = serde_json::from_value(point.custom_data)
-                        .map_err(|e| format!("Failed to deserialize custom data: {}", e))?;
-                    let spatial_object = SpatialObject {
-                        uuid: point.id.unwrap(),
+    /// Converts a page of points read from the persistent database into objects, quarantining
+    /// the ones that fail to load if `config.lenient_load` is set.
+    fn convert_points(
+        &self,
+        region_id: Uuid,
+        points: Vec<Result<Point, CorruptPoint>>,
+        objects: &mut Vec<SpatialObject<T>>,
+        failures: &mut Vec<LoadFailure>,
+    ) -> Result<(), String> {
+        for point in points {
+            let corrupt = match point {
+                Ok(point) => match serde_json::from_value::<T>(point.custom_data.clone()) {
+                    Ok(custom_data) => {
+                        objects.push(SpatialObject {
+                            uuid: point.id.unwrap(),
+                            object_type: ObjectType::from(point.object_type),
+                            point: [point.x, point.y, point.z],
+                            custom_data: Arc::new(custom_data),
+                            size: point.size,
+                            version: point.version,
+                            velocity: point.velocity,
+                            orientation: point.orientation,
+                        });
+                        continue;
+                    }
+                    Err(e) => CorruptPoint {
+                        id: point.id.unwrap().to_string(),
+                        x: point.x,
+                        y: point.y,
+                        z: point.z,
                         object_type: point.object_type,
-                        point: [point.x, point.y, point.z],
-                        custom_data: Arc::new(custom_data),
-                    };
-                    region.rtree.insert(spatial_object);
-                }
+                        raw_custom_data: Some(point.custom_data.to_string()),
+                        error: format!("Failed to deserialize custom data: {}", e),
+                    },
+                },
+                Err(corrupt) => corrupt,
+            };
+
+            if !self.config.lenient_load {
+                return Err(format!("Failed to load object {} in region {}: {}", corrupt.id, region_id, corrupt.error));
             }
+
+            // Move the object aside so it survives the next persist and can be repaired later
+            self.persistent_db.quarantine_point(&corrupt, region_id)
+                .map_err(|e| format!("Failed to quarantine object {}: {}", corrupt.id, e))?;
+            failures.push(load_failure(corrupt, region_id));
         }
+        Ok(())
+    }
+
+    /// Returns the report describing what was loaded from the persistent database at startup.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// let report = vault_manager.load_report();
+    /// println!("Loaded {} objects in {} regions", report.total_points(), report.regions_loaded);
+    /// ```
+    pub fn load_report(&self) -> LoadReport {
+        self.load_report.read().unwrap().clone()
+    }
+
+    /// Lists the objects held in quarantine.
+    ///
+    /// Objects are quarantined when they can't be loaded at startup in lenient mode, for example
+    /// because their custom data no longer deserializes into `T`. Their raw payload is kept so
+    /// they can be inspected and repaired.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<LoadFailure>, String>` - The quarantined objects, oldest first, if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// for object in vault_manager.quarantined_objects().expect("Failed to list quarantine") {
+    ///     println!("{} ({}): {} -> {:?}", object.point_id, object.object_type, object.error, object.raw_custom_data);
+    /// }
+    /// ```
+    pub fn quarantined_objects(&self) -> Result<Vec<LoadFailure>, String> {
+        let quarantined = self.persistent_db.get_quarantined_points()
+            .map_err(|e| format!("Failed to load quarantined objects: {}", e))?;
+        Ok(quarantined.into_iter()
+            .map(|quarantined| load_failure(quarantined.point, quarantined.region_id))
+            .collect())
+    }
+
+    /// Restores a quarantined object with repaired custom data.
+    ///
+    /// The object is added back to the region it was quarantined from, at its stored position,
+    /// and removed from quarantine.
+    ///
+    /// # Arguments
+    ///
+    /// * `point_id` - The ID of the quarantined object, as listed by `quarantined_objects`.
+    /// * `custom_data` - The repaired custom data.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), String>` - An empty result if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # use std::sync::Arc;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// for object in vault_manager.quarantined_objects().unwrap() {
+    ///     let repaired = Arc::new(CustomData { /* ... */ });
+    ///     vault_manager.repair_quarantined_object(&object.point_id, repaired).expect("Failed to repair object");
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the object isn't quarantined, its stored ID isn't a valid
+    /// UUID, or its region isn't loaded.
+    pub fn repair_quarantined_object(&self, point_id: &str, custom_data: Arc<T>) -> Result<(), String> {
+        let quarantined = self.quarantined_objects()?
+            .into_iter()
+            .find(|object| object.point_id == point_id)
+            .ok_or_else(|| format!("Object not in quarantine: {}", point_id))?;
+        let uuid = Uuid::parse_str(point_id)
+            .map_err(|e| format!("Quarantined object has an invalid ID {}: {}", point_id, e))?;
+
+        let [x, y, z] = quarantined.point;
+        self.add_object(quarantined.region_id, uuid, &quarantined.object_type, x, y, z, custom_data)?;
+        self.persistent_db.remove_quarantined_point(point_id)
+            .map_err(|e| format!("Failed to remove object from quarantine: {}", e))?;
+        Ok(())
+    }
 
+    /// Permanently deletes a quarantined object.
+    ///
+    /// # Arguments
+    ///
+    /// * `point_id` - The ID of the quarantined object, as listed by `quarantined_objects`.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), String>` - An empty result if successful, or an error message if not.
+    pub fn discard_quarantined_object(&self, point_id: &str) -> Result<(), String> {
+        let removed = self.persistent_db.remove_quarantined_point(point_id)
+            .map_err(|e| format!("Failed to remove object from quarantine: {}", e))?;
+        if !removed {
+            return Err(format!("Object not in quarantine: {}", point_id));
+        }
         Ok(())
     }
 
@@ -209,7 +617,7 @@ impl<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> Vault
     ///
     /// ```
     /// # use your_crate::{VaultManager, CustomData};
-    /// # let mut vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
     /// let center = [0.0, 0.0, 0.0];
     /// let radius = 100.0;
     /// let region_id = vault_manager.create_or_load_region(center, radius).expect("Failed to create region");
@@ -219,34 +627,39 @@ impl<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> Vault
     ///
     /// - Regions are spherical, defined by a center point and a radius.
     /// - Overlapping regions are allowed, but may impact performance for objects in the overlapped areas.
-    pub fn create_or_load_region(&mut self, center: [f64; 3], radius: f64) -> Result<Uuid, String> {
+    /// - The center must be finite and the radius finite and non-negative.
+    pub fn create_or_load_region(&self, center: [f64; 3], radius: f64) -> Result<Uuid, String> {
+        validate_point(center).map_err(|e| format!("Invalid region center: {}", e))?;
+        if !radius.is_finite() || radius < 0.0 {
+            return Err(format!("Invalid region radius: {}", radius));
+        }
+        let creation = self.region_creation.lock().unwrap();
+
         // Check if a region with the same center and radius already exists
-        if let Some(existing_region) = self.regions.values().find(|r| {
-            let r = r.lock().unwrap();
-            r.center == center && r.radius == radius
+        if let Some(existing_region) = self.region_handles().into_iter().find_map(|(region_id, region)| {
+            let region = self.locks.acquire(region_id, &region);
+            (region.center == center && region.radius == radius).then_some(region.id)
         }) {
-            return Ok(existing_region.lock().unwrap().id);
+            return Ok(existing_region);
         }
 
         // Generate a new UUID for the region
         let region_id = Uuid::new_v4();
-        // Create a new RTree for the region
-        let rtree = RTree::new();
-
-        // Create a new VaultRegion
-        let region = VaultRegion {
-            id: region_id,
-            center,
-            radius,
-            rtree,
-        };
+
+        // Create a new VaultRegion with empty spatial indexes
+        let region = VaultRegion::new(region_id, center, radius);
 
         // Insert the new region into the regions HashMap
-        self.regions.insert(region_id, Arc::new(Mutex::new(region)));
+        self.regions.write().unwrap().insert(region_id, Arc::new(Mutex::new(region)));
+        drop(creation);
+        self.touch_region(region_id);
+        self.sync_auto_persist_regions();
 
         // Persist the region to the database
         self.persistent_db.create_region(region_id, center, radius)
             .map_err(|e| format!("Failed to persist region to database: {}", e))?;
+        self.emit([VaultEvent::RegionCreated { region_id, center, radius }]);
+        self.evict_regions()?;
 
         Ok(region_id)
     }
@@ -276,7 +689,7 @@ impl<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> Vault
     /// # use your_crate::{VaultManager, CustomData};
     /// # use uuid::Uuid;
     /// # use std::sync::Arc;
-    /// # let mut vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
     /// # let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0).unwrap();
     /// let object_id = Uuid::new_v4();
     /// let custom_data = Arc::new(CustomData { /* ... */ });
@@ -288,92 +701,165 @@ impl<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> Vault
     /// - The object is added to the specified region regardless of its coordinates. Ensure the coordinates fall within the region's bounds for consistent behavior.
     /// - If an object with the same UUID already exists, it will be overwritten.
     /// - The `custom_data` is stored as an `Arc<T>` to allow efficient sharing of data between objects.
+    /// - Objects added this way have a zero size. Use `add_spatial_object` to add an object with an extent.
+    /// - Positions are checked against `VaultConfig::world_bounds`; non-finite coordinates are always rejected.
     pub fn add_object(&self, region_id: Uuid, uuid: Uuid, object_type: &str, x: f64, y: f64, z: f64, custom_data: Arc<T>) -> Result<(), String> {
-        let region = self.regions.get(&region_id)
-            .ok_or_else(|| format!("Region not found: {}", region_id))?;
-        
-        let mut region = region.lock().unwrap();
-        
         let object = SpatialObject {
             uuid,
-            object_type: object_type.to_string(),
+            object_type: ObjectType::from(object_type),
             point: [x, y, z],
-            custom_data: custom_data.clone(),
+            custom_data,
+            size: [0.0; 3],
+            version: 0,
+            velocity: None,
+            orientation: None,
         };
+
+        self.add_spatial_object(region_id, object)
+    }
+
+    /// Adds a fully constructed object to a specific region.
+    ///
+    /// This behaves like `add_object`, but takes every field of the object, including its size.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_id` - The UUID of the region to add the object to.
+    /// * `object` - The object to add.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), String>` - An empty result if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, SpatialObject, CustomData};
+    /// # use uuid::Uuid;
+    /// # use std::sync::Arc;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let region_id = Uuid::new_v4();
+    /// let wall = SpatialObject {
+    ///     uuid: Uuid::new_v4(),
+    ///     object_type: "building".to_string(),
+    ///     point: [10.0, 2.5, 0.0],
+    ///     custom_data: Arc::new(CustomData { /* ... */ }),
+    ///     size: [20.0, 5.0, 1.0],
+    ///     version: 0,
+    ///     velocity: None,
+    ///     orientation: None,
+    /// };
+    /// vault_manager.add_spatial_object(region_id, wall).expect("Failed to add object");
+    /// ```
+    pub fn add_spatial_object(&self, region_id: Uuid, mut object: SpatialObject<T>) -> Result<(), String> {
+        self.check_type(&object)?;
+        let handle = self.region(region_id)?;
+        let mut region = self.lock_region(region_id, &handle)?;
+        object.point = self.check_position(object.uuid, object.point)?;
         
-        region.rtree.insert(object.clone());
 
         let point = Point {
-            id: Some(uuid),
-            x,
-            y,
-            z,
-            object_type: object_type.to_string(),
-            custom_data: serde_json::to_value((*custom_data).clone()).map_err(|e| format!("Failed to serialize custom data: {}", e))?,
+            id: Some(object.uuid),
+            x: object.point[0],
+            y: object.point[1],
+            z: object.point[2],
+            object_type: object.object_type.to_string(),
+            custom_data: serde_json::to_value((*object.custom_data).clone()).map_err(|e| format!("Failed to serialize custom data: {}", e))?,
+            size: object.size,
+            version: object.version,
+            velocity: object.velocity,
+            orientation: object.orientation,
         };
         
+        let added = self.has_subscribers().then(|| object.clone());
+        self.index_objects(region_id, [object.uuid]);
+        region.insert_object(object);
+
         self.persistent_db.add_point(&point, region_id)
             .map_err(|e| format!("Failed to add point to persistent database: {}", e))?;
+        self.emit(added.map(|object| VaultEvent::ObjectAdded { region_id, object }));
 
         Ok(())
     }
 
-    /// Queries objects within a specific region.
+    /// Adds an object to whichever region contains its position.
     ///
-    /// This function searches for objects within a given bounding box in a specified region.
-    /// It's useful for finding all objects in a particular area, such as for rendering or game logic.
+    /// This lets callers treat the vault as a single world-space store without tracking region
+    /// UUIDs. If several regions contain the position, the one with the closest center is used.
     ///
     /// # Arguments
     ///
-    /// * `region_id` - The UUID of the region to query.
-    /// * `min_x`, `min_y`, `min_z` - The minimum coordinates of the bounding box.
-    /// * `max_x`, `max_y`, `max_z` - The maximum coordinates of the bounding box.
+    /// * `uuid` - The UUID of the object being added.
+    /// * `object_type` - The type of the object being added (e.g., "player", "building", "resource").
+    /// * `position` - The position of the object [x, y, z].
+    /// * `size` - The extent of the object along each axis.
+    /// * `custom_data` - The custom data associated with the object, wrapped in an `Arc`.
     ///
     /// # Returns
     ///
-    /// * `Result<Vec<SpatialObject<T>>, String>` - A vector of `SpatialObject`s within the bounding box if successful, or an error message if not.
+    /// * `Result<Uuid, String>` - The UUID of the region the object was added to, or an error message if not.
     ///
     /// # Examples
     ///
     /// ```
-    /// # use your_crate::{VaultManager, CustomData};
+    /// # use your_crate::{VaultManager, VaultConfig, CustomData};
     /// # use uuid::Uuid;
-    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
-    /// # let region_id = Uuid::new_v4();
-    /// let objects = vault_manager.query_region(region_id, 0.0, 0.0, 0.0, 10.0, 10.0, 10.0).expect("Failed to query region");
-    /// for object in objects {
-    ///     println!("Found object: {:?}", object.uuid);
-    /// }
+    /// # use std::sync::Arc;
+    /// let config = VaultConfig { auto_region_radius: Some(500.0), ..VaultConfig::default() };
+    /// let vault_manager: VaultManager<CustomData> = VaultManager::with_config("path/to/database.db", config).unwrap();
+    /// let region_id = vault_manager.add_object_auto(Uuid::new_v4(), "tree", [1200.0, 0.0, -300.0], [1.0, 8.0, 1.0], Arc::new(CustomData { /* ... */ }))
+    ///     .expect("Failed to add object");
     /// ```
     ///
     /// # Notes
     ///
-    /// - The query is performed using an R-tree, which provides efficient spatial searching.
-    /// - Objects intersecting the bounding box are included in the results, not just those fully contained.
-    pub fn query_region(&self, region_id: Uuid, min_x: f64, min_y: f64, min_z: f64, max_x: f64, max_y: f64, max_z: f64) -> Result<Vec<SpatialObject<T>>, String> {
-        let region = self.regions.get(&region_id)
-            .ok_or_else(|| format!("Region not found: {}", region_id))?;
-        
-        let region = region.lock().unwrap();
-        let envelope = AABB::from_corners([min_x, min_y, min_z], [max_x, max_y, max_z]);
-        let results: Vec<SpatialObject<T>> = region.rtree.locate_in_envelope(&envelope)
-            .cloned()
-            .collect();
+    /// - If no region contains the position and `VaultConfig::auto_region_radius` is set, a region
+    ///   is created on the grid cell containing it; otherwise an error is returned.
+    /// - The position is checked against `VaultConfig::world_bounds` before the region is chosen.
+    pub fn add_object_auto(&self, uuid: Uuid, object_type: &str, position: [f64; 3], size: [f64; 3], custom_data: Arc<T>) -> Result<Uuid, String> {
+        let position = self.check_position(uuid, position)?;
+        let object = SpatialObject::new(uuid, object_type, position, custom_data)
+            .and_then(|object| object.with_size(size))
+            .map_err(|e| format!("Invalid object {}: {}", uuid, e))?;
+        self.check_type(&object)?;
 
-        Ok(results)
+        let region_id = match self.region_containing(position) {
+            Some(region_id) => region_id,
+            None => match self.config.auto_region_radius {
+                Some(radius) => {
+                    let center = position.map(|coordinate| (coordinate / (2.0 * radius)).round() * 2.0 * radius);
+                    self.create_or_load_region(center, radius)?
+                }
+                None => return Err(format!("No region contains position {:?}", position)),
+            },
+        };
+
+        self.add_spatial_object(region_id, object)?;
+
+        if let Some(max_objects) = self.config.max_objects_per_region {
+            let handle = self.region(region_id)?;
+            let region = self.lock_region(region_id, &handle)?;
+            let (size, center) = (region.rtree.size(), region.center);
+            drop(region);
+            if size > max_objects {
+                let octants = self.split_region(region_id)?;
+                return Ok(octants[octant_of(center, position)]);
+            }
+        }
+
+        Ok(region_id)
     }
 
-    /// Transfers a player (object) from one region to another.
+    /// Adds many objects to a specific region in one call.
     ///
-    /// This function moves a player object from its current region to a new region,
-    /// updating both the in-memory structures and the persistent database. It's particularly
-    /// useful for handling player movement between different areas of your game world.
+    /// This is much faster than calling `add_object` in a loop: the in-memory index of an empty
+    /// region is built in a single bulk load, and all objects are written to the persistent
+    /// database in one transaction.
     ///
     /// # Arguments
     ///
-    /// * `player_uuid` - The UUID of the player to transfer.
-    /// * `from_region_id` - The UUID of the source region.
-    /// * `to_region_id` - The UUID of the destination region.
+    /// * `region_id` - The UUID of the region to add the objects to.
+    /// * `objects` - The objects to add.
     ///
     /// # Returns
     ///
@@ -382,169 +868,4778 @@ impl<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> Vault
     /// # Examples
     ///
     /// ```
-    /// # use your_crate::{VaultManager, CustomData};
+    /// # use your_crate::{VaultManager, SpatialObject, ObjectType, CustomData};
     /// # use uuid::Uuid;
-    /// # let mut vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
-    /// # let from_region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0).unwrap();
-    /// # let to_region_id = vault_manager.create_or_load_region([200.0, 200.0, 200.0], 100.0).unwrap();
-    /// # let player_id = Uuid::new_v4();
-    /// # let custom_data = CustomData { /* ... */ };
-    /// # vault_manager.add_object(from_region_id, player_id, "player", 1.0, 2.0, 3.0, custom_data).unwrap();
-    /// vault_manager.transfer_player(player_id, from_region_id, to_region_id).expect("Failed to transfer player");
+    /// # use std::sync::Arc;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let region_id = Uuid::new_v4();
+    /// let trees: Vec<SpatialObject<CustomData>> = (0..10_000)
+    ///     .map(|i| SpatialObject {
+    ///         uuid: Uuid::new_v4(),
+    ///         object_type: ObjectType::from("resource"),
+    ///         point: [i as f64, 0.0, 0.0],
+    ///         custom_data: Arc::new(CustomData { /* ... */ }),
+    ///         size: [0.0, 0.0, 0.0],
+    ///         version: 0,
+    ///         velocity: None,
+    ///         orientation: None,
+    ///     })
+    ///     .collect();
+    /// vault_manager.add_objects(region_id, trees).expect("Failed to add objects");
     /// ```
     ///
     /// # Notes
     ///
-    /// - The player's position is updated to the center of the destination region.
-    /// - This method does not check if the new position is valid within the game world; that logic should be handled separately.
-    /// - The persistent database is not updated in this method; call `persist_to_disk()` to save changes.
-    pub fn transfer_player(&self, player_uuid: Uuid, from_region_id: Uuid, to_region_id: Uuid) -> Result<(), String> {
-        let from_region = self.regions.get(&from_region_id)
-            .ok_or_else(|| format!("Source region not found: {}", from_region_id))?;
-        let to_region = self.regions.get(&to_region_id)
-            .ok_or_else(|| format!("Destination region not found: {}", to_region_id))?;
-
-        let mut from_region = from_region.lock().unwrap();
-        let mut to_region = to_region.lock().unwrap();
-
-        let player = from_region.rtree.iter()
-            .find(|obj| obj.uuid == player_uuid)
-            .cloned()
-            .ok_or_else(|| format!("Player not found in source region: {}", player_uuid))?;
-
-        from_region.rtree.remove(&player);
-
-        let updated_player = SpatialObject {
-            uuid: player.uuid,
-            object_type: player.object_type,
-            point: to_region.center,
-            custom_data: player.custom_data.clone(),
-        };
-
-        to_region.rtree.insert(updated_player);
-
-        // TODO: Update the player's position in the persistent database
-
-        Ok(())
+    /// - If writing to the persistent database fails, nothing is written and the in-memory region is left unchanged.
+    pub fn add_objects(&self, region_id: Uuid, objects: Vec<SpatialObject<T>>) -> Result<(), String> {
+        self.import_objects(region_id, objects, &OperationHandle::new())
     }
 
-    /// Persists all in-memory databases to disk.
+    /// Adds many objects to a specific region, reporting progress through `handle`.
+    ///
+    /// This behaves like `add_objects`, which makes it suitable for imports. The progress is
+    /// counted in objects written, and the import can be paused or cancelled through any clone
+    /// of the handle. A cancelled import adds nothing.
+    ///
+    /// # Arguments
     ///
-    /// This function saves all objects from all regions to the persistent database.
-    /// It's important to call this method periodically to ensure data is not lost in case of unexpected shutdowns.
+    /// * `region_id` - The UUID of the region to add the objects to.
+    /// * `objects` - The objects to add.
+    /// * `handle` - The handle to report progress through.
     ///
     /// # Returns
     ///
-    /// * `Result<(), String>` - An empty result if successful, or an error message if not.
+    /// * `Result<(), String>` - An empty result if successful, or an error message if the operation failed or was cancelled.
     ///
     /// # Examples
     ///
     /// ```
-    /// # use your_crate::{VaultManager, CustomData};
-    /// # let mut vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
-    /// vault_manager.persist_to_disk().expect("Failed to persist data to disk");
+    /// # use your_crate::{VaultManager, SpatialObject, CustomData, OperationHandle};
+    /// # use uuid::Uuid;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let region_id = Uuid::new_v4();
+    /// # let objects: Vec<SpatialObject<CustomData>> = Vec::new();
+    /// let handle = OperationHandle::new();
+    /// vault_manager.add_objects_tracked(region_id, objects, &handle).expect("Failed to import objects");
     /// ```
     ///
     /// # Notes
     ///
-    /// - This operation can be time-consuming for large datasets. Consider running it in a separate thread.
-    /// - The method provides progress feedback using a progress bar.
-    /// - All existing points in the database are cleared before persisting the current state.
-    pub fn persist_to_disk(&self) -> Result<(), String> {
-        let start_time = std::time::Instant::now();
-        let mut total_points = 0;
+    /// - The region stays locked while the import is paused.
+    pub fn add_objects_tracked(&self, region_id: Uuid, objects: Vec<SpatialObject<T>>, handle: &OperationHandle) -> Result<(), String> {
+        let result = self.import_objects(region_id, objects, handle);
+        handle.finish(&result);
+        result
+    }
 
-        self.persistent_db.clear_all_points()
-            .map_err(|e| format!("Failed to clear existing points from database: {}", e))?;
+    /// Writes objects to the persistent database in one transaction, then adds them to their region.
+    fn import_objects(&self, region_id: Uuid, mut objects: Vec<SpatialObject<T>>, handle: &OperationHandle) -> Result<(), String> {
+        let target = self.region(region_id)?;
+        let mut region = self.lock_region(region_id, &target)?;
+        for object in &mut objects {
+            self.check_type(object)?;
+            object.point = self.check_position(object.uuid, object.point)?;
+        }
+        handle.start(objects.len() as u64);
 
-        for (_, region) in &self.regions {
-            let region = region.lock().unwrap();
-            total_points += region.rtree.size();
+        self.persistent_db.begin_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        let written = objects.chunks(LIMIT_CHECK_INTERVAL).try_for_each(|chunk| {
+            handle.checkpoint()?;
+            self.write_objects(region_id, chunk)?;
+            handle.advance(chunk.len() as u64);
+            Ok::<(), String>(())
+        });
+        if let Err(e) = written {
+            let _ = self.persistent_db.rollback_transaction();
+            return Err(e);
         }
+        self.persistent_db.commit_transaction()
+            .map_err(|e| format!("Failed to commit transaction: {}", e))?;
 
-        let pb = ProgressBar::new(total_points as u64);
-        pb.set_style(ProgressStyle::default_bar()
-            .template("[{elapsed_precise}] {bar:40.cyan/blue} {pos:>7}/{len:7} {msg}")
-            .unwrap()
-            .progress_chars("##-"));
+        self.emit(objects.iter().map(|object| VaultEvent::ObjectAdded { region_id, object: object.clone() }));
+        self.index_objects(region_id, objects.iter().map(|obj| obj.uuid));
+        region.insert_objects(objects);
 
-        for (region_id, region) in &self.regions {
-            let region = region.lock().unwrap();
-            for obj in region.rtree.iter() {
-                let point = Point {
-                    id: Some(obj.uuid),
-                    x: obj.point[0],
-                    y: obj.point[1],
-                    z: obj.point[2],
-                    object_type: obj.object_type.clone(),
-                    custom_data: serde_json::to_value((*obj.custom_data).clone())
-                        .map_err(|e| format!("Failed to serialize custom data: {}", e))?,
-                };
-                self.persistent_db.add_point(&point, *region_id)
-                    .map_err(|e| format!("Failed to persist point to database: {}", e))?;
-                pb.inc(1);
+        Ok(())
+    }
+
+    /// Writes objects to the persistent database as points of the given region.
+    fn write_objects(&self, region_id: Uuid, objects: &[SpatialObject<T>]) -> Result<(), String> {
+        write_points(&self.persistent_db, region_id, objects)?;
+        self.clear_journal(objects)
+    }
+
+    /// Appends an object changed in memory to the journal, if `VaultConfig::journal` is set.
+    fn journal_object(&self, object: &SpatialObject<T>) -> Result<(), String> {
+        if !self.config.journal {
+            return Ok(());
+        }
+        self.persistent_db.journal_point(&to_point(object)?)
+            .map_err(|e| format!("Failed to journal object {}: {}", object.uuid, e))
+    }
+
+    /// Drops the journal entries of objects that have been written to the persistent database.
+    fn clear_journal(&self, objects: &[SpatialObject<T>]) -> Result<(), String> {
+        if !self.config.journal {
+            return Ok(());
+        }
+        let object_ids: Vec<Uuid> = objects.iter().map(|obj| obj.uuid).collect();
+        self.persistent_db.clear_journal(&object_ids)
+            .map_err(|e| format!("Failed to clear journal: {}", e))
+    }
+
+    /// Queries objects within a specific region.
+    ///
+    /// This function searches for objects within a given bounding box in a specified region.
+    /// It's useful for finding all objects in a particular area, such as for rendering or game logic.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_id` - The UUID of the region to query.
+    /// * `min_x`, `min_y`, `min_z` - The minimum coordinates of the bounding box.
+    /// * `max_x`, `max_y`, `max_z` - The maximum coordinates of the bounding box.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<SpatialObject<T>>, String>` - A vector of `SpatialObject`s within the bounding box if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # use uuid::Uuid;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let region_id = Uuid::new_v4();
+    /// let objects = vault_manager.query_region(region_id, 0.0, 0.0, 0.0, 10.0, 10.0, 10.0).expect("Failed to query region");
+    /// for object in objects {
+    ///     println!("Found object: {:?}", object.uuid);
+    /// }
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - The query is performed using an R-tree, which provides efficient spatial searching.
+    /// - Only objects whose size-based bounding box is fully contained in the query box are included;
+    ///   use `query_overlapping` to also find objects that merely intersect it.
+    /// - Results are returned in the order set by `VaultConfig::query_order`, like those of the
+    ///   other queries returning a `Vec`.
+    pub fn query_region(&self, region_id: Uuid, min_x: f64, min_y: f64, min_z: f64, max_x: f64, max_y: f64, max_z: f64) -> Result<Vec<SpatialObject<T>>, String> {
+        self.query_region_with_limits(region_id, [min_x, min_y, min_z], [max_x, max_y, max_z], &QueryLimits::none())
+    }
+
+    /// Queries objects within a specific region without cloning them into a `Vec`.
+    ///
+    /// This behaves like `query_region`, but returns a `RegionQuery` whose objects are borrowed
+    /// while iterating, so dense areas can be streamed without a memory spike.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_id` - The UUID of the region to query.
+    /// * `min` - The minimum coordinates of the bounding box [x, y, z].
+    /// * `max` - The maximum coordinates of the bounding box [x, y, z].
+    ///
+    /// # Returns
+    ///
+    /// * `Result<RegionQuery<T>, String>` - The query to iterate if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # use uuid::Uuid;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let region_id = Uuid::new_v4();
+    /// let query = vault_manager.query_region_iter(region_id, [-500.0, -500.0, -500.0], [500.0, 500.0, 500.0]).expect("Failed to query region");
+    /// for object in &query {
+    ///     // ... send the object to a client ...
+    /// }
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - The query reads a snapshot of the region. While the query is alive, the first write to the
+    ///   region copies its spatial index, so drop it when done.
+    /// - Objects are streamed in the order of the spatial index, whatever `VaultConfig::query_order`
+    ///   is set to, since sorting them would mean collecting them first.
+    pub fn query_region_iter(&self, region_id: Uuid, min: [f64; 3], max: [f64; 3]) -> Result<RegionQuery<T>, String> {
+        let envelope = query_box(min, max)?;
+        let handle = self.region(region_id)?;
+        let region = self.lock_region(region_id, &handle)?;
+        Ok(RegionQuery::new(region.snapshot(), envelope))
+    }
+
+    /// Queries one page of the objects within a specific region.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_id` - The UUID of the region to query.
+    /// * `min` - The minimum coordinates of the bounding box [x, y, z].
+    /// * `max` - The maximum coordinates of the bounding box [x, y, z].
+    /// * `offset` - The number of matching objects to skip.
+    /// * `limit` - The maximum number of objects to return.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<SpatialObject<T>>, String>` - Up to `limit` objects if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # use uuid::Uuid;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let region_id = Uuid::new_v4();
+    /// let mut offset = 0;
+    /// loop {
+    ///     let page = vault_manager.query_region_page(region_id, [-500.0; 3], [500.0; 3], offset, 100).expect("Failed to query region");
+    ///     if page.is_empty() {
+    ///         break;
+    ///     }
+    ///     offset += page.len();
+    /// }
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - Pages follow the order of the region's spatial index, or `VaultConfig::query_order` if it's
+    ///   set. It stays the same while the region is unchanged; a change between two pages may shift
+    ///   objects into or out of the next page. To page through a consistent view, use
+    ///   `query_region_iter` instead.
+    /// - With a sorted `query_order`, every matching object is cloned to sort them before the page
+    ///   is cut out.
+    pub fn query_region_page(&self, region_id: Uuid, min: [f64; 3], max: [f64; 3], offset: usize, limit: usize) -> Result<Vec<SpatialObject<T>>, String> {
+        let envelope = query_box(min, max)?;
+        let handle = self.region(region_id)?;
+        let region = self.lock_region(region_id, &handle)?;
+        if self.config.query_order != QueryOrder::Index {
+            let mut results: Vec<SpatialObject<T>> = region.rtree.locate_in_envelope(&envelope).cloned().collect();
+            self.order_results(&mut results, Some(box_center(min, max)));
+            return Ok(results.into_iter().skip(offset).take(limit).collect());
+        }
+        let page = region.rtree.locate_in_envelope(&envelope)
+            .skip(offset)
+            .take(limit)
+            .cloned()
+            .collect();
+        Ok(page)
+    }
+
+    /// Counts the objects within a specific region that fall inside a bounding box.
+    ///
+    /// This matches the length of `query_region`'s result, but no object is cloned. If the region
+    /// isn't loaded, the count is served by the persistent database without loading it.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_id` - The UUID of the region to count objects in.
+    /// * `min` - The minimum coordinates of the bounding box [x, y, z].
+    /// * `max` - The maximum coordinates of the bounding box [x, y, z].
+    ///
+    /// # Returns
+    ///
+    /// * `Result<usize, String>` - The number of objects inside the box if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # use uuid::Uuid;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let region_id = Uuid::new_v4();
+    /// let count = vault_manager.count_in_region(region_id, [-50.0, -50.0, -50.0], [50.0, 50.0, 50.0]).expect("Failed to count objects");
+    /// println!("{} objects near the spawn point", count);
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - Counts of unloaded regions come from the last persisted state, which an unloaded region
+    ///   always matches, since regions are persisted before they're unloaded.
+    pub fn count_in_region(&self, region_id: Uuid, min: [f64; 3], max: [f64; 3]) -> Result<usize, String> {
+        let envelope = query_box(min, max)?;
+        let handle = self.region(region_id)?;
+        let region = self.locks.acquire(region_id, &handle);
+        if self.is_region_loaded(region_id) {
+            return Ok(region.rtree.locate_in_envelope(&envelope).count());
+        }
+        self.persistent_db.count_points(region_id, min, max)
+            .map_err(|e| format!("Failed to count objects in region {}: {}", region_id, e))
+    }
+
+    /// Queries objects within a sphere in a specific region.
+    ///
+    /// This function returns every object whose distance to `center` is at most `radius`, using the
+    /// in-memory R-tree. Unlike a cube-shaped `query_region` around the same center, it doesn't
+    /// return objects sitting in the corners of the cube.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_id` - The UUID of the region to query.
+    /// * `center` - The center of the sphere [x, y, z].
+    /// * `radius` - The radius of the sphere.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<SpatialObject<T>>, String>` - A vector of `SpatialObject`s within the sphere if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # use uuid::Uuid;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let region_id = Uuid::new_v4();
+    /// let nearby = vault_manager.query_radius(region_id, [0.0, 0.0, 0.0], 25.0).expect("Failed to query radius");
+    /// println!("{} objects within 25 units", nearby.len());
+    /// ```
+    pub fn query_radius(&self, region_id: Uuid, center: [f64; 3], radius: f64) -> Result<Vec<SpatialObject<T>>, String> {
+        let handle = self.region(region_id)?;
+        let region = self.lock_region(region_id, &handle)?;
+
+        validate_point(center).map_err(|e| format!("Invalid query center: {}", e))?;
+        if radius.is_nan() || radius < 0.0 {
+            return Err(format!("Invalid query radius: {}", radius));
+        }
+
+        let mut results: Vec<SpatialObject<T>> = region.rtree.locate_within_distance(center, radius * radius)
+            .cloned()
+            .collect();
+        self.order_results(&mut results, Some(center));
+
+        Ok(results)
+    }
+
+    /// Queries objects within a bounding box across every loaded region.
+    ///
+    /// This function runs the bounding-box query against all regions and merges the results, so
+    /// callers don't need to know which region(s) a volume overlaps. Regions whose objects all lie
+    /// outside the query box are skipped without searching their R-trees.
+    ///
+    /// # Arguments
+    ///
+    /// * `min_x`, `min_y`, `min_z` - The minimum coordinates of the bounding box.
+    /// * `max_x`, `max_y`, `max_z` - The maximum coordinates of the bounding box.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<SpatialObject<T>>, String>` - A vector of `SpatialObject`s within the bounding box if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// let objects = vault_manager.query_all(-150.0, -10.0, -10.0, 150.0, 10.0, 10.0).expect("Failed to query regions");
+    /// println!("Found {} objects across all regions", objects.len());
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - Objects are matched like `query_region` matches them: their size-based bounding box must be
+    ///   fully contained in the query box, wherever they lie relative to their region's bounds.
+    /// - Regions are locked one at a time.
+    /// - Unloaded regions are skipped; use `query_world_cold` to include them.
+    pub fn query_all(&self, min_x: f64, min_y: f64, min_z: f64, max_x: f64, max_y: f64, max_z: f64) -> Result<Vec<SpatialObject<T>>, String> {
+        let envelope = query_box([min_x, min_y, min_z], [max_x, max_y, max_z])?;
+        let mut results = Vec::new();
+
+        for (region_id, region) in self.region_handles() {
+            let region = self.locks.acquire(region_id, &region);
+            // Objects may lie outside their region's bounds, so prune by the objects themselves
+            if !region.rtree.root().envelope().intersects(&envelope) {
+                continue;
+            }
+            results.extend(region.rtree.locate_in_envelope(&envelope).cloned());
+        }
+        self.order_results(&mut results, Some(box_center([min_x, min_y, min_z], [max_x, max_y, max_z])));
+
+        Ok(results)
+    }
+
+    /// Queries objects within a bounding box across the whole world, including regions that aren't loaded.
+    ///
+    /// Loaded regions are searched in memory like `query_all`. Objects belonging to regions that
+    /// aren't in memory are read straight from the persistent database, without loading their
+    /// regions, so analytics over the whole world don't force everything into memory.
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - The minimum corner of the query box [x, y, z].
+    /// * `max` - The maximum corner of the query box [x, y, z].
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<SpatialObject<T>>, String>` - A vector of `SpatialObject`s within the bounding box if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// let objects = vault_manager.query_world_cold([-5000.0, -100.0, -5000.0], [5000.0, 100.0, 5000.0])
+    ///     .expect("Failed to query world");
+    /// println!("{} objects in the world", objects.len());
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - Objects in loaded regions reflect in-memory state, including changes not yet persisted.
+    /// - Objects read from the database are returned as they were last persisted.
+    /// - Both sources match objects the same way: their size-based bounding box must be fully
+    ///   contained in the query box, so whether an object is returned doesn't depend on whether
+    ///   its region is loaded.
+    pub fn query_world_cold(&self, min: [f64; 3], max: [f64; 3]) -> Result<Vec<SpatialObject<T>>, String> {
+        let mut results = self.query_all(min[0], min[1], min[2], max[0], max[1], max[2])?;
+
+        let points = self.persistent_db.get_points_in_aabb(min, max)
+            .map_err(|e| format!("Failed to query persistent database: {}", e))?;
+        for (region_id, point) in points {
+            if self.is_region_loaded(region_id) {
+                continue;
+            }
+            let uuid = point.id.ok_or("Stored point has no ID")?;
+            let custom_data: T = serde_json::from_value(point.custom_data)
+                .map_err(|e| format!("Failed to deserialize custom data of object {}: {}", uuid, e))?;
+            results.push(SpatialObject {
+                uuid,
+                object_type: ObjectType::from(point.object_type),
+                point: [point.x, point.y, point.z],
+                custom_data: Arc::new(custom_data),
+                size: point.size,
+                version: point.version,
+                velocity: point.velocity,
+                orientation: point.orientation,
+            });
+        }
+        self.order_results(&mut results, Some(box_center(min, max)));
+
+        Ok(results)
+    }
+
+    /// Queries objects within a specific region, aborting if the given limits are exceeded.
+    ///
+    /// This behaves like `query_region`, but periodically checks `limits` while collecting results
+    /// so a query over a very dense region can't stall a game tick indefinitely.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_id` - The UUID of the region to query.
+    /// * `min` - The minimum corner of the bounding box [x, y, z].
+    /// * `max` - The maximum corner of the bounding box [x, y, z].
+    /// * `limits` - The deadline and/or cancellation token bounding the query.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<SpatialObject<T>>, String>` - A vector of `SpatialObject`s within the bounding box if successful, or an error message if the query failed or was aborted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData, QueryLimits};
+    /// # use uuid::Uuid;
+    /// # use std::time::Duration;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let region_id = Uuid::new_v4();
+    /// let limits = QueryLimits::with_timeout(Duration::from_millis(2));
+    /// let objects = vault_manager.query_region_with_limits(region_id, [0.0, 0.0, 0.0], [10.0, 10.0, 10.0], &limits)
+    ///     .unwrap_or_default();
+    /// ```
+    pub fn query_region_with_limits(&self, region_id: Uuid, min: [f64; 3], max: [f64; 3], limits: &QueryLimits) -> Result<Vec<SpatialObject<T>>, String> {
+        let handle = self.region(region_id)?;
+        let region = self.lock_region(region_id, &handle)?;
+
+        limits.check()?;
+        let envelope = query_box(min, max)?;
+        let mut results: Vec<SpatialObject<T>> = Vec::new();
+        for obj in region.rtree.locate_in_envelope(&envelope) {
+            // Checking the clock on every object would dominate small queries
+            if results.len() % LIMIT_CHECK_INTERVAL == LIMIT_CHECK_INTERVAL - 1 {
+                limits.check()?;
+            }
+            results.push(obj.clone());
+        }
+        self.order_results(&mut results, Some(box_center(min, max)));
+
+        Ok(results)
+    }
+
+    /// Queries objects of a single type within a specified region.
+    ///
+    /// Each region keeps a separate spatial index per object type, so this only visits objects
+    /// of the requested type instead of filtering the full result of `query_region`.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_id` - The UUID of the region to query.
+    /// * `min` - The minimum corner of the query box [x, y, z].
+    /// * `max` - The maximum corner of the query box [x, y, z].
+    /// * `object_type` - The type of object to return (e.g., "player").
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<SpatialObject<T>>, String>` - A vector of matching `SpatialObject`s if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # use uuid::Uuid;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let region_id = Uuid::new_v4();
+    /// let players = vault_manager.query_region_by_type(region_id, [0.0, 0.0, 0.0], [10.0, 10.0, 10.0], "player")
+    ///     .expect("Failed to query players");
+    /// ```
+    pub fn query_region_by_type(&self, region_id: Uuid, min: [f64; 3], max: [f64; 3], object_type: &str) -> Result<Vec<SpatialObject<T>>, String> {
+        let handle = self.region(region_id)?;
+        let region = self.lock_region(region_id, &handle)?;
+
+        let envelope = query_box(min, max)?;
+        let mut results = match region.objects_of_type(object_type) {
+            Some(typed) => typed.locate_in_envelope(&envelope).cloned().collect(),
+            None => Vec::new(),
+        };
+        self.order_results(&mut results, Some(box_center(min, max)));
+
+        Ok(results)
+    }
+
+    /// Counts the objects of each type within a specified region.
+    ///
+    /// The counts come from the region's per-type spatial indexes, so no object is visited.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_id` - The UUID of the region to count objects in.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<HashMap<ObjectType, usize>, String>` - The number of objects of each type present in the region, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # use uuid::Uuid;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let region_id = Uuid::new_v4();
+    /// let counts = vault_manager.count_by_type(region_id).expect("Failed to count objects");
+    /// println!("{} players online here", counts.get("player").copied().unwrap_or(0));
+    /// ```
+    pub fn count_by_type(&self, region_id: Uuid) -> Result<HashMap<ObjectType, usize>, String> {
+        let handle = self.region(region_id)?;
+        let region = self.lock_region(region_id, &handle)?;
+        Ok(region.type_counts())
+    }
+
+    /// Returns statistics of a region: its object counts, occupied space, R-tree depth and memory use.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_id` - The UUID of the region to describe.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<RegionStats, String>` - The region's statistics if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # use uuid::Uuid;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let region_id = Uuid::new_v4();
+    /// let stats = vault_manager.region_stats(region_id).expect("Failed to get region statistics");
+    /// println!("{} objects, tree depth {}, about {} KiB", stats.object_count, stats.tree_depth, stats.estimated_memory / 1024);
+    /// if let Some((min, max)) = stats.occupied_bounds {
+    ///     println!("Objects span {:?} to {:?}", min, max);
+    /// }
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - A region that isn't loaded is loaded first, since the statistics describe its in-memory indexes.
+    /// - The memory estimate covers the spatial indexes, the region's bookkeeping and one copy of each
+    ///   distinct custom data value, but not memory the custom data owns on the heap, such as strings.
+    pub fn region_stats(&self, region_id: Uuid) -> Result<RegionStats, String> {
+        let handle = self.region(region_id)?;
+        let region = self.lock_region(region_id, &handle)?;
+        Ok(region.stats())
+    }
+
+    /// Queries objects of a single type within a bounding box across every loaded region.
+    ///
+    /// This combines `query_all` and `query_region_by_type`: regions whose objects of `object_type`
+    /// all lie outside the query box are skipped, and only the spatial index of that type is searched
+    /// in the others.
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - The minimum corner of the query box [x, y, z].
+    /// * `max` - The maximum corner of the query box [x, y, z].
+    /// * `object_type` - The type of object to return (e.g., "player").
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<SpatialObject<T>>, String>` - A vector of matching `SpatialObject`s if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// let players = vault_manager.query_all_by_type([-500.0; 3], [500.0; 3], "player").expect("Failed to query players");
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - Unloaded regions are skipped, like in `query_all`.
+    pub fn query_all_by_type(&self, min: [f64; 3], max: [f64; 3], object_type: &str) -> Result<Vec<SpatialObject<T>>, String> {
+        let envelope = query_box(min, max)?;
+        let mut results = Vec::new();
+
+        for (region_id, region) in self.region_handles() {
+            let region = self.locks.acquire(region_id, &region);
+            // Objects may lie outside their region's bounds, so prune by the objects themselves
+            if let Some(typed) = region.objects_of_type(object_type) {
+                if !typed.root().envelope().intersects(&envelope) {
+                    continue;
+                }
+                results.extend(typed.locate_in_envelope(&envelope).cloned());
+            }
+        }
+        self.order_results(&mut results, Some(box_center(min, max)));
+
+        Ok(results)
+    }
+
+    /// Queries objects within a specified region that match a filter.
+    ///
+    /// The filter runs against each candidate before it's cloned, so rejected objects cost
+    /// nothing beyond the check itself.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_id` - The UUID of the region to query.
+    /// * `min` - The minimum corner of the query box [x, y, z].
+    /// * `max` - The maximum corner of the query box [x, y, z].
+    /// * `filter` - Returns `true` for objects that should be included in the result.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<SpatialObject<T>>, String>` - A vector of matching `SpatialObject`s if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # use uuid::Uuid;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let region_id = Uuid::new_v4();
+    /// // Everything in the area that isn't a building
+    /// let loose = vault_manager.query_region_filtered(region_id, [0.0, 0.0, 0.0], [10.0, 10.0, 10.0], |obj| obj.object_type != "building")
+    ///     .expect("Failed to query region");
+    /// ```
+    pub fn query_region_filtered<F>(&self, region_id: Uuid, min: [f64; 3], max: [f64; 3], filter: F) -> Result<Vec<SpatialObject<T>>, String>
+    where
+        F: Fn(&SpatialObject<T>) -> bool,
+    {
+        let handle = self.region(region_id)?;
+        let region = self.lock_region(region_id, &handle)?;
+
+        let envelope = query_box(min, max)?;
+        let mut results: Vec<SpatialObject<T>> = region.rtree.locate_in_envelope(&envelope)
+            .filter(|obj| filter(obj))
+            .cloned()
+            .collect();
+        self.order_results(&mut results, Some(box_center(min, max)));
+
+        Ok(results)
+    }
+
+    /// Queries objects within a specified region whose custom data matches a predicate.
+    ///
+    /// This is `query_region_filtered` for predicates that only look at custom data, such as
+    /// "players above level 50". The predicate runs before an object is cloned out of the R-tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_id` - The UUID of the region to query.
+    /// * `min` - The minimum corner of the query box [x, y, z].
+    /// * `max` - The maximum corner of the query box [x, y, z].
+    /// * `predicate` - Returns `true` for custom data whose objects should be included in the result.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<SpatialObject<T>>, String>` - A vector of matching `SpatialObject`s if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # use uuid::Uuid;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let region_id = Uuid::new_v4();
+    /// let veterans = vault_manager.query_region_by_data(region_id, [-50.0; 3], [50.0; 3], |data: &CustomData| data.level > 50)
+    ///     .expect("Failed to query region");
+    /// ```
+    pub fn query_region_by_data<F>(&self, region_id: Uuid, min: [f64; 3], max: [f64; 3], predicate: F) -> Result<Vec<SpatialObject<T>>, String>
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.query_region_filtered(region_id, min, max, |obj| predicate(&obj.custom_data))
+    }
+
+    /// Queries objects within a specified region that match a filter expression.
+    ///
+    /// This is the string-based counterpart of `query_region_filtered`, for filters that come
+    /// from tools or clients rather than Rust code.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_id` - The UUID of the region to query.
+    /// * `min` - The minimum corner of the query box [x, y, z].
+    /// * `max` - The maximum corner of the query box [x, y, z].
+    /// * `filter` - A parsed filter expression.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<SpatialObject<T>>, String>` - A vector of matching `SpatialObject`s if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData, Filter};
+    /// # use uuid::Uuid;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let region_id = Uuid::new_v4();
+    /// let filter = Filter::parse(r#"type == "player" && data.level >= 10"#).expect("Invalid filter");
+    /// let veterans = vault_manager.query_region_where(region_id, [0.0, 0.0, 0.0], [10.0, 10.0, 10.0], &filter)
+    ///     .expect("Failed to query region");
+    /// ```
+    pub fn query_region_where(&self, region_id: Uuid, min: [f64; 3], max: [f64; 3], filter: &Filter) -> Result<Vec<SpatialObject<T>>, String> {
+        self.query_region_filtered(region_id, min, max, |obj| filter.matches(obj))
+    }
+
+    /// Queries objects inside a cone within a specific region.
+    ///
+    /// This function finds every object that lies within `range` of the `apex` and within `angle`
+    /// of the cone's axis. It's intended for AI vision checks, spotlights, and other directional
+    /// sensing in game logic.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_id` - The UUID of the region to query.
+    /// * `apex` - The tip of the cone [x, y, z], e.g. the eye position of an NPC.
+    /// * `direction` - The direction the cone opens towards. It does not need to be normalized.
+    /// * `angle` - The half-angle of the cone in radians, measured from its axis.
+    /// * `range` - The maximum distance from the apex.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<SpatialObject<T>>, String>` - A vector of `SpatialObject`s inside the cone if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # use uuid::Uuid;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let region_id = Uuid::new_v4();
+    /// // Everything a guard at the origin can see looking down the x-axis with a 90 degree field of view
+    /// let visible = vault_manager.query_cone(region_id, [0.0, 0.0, 0.0], [1.0, 0.0, 0.0], std::f64::consts::FRAC_PI_4, 50.0)
+    ///     .expect("Failed to query cone");
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - Candidates are first gathered from the R-tree using the bounding box of the cone's range,
+    ///   then filtered with exact distance and angle tests against their positions, so sized
+    ///   objects are matched by their center.
+    /// - An object located exactly at the apex is considered inside the cone.
+    pub fn query_cone(&self, region_id: Uuid, apex: [f64; 3], direction: [f64; 3], angle: f64, range: f64) -> Result<Vec<SpatialObject<T>>, String> {
+        let handle = self.region(region_id)?;
+        let region = self.lock_region(region_id, &handle)?;
+
+        validate_point(apex).map_err(|e| format!("Invalid cone apex: {}", e))?;
+        let direction_length = (direction[0] * direction[0] + direction[1] * direction[1] + direction[2] * direction[2]).sqrt();
+        if !direction_length.is_finite() || direction_length == 0.0 {
+            return Err("Cone direction must be a finite, non-zero vector".to_string());
+        }
+        if !(0.0..=std::f64::consts::PI).contains(&angle) || !range.is_finite() || range < 0.0 {
+            return Err(format!("Invalid cone parameters: angle {}, range {}", angle, range));
+        }
+        let axis = [direction[0] / direction_length, direction[1] / direction_length, direction[2] / direction_length];
+        let cos_angle = angle.cos();
+
+        let envelope = AABB::from_corners(
+            [apex[0] - range, apex[1] - range, apex[2] - range],
+            [apex[0] + range, apex[1] + range, apex[2] + range],
+        );
+        let mut results: Vec<SpatialObject<T>> = region.rtree.locate_in_envelope_intersecting(&envelope)
+            .filter(|obj| {
+                let offset = [obj.point[0] - apex[0], obj.point[1] - apex[1], obj.point[2] - apex[2]];
+                let distance_2 = offset[0] * offset[0] + offset[1] * offset[1] + offset[2] * offset[2];
+                if distance_2 > range * range {
+                    return false;
+                }
+                if distance_2 == 0.0 {
+                    return true;
+                }
+                let along_axis = offset[0] * axis[0] + offset[1] * axis[1] + offset[2] * axis[2];
+                along_axis >= distance_2.sqrt() * cos_angle
+            })
+            .cloned()
+            .collect();
+        self.order_results(&mut results, Some(apex));
+
+        Ok(results)
+    }
+
+    /// Queries objects inside a view frustum within a specific region.
+    ///
+    /// This finds what a client camera can see without approximating its view with a large
+    /// bounding box. Subtrees of the spatial index that lie entirely outside the frustum are
+    /// skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_id` - The UUID of the region to query.
+    /// * `frustum` - The frustum to test, e.g. built with `Frustum::from_view_projection`.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<SpatialObject<T>>, String>` - A vector of `SpatialObject`s inside the frustum if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData, Frustum};
+    /// # use uuid::Uuid;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let region_id = Uuid::new_v4();
+    /// # let view_projection = [[1.0, 0.0, 0.0, 0.0], [0.0, 1.0, 0.0, 0.0], [0.0, 0.0, 1.0, 0.0], [0.0, 0.0, 0.0, 1.0]];
+    /// let frustum = Frustum::from_view_projection(view_projection);
+    /// let visible = vault_manager.query_frustum(region_id, &frustum).expect("Failed to query frustum");
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - Objects are tested by their bounding box, so an object with a size is returned if any part of it may be visible.
+    /// - The test is conservative near the frustum's edges and corners and may include objects just outside it.
+    pub fn query_frustum(&self, region_id: Uuid, frustum: &Frustum) -> Result<Vec<SpatialObject<T>>, String> {
+        let handle = self.region(region_id)?;
+        let region = self.lock_region(region_id, &handle)?;
+
+        let mut results: Vec<SpatialObject<T>> = region.rtree.locate_with_selection_function(FrustumSelection(frustum))
+            .cloned()
+            .collect();
+        // A frustum has no center to measure from, so it's always sorted by UUID
+        self.order_results(&mut results, None);
+
+        Ok(results)
+    }
+
+    /// Casts a ray through a specific region and returns every object it hits.
+    ///
+    /// The ray is tested against each object's bounding box, which is derived from the object's
+    /// `size`. It's intended for line-of-sight checks and hitscan projectiles on the server.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_id` - The UUID of the region to cast the ray through.
+    /// * `origin` - The start of the ray [x, y, z].
+    /// * `direction` - The direction of the ray. It does not need to be normalized.
+    /// * `max_distance` - How far along the ray to test for hits.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<RaycastHit<T>>, String>` - The hits ordered by distance from the origin, nearest first, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # use uuid::Uuid;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let region_id = Uuid::new_v4();
+    /// let hits = vault_manager.raycast(region_id, [0.0, 1.5, 0.0], [1.0, 0.0, 0.0], 100.0)
+    ///     .expect("Failed to cast ray");
+    /// if let Some(first) = hits.first() {
+    ///     println!("Line of sight blocked by {} at {}", first.object.uuid, first.distance);
+    /// }
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - Objects with a zero size are only hit if they lie exactly on the ray.
+    /// - An object whose box contains the origin is hit at distance zero.
+    pub fn raycast(&self, region_id: Uuid, origin: [f64; 3], direction: [f64; 3], max_distance: f64) -> Result<Vec<RaycastHit<T>>, String> {
+        let handle = self.region(region_id)?;
+        let region = self.lock_region(region_id, &handle)?;
+
+        validate_point(origin).map_err(|e| format!("Invalid ray origin: {}", e))?;
+        if max_distance.is_nan() || max_distance < 0.0 {
+            return Err(format!("Invalid ray distance: {}", max_distance));
+        }
+        let length = (direction[0] * direction[0] + direction[1] * direction[1] + direction[2] * direction[2]).sqrt();
+        if length == 0.0 || !length.is_finite() {
+            return Err("Ray direction must be a non-zero, finite vector".to_string());
+        }
+        let direction = [direction[0] / length, direction[1] / length, direction[2] / length];
+        let end = [
+            origin[0] + direction[0] * max_distance,
+            origin[1] + direction[1] * max_distance,
+            origin[2] + direction[2] * max_distance,
+        ];
+
+        // Only objects whose boxes touch the box around the ray segment can be hit
+        let segment = AABB::from_corners(origin, end);
+        let mut hits: Vec<RaycastHit<T>> = region.rtree.locate_in_envelope_intersecting(&segment)
+            .filter_map(|obj| {
+                ray_box_distance(origin, direction, max_distance, &obj.envelope())
+                    .map(|distance| RaycastHit { object: obj.clone(), distance })
+            })
+            .collect();
+        hits.sort_by(|a, b| a.distance.total_cmp(&b.distance).then(a.object.uuid.cmp(&b.object.uuid)));
+
+        Ok(hits)
+    }
+
+    /// Queries objects in a vertical column within a specific region.
+    ///
+    /// This function finds every object located at the given x/z position with a y-coordinate
+    /// between `y_min` and `y_max`. It's meant for "what's above/below this spot" checks that are
+    /// common in voxel and flight games, such as finding the ground under a player or the blocks
+    /// stacked on a tile.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_id` - The UUID of the region to query.
+    /// * `x` - The x-coordinate of the column.
+    /// * `z` - The z-coordinate of the column.
+    /// * `y_min` - The lowest y-coordinate to include.
+    /// * `y_max` - The highest y-coordinate to include.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<SpatialObject<T>>, String>` - The objects in the column ordered from bottom to top if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # use uuid::Uuid;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let region_id = Uuid::new_v4();
+    /// // Find the highest block below y = 64 at tile (12, 7)
+    /// let column = vault_manager.query_column(region_id, 12.0, 7.0, f64::MIN, 64.0).expect("Failed to query column");
+    /// let ground = column.last();
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - Objects must share the x/z coordinates of the column to within `COLUMN_TOLERANCE`, which
+    ///   suits grid-aligned worlds. Sized objects are matched by their position, not their extent.
+    /// - Results are sorted by ascending y-coordinate, so the first and last entries are the lowest and highest objects.
+    pub fn query_column(&self, region_id: Uuid, x: f64, z: f64, y_min: f64, y_max: f64) -> Result<Vec<SpatialObject<T>>, String> {
+        let handle = self.region(region_id)?;
+        let region = self.lock_region(region_id, &handle)?;
+
+        if !x.is_finite() || !z.is_finite() {
+            return Err(format!("Invalid column position: x {}, z {}", x, z));
+        }
+        if y_min.is_nan() || y_max.is_nan() || y_min > y_max {
+            return Err(format!("Invalid column range: {} to {}", y_min, y_max));
+        }
+
+        // Sized objects extend past their position, so gather every object touching the column first
+        let envelope = AABB::from_corners(
+            [x - COLUMN_TOLERANCE, y_min, z - COLUMN_TOLERANCE],
+            [x + COLUMN_TOLERANCE, y_max, z + COLUMN_TOLERANCE],
+        );
+        let mut results: Vec<SpatialObject<T>> = region.rtree.locate_in_envelope_intersecting(&envelope)
+            .filter(|obj| {
+                (obj.point[0] - x).abs() <= COLUMN_TOLERANCE
+                    && (obj.point[2] - z).abs() <= COLUMN_TOLERANCE
+                    && (y_min..=y_max).contains(&obj.point[1])
+            })
+            .cloned()
+            .collect();
+        results.sort_by(|a, b| a.point[1].total_cmp(&b.point[1]).then(a.uuid.cmp(&b.uuid)));
+
+        Ok(results)
+    }
+
+    /// Queries the objects of a region whose bounding boxes overlap a box.
+    ///
+    /// Unlike `query_region`, which only returns objects whose size-based bounding box is fully
+    /// contained in the box, this returns every object whose bounding box intersects it, touching
+    /// included, which is what a collision or trigger volume check needs. Objects with an orientation are tested with their
+    /// rotated box rather than the axis-aligned box enclosing it.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_id` - The UUID of the region to query.
+    /// * `min` - The minimum corner of the box [x, y, z].
+    /// * `max` - The maximum corner of the box [x, y, z].
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<SpatialObject<T>>, String>` - The objects overlapping the box if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # use uuid::Uuid;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let region_id = Uuid::new_v4();
+    /// // Everything the explosion's blast box touches
+    /// let hit = vault_manager.query_overlapping(region_id, [8.0, 0.0, 8.0], [12.0, 4.0, 12.0]).expect("Failed to query overlapping objects");
+    /// ```
+    pub fn query_overlapping(&self, region_id: Uuid, min: [f64; 3], max: [f64; 3]) -> Result<Vec<SpatialObject<T>>, String> {
+        let envelope = query_box(min, max)?;
+        let query = OrientedBox::axis_aligned(min, max);
+        let handle = self.region(region_id)?;
+        let region = self.lock_region(region_id, &handle)?;
+        let mut results: Vec<SpatialObject<T>> = region.rtree.locate_in_envelope_intersecting(&envelope)
+            .filter(|obj| obj.orientation.is_none() || obj.oriented_box().intersects(&query))
+            .cloned()
+            .collect();
+        self.order_results(&mut results, Some(box_center(min, max)));
+        Ok(results)
+    }
+
+    /// Finds every pair of objects in a region whose bounding boxes overlap.
+    ///
+    /// This is the broad phase of collision detection: the pairs are candidates whose exact
+    /// shapes the caller tests in its narrow phase. The region's R-tree is joined with itself, so
+    /// only objects close to each other are compared instead of every pair.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_id` - The UUID of the region to check.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<(SpatialObject<T>, SpatialObject<T>)>, String>` - The overlapping pairs if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # use uuid::Uuid;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let region_id = Uuid::new_v4();
+    /// for (a, b) in vault_manager.find_intersections(region_id).expect("Failed to find intersections") {
+    ///     // ... run the narrow phase on a and b ...
+    /// }
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - Each pair is returned once, with the smaller UUID first.
+    /// - Boxes that only touch count as overlapping. Objects with a zero size only overlap objects
+    ///   whose boxes contain their point.
+    /// - Objects with an orientation are compared by their rotated boxes, so the boxes enclosing
+    ///   them overlapping isn't enough.
+    pub fn find_intersections(&self, region_id: Uuid) -> Result<Vec<ObjectPair<T>>, String> {
+        let handle = self.region(region_id)?;
+        let region = self.lock_region(region_id, &handle)?;
+        let mut pairs: Vec<ObjectPair<T>> = region.rtree.intersection_candidates_with_other_tree(&region.rtree)
+            .filter(|(a, b)| a.uuid < b.uuid)
+            .filter(|(a, b)| (a.orientation.is_none() && b.orientation.is_none()) || a.oriented_box().intersects(&b.oriented_box()))
+            .map(|(a, b)| (a.clone(), b.clone()))
+            .collect();
+        if self.config.query_order != QueryOrder::Index {
+            pairs.sort_unstable_by_key(|(a, b)| (a.uuid, b.uuid));
+        }
+        Ok(pairs)
+    }
+
+    /// Finds the `k` objects closest to a point within a specific region.
+    ///
+    /// This function walks the region's R-tree in order of increasing distance, so it only visits
+    /// as many objects as needed. It's the building block for aggro, targeting, and "closest
+    /// resource" logic.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_id` - The UUID of the region to query.
+    /// * `point` - The query point [x, y, z].
+    /// * `k` - The maximum number of objects to return.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<SpatialObject<T>>, String>` - Up to `k` objects sorted by distance, nearest first, if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # use uuid::Uuid;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let region_id = Uuid::new_v4();
+    /// let targets = vault_manager.query_k_nearest(region_id, [10.0, 0.0, 5.0], 3).expect("Failed to query nearest objects");
+    /// if let Some(closest) = targets.first() {
+    ///     println!("Closest target: {}", closest.uuid);
+    /// }
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - Only the given region is searched. Use `query_nearest_neighbors` with `include_adjacent` to
+    ///   also consider objects across the region's boundary.
+    pub fn query_k_nearest(&self, region_id: Uuid, point: [f64; 3], k: usize) -> Result<Vec<SpatialObject<T>>, String> {
+        self.query_nearest_neighbors(region_id, point, k, false)
+    }
+
+    /// Finds the objects closest to a point, optionally looking beyond the region's boundary.
+    ///
+    /// This function returns up to `k` objects ordered by their distance to `point`. When
+    /// `include_adjacent` is set, neighbouring regions holding objects within the current search
+    /// distance are searched as well and all candidates are merged by true distance, so results
+    /// near region edges aren't missing objects that sit just across the boundary.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_id` - The UUID of the region the search starts in.
+    /// * `point` - The query point [x, y, z].
+    /// * `k` - The maximum number of objects to return.
+    /// * `include_adjacent` - Whether to also search neighbouring regions.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<SpatialObject<T>>, String>` - The closest objects, nearest first, if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # use uuid::Uuid;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let region_id = Uuid::new_v4();
+    /// let nearest = vault_manager.query_nearest_neighbors(region_id, [95.0, 0.0, 0.0], 5, true)
+    ///     .expect("Failed to query nearest neighbors");
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - Neighbouring regions are pruned using their bounding boxes (see `VaultRegion::bounds`), which
+    ///   assumes objects lie within the bounds of the region they were added to.
+    /// - Only one region is locked at a time.
+    pub fn query_nearest_neighbors(&self, region_id: Uuid, point: [f64; 3], k: usize, include_adjacent: bool) -> Result<Vec<SpatialObject<T>>, String> {
+        let handle = self.region(region_id)?;
+        let region = self.lock_region(region_id, &handle)?;
+
+        validate_point(point).map_err(|e| format!("Invalid query point: {}", e))?;
+        if k == 0 {
+            return Ok(Vec::new());
+        }
+
+        let mut candidates = self.nearest_candidates(&region.rtree, point, k);
+        drop(region);
+
+        if include_adjacent {
+            // Anything farther away than the current k-th candidate can't improve the result
+            let search_distance_2 = if candidates.len() == k {
+                candidates[k - 1].1
+            } else {
+                f64::INFINITY
+            };
+
+            for (other_id, other_region) in self.region_handles() {
+                if other_id == region_id {
+                    continue;
+                }
+                // Objects may lie outside their region's bounds, so prune by the objects themselves
+                if self.locks.acquire(other_id, &other_region).rtree.root().envelope().distance_2(&point) > search_distance_2 {
+                    continue;
+                }
+                let other_region = self.lock_region(other_id, &other_region)?;
+                candidates.extend(
+                    self.nearest_candidates(&other_region.rtree, point, k).into_iter()
+                        .take_while(|(_, distance_2)| *distance_2 <= search_distance_2),
+                );
+            }
+        }
+
+        if include_adjacent || self.config.query_order != QueryOrder::Index {
+            candidates.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.uuid.cmp(&b.0.uuid)));
+            candidates.truncate(k);
+        }
+
+        Ok(candidates.into_iter().map(|(obj, _)| obj).collect())
+    }
+
+    /// Transfers a player (object) from one region to another.
+    ///
+    /// This function moves a player object from its current region to a new region,
+    /// updating both the in-memory structures and the persistent database. It's particularly
+    /// useful for handling player movement between different areas of your game world.
+    ///
+    /// # Arguments
+    ///
+    /// * `player_uuid` - The UUID of the player to transfer.
+    /// * `from_region_id` - The UUID of the source region.
+    /// * `to_region_id` - The UUID of the destination region.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), String>` - An empty result if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # use uuid::Uuid;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let from_region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0).unwrap();
+    /// # let to_region_id = vault_manager.create_or_load_region([200.0, 200.0, 200.0], 100.0).unwrap();
+    /// # let player_id = Uuid::new_v4();
+    /// # let custom_data = CustomData { /* ... */ };
+    /// # vault_manager.add_object(from_region_id, player_id, "player", 1.0, 2.0, 3.0, custom_data).unwrap();
+    /// vault_manager.transfer_player(player_id, from_region_id, to_region_id).expect("Failed to transfer player");
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - The player's position is updated to the center of the destination region.
+    /// - This method does not check if the new position is valid within the game world; that logic should be handled separately.
+    /// - The new position and region are written to the persistent database in one transaction before the
+    ///   in-memory regions change, so the transfer survives a crash without calling `persist_to_disk()`.
+    /// - Equivalent to `transfer_object(player_uuid, from_region_id, to_region_id, false)`.
+    pub fn transfer_player(&self, player_uuid: Uuid, from_region_id: Uuid, to_region_id: Uuid) -> Result<(), String> {
+        self.transfer_object(player_uuid, from_region_id, to_region_id, false)?;
+        self.players.lock().unwrap().insert(player_uuid, to_region_id);
+        Ok(())
+    }
+
+    /// Adds a player to the world, in the region containing its position.
+    ///
+    /// # Arguments
+    ///
+    /// * `player_id` - The UUID of the player.
+    /// * `position` - The position to spawn the player at [x, y, z].
+    /// * `custom_data` - The custom data associated with the player, wrapped in an `Arc`.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Uuid, String>` - The UUID of the region the player was spawned in, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # use uuid::Uuid;
+    /// # use std::sync::Arc;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// let player_id = Uuid::new_v4();
+    /// let region_id = vault_manager.spawn_player(player_id, [0.0, 1.0, 0.0], Arc::new(CustomData { /* ... */ }))
+    ///     .expect("Failed to spawn player");
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - Players are objects of type "player", so the rest of the API sees them like any other object.
+    /// - The region is chosen like in `add_object_auto`, and the player is written to the persistent database immediately.
+    /// - Spawning a player that already exists fails.
+    pub fn spawn_player(&self, player_id: Uuid, position: [f64; 3], custom_data: Arc<T>) -> Result<Uuid, String> {
+        if self.player_region(player_id)?.is_some() {
+            return Err(format!("Player already spawned: {}", player_id));
+        }
+        let region_id = self.add_object_auto(player_id, PLAYER_TYPE, position, [0.0; 3], custom_data)?;
+        self.players.lock().unwrap().insert(player_id, region_id);
+        Ok(region_id)
+    }
+
+    /// Moves a player, changing its region if it leaves the current one.
+    ///
+    /// This behaves like `move_object`, but finds the player through the player index instead of
+    /// searching every region.
+    ///
+    /// # Arguments
+    ///
+    /// * `player_id` - The UUID of the player.
+    /// * `position` - The new position of the player [x, y, z].
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Uuid, String>` - The UUID of the region holding the player after the move, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # use uuid::Uuid;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let player_id = Uuid::new_v4();
+    /// vault_manager.move_player(player_id, [10.0, 1.0, -3.0]).expect("Failed to move player");
+    /// ```
+    pub fn move_player(&self, player_id: Uuid, position: [f64; 3]) -> Result<Uuid, String> {
+        let region_id = self.player_region(player_id)?
+            .ok_or_else(|| format!("Player not found: {}", player_id))?;
+        // Objects the player carries move along with it, like with `move_object`
+        let region_id = if self.children_of(player_id).is_empty() {
+            self.relocate_object(player_id, region_id, position, None)?
+        } else {
+            self.move_with_attached(player_id, position, None)?
+        };
+        self.players.lock().unwrap().insert(player_id, region_id);
+        Ok(region_id)
+    }
+
+    /// Finds every player within a radius of a position, across all loaded regions.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - The center of the search [x, y, z].
+    /// * `radius` - The search radius.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<SpatialObject<T>>, String>` - The players within the radius, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// for player in vault_manager.players_near([0.0, 0.0, 0.0], 50.0).expect("Failed to find players") {
+    ///     println!("Player {} is nearby", player.uuid);
+    /// }
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - Only the players' type index is searched, so other objects don't slow the query down.
+    /// - Unloaded regions are skipped like in `query_all`.
+    pub fn players_near(&self, position: [f64; 3], radius: f64) -> Result<Vec<SpatialObject<T>>, String> {
+        validate_point(position).map_err(|e| format!("Invalid query center: {}", e))?;
+        if radius.is_nan() || radius < 0.0 {
+            return Err(format!("Invalid query radius: {}", radius));
+        }
+
+        let mut results = Vec::new();
+        for (region_id, region) in self.region_handles() {
+            let region = self.locks.acquire(region_id, &region);
+            // Players may stand outside their region's bounds, so prune by the players themselves
+            if let Some(players) = region.objects_of_type(PLAYER_TYPE) {
+                if players.root().envelope().distance_2(&position) > radius * radius {
+                    continue;
+                }
+                results.extend(players.locate_within_distance(position, radius * radius).cloned());
+            }
+        }
+        Ok(results)
+    }
+
+    /// Returns the region holding a player, refreshing the player index if it's out of date.
+    ///
+    /// Players can also be moved or removed through the generic object API, so the indexed region
+    /// is only trusted after checking that it still holds the player.
+    fn player_region(&self, player_id: Uuid) -> Result<Option<Uuid>, String> {
+        let indexed = self.players.lock().unwrap().get(&player_id).copied();
+        if let Some(region_id) = indexed {
+            if let Ok(region) = self.region(region_id) {
+                let region = self.locks.acquire(region_id, &region);
+                let holds_player = region.get_object(player_id)
+                    .is_some_and(|obj| obj.object_type == PLAYER_TYPE);
+                if holds_player {
+                    return Ok(Some(region_id));
+                }
+            }
+        }
+
+        let found = self.find_object(player_id)?
+            .filter(|(_, object)| object.object_type == PLAYER_TYPE)
+            .map(|(region_id, _)| region_id);
+        let mut players = self.players.lock().unwrap();
+        match found {
+            Some(region_id) => players.insert(player_id, region_id),
+            None => players.remove(&player_id),
+        };
+        Ok(found)
+    }
+
+    /// Transfers any object from one region to another.
+    ///
+    /// Unlike `transfer_player`, this can keep the object's world coordinates instead of moving
+    /// it to the destination region's center.
+    ///
+    /// # Arguments
+    ///
+    /// * `object_id` - The UUID of the object to transfer.
+    /// * `from_region_id` - The UUID of the source region.
+    /// * `to_region_id` - The UUID of the destination region.
+    /// * `keep_position` - Keep the object's current coordinates. They must lie within the destination region's bounding cube.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), String>` - An empty result if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # use uuid::Uuid;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let (object_id, from_region_id, to_region_id) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+    /// // A cart rolling across a region border keeps its coordinates
+    /// vault_manager.transfer_object(object_id, from_region_id, to_region_id, true).expect("Failed to transfer object");
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - The new position and region are written to the persistent database in one transaction before the in-memory regions change.
+    /// - Transferring an object to the region it's already in only moves it when `keep_position` is false.
+    /// - Both regions are locked in ascending order of their UUIDs, whatever the direction of the
+    ///   transfer, so concurrent transfers between the same regions can't deadlock.
+    pub fn transfer_object(&self, object_id: Uuid, from_region_id: Uuid, to_region_id: Uuid, keep_position: bool) -> Result<(), String> {
+        let source = self.region(from_region_id)
+            .map_err(|_| format!("Source region not found: {}", from_region_id))?;
+        let destination = self.region(to_region_id)
+            .map_err(|_| format!("Destination region not found: {}", to_region_id))?;
+
+        // Locking the same region twice would deadlock
+        let (mut from_region, mut to_region) = if from_region_id == to_region_id {
+            (self.lock_region(from_region_id, &source)?, None)
+        } else {
+            let (from_region, to_region) = self.lock_region_pair((from_region_id, &source), (to_region_id, &destination))?;
+            (from_region, Some(to_region))
+        };
+
+        let object = from_region.get_object(object_id)
+            .cloned()
+            .ok_or_else(|| format!("Object not found in source region: {}", object_id))?;
+
+        let (to_center, to_bounds) = match &to_region {
+            Some(to_region) => (to_region.center, to_region.bounds()),
+            None => (from_region.center, from_region.bounds()),
+        };
+        let new_position = if keep_position {
+            if !to_bounds.contains_point(&object.point) {
+                return Err(format!(
+                    "Object {} at {:?} lies outside destination region {}",
+                    object_id, object.point, to_region_id
+                ));
+            }
+            object.point
+        } else {
+            to_center
+        };
+
+        let updated_object = SpatialObject {
+            point: new_position,
+            version: object.version + 1,
+            ..object.clone()
+        };
+
+        // Update the persistent database first so a failure leaves memory untouched
+        let changed_at = from_region.dirty_since(object_id);
+        self.write_position_and_region(&updated_object, Some(to_region_id), changed_at.is_some())?;
+
+        // Only the position was written, so other unflushed changes move with the object
+        from_region.remove_object(&object);
+
+        let target = to_region.as_deref_mut().unwrap_or(&mut from_region);
+        target.insert_object(updated_object);
+        self.index_objects(to_region_id, [object_id]);
+        if let Some(changed_at) = changed_at {
+            target.mark_dirty_at(object_id, changed_at);
+        }
+        self.emit([VaultEvent::ObjectMoved { object_id, from_region: from_region_id, to_region: to_region_id, position: new_position }]);
+
+        Ok(())
+    }
+
+    /// Writes a moved object's new position and version, and optionally its new region, to the
+    /// persistent database in a single transaction.
+    ///
+    /// An object with `unflushed` changes is journaled in the same transaction, so the journal
+    /// entry keeps up with the move.
+    fn write_position_and_region(&self, moved: &SpatialObject<T>, region_id: Option<Uuid>, unflushed: bool) -> Result<(), String> {
+        let (object_id, position) = (moved.uuid, moved.point);
+        self.persistent_db.begin_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        let written = self.persistent_db.update_point_position(object_id, position[0], position[1], position[2])
+            .and_then(|_| self.persistent_db.update_point_version(object_id, moved.version))
+            .and_then(|_| match region_id {
+                Some(region_id) => self.persistent_db.update_point_region(object_id, region_id),
+                None => Ok(()),
+            })
+            .map_err(|e| format!("Failed to update point position in persistent database: {}", e))
+            .and_then(|_| match unflushed {
+                true => self.journal_object(moved),
+                false => Ok(()),
+            });
+        if let Err(e) = written {
+            let _ = self.persistent_db.rollback_transaction();
+            return Err(e);
+        }
+        self.persistent_db.commit_transaction()
+            .map_err(|e| format!("Failed to commit transaction: {}", e))
+    }
+
+    /// Moves an object, including its custom data, from one vault to another.
+    ///
+    /// This is used for characters travelling between shards that are backed by separate
+    /// databases. The object keeps its position and is added to `dst_region` in the destination
+    /// vault before it's removed from the source vault, so it's never lost.
+    ///
+    /// # Arguments
+    ///
+    /// * `src` - The vault currently holding the object.
+    /// * `dst` - The vault to move the object to.
+    /// * `object_id` - The UUID of the object to move.
+    /// * `dst_region` - The UUID of the region in `dst` that receives the object.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), String>` - An empty result if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # use uuid::Uuid;
+    /// # let shard_a: VaultManager<CustomData> = VaultManager::new("shard_a.db").unwrap();
+    /// # let shard_b: VaultManager<CustomData> = VaultManager::new("shard_b.db").unwrap();
+    /// # let player_id = Uuid::new_v4();
+    /// let arrival = shard_b.create_or_load_region([0.0, 0.0, 0.0], 100.0).unwrap();
+    /// VaultManager::transfer_between_vaults(&shard_a, &shard_b, player_id, arrival)
+    ///     .expect("Failed to transfer player between shards");
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - The transfer is best effort rather than a distributed transaction. If the object can't be
+    ///   removed from the source vault, it's removed from the destination again and an error is
+    ///   returned. Only if that compensation also fails can the object end up in both vaults, and
+    ///   the error says so.
+    /// - The destination's world bounds apply to the object's position.
+    /// - An object whose UUID already exists in the destination vault is not transferred.
+    pub fn transfer_between_vaults(src: &VaultManager<T>, dst: &VaultManager<T>, object_id: Uuid, dst_region: Uuid) -> Result<(), String> {
+        let object = src.get_object(object_id)?
+            .ok_or_else(|| format!("Object not found in source vault: {}", object_id))?;
+        if dst.get_object(object_id)?.is_some() {
+            return Err(format!("Object already exists in destination vault: {}", object_id));
+        }
+
+        dst.add_spatial_object(dst_region, object)
+            .map_err(|e| format!("Failed to add object to destination vault: {}", e))?;
+
+        if let Err(e) = src.remove_object(object_id) {
+            return match dst.remove_object(object_id) {
+                Ok(()) => Err(format!("Failed to remove object from source vault, transfer reverted: {}", e)),
+                Err(revert_error) => Err(format!(
+                    "Failed to remove object from source vault ({}) and to revert the transfer ({}); object {} exists in both vaults",
+                    e, revert_error, object_id
+                )),
+            };
+        }
+
+        Ok(())
+    }
+
+    /// Persists all in-memory changes to disk.
+    ///
+    /// This function writes every object changed in memory since it was last persisted, such as
+    /// objects modified with `update_object`, to the persistent database. Other operations write
+    /// through to the database as they happen. It's important to call this method periodically
+    /// to ensure data is not lost in case of unexpected shutdowns.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), String>` - An empty result if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// vault_manager.persist_to_disk().expect("Failed to persist data to disk");
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - Only changed objects are written, so the cost depends on the amount of change rather than the size of the world.
+    /// - The method provides progress feedback using a progress bar.
+    /// - All changes are written in a single transaction, so a failed persist leaves the previous state intact
+    ///   and the changes are retried by the next persist.
+    /// - Regions with changes stay locked until the write is done, so an object can't be changed or
+    ///   removed halfway through and then written back in its old state.
+    pub fn persist_to_disk(&self) -> Result<(), String> {
+        self.persist_to_disk_with_limits(&QueryLimits::none())
+    }
+
+    /// Persists all in-memory changes to disk, aborting if the given limits are exceeded.
+    ///
+    /// This behaves like `persist_to_disk`, but checks `limits` between objects. The whole write
+    /// happens inside a single database transaction, so an aborted persist is rolled back and the
+    /// previously persisted state is left untouched.
+    ///
+    /// # Arguments
+    ///
+    /// * `limits` - The deadline and/or cancellation token bounding the operation.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), String>` - An empty result if successful, or an error message if the operation failed or was aborted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData, QueryLimits};
+    /// # use std::time::Duration;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// let limits = QueryLimits::with_timeout(Duration::from_secs(5));
+    /// if let Err(e) = vault_manager.persist_to_disk_with_limits(&limits) {
+    ///     println!("Persist aborted, will retry next tick: {}", e);
+    /// }
+    /// ```
+    pub fn persist_to_disk_with_limits(&self, limits: &QueryLimits) -> Result<(), String> {
+        self.persist(limits, &OperationHandle::new())
+    }
+
+    /// Persists all in-memory changes to disk, reporting progress through `handle`.
+    ///
+    /// This behaves like `persist_to_disk`. The progress is counted in objects written, and the
+    /// persist can be paused or cancelled between objects through any clone of the handle. A
+    /// cancelled persist is rolled back like one aborted by `persist_to_disk_with_limits`.
+    ///
+    /// # Arguments
+    ///
+    /// * `handle` - The handle to report progress through.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), String>` - An empty result if successful, or an error message if the operation failed or was cancelled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData, OperationHandle};
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// let handle = OperationHandle::new();
+    /// // Hand a clone to a dashboard thread, which may call handle.pause() or handle.cancel()
+    /// vault_manager.persist_to_disk_tracked(&handle).expect("Failed to persist data to disk");
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - The database transaction stays open, and regions with changes stay locked, while the persist is paused.
+    pub fn persist_to_disk_tracked(&self, handle: &OperationHandle) -> Result<(), String> {
+        let result = self.persist(&QueryLimits::none(), handle);
+        handle.finish(&result);
+        result
+    }
+
+    /// Writes changed objects to the persistent database for at most the budgeted time.
+    ///
+    /// Call this once per game tick to spread persistence over many ticks instead of stalling
+    /// a single one. Objects of higher priority are written first, and among objects of equal
+    /// priority the most recently changed ones. Objects that don't fit in the budget stay dirty
+    /// and are written by a later call.
+    ///
+    /// # Arguments
+    ///
+    /// * `budget` - The time to spend and the priority of each object type.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<usize, String>` - The number of objects written, or an error message if the write failed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{PersistBudget, VaultManager, CustomData};
+    /// # use std::time::Duration;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// let budget = PersistBudget::per_tick(Duration::from_millis(2)).with_priority("player", 10);
+    /// let written = vault_manager.persist_tick(&budget).expect("Failed to persist changes");
+    /// println!("Saved {} objects this tick", written);
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - At least one object is written per call, so a tiny budget still makes progress.
+    /// - The budget covers finding and writing the objects. Committing the transaction comes on top.
+    /// - Regions with changes stay locked until the transaction is committed, like with `persist_to_disk`.
+    /// - Objects that are changed constantly can keep older changes waiting. Call `persist_to_disk`
+    ///   when the server has time to spare, such as on shutdown.
+    pub fn persist_tick(&self, budget: &PersistBudget) -> Result<usize, String> {
+        let deadline = Instant::now() + budget.per_tick;
+
+        // Changed regions stay locked until the write is done, so an object removed meanwhile
+        // can't be written back. They're locked in ascending order, like batches lock them.
+        let mut handles = self.region_handles();
+        handles.sort_unstable_by_key(|(region_id, _)| *region_id);
+        let mut regions = HashMap::new();
+        for (region_id, handle) in &handles {
+            let region = self.locks.acquire(*region_id, handle);
+            if region.dirty_count() > 0 {
+                regions.insert(*region_id, region);
+            }
+        }
+
+        // Most important objects first, then the most recently changed ones
+        let mut pending: Vec<(i32, Instant, Uuid, SpatialObject<T>)> = Vec::new();
+        for (region_id, region) in &regions {
+            pending.extend(region.rtree.iter().filter_map(|obj| {
+                region.dirty_since(obj.uuid).map(|changed_at| (budget.priority(obj.object_type), changed_at, *region_id, obj.clone()))
+            }));
+        }
+        if pending.is_empty() {
+            return Ok(0);
+        }
+        pending.sort_unstable_by(|a, b| b.0.cmp(&a.0).then(b.1.cmp(&a.1)));
+
+        self.persistent_db.begin_transaction()
+            .map_err(|e| format!("Failed to begin persistence transaction: {}", e))?;
+        let mut written = 0;
+        for (_, _, region_id, object) in &pending {
+            if written > 0 && Instant::now() >= deadline {
+                break;
+            }
+            if let Err(e) = self.write_objects(*region_id, std::slice::from_ref(object)) {
+                let _ = self.persistent_db.rollback_transaction();
+                return Err(e);
+            }
+            written += 1;
+        }
+        self.persistent_db.commit_transaction()
+            .map_err(|e| format!("Failed to commit persistence transaction: {}", e))?;
+
+        for (_, changed_at, region_id, object) in &pending[..written] {
+            if let Some(region) = regions.get_mut(region_id) {
+                region.clear_dirty(object.uuid, *changed_at);
+            }
+        }
+
+        Ok(written)
+    }
+
+    /// Writes every changed object to the persistent database in one transaction.
+    fn persist(&self, limits: &QueryLimits, handle: &OperationHandle) -> Result<(), String> {
+        limits.check()?;
+        let start_time = Instant::now();
+
+        // Changed regions stay locked until the write is done, so an object removed meanwhile
+        // can't be written back. They're locked in ascending order, like batches lock them.
+        let mut handles = self.region_handles();
+        handles.sort_unstable_by_key(|(region_id, _)| *region_id);
+        let mut regions = Vec::new();
+        for (region_id, handle) in &handles {
+            let region = self.locks.acquire(*region_id, handle);
+            if region.dirty_count() > 0 {
+                regions.push((*region_id, region));
+            }
+        }
+        let pending: Vec<(Uuid, Vec<SpatialObject<T>>)> = regions.iter()
+            .map(|(region_id, region)| {
+                let objects = region.rtree.iter()
+                    .filter(|obj| region.is_dirty(obj.uuid))
+                    .cloned()
+                    .collect();
+                (*region_id, objects)
+            })
+            .collect();
+        handle.start(pending.iter().map(|(_, objects)| objects.len() as u64).sum());
+
+        self.persistent_db.begin_transaction()
+            .map_err(|e| format!("Failed to begin persistence transaction: {}", e))?;
+
+        // Objects stay dirty until the transaction commits, so a failed persist is retried next time
+        let total_points = match self.write_dirty_points(&pending, limits, handle) {
+            Ok(total_points) => total_points,
+            Err(e) => {
+                self.persistent_db.rollback_transaction()
+                    .map_err(|rollback_err| format!("{} (rollback also failed: {})", e, rollback_err))?;
+                return Err(e);
+            }
+        };
+
+        self.persistent_db.commit_transaction()
+            .map_err(|e| format!("Failed to commit persistence transaction: {}", e))?;
+        for (_, region) in &mut regions {
+            region.take_dirty();
+        }
+        drop(regions);
+
+        let duration = start_time.elapsed();
+        println!("Persisted {} points in {:?}", total_points, duration);
+        if total_points > 0 {
+            println!("Average time per point: {:?}", duration / total_points as u32);
+        }
+        Ok(())
+    }
+
+    /// Writes changed objects to the persistent database, replacing their stored versions.
+    ///
+    /// Must be called inside a transaction; see `persist_to_disk_with_limits`.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<usize, String>` - The number of points written if successful, or an error message if not.
+    fn write_dirty_points(&self, pending: &[(Uuid, Vec<SpatialObject<T>>)], limits: &QueryLimits, handle: &OperationHandle) -> Result<usize, String> {
+        let total_points: usize = pending.iter().map(|(_, objects)| objects.len()).sum();
+
+        let pb = ProgressBar::new(total_points as u64);
+        pb.set_style(ProgressStyle::default_bar()
+            .template("[{elapsed_precise}] {bar:40.cyan/blue} {pos:>7}/{len:7} {msg}")
+            .unwrap()
+            .progress_chars("##-"));
+
+        // Backends that write many rows per statement get whole batches, the others one point at a time
+        let batch_size = if self.persistent_db.capabilities().batch_writes {
+            MySQLGeo::POINT_BATCH_SIZE
+        } else {
+            1
+        };
+        for (region_id, objects) in pending {
+            for chunk in objects.chunks(batch_size) {
+                limits.check()
+                    .and_then(|_| handle.checkpoint())
+                    .inspect_err(|_| pb.abandon_with_message("Persist aborted"))?;
+                let points = chunk.iter()
+                    .map(|obj| to_point(obj).map(|point| (point, *region_id)))
+                    .collect::<Result<Vec<_>, String>>()?;
+                self.persistent_db.upsert_points(&points)
+                    .map_err(|e| format!("Failed to persist points to database: {}", e))?;
+                self.clear_journal(chunk)?;
+                pb.inc(chunk.len() as u64);
+                handle.advance(chunk.len() as u64);
+            }
+        }
+
+        pb.finish_with_message("Points persisted");
+        Ok(total_points)
+    }
+
+    /// Returns the IDs of every region, loaded or not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// for region_id in vault_manager.region_ids() {
+    ///     println!("Region {} loaded: {}", region_id, vault_manager.is_region_loaded(region_id));
+    /// }
+    /// ```
+    pub fn region_ids(&self) -> Vec<Uuid> {
+        let mut region_ids: Vec<Uuid> = self.regions.read().unwrap().keys().copied().collect();
+        if self.config.query_order != QueryOrder::Index {
+            region_ids.sort_unstable();
+        }
+        region_ids
+    }
+
+    /// Returns the number of regions, loaded or not.
+    pub fn region_count(&self) -> usize {
+        self.regions.read().unwrap().len()
+    }
+
+    /// Returns the object types registered with the manager, keyed by type.
+    pub fn object_types(&self) -> HashMap<ObjectType, TypeDefinition> {
+        self.object_types.read().unwrap().clone()
+    }
+
+    /// Registers an object type, or replaces the rules of an already registered one.
+    ///
+    /// Objects added or updated afterwards are checked against the definition. Objects already
+    /// stored aren't checked again.
+    ///
+    /// # Arguments
+    ///
+    /// * `definition` - The type and the rules its objects must follow; a type name registers it without rules.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{TypeDefinition, VaultManager, CustomData};
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// vault_manager.register_object_type("npc");
+    /// vault_manager.register_object_type(TypeDefinition::new("vehicle").with_max_size([10.0, 5.0, 20.0]));
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - Validators run while the registry is locked, so they must not register or unregister types.
+    pub fn register_object_type(&self, definition: impl Into<TypeDefinition>) {
+        let definition = definition.into();
+        self.object_types.write().unwrap().insert(definition.object_type, definition);
+    }
+
+    /// Unregisters an object type.
+    ///
+    /// # Arguments
+    ///
+    /// * `object_type` - The type to unregister.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<TypeDefinition>` - The definition of the type, or `None` if it wasn't registered.
+    ///
+    /// # Notes
+    ///
+    /// - Objects of the type stay in the vault. With `VaultConfig::reject_unknown_types` set,
+    ///   they can't be updated until the type is registered again.
+    pub fn unregister_object_type(&self, object_type: &str) -> Option<TypeDefinition> {
+        self.object_types.write().unwrap().remove(object_type)
+    }
+
+    /// Returns the definition of a registered object type.
+    ///
+    /// # Arguments
+    ///
+    /// * `object_type` - The type to look up.
+    pub fn object_type(&self, object_type: &str) -> Option<TypeDefinition> {
+        self.object_types.read().unwrap().get(object_type).cloned()
+    }
+
+    /// Returns the persistent database backing the manager.
+    pub(crate) fn database(&self) -> &MySQLGeo::Database {
+        &self.persistent_db
+    }
+
+    /// Gets a reference to a region by its ID.
+    ///
+    /// This method is useful when you need to perform operations on a specific region.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_id` - The UUID of the region to retrieve.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Arc<Mutex<VaultRegion<T>>>>` - An `Option` containing a reference to the region if found, or `None` if not found.
+    ///
+    /// # Notes
+    ///
+    /// - The region is loaded before it's returned; `None` is also returned if loading fails.
+    /// - With `VaultConfig::max_loaded_regions` set, the region may be unloaded again while the
+    ///   reference is held, leaving it empty. Prefer the query methods, which reload it as needed.
+    pub fn get_region(&self, region_id: Uuid) -> Option<Arc<Mutex<VaultRegion<T>>>> {
+        let region = self.region(region_id).ok()?;
+        drop(self.lock_region(region_id, &region).ok()?);
+        Some(region)
+    }
+
+    /// Flushes a region's objects to the persistent database and drops them from memory.
+    ///
+    /// The region itself stays known: its objects are reloaded transparently the next time
+    /// the region is accessed. Unloading a region that isn't loaded does nothing.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_id` - The UUID of the region to unload.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), String>` - An empty result if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # use uuid::Uuid;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let region_id = Uuid::new_v4();
+    /// vault_manager.unload_region(region_id).expect("Failed to unload region");
+    /// assert!(!vault_manager.is_region_loaded(region_id));
+    /// // Querying the region loads it again
+    /// let objects = vault_manager.query_region(region_id, 0.0, 0.0, 0.0, 10.0, 10.0, 10.0).unwrap();
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - Set `VaultConfig::max_loaded_regions` to unload the least recently used regions automatically.
+    /// - Snapshots taken with `iter_region_snapshot` stay valid after the region is unloaded.
+    pub fn unload_region(&self, region_id: Uuid) -> Result<(), String> {
+        let handle = self.region(region_id)?;
+        let mut region = self.locks.acquire(region_id, &handle);
+        if !self.is_region_loaded(region_id) {
+            return Ok(());
+        }
+        self.unload_locked(&mut region)
+    }
+
+    /// Returns `true` if the objects of a region are currently in memory.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_id` - The UUID of the region to check.
+    pub fn is_region_loaded(&self, region_id: Uuid) -> bool {
+        self.loaded_regions.lock().unwrap().contains_key(&region_id)
+    }
+
+    /// Takes a consistent snapshot of a region's objects for iteration.
+    ///
+    /// The region is only locked while the snapshot is taken, which costs no more than cloning
+    /// an `Arc`. Iterating the snapshot never blocks writes to the region, and writes made
+    /// afterwards don't show up in it, so long-running exports see a consistent state.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_id` - The UUID of the region to snapshot.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<RegionSnapshot<T>, String>` - The snapshot if successful, or an error message if the region doesn't exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # use uuid::Uuid;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let region_id = Uuid::new_v4();
+    /// let snapshot = vault_manager.iter_region_snapshot(region_id).expect("Failed to snapshot region");
+    /// for object in &snapshot {
+    ///     println!("Exporting {}", object.uuid);
+    /// }
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - While a snapshot is alive, the first write to the region copies its spatial index.
+    ///   Drop snapshots when done to avoid paying for that copy repeatedly.
+    pub fn iter_region_snapshot(&self, region_id: Uuid) -> Result<RegionSnapshot<T>, String> {
+        let handle = self.region(region_id)?;
+        let region = self.lock_region(region_id, &handle)?;
+        let snapshot = region.snapshot();
+        Ok(snapshot)
+    }
+
+    /// Brings a reconnecting client up to date with a region.
+    ///
+    /// A client that was sent a `CatchUpToken` before hands it back here. If the region still
+    /// remembers every change since that token, only those changes are returned; otherwise, or
+    /// without a token, the client gets a full snapshot of the region.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_id` - The UUID of the region the client is in.
+    /// * `token` - The token the client was last sent for the region, or `None` on first join.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<CatchUp<T>, String>` - The snapshot or delta to send, with a new token, or an error message if the region doesn't exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{CatchUp, CatchUpToken, VaultManager, CustomData};
+    /// # use uuid::Uuid;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let region_id = Uuid::new_v4();
+    /// # let saved_token: Option<CatchUpToken> = None;
+    /// let catch_up = vault_manager.catch_up(region_id, saved_token).expect("Failed to catch up");
+    /// let saved_token = Some(catch_up.token());
+    /// // ... send catch_up to the client ...
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - A region remembers its last 1024 removals; older tokens get a snapshot.
+    /// - A snapshot is also returned when the delta wouldn't be smaller than one.
+    /// - Tokens don't survive reopening the manager, splitting or merging the region.
+    pub fn catch_up(&self, region_id: Uuid, token: Option<CatchUpToken>) -> Result<CatchUp<T>, String> {
+        let handle = self.region(region_id)?;
+        let region = self.lock_region(region_id, &handle)?;
+        let current = region.catch_up_token();
+        if let Some((changed, removed)) = token.and_then(|token| region.changes_since(token)) {
+            if changed.len() + removed.len() < region.rtree.size() {
+                return Ok(CatchUp::Delta { changed, removed, token: current });
+            }
+        }
+        Ok(CatchUp::Snapshot { objects: region.rtree.iter().cloned().collect(), token: current })
+    }
+
+    /// Subscribes to the events describing every change to the manager's objects and regions.
+    ///
+    /// Each subscriber gets its own channel receiving every event from now on, in the order the
+    /// changes were made. Dropping the receiver unsubscribes.
+    ///
+    /// # Returns
+    ///
+    /// * `Receiver<VaultEvent<T>>` - The channel the events are sent to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultEvent, VaultManager, CustomData};
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// let events = vault_manager.subscribe();
+    /// // ... after the tick ...
+    /// for event in events.try_iter() {
+    ///     if let VaultEvent::ObjectAdded { region_id, object } = event {
+    ///         println!("{} spawned in {}", object.uuid, region_id);
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - Events are sent once the change is committed, so failed operations emit nothing.
+    /// - The channels are unbounded; a subscriber that never reads them keeps every event in memory.
+    /// - Loading and unloading regions isn't a change and emits nothing.
+    pub fn subscribe(&self) -> Receiver<VaultEvent<T>> {
+        let (sender, receiver) = mpsc::channel();
+        self.subscribers.lock().unwrap().push(sender);
+        receiver
+    }
+
+    /// Returns the changes recorded in the change log after a given sequence number.
+    ///
+    /// Replication and auditing systems keep the sequence number of the last change they saw,
+    /// and pass it here to get the ones made since, in the order they were made. Pass zero to
+    /// read the log from its start.
+    ///
+    /// # Arguments
+    ///
+    /// * `seq` - The sequence number of the last change already seen.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<Change<T>>, String>` - The changes made after `seq`, oldest first, or an error message if they can't all be returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, VaultConfig, CustomData};
+    /// # let config = VaultConfig { change_log_capacity: 10_000, ..VaultConfig::default() };
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::with_config("path/to/database.db", config).unwrap();
+    /// # let mut last_seen = 0;
+    /// for change in vault_manager.changes_since(last_seen).expect("Replica must resync") {
+    ///     last_seen = change.seq;
+    ///     // ... apply change.event to the replica ...
+    /// }
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - Fails if the change log is disabled, see `VaultConfig::change_log_capacity`.
+    /// - Fails if changes after `seq` were already dropped from the log, or if `seq` is ahead of
+    ///   the log because the manager was reopened since. The reader has to resync from a full
+    ///   copy of the world in both cases.
+    pub fn changes_since(&self, seq: u64) -> Result<Vec<Change<T>>, String> {
+        self.change_log.lock().unwrap().since(seq)
+    }
+
+    /// Returns the sequence number of the last change recorded in the change log, or zero if there was none.
+    pub fn latest_change(&self) -> u64 {
+        self.change_log.lock().unwrap().latest()
+    }
+
+    /// Moves an object to a new position, in memory and in the persistent database.
+    ///
+    /// If the new position lies outside the bounding cube of the object's current region, the
+    /// object is reassigned to the region containing the new position. When several regions
+    /// contain it, the one whose center is closest wins.
+    ///
+    /// # Arguments
+    ///
+    /// * `object_id` - The UUID of the object to move.
+    /// * `new_position` - The new coordinates of the object [x, y, z].
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Uuid, String>` - The UUID of the region the object belongs to after the move, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # use uuid::Uuid;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let object_id = Uuid::new_v4();
+    /// let region_id = vault_manager.move_object(object_id, [12.0, 0.0, -4.0]).expect("Failed to move object");
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - The new position is checked against `VaultConfig::world_bounds` like in `add_object`.
+    /// - If no region contains the new position, the object stays in its current region.
+    /// - The move increments the object's version. Use `move_object_versioned` to reject the move
+    ///   if another system changed the object first.
+    /// - Objects attached to the object with `attach_object` move along with it, keeping their
+    ///   offset from it, and may change regions themselves. All of them are moved atomically, as
+    ///   with `apply_batch`, and the move fails if any of them can't be moved.
+    pub fn move_object(&self, object_id: Uuid, new_position: [f64; 3]) -> Result<Uuid, String> {
+        self.move_with_attached(object_id, new_position, None)
+    }
+
+    /// Moves an object like `move_object`, unless it was changed since the caller read it.
+    ///
+    /// # Arguments
+    ///
+    /// * `object_id` - The UUID of the object to move.
+    /// * `new_position` - The new coordinates of the object [x, y, z].
+    /// * `expected_version` - The version of the object the caller last read.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Uuid, String>` - The UUID of the region the object belongs to after the move, or an error message
+    ///   if not, including when the stored version is newer than `expected_version`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # use uuid::Uuid;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let object_id = Uuid::new_v4();
+    /// let object = vault_manager.get_object(object_id).unwrap().unwrap();
+    /// // Fails if another system moved or updated the object in the meantime
+    /// vault_manager.move_object_versioned(object_id, [12.0, 0.0, -4.0], object.version).expect("Failed to move object");
+    /// ```
+    pub fn move_object_versioned(&self, object_id: Uuid, new_position: [f64; 3], expected_version: u64) -> Result<Uuid, String> {
+        self.move_with_attached(object_id, new_position, Some(expected_version))
+    }
+
+    /// Moves an object and every object attached to it by the same offset, checking the object's version if one is given.
+    ///
+    /// An object without attachments is moved on its own; otherwise all of them are moved as one batch.
+    fn move_with_attached(&self, object_id: Uuid, new_position: [f64; 3], expected_version: Option<u64>) -> Result<Uuid, String> {
+        let (region_id, object) = self.find_object(object_id)?
+            .ok_or_else(|| format!("Object not found: {}", object_id))?;
+        let attached = self.hierarchy.lock().unwrap().descendants(object_id);
+        if attached.is_empty() {
+            return self.relocate_object(object_id, region_id, new_position, expected_version);
+        }
+
+        let new_position = self.check_position(object_id, new_position)?;
+        let offset: [f64; 3] = std::array::from_fn(|axis| new_position[axis] - object.point[axis]);
+        let mut mutations = vec![Mutation::Move { object_id, position: new_position }];
+        for attached_id in attached {
+            // Soft-removed objects stay attached, but aren't moved until they're restored
+            if let Some((_, attached)) = self.find_object(attached_id)? {
+                let position = std::array::from_fn(|axis| attached.point[axis] + offset[axis]);
+                mutations.push(Mutation::Move { object_id: attached_id, position });
+            }
+        }
+        self.apply_mutations(mutations, expected_version.map(|version| (object_id, version)))?;
+
+        self.find_object(object_id)?
+            .map(|(region_id, _)| region_id)
+            .ok_or_else(|| format!("Object {} was changed concurrently", object_id))
+    }
+
+    /// Moves an object out of `from_region_id`, checking its version against `expected_version` if one is given.
+    fn relocate_object(&self, object_id: Uuid, from_region_id: Uuid, new_position: [f64; 3], expected_version: Option<u64>) -> Result<Uuid, String> {
+        let new_position = self.check_position(object_id, new_position)?;
+
+        let source = self.region(from_region_id)?;
+        let stays = self.locks.acquire(from_region_id, &source).bounds().contains_point(&new_position);
+        let to_region_id = if stays {
+            from_region_id
+        } else {
+            self.region_containing(new_position).unwrap_or(from_region_id)
+        };
+
+        // Both regions are loaded before the database changes, so neither reload sees the move
+        let new_region = (to_region_id != from_region_id).then_some(to_region_id);
+        let destination = new_region.map(|to_region_id| self.region(to_region_id)).transpose()?;
+        let (mut from_region, mut to_region) = match (new_region, &destination) {
+            (Some(to_region_id), Some(destination)) => {
+                let (from_region, to_region) = self.lock_region_pair((from_region_id, &source), (to_region_id, destination))?;
+                (from_region, Some(to_region))
+            }
+            _ => (self.lock_region(from_region_id, &source)?, None),
+        };
+
+        // The object may have changed since it was found, so check the locked copy
+        let object = from_region.get_object(object_id)
+            .cloned()
+            .ok_or_else(|| format!("Object {} was changed concurrently", object_id))?;
+        if let Some(expected_version) = expected_version {
+            check_version(&object, expected_version)?;
+        }
+        let moved = SpatialObject { point: new_position, version: object.version + 1, ..object.clone() };
+        let changed_at = from_region.dirty_since(object_id);
+        self.write_position_and_region(&moved, new_region, changed_at.is_some())?;
+
+        from_region.remove_object(&object);
+        let target = to_region.as_deref_mut().unwrap_or(&mut from_region);
+        target.insert_object(moved);
+        self.index_objects(to_region_id, [object_id]);
+        if let Some(changed_at) = changed_at {
+            target.mark_dirty_at(object_id, changed_at);
+        }
+        self.emit([VaultEvent::ObjectMoved { object_id, from_region: from_region_id, to_region: to_region_id, position: new_position }]);
+
+        Ok(to_region_id)
+    }
+
+    /// Attaches an object to another one, so that it follows it when it's moved.
+    ///
+    /// # Arguments
+    ///
+    /// * `object_id` - The UUID of the object to attach, such as a turret or a passenger.
+    /// * `parent_id` - The UUID of the object it follows, such as a ship or a mount.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), String>` - An empty result if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # use uuid::Uuid;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let (player, horse) = (Uuid::new_v4(), Uuid::new_v4());
+    /// vault_manager.attach_object(player, horse).expect("Failed to mount horse");
+    /// // The rider moves along with the horse
+    /// vault_manager.move_object(horse, [30.0, 0.0, 12.0]).expect("Failed to move horse");
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - An object has at most one parent; attaching it again replaces the previous one.
+    /// - Fails if either object doesn't exist, or if `parent_id` is `object_id` or follows it
+    ///   already, since the objects would follow each other.
+    /// - Attachments are stored in the persistent database, and dropped when either object is removed.
+    pub fn attach_object(&self, object_id: Uuid, parent_id: Uuid) -> Result<(), String> {
+        for uuid in [object_id, parent_id] {
+            if self.find_object(uuid)?.is_none() {
+                return Err(format!("Object not found: {}", uuid));
+            }
+        }
+
+        // Checked and written under the lock, so concurrent attachments can't form a cycle
+        let mut hierarchy = self.hierarchy.lock().unwrap();
+        if hierarchy.is_ancestor(object_id, parent_id) {
+            return Err(format!("Cannot attach {} to {}, which follows it", object_id, parent_id));
+        }
+        self.persistent_db.set_point_parent(object_id, Some(parent_id))
+            .map_err(|e| format!("Failed to store object attachment: {}", e))?;
+        hierarchy.attach(object_id, parent_id);
+        Ok(())
+    }
+
+    /// Detaches an object from the object it follows.
+    ///
+    /// # Arguments
+    ///
+    /// * `object_id` - The UUID of the attached object.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Option<Uuid>, String>` - The UUID of the object it was attached to, `None` if it wasn't attached, or an error message.
+    pub fn detach_object(&self, object_id: Uuid) -> Result<Option<Uuid>, String> {
+        let mut hierarchy = self.hierarchy.lock().unwrap();
+        if hierarchy.parent(object_id).is_none() {
+            return Ok(None);
+        }
+        self.persistent_db.set_point_parent(object_id, None)
+            .map_err(|e| format!("Failed to remove object attachment: {}", e))?;
+        Ok(hierarchy.detach(object_id))
+    }
+
+    /// Returns the object an object is attached to, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `object_id` - The UUID of the object.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Uuid>` - The UUID of its parent, or `None` if it isn't attached.
+    pub fn parent_of(&self, object_id: Uuid) -> Option<Uuid> {
+        self.hierarchy.lock().unwrap().parent(object_id)
+    }
+
+    /// Returns the objects attached directly to an object.
+    ///
+    /// # Arguments
+    ///
+    /// * `object_id` - The UUID of the parent object.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<Uuid>` - The UUIDs of the objects attached to it, sorted.
+    pub fn children_of(&self, object_id: Uuid) -> Vec<Uuid> {
+        self.hierarchy.lock().unwrap().children(object_id)
+    }
+
+    /// Starts a transaction collecting object mutations to apply together.
+    ///
+    /// # Returns
+    ///
+    /// * `VaultTransaction<T>` - An empty transaction. Its mutations are applied with `apply_batch` on `commit`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # use uuid::Uuid;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let (ore, player) = (Uuid::new_v4(), Uuid::new_v4());
+    /// let mut transaction = vault_manager.begin();
+    /// transaction.remove(ore).move_object(player, [4.0, 0.0, 2.0]);
+    /// if transaction.len() > 1 {
+    ///     transaction.commit().expect("Failed to mine ore");
+    /// } else {
+    ///     transaction.rollback();
+    /// }
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - Nothing is locked or written until `commit`, so other threads can change the objects in
+    ///   the meantime. Version-checked updates detect such changes when the transaction commits.
+    pub fn begin(&self) -> VaultTransaction<'_, T> {
+        VaultTransaction::new(self)
+    }
+
+    /// Applies several object mutations atomically.
+    ///
+    /// Either every mutation is applied or none is. All changes are written to the persistent
+    /// database in a single transaction, and every affected region stays locked until memory is
+    /// updated, so other threads never observe part of the batch. Use this for composite game
+    /// actions, such as crafting a building that consumes resources.
+    ///
+    /// # Arguments
+    ///
+    /// * `mutations` - The mutations to apply, in order. Later mutations see the effects of earlier ones.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), String>` - An empty result if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, Mutation, SpatialObject, CustomData};
+    /// # use uuid::Uuid;
+    /// # use std::sync::Arc;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let (region_id, wood, player) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+    /// let building = SpatialObject::new(Uuid::new_v4(), "building", [10.0, 0.0, 10.0], Arc::new(CustomData { /* ... */ })).unwrap();
+    /// vault_manager.apply_batch(vec![
+    ///     Mutation::Remove { object_id: wood },
+    ///     Mutation::Add { region_id, object: building },
+    ///     Mutation::Move { object_id: player, position: [12.0, 0.0, 10.0] },
+    /// ]).expect("Failed to craft building");
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - Positions are checked against `VaultConfig::world_bounds` like in `add_object`.
+    /// - Moved objects change regions like in `move_object`.
+    /// - Updated objects are written immediately, unlike with `update_object`, and are version
+    ///   checked the same way. The whole batch fails on a version conflict.
+    /// - Adding an object with the UUID of an existing one replaces it.
+    pub fn apply_batch(&self, mutations: Vec<Mutation<T>>) -> Result<(), String> {
+        self.apply_mutations(mutations, None)
+    }
+
+    /// Applies mutations like `apply_batch`, first checking the version of an object the batch moves if one is given.
+    fn apply_mutations(&self, mut mutations: Vec<Mutation<T>>, expected_version: Option<(Uuid, u64)>) -> Result<(), String> {
+        // Resolve the region of every object before and after the batch, without holding locks
+        let mut before: HashMap<Uuid, Option<Uuid>> = HashMap::new();
+        let mut after: HashMap<Uuid, Option<Uuid>> = HashMap::new();
+        let mut move_targets = Vec::new();
+        for mutation in &mut mutations {
+            let object_id = mutation.object_id();
+            let current = match after.get(&object_id) {
+                Some(region_id) => *region_id,
+                None => {
+                    let region_id = self.find_object(object_id)?.map(|(region_id, _)| region_id);
+                    before.insert(object_id, region_id);
+                    region_id
+                }
+            };
+            let found = || current.ok_or_else(|| format!("Object not found: {}", object_id));
+            let region_id = match mutation {
+                Mutation::Add { region_id, object } => {
+                    if !self.has_region(*region_id) {
+                        return Err(format!("Region not found: {}", region_id));
+                    }
+                    self.check_type(object)?;
+                    object.point = self.check_position(object_id, object.point)?;
+                    Some(*region_id)
+                }
+                Mutation::Remove { .. } => {
+                    found()?;
+                    None
+                }
+                Mutation::Update { object } => {
+                    self.check_type(object)?;
+                    object.point = self.check_position(object_id, object.point)?;
+                    Some(found()?)
+                }
+                Mutation::Move { position, .. } => {
+                    let from_region_id = found()?;
+                    *position = self.check_position(object_id, *position)?;
+                    let source = self.region(from_region_id)?;
+                    let stays = self.locks.acquire(from_region_id, &source).bounds().contains_point(position);
+                    let to_region_id = if stays {
+                        from_region_id
+                    } else {
+                        self.region_containing(*position).unwrap_or(from_region_id)
+                    };
+                    move_targets.push(to_region_id);
+                    Some(to_region_id)
+                }
+            };
+            after.insert(object_id, region_id);
+        }
+
+        // Lock every affected region in ascending order, so batches can't deadlock each other
+        let mut region_ids: Vec<Uuid> = before.values().chain(after.values()).flatten().copied().collect();
+        region_ids.sort_unstable();
+        region_ids.dedup();
+        let handles = region_ids.into_iter()
+            .map(|region_id| Ok((region_id, self.region(region_id)?)))
+            .collect::<Result<Vec<_>, String>>()?;
+        let mut regions = HashMap::with_capacity(handles.len());
+        for (region_id, handle) in &handles {
+            regions.insert(*region_id, self.lock_region(*region_id, handle)?);
+        }
+
+        // Work out the final state of every object
+        let mut originals: HashMap<Uuid, (Uuid, SpatialObject<T>)> = HashMap::new();
+        let mut staged: HashMap<Uuid, Option<(Uuid, SpatialObject<T>)>> = HashMap::new();
+        let mut order = Vec::new();
+        let mut move_targets = move_targets.into_iter();
+        for mutation in mutations {
+            let object_id = mutation.object_id();
+            let current = match staged.entry(object_id) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    let original = match before[&object_id] {
+                        Some(region_id) => {
+                            let object = regions[&region_id].get_object(object_id).cloned()
+                                .ok_or_else(|| format!("Object {} was changed concurrently", object_id))?;
+                            originals.insert(object_id, (region_id, object.clone()));
+                            Some((region_id, object))
+                        }
+                        None => None,
+                    };
+                    order.push(object_id);
+                    entry.insert(original)
+                }
+            };
+            *current = match mutation {
+                Mutation::Add { region_id, object } => Some((region_id, object)),
+                Mutation::Remove { .. } => None,
+                Mutation::Update { mut object } => match current.take() {
+                    Some((region_id, stored)) => {
+                        check_version(&stored, object.version)?;
+                        object.version = stored.version + 1;
+                        Some((region_id, object))
+                    }
+                    None => None,
+                },
+                Mutation::Move { position, .. } => {
+                    let to_region_id = move_targets.next().expect("A target was resolved for every move");
+                    if let (Some((checked_id, expected_version)), Some((_, object))) = (expected_version, current.as_ref()) {
+                        if checked_id == object_id {
+                            check_version(object, expected_version)?;
+                        }
+                    }
+                    current.take().map(|(_, object)| (to_region_id, SpatialObject { point: position, version: object.version + 1, ..object }))
+                }
+            };
+        }
+
+        self.persistent_db.begin_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        let written = (|| {
+            let mut removed = Vec::new();
+            for object_id in &order {
+                match &staged[object_id] {
+                    Some((region_id, object)) => self.write_objects(*region_id, std::slice::from_ref(object))?,
+                    None if originals.contains_key(object_id) => removed.push(*object_id),
+                    None => {}
+                }
+            }
+            self.persistent_db.remove_points(&removed)
+                .map_err(|e| format!("Failed to remove points from persistent database: {}", e))?;
+            Ok::<(), String>(())
+        })();
+        if let Err(e) = written {
+            let _ = self.persistent_db.rollback_transaction();
+            return Err(e);
+        }
+        self.persistent_db.commit_transaction()
+            .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+        // Every object was written in full, so none of them are left dirty
+        for object_id in order {
+            if self.has_subscribers() {
+                let before = originals.get(&object_id).map(|(region_id, object)| (*region_id, object));
+                let after = staged[&object_id].as_ref().map(|(region_id, object)| (*region_id, object));
+                self.emit(VaultEvent::for_change(before, after));
+            }
+            let removed = originals.remove(&object_id)
+                .and_then(|(region_id, original)| regions.get_mut(&region_id).unwrap().remove_object(&original));
+            match staged.remove(&object_id).flatten() {
+                Some((region_id, object)) => {
+                    self.index_objects(region_id, [object_id]);
+                    regions.get_mut(&region_id).unwrap().insert_object(object);
+                }
+                None => {
+                    self.unindex_objects([object_id]);
+                    self.forget_attachments([object_id]);
+                    if let Some(removed) = removed {
+                        self.object_pool.lock().unwrap().release(removed);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Locks a region, first loading its objects from the persistent database if it isn't loaded.
+    ///
+    /// Every call counts as an access for `VaultConfig::max_loaded_regions`. Loading a region can
+    /// unload the least recently used ones; regions locked at the time are skipped.
+    ///
+    /// Fails if the region was deleted, split or merged after `region` was looked up.
+    fn lock_region<'a>(&'a self, region_id: Uuid, region: &'a RegionHandle<T>) -> Result<RegionGuard<'a, T>, String> {
+        let mut region = self.locks.acquire(region_id, region);
+        if region.is_retired() {
+            return Err(format!("Region not found: {}", region_id));
+        }
+        if self.touch_region(region_id) {
+            return Ok(region);
+        }
+
+        match self.read_region_objects(region_id) {
+            Ok((objects, _)) => {
+                self.index_objects(region_id, objects.iter().map(|obj| obj.uuid));
+                region.insert_objects(objects);
+            }
+            Err(e) => {
+                self.loaded_regions.lock().unwrap().remove(&region_id);
+                return Err(e);
+            }
+        }
+        self.evict_regions()?;
+        Ok(region)
+    }
+
+    /// Locks two different regions in ascending order of their UUIDs.
+    ///
+    /// Every caller that holds two regions at once takes them in the same order, so two
+    /// transfers in opposite directions can't deadlock.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(RegionGuard<T>, RegionGuard<T>), String>` - The guards of
+    ///   `first` and `second`, in that order, if successful, or an error message if not.
+    fn lock_region_pair<'a>(&'a self, first: (Uuid, &'a RegionHandle<T>), second: (Uuid, &'a RegionHandle<T>)) -> Result<(RegionGuard<'a, T>, RegionGuard<'a, T>), String> {
+        if first.0 < second.0 {
+            let first = self.lock_region(first.0, first.1)?;
+            let second = self.lock_region(second.0, second.1)?;
+            Ok((first, second))
+        } else {
+            let second = self.lock_region(second.0, second.1)?;
+            let first = self.lock_region(first.0, first.1)?;
+            Ok((first, second))
+        }
+    }
+
+    /// Looks up a region, without locking or loading it.
+    fn region(&self, region_id: Uuid) -> Result<RegionHandle<T>, String> {
+        self.regions.read().unwrap().get(&region_id)
+            .cloned()
+            .ok_or_else(|| format!("Region not found: {}", region_id))
+    }
+
+    /// Returns every region, without locking or loading them.
+    ///
+    /// The region map isn't kept locked, so callers can lock regions while other threads add
+    /// and remove them.
+    fn region_handles(&self) -> Vec<(Uuid, RegionHandle<T>)> {
+        self.regions.read().unwrap().iter()
+            .map(|(region_id, region)| (*region_id, region.clone()))
+            .collect()
+    }
+
+    /// Sorts query results in the order set by `VaultConfig::query_order`.
+    ///
+    /// Distances are measured from `center`; queries without one are sorted by UUID instead.
+    fn order_results(&self, results: &mut [SpatialObject<T>], center: Option<[f64; 3]>) {
+        match (self.config.query_order, center) {
+            (QueryOrder::Index, _) => {}
+            (QueryOrder::Distance, Some(center)) => results.sort_unstable_by(|a, b| {
+                a.distance_2(&center).total_cmp(&b.distance_2(&center)).then(a.uuid.cmp(&b.uuid))
+            }),
+            (QueryOrder::Uuid, _) | (QueryOrder::Distance, None) => results.sort_unstable_by_key(|obj| obj.uuid),
+        }
+    }
+
+    /// Returns the `k` objects of an R-tree closest to `point`, with their squared distances, closest first.
+    ///
+    /// With a sorted `VaultConfig::query_order`, objects as far away as the k-th one are
+    /// returned too, so the caller can break the tie by UUID instead of by the R-tree's layout.
+    fn nearest_candidates(&self, rtree: &RTree<SpatialObject<T>>, point: [f64; 3], k: usize) -> Vec<(SpatialObject<T>, f64)> {
+        let mut nearest = rtree.nearest_neighbor_iter_with_distance_2(&point);
+        let mut candidates: Vec<(SpatialObject<T>, f64)> = nearest.by_ref()
+            .take(k)
+            .map(|(obj, distance_2)| (obj.clone(), distance_2))
+            .collect();
+        if self.config.query_order != QueryOrder::Index && candidates.len() == k {
+            let furthest = candidates[k - 1].1;
+            candidates.extend(
+                nearest.take_while(|(_, distance_2)| *distance_2 <= furthest)
+                    .map(|(obj, distance_2)| (obj.clone(), distance_2)),
+            );
+        }
+        candidates
+    }
+
+    /// Returns `true` if a region with the given UUID exists.
+    fn has_region(&self, region_id: Uuid) -> bool {
+        self.regions.read().unwrap().contains_key(&region_id)
+    }
+
+    /// Marks a region as loaded and most recently used.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - `true` if the region was already loaded.
+    fn touch_region(&self, region_id: Uuid) -> bool {
+        let tick = self.access_clock.fetch_add(1, Ordering::Relaxed);
+        self.loaded_regions.lock().unwrap().insert(region_id, tick).is_some()
+    }
+
+    /// Unloads the least recently used regions until `VaultConfig::max_loaded_regions` is respected.
+    fn evict_regions(&self) -> Result<(), String> {
+        let Some(limit) = self.config.max_loaded_regions else {
+            return Ok(());
+        };
+        let mut by_age: Vec<(u64, Uuid)> = self.loaded_regions.lock().unwrap()
+            .iter()
+            .map(|(region_id, tick)| (*tick, *region_id))
+            .collect();
+        let mut excess = by_age.len().saturating_sub(limit);
+        by_age.sort_unstable();
+
+        for (_, region_id) in by_age {
+            if excess == 0 {
+                break;
+            }
+            // A region that's locked is in use, so it isn't a candidate
+            let Ok(handle) = self.region(region_id) else {
+                continue;
+            };
+            let Some(mut region) = self.locks.try_acquire(region_id, &handle) else {
+                continue;
+            };
+            self.unload_locked(&mut region)?;
+            excess -= 1;
+        }
+        Ok(())
+    }
+
+    /// Writes a locked region's changed objects to the persistent database and drops its objects from memory.
+    fn unload_locked(&self, region: &mut VaultRegion<T>) -> Result<(), String> {
+        if region.dirty_count() > 0 {
+            let objects: Vec<SpatialObject<T>> = region.rtree.iter()
+                .filter(|obj| region.is_dirty(obj.uuid))
+                .cloned()
+                .collect();
+
+            self.persistent_db.begin_transaction()
+                .map_err(|e| format!("Failed to begin transaction: {}", e))?;
+            if let Err(e) = self.write_objects(region.id, &objects) {
+                self.persistent_db.rollback_transaction()
+                    .map_err(|rollback_err| format!("{} (rollback also failed: {})", e, rollback_err))?;
+                return Err(e);
+            }
+            self.persistent_db.commit_transaction()
+                .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+            region.take_dirty();
+        }
+
+        self.unindex_objects(region.rtree.iter().map(|obj| obj.uuid));
+        region.clear_objects();
+        self.loaded_regions.lock().unwrap().remove(&region.id);
+        Ok(())
+    }
+
+    /// Finds an object and the region holding it.
+    ///
+    /// The object index is tried first. It's only trusted after checking that the indexed region
+    /// still holds the object; otherwise the regions are searched and the index is corrected.
+    fn find_object(&self, object_id: Uuid) -> Result<Option<(Uuid, SpatialObject<T>)>, String> {
+        let indexed = self.object_regions.lock().unwrap().get(&object_id).copied();
+        if let Some(region_id) = indexed {
+            if let Ok(region) = self.region(region_id) {
+                let region = self.locks.acquire(region_id, &region);
+                if let Some(object) = region.get_object(object_id) {
+                    return Ok(Some((region_id, object.clone())));
+                }
+            }
+        }
+
+        let found = self.search_object(object_id)?;
+        let mut object_regions = self.object_regions.lock().unwrap();
+        match &found {
+            Some((region_id, _)) => object_regions.insert(object_id, *region_id),
+            None => object_regions.remove(&object_id),
+        };
+        Ok(found)
+    }
+
+    /// Searches the regions for an object, bypassing the object index.
+    ///
+    /// Loaded regions are searched first. If the object isn't in any of them, the persistent
+    /// database is asked which region holds it, and that region is loaded if it isn't already.
+    fn search_object(&self, object_id: Uuid) -> Result<Option<(Uuid, SpatialObject<T>)>, String> {
+        let loaded = self.region_handles().into_iter().find_map(|(region_id, region)| {
+            let region = self.locks.acquire(region_id, &region);
+            region.get_object(object_id).map(|obj| (region_id, obj.clone()))
+        });
+        if loaded.is_some() {
+            return Ok(loaded);
+        }
+
+        let stored_region = self.persistent_db.get_point_region(object_id)
+            .map_err(|e| format!("Failed to look up region of object {}: {}", object_id, e))?;
+        let Some(region_id) = stored_region.filter(|region_id| {
+            self.has_region(*region_id) && !self.is_region_loaded(*region_id)
+        }) else {
+            return Ok(None);
+        };
+        let handle = self.region(region_id)?;
+        let region = self.lock_region(region_id, &handle)?;
+        Ok(region.get_object(object_id).map(|obj| (region_id, obj.clone())))
+    }
+
+    /// Records the region now holding each of the given objects in the object index.
+    fn index_objects(&self, region_id: Uuid, object_ids: impl IntoIterator<Item = Uuid>) {
+        let mut object_regions = self.object_regions.lock().unwrap();
+        object_regions.extend(object_ids.into_iter().map(|object_id| (object_id, region_id)));
+    }
+
+    /// Drops objects that were removed or unloaded from the object index.
+    fn unindex_objects(&self, object_ids: impl IntoIterator<Item = Uuid>) {
+        let mut object_regions = self.object_regions.lock().unwrap();
+        for object_id in object_ids {
+            object_regions.remove(&object_id);
+        }
+    }
+
+    /// Detaches removed objects from their parents, and the objects attached to them from them.
+    ///
+    /// The persistent database drops their attachments along with their rows.
+    fn forget_attachments(&self, object_ids: impl IntoIterator<Item = Uuid>) {
+        let mut hierarchy = self.hierarchy.lock().unwrap();
+        for object_id in object_ids {
+            hierarchy.forget(object_id);
+        }
+    }
+
+    /// Returns the region whose bounding cube contains `point`, preferring the closest center.
+    fn region_containing(&self, point: [f64; 3]) -> Option<Uuid> {
+        self.region_handles().into_iter()
+            .filter_map(|(region_id, region)| {
+                let region = self.locks.acquire(region_id, &region);
+                let bounds = region.bounds();
+                bounds.contains_point(&point).then(|| {
+                    let center = bounds.center();
+                    let distance_2: f64 = (0..3).map(|axis| (center[axis] - point[axis]).powi(2)).sum();
+                    (region.id, distance_2)
+                })
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(region_id, _)| region_id)
+    }
+
+    /// Cross-checks a sample of in-memory objects and database rows, continuing where the last call stopped.
+    ///
+    /// Each call checks up to `samples` objects of the loaded regions against their rows, and up
+    /// to `samples` rows against the loaded regions. Successive calls walk every object and
+    /// every row, then start over, so calling this once per tick keeps checking the whole vault
+    /// at a bounded cost per tick.
+    ///
+    /// # Arguments
+    ///
+    /// * `samples` - The number of objects, and separately of rows, to check.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<Divergence>, String>` - The disagreements found by this call, or an error message if the check failed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// vault_manager.verify_tick(50).expect("Failed to verify");
+    /// let stats = vault_manager.consistency_stats();
+    /// println!("{} divergences in {} objects checked", stats.divergences(), stats.objects_checked);
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - Regions that aren't loaded are skipped; their rows are the only copy of their objects.
+    /// - Each region is locked only while its sampled objects are checked.
+    pub fn verify_tick(&self, samples: usize) -> Result<Vec<Divergence>, String> {
+        let mut cursor = self.verifier.lock().unwrap();
+        let mut divergences = Vec::new();
+
+        // Objects in memory, a region at a time
+        let mut remaining = samples;
+        let mut restarted = false;
+        while remaining > 0 {
+            let (region_id, checked) = match cursor.current {
+                Some(current) => current,
+                None => match cursor.regions.pop() {
+                    Some(region_id) => (region_id, 0),
+                    None if restarted => break,
+                    None => {
+                        // Starting over; the first call has no finished pass to count
+                        if cursor.stats.objects_checked > 0 {
+                            cursor.stats.passes += 1;
+                        }
+                        let mut region_ids = self.region_ids();
+                        region_ids.sort_unstable_by(|a, b| b.cmp(a));
+                        cursor.regions = region_ids;
+                        restarted = true;
+                        continue;
+                    }
+                },
+            };
+            cursor.current = None;
+            let Some(region) = self.region(region_id).ok().filter(|_| self.is_region_loaded(region_id)) else {
+                continue;
+            };
+            let region = self.locks.acquire(region_id, &region);
+            let objects: Vec<SpatialObject<T>> = region.rtree.iter().skip(checked).take(remaining).cloned().collect();
+            if objects.is_empty() {
+                continue;
+            }
+            cursor.current = Some((region_id, checked + objects.len()));
+            remaining -= objects.len();
+            for object in &objects {
+                cursor.stats.objects_checked += 1;
+                if let Some(kind) = self.check_object_row(region_id, object, region.is_dirty(object.uuid))? {
+                    divergences.push(Divergence { object_id: object.uuid, region_id, kind });
+                }
+            }
+        }
+
+        // Rows in the database, in key order
+        let rows = self.persistent_db.get_point_keys(cursor.last_row, samples)
+            .map_err(|e| format!("Failed to read points to verify: {}", e))?;
+        cursor.last_row = if rows.len() < samples { None } else { rows.last().map(|(object_id, _)| *object_id) };
+        for (object_id, region_id) in rows {
+            let kind = match self.region(region_id).ok() {
+                None => Some(DivergenceKind::OrphanedRow),
+                Some(_) if !self.is_region_loaded(region_id) => None,
+                Some(region) => {
+                    let region = self.locks.acquire(region_id, &region);
+                    // The object may have moved here or away since the row was read
+                    let stored_region = self.persistent_db.get_point_region(object_id)
+                        .map_err(|e| format!("Failed to read point {}: {}", object_id, e))?;
+                    (stored_region == Some(region_id) && !region.contains_object(object_id)).then_some(DivergenceKind::MissingObject)
+                }
+            };
+            cursor.stats.rows_checked += 1;
+            if let Some(kind) = kind {
+                divergences.push(Divergence { object_id, region_id, kind });
+            }
+        }
+
+        for divergence in &divergences {
+            cursor.stats.record(divergence);
+        }
+        Ok(divergences)
+    }
+
+    /// Compares an in-memory object with its row, while its region is locked.
+    fn check_object_row(&self, region_id: Uuid, object: &SpatialObject<T>, dirty: bool) -> Result<Option<DivergenceKind>, String> {
+        let stored = self.persistent_db.get_point(object.uuid)
+            .map_err(|e| format!("Failed to read point {}: {}", object.uuid, e))?;
+        let Some((stored_region, point)) = stored else {
+            return Ok(Some(DivergenceKind::MissingRow));
+        };
+        if stored_region != region_id {
+            return Ok(Some(DivergenceKind::WrongRegion { stored_region }));
+        }
+        if dirty {
+            return Ok(None);
+        }
+        let custom_data = serde_json::to_value(&*object.custom_data)
+            .map_err(|e| format!("Failed to serialize custom data: {}", e))?;
+        let matches = [point.x, point.y, point.z] == object.point
+            && point.size == object.size
+            && point.object_type == object.object_type.as_str()
+            && point.version == object.version
+            && point.velocity == object.velocity
+            && point.orientation == object.orientation
+            && point.custom_data == custom_data;
+        Ok((!matches).then_some(DivergenceKind::StaleRow))
+    }
+
+    /// Returns the totals of the consistency checks made with `verify_tick` so far.
+    pub fn consistency_stats(&self) -> ConsistencyStats {
+        self.verifier.lock().unwrap().stats
+    }
+
+    /// Returns `true` if anyone subscribed to events or the change log is enabled, so events are worth building.
+    fn has_subscribers(&self) -> bool {
+        !self.subscribers.lock().unwrap().is_empty() || self.change_log.lock().unwrap().is_enabled()
+    }
+
+    /// Records events in the change log and sends them to every subscriber, forgetting the ones that dropped their receiver.
+    fn emit(&self, events: impl IntoIterator<Item = VaultEvent<T>>) {
+        let mut subscribers = self.subscribers.lock().unwrap();
+        let mut change_log = self.change_log.lock().unwrap();
+        if subscribers.is_empty() && !change_log.is_enabled() {
+            return;
+        }
+        let recorded_at = SystemTime::now();
+        for event in events {
+            subscribers.retain(|subscriber| subscriber.send(event.clone()).is_ok());
+            change_log.record(event, recorded_at);
+        }
+    }
+
+    /// Deletes a region from memory and the persistent database.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_id` - The UUID of the region to delete.
+    /// * `cascade` - Also delete every object in the region. Without it, deleting a region that still holds objects fails.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<usize, String>` - The number of objects deleted with the region, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 100.0).unwrap();
+    /// // Fails if anything was added to the region in the meantime
+    /// vault_manager.delete_region(region_id, false).expect("Failed to delete region");
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - The region and its objects are deleted from the persistent database in one transaction.
+    /// - Custom data allocations of deleted objects are returned to the object pool.
+    pub fn delete_region(&self, region_id: Uuid, cascade: bool) -> Result<usize, String> {
+        let handle = self.region(region_id)?;
+        let mut region = self.lock_region(region_id, &handle)?;
+        let object_count = region.rtree.size();
+        if object_count > 0 && !cascade {
+            return Err(format!("Region {} still holds {} objects", region_id, object_count));
+        }
+
+        self.persistent_db.begin_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        if let Err(e) = self.persistent_db.delete_region(region_id, cascade) {
+            let _ = self.persistent_db.rollback_transaction();
+            return Err(format!("Failed to delete region from database: {}", e));
+        }
+        self.persistent_db.commit_transaction()
+            .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+        self.emit(region.rtree.iter().map(|object| VaultEvent::ObjectRemoved { region_id, object_id: object.uuid }));
+        self.emit([VaultEvent::RegionRemoved { region_id }]);
+        self.unindex_objects(region.rtree.iter().map(|object| object.uuid));
+        self.forget_attachments(region.rtree.iter().map(|object| object.uuid));
+        self.regions.write().unwrap().remove(&region_id);
+        self.loaded_regions.lock().unwrap().remove(&region_id);
+        self.simulation_ticks.lock().unwrap().remove(&region_id);
+
+        // The type index holds clones, which would keep the custom data out of the pool
+        region.type_index.clear();
+        let mut pool = self.object_pool.lock().unwrap();
+        for object in Arc::make_mut(&mut region.rtree).drain() {
+            pool.release(object);
+        }
+        region.retire();
+
+        Ok(object_count)
+    }
+
+    /// Splits a region into eight octants and redistributes its objects among them.
+    ///
+    /// Each octant is a region with half the radius of the original, centered in one corner of
+    /// its cube. Objects are assigned to the octant containing their position. The new regions
+    /// and object assignments are written to the persistent database in one transaction, and the
+    /// original region is removed.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_id` - The UUID of the region to split.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<Uuid>, String>` - The UUIDs of the eight new regions if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// let region_id = vault_manager.create_or_load_region([0.0, 0.0, 0.0], 1000.0).unwrap();
+    /// let octants = vault_manager.split_region(region_id).expect("Failed to split region");
+    /// assert_eq!(octants.len(), 8);
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - The original region's UUID is no longer valid afterwards. Callers holding it must look up the new region.
+    /// - Objects positioned outside the original cube go to the octant on their side of the center.
+    pub fn split_region(&self, region_id: Uuid) -> Result<Vec<Uuid>, String> {
+        let handle = self.region(region_id)?;
+        let mut region = self.lock_region(region_id, &handle)?;
+        if region.radius <= 0.0 {
+            return Err(format!("Region {} is too small to split", region_id));
+        }
+
+        let half = region.radius / 2.0;
+        let mut octants: Vec<VaultRegion<T>> = (0..8)
+            .map(|octant| {
+                let center = [0, 1, 2].map(|axis| {
+                    let sign = if octant & (1 << axis) != 0 { 1.0 } else { -1.0 };
+                    region.center[axis] + sign * half
+                });
+                VaultRegion::new(Uuid::new_v4(), center, half)
+            })
+            .collect();
+        let mut contents: Vec<Vec<SpatialObject<T>>> = vec![Vec::new(); 8];
+        for object in region.rtree.iter() {
+            contents[octant_of(region.center, object.point)].push(object.clone());
+        }
+
+        self.persistent_db.begin_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        let written = (|| {
+            for (octant, objects) in octants.iter().zip(&contents) {
+                self.persistent_db.create_region(octant.id, octant.center, octant.radius)
+                    .map_err(|e| format!("Failed to persist region to database: {}", e))?;
+                for object in objects {
+                    self.persistent_db.update_point_region(object.uuid, octant.id)
+                        .map_err(|e| format!("Failed to reassign object {}: {}", object.uuid, e))?;
+                }
+            }
+            self.persistent_db.remove_region(region_id)
+                .map_err(|e| format!("Failed to remove region from database: {}", e))
+        })();
+        if let Err(e) = written {
+            let _ = self.persistent_db.rollback_transaction();
+            return Err(e);
+        }
+        self.persistent_db.commit_transaction()
+            .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+        self.emit(octants.iter().map(|octant| VaultEvent::RegionCreated { region_id: octant.id, center: octant.center, radius: octant.radius }));
+        self.emit(octants.iter().zip(&contents).flat_map(|(octant, objects)| {
+            objects.iter().map(|object| VaultEvent::ObjectMoved { object_id: object.uuid, from_region: region_id, to_region: octant.id, position: object.point })
+        }));
+        self.emit([VaultEvent::RegionRemoved { region_id }]);
+
+        // The dirty set moves to the octants, so nothing is left for the auto-persist worker here
+        let dirty = region.take_dirty();
+        self.loaded_regions.lock().unwrap().remove(&region_id);
+        // The octants carry on simulating from where the region left off
+        let last_tick = self.simulation_ticks.lock().unwrap().remove(&region_id);
+        let mut octant_ids = Vec::with_capacity(8);
+        let mut octant_regions = Vec::with_capacity(8);
+        for (mut octant, objects) in octants.drain(..).zip(contents) {
+            let octant_dirty: Vec<(Uuid, Instant)> = objects.iter()
+                .filter_map(|obj| dirty.get(&obj.uuid).map(|changed_at| (obj.uuid, *changed_at)))
+                .collect();
+            self.index_objects(octant.id, objects.iter().map(|obj| obj.uuid));
+            octant.insert_objects(objects);
+            for (uuid, changed_at) in octant_dirty {
+                octant.mark_dirty_at(uuid, changed_at);
+            }
+            octant_ids.push(octant.id);
+            if let Some(last_tick) = last_tick {
+                self.simulation_ticks.lock().unwrap().insert(octant.id, last_tick);
+            }
+            self.touch_region(octant.id);
+            octant_regions.push((octant.id, Arc::new(Mutex::new(octant))));
+        }
+        // The octants replace the region in one step, so every position stays covered
+        let mut regions = self.regions.write().unwrap();
+        regions.remove(&region_id);
+        regions.extend(octant_regions);
+        drop(regions);
+        region.retire();
+        drop(region);
+        self.sync_auto_persist_regions();
+        self.evict_regions()?;
+
+        Ok(octant_ids)
+    }
+
+    /// Merges several regions into a single new region.
+    ///
+    /// Every object of the merged regions is moved into the new region, which is written to the
+    /// persistent database together with the reassigned objects in one transaction. The old
+    /// region records are removed. Use this to compact sparsely populated regions, e.g. after
+    /// mass despawns.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_ids` - The UUIDs of the regions to merge.
+    /// * `center` - The center of the new region [x, y, z].
+    /// * `radius` - The radius of the new region.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Uuid, String>` - The UUID of the new region if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// let west = vault_manager.create_or_load_region([-50.0, 0.0, 0.0], 50.0).unwrap();
+    /// let east = vault_manager.create_or_load_region([50.0, 0.0, 0.0], 50.0).unwrap();
+    /// let merged = vault_manager.merge_regions(&[west, east], [0.0, 0.0, 0.0], 100.0).expect("Failed to merge regions");
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - The merged regions' UUIDs are no longer valid afterwards.
+    /// - Objects keep their positions, even if they lie outside the new region's bounds.
+    /// - If writing to the persistent database fails, nothing is changed.
+    pub fn merge_regions(&self, region_ids: &[Uuid], center: [f64; 3], radius: f64) -> Result<Uuid, String> {
+        validate_point(center).map_err(|e| format!("Invalid region center: {}", e))?;
+        if !radius.is_finite() || radius < 0.0 {
+            return Err(format!("Invalid region radius: {}", radius));
+        }
+        if region_ids.is_empty() {
+            return Err("No regions to merge".to_string());
+        }
+        for (i, region_id) in region_ids.iter().enumerate() {
+            if region_ids[..i].contains(region_id) {
+                return Err(format!("Region listed twice: {}", region_id));
+            }
+            if !self.has_region(*region_id) {
+                return Err(format!("Region not found: {}", region_id));
+            }
+        }
+
+        // Every region stays locked until it's replaced, so nothing added to it meanwhile is lost
+        let mut sorted_ids = region_ids.to_vec();
+        sorted_ids.sort_unstable();
+        let handles = sorted_ids.into_iter()
+            .map(|region_id| Ok((region_id, self.region(region_id)?)))
+            .collect::<Result<Vec<_>, String>>()?;
+        let mut locked = HashMap::with_capacity(handles.len());
+        for (region_id, handle) in &handles {
+            locked.insert(*region_id, self.lock_region(*region_id, handle)?);
+        }
+
+        let mut objects: Vec<SpatialObject<T>> = Vec::new();
+        let mut origins: Vec<Uuid> = Vec::new();
+        let mut dirty: Vec<(Uuid, Instant)> = Vec::new();
+        for region_id in region_ids {
+            let region = &locked[region_id];
+            objects.extend(region.rtree.iter().cloned());
+            origins.resize(objects.len(), *region_id);
+            dirty.extend(region.dirty().iter().map(|(uuid, changed_at)| (*uuid, *changed_at)));
+        }
+        let merged_id = Uuid::new_v4();
+
+        self.persistent_db.begin_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        if let Err(e) = self.write_merge(region_ids, merged_id, center, radius, &objects) {
+            let _ = self.persistent_db.rollback_transaction();
+            return Err(e);
+        }
+        self.persistent_db.commit_transaction()
+            .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+        self.emit([VaultEvent::RegionCreated { region_id: merged_id, center, radius }]);
+        self.emit(objects.iter().zip(&origins).map(|(object, from_region)| {
+            VaultEvent::ObjectMoved { object_id: object.uuid, from_region: *from_region, to_region: merged_id, position: object.point }
+        }));
+        self.emit(region_ids.iter().map(|region_id| VaultEvent::RegionRemoved { region_id: *region_id }));
+
+        // The merged region catches up from the least recently simulated of its parts
+        let mut last_tick: Option<u64> = None;
+        for region_id in region_ids {
+            self.loaded_regions.lock().unwrap().remove(region_id);
+            if let Some(tick) = self.simulation_ticks.lock().unwrap().remove(region_id) {
+                last_tick = Some(last_tick.map_or(tick, |last| last.min(tick)));
+            }
+        }
+        if let Some(last_tick) = last_tick {
+            self.simulation_ticks.lock().unwrap().insert(merged_id, last_tick);
+        }
+        let mut merged = VaultRegion::new(merged_id, center, radius);
+        self.index_objects(merged_id, objects.iter().map(|obj| obj.uuid));
+        merged.insert_objects(objects);
+        for (uuid, changed_at) in dirty {
+            merged.mark_dirty_at(uuid, changed_at);
+        }
+        self.touch_region(merged_id);
+        let mut regions = self.regions.write().unwrap();
+        for region_id in region_ids {
+            regions.remove(region_id);
+        }
+        regions.insert(merged_id, Arc::new(Mutex::new(merged)));
+        drop(regions);
+        // The dirty sets moved to the merged region, so nothing is left for the auto-persist worker
+        for region in locked.values_mut() {
+            region.retire();
+        }
+        drop(locked);
+        self.sync_auto_persist_regions();
+        self.evict_regions()?;
+
+        Ok(merged_id)
+    }
+
+    /// Writes a region merge to the persistent database: the new region, the reassigned objects
+    /// and the removal of the old regions.
+    fn write_merge(&self, region_ids: &[Uuid], merged_id: Uuid, center: [f64; 3], radius: f64, objects: &[SpatialObject<T>]) -> Result<(), String> {
+        self.persistent_db.create_region(merged_id, center, radius)
+            .map_err(|e| format!("Failed to persist region to database: {}", e))?;
+        for object in objects {
+            self.persistent_db.update_point_region(object.uuid, merged_id)
+                .map_err(|e| format!("Failed to reassign object {}: {}", object.uuid, e))?;
+        }
+        for region_id in region_ids {
+            self.persistent_db.remove_region(*region_id)
+                .map_err(|e| format!("Failed to remove region from database: {}", e))?;
+        }
+        Ok(())
+    }
+
+    /// Splits every region holding more objects than `VaultConfig::max_objects_per_region`.
+    ///
+    /// Each oversized region is split once; an octant that's still oversized is split on the next call.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<HashMap<Uuid, Vec<Uuid>>, String>` - The octants each split region was replaced by, or an error message if a split failed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, VaultConfig, CustomData};
+    /// let config = VaultConfig { max_objects_per_region: Some(50_000), ..VaultConfig::default() };
+    /// let vault_manager: VaultManager<CustomData> = VaultManager::with_config("path/to/database.db", config).unwrap();
+    /// for (old_region, octants) in vault_manager.split_oversized_regions().expect("Failed to split regions") {
+    ///     println!("Region {} was split into {:?}", old_region, octants);
+    /// }
+    /// ```
+    pub fn split_oversized_regions(&self) -> Result<HashMap<Uuid, Vec<Uuid>>, String> {
+        self.split_regions(&OperationHandle::new())
+    }
+
+    /// Splits every oversized region, reporting progress through `handle`.
+    ///
+    /// This behaves like `split_oversized_regions`. The progress is counted in regions split,
+    /// and the operation can be paused or cancelled between regions through any clone of the
+    /// handle. Regions split before a cancellation stay split.
+    ///
+    /// # Arguments
+    ///
+    /// * `handle` - The handle to report progress through.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<HashMap<Uuid, Vec<Uuid>>, String>` - The octants each split region was replaced by, or an error message if a split failed or the operation was cancelled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, VaultConfig, CustomData, OperationHandle};
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// let handle = OperationHandle::new();
+    /// let splits = vault_manager.split_oversized_regions_tracked(&handle).expect("Failed to split regions");
+    /// ```
+    pub fn split_oversized_regions_tracked(&self, handle: &OperationHandle) -> Result<HashMap<Uuid, Vec<Uuid>>, String> {
+        let result = self.split_regions(handle);
+        handle.finish(&result);
+        result
+    }
+
+    /// Splits every region holding more objects than `VaultConfig::max_objects_per_region`.
+    fn split_regions(&self, handle: &OperationHandle) -> Result<HashMap<Uuid, Vec<Uuid>>, String> {
+        let mut splits = HashMap::new();
+        let Some(max_objects) = self.config.max_objects_per_region else {
+            handle.start(0);
+            return Ok(splits);
+        };
+
+        let oversized: Vec<Uuid> = self.region_handles().into_iter()
+            .filter(|(region_id, region)| self.locks.acquire(*region_id, region).rtree.size() > max_objects)
+            .map(|(region_id, _)| region_id)
+            .collect();
+        handle.start(oversized.len() as u64);
+        for region_id in oversized {
+            handle.checkpoint()?;
+            let octants = self.split_region(region_id)?;
+            splits.insert(region_id, octants);
+            handle.advance(1);
+        }
+
+        Ok(splits)
+    }
+
+    /// Saves a named snapshot of the whole world, replacing any snapshot with the same name.
+    ///
+    /// The snapshot holds every region and object, including custom data and TTLs, whether the
+    /// region is loaded or not. Use `restore` to roll the world back to it.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name to save the snapshot under (e.g., a save slot).
+    ///
+    /// # Returns
+    ///
+    /// * `Result<WorldSnapshot, String>` - A description of the saved snapshot if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// let saved = vault_manager.snapshot("slot-1").expect("Failed to save snapshot");
+    /// println!("Saved {} objects", saved.object_count);
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - Unpersisted changes are persisted first, so the snapshot matches the world in memory.
+    /// - Soft-deleted objects aren't part of the snapshot.
+    pub fn snapshot(&self, name: &str) -> Result<WorldSnapshot, String> {
+        self.persist_to_disk()?;
+
+        let created_at = SystemTime::now().duration_since(UNIX_EPOCH)
+            .map_err(|e| format!("System clock is before the Unix epoch: {}", e))?
+            .as_millis() as i64;
+        self.persistent_db.begin_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        let (region_count, object_count) = match self.persistent_db.save_snapshot(name, created_at) {
+            Ok(counts) => counts,
+            Err(e) => {
+                let _ = self.persistent_db.rollback_transaction();
+                return Err(format!("Failed to save snapshot {}: {}", name, e));
+            }
+        };
+        self.persistent_db.commit_transaction()
+            .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+        Ok(WorldSnapshot {
+            name: name.to_string(),
+            created_at: UNIX_EPOCH + Duration::from_millis(created_at as u64),
+            region_count,
+            object_count,
+        })
+    }
+
+    /// Rolls the whole world back to a named snapshot.
+    ///
+    /// Every region and object is replaced by the contents of the snapshot, in memory and in
+    /// the persistent database. Changes made since the snapshot, persisted or not, are discarded.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the snapshot to restore.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), String>` - An empty result if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// vault_manager.restore("slot-1").expect("Failed to restore snapshot");
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - The database is restored in one transaction; if it fails, the world is left as it was.
+    /// - Subscribers receive `RegionRemoved` for every previous region and `RegionCreated` for every
+    ///   restored one. Catch-up tokens from before the restore are answered with full snapshots.
+    /// - Regions are loaded again following `VaultConfig::lazy_load` and `max_loaded_regions`,
+    ///   and `load_report` describes that load.
+    pub fn restore(&self, name: &str) -> Result<(), String> {
+        // Hold every region so nothing, including the auto-persist worker, writes them meanwhile
+        let creation = self.region_creation.lock().unwrap();
+        let mut handles = self.region_handles();
+        handles.sort_unstable_by_key(|(region_id, _)| *region_id);
+        let mut regions: Vec<_> = handles.iter()
+            .map(|(region_id, region)| self.locks.acquire(*region_id, region))
+            .collect();
+
+        self.persistent_db.begin_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        let restored = self.persistent_db.restore_snapshot(name)
+            .map_err(|e| format!("Failed to restore snapshot {}: {}", name, e));
+        if restored != Ok(true) {
+            let _ = self.persistent_db.rollback_transaction();
+            return Err(restored.err().unwrap_or_else(|| format!("Snapshot not found: {}", name)));
+        }
+        self.persistent_db.commit_transaction()
+            .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+        // Unpersisted changes belong to the discarded world
+        for region in &mut regions {
+            region.retire();
+        }
+        self.regions.write().unwrap().clear();
+        self.loaded_regions.lock().unwrap().clear();
+        self.simulation_ticks.lock().unwrap().clear();
+        self.players.lock().unwrap().clear();
+        self.object_regions.lock().unwrap().clear();
+        drop(regions);
+        *self.verifier.lock().unwrap() = VerifyCursor::default();
+        self.load_regions_from_db()?;
+        self.load_expiries()?;
+        self.load_hierarchy()?;
+        drop(creation);
+        self.sync_auto_persist_regions();
+
+        self.emit(handles.into_iter().map(|(region_id, _)| VaultEvent::RegionRemoved { region_id }));
+        if self.has_subscribers() {
+            let created: Vec<VaultEvent<T>> = self.region_handles().into_iter()
+                .map(|(region_id, region)| {
+                    let region = self.locks.acquire(region_id, &region);
+                    VaultEvent::RegionCreated { region_id, center: region.center, radius: region.radius }
+                })
+                .collect();
+            self.emit(created);
+        }
+        Ok(())
+    }
+
+    /// Lists the snapshots saved in the persistent database, newest first.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<WorldSnapshot>, String>` - The saved snapshots if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// for snapshot in vault_manager.snapshots().expect("Failed to list snapshots") {
+    ///     println!("{} ({} regions)", snapshot.name, snapshot.region_count);
+    /// }
+    /// ```
+    pub fn snapshots(&self) -> Result<Vec<WorldSnapshot>, String> {
+        let snapshots = self.persistent_db.get_snapshots()
+            .map_err(|e| format!("Failed to list snapshots: {}", e))?;
+        Ok(snapshots.into_iter()
+            .map(|(name, created_at, region_count, object_count)| WorldSnapshot {
+                name,
+                created_at: UNIX_EPOCH + Duration::from_millis(created_at.max(0) as u64),
+                region_count,
+                object_count,
+            })
+            .collect())
+    }
+
+    /// Deletes a named snapshot.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the snapshot to delete.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<bool, String>` - `true` if the snapshot existed, or an error message if the deletion failed.
+    pub fn delete_snapshot(&self, name: &str) -> Result<bool, String> {
+        self.persistent_db.delete_snapshot(name)
+            .map_err(|e| format!("Failed to delete snapshot {}: {}", name, e))
+    }
+
+    /// Exports a region and its objects to a portable file.
+    ///
+    /// The file holds everything needed to recreate the region, including custom data and
+    /// expiry times, independent of the persistent database. See `import_region`.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_id` - The UUID of the region to export.
+    /// * `path` - The file to write. An existing file is replaced.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<usize, String>` - The number of objects exported if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # use uuid::Uuid;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let region_id = Uuid::new_v4();
+    /// let exported = vault_manager.export_region(region_id, "regions/castle.json").expect("Failed to export region");
+    /// println!("Exported {} objects", exported);
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - The objects are exported as they are in memory, including changes that haven't been persisted yet.
+    /// - A region that isn't loaded is loaded first.
+    pub fn export_region(&self, region_id: Uuid, path: &str) -> Result<usize, String> {
+        let handle = self.region(region_id)?;
+        let region = self.lock_region(region_id, &handle)?;
+        let expiries = self.expiries.lock().unwrap().clone();
+        let objects = region.rtree.iter()
+            .map(|object| {
+                let expires_at = expiries.get(&object.uuid)
+                    .map(|expires_at| expires_at.duration_since(UNIX_EPOCH).map(|since| since.as_millis() as i64))
+                    .transpose()
+                    .map_err(|e| format!("Failed to read expiry of object {}: {}", object.uuid, e))?;
+                object_record(object, expires_at)
+            })
+            .collect::<Result<Vec<ObjectRecord>, String>>()?;
+        let record = RegionRecord { id: region.id, center: region.center, radius: region.radius };
+        drop(region);
+
+        let object_count = objects.len();
+        RegionFile::new(record, objects).write(Path::new(path))?;
+        Ok(object_count)
+    }
+
+    /// Imports a region written by `export_region`, with its objects.
+    ///
+    /// The region keeps the UUID, center and radius it was exported with, and its objects keep
+    /// their UUIDs, so references to them stay valid across servers.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The region file to read.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Uuid, String>` - The UUID of the imported region if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// let region_id = vault_manager.import_region("regions/castle.json").expect("Failed to import region");
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - The import fails if the region or any of its objects already exists; delete them first to replace them.
+    /// - Objects are checked against the object type registry and the world bounds like newly added ones.
+    /// - The region and its objects are written to the persistent database in one transaction.
+    pub fn import_region(&self, path: &str) -> Result<Uuid, String> {
+        let file = RegionFile::read(Path::new(path))?;
+        let RegionRecord { id: region_id, center, radius } = file.region;
+        validate_point(center).map_err(|e| format!("Invalid region center: {}", e))?;
+        if !radius.is_finite() || radius < 0.0 {
+            return Err(format!("Invalid region radius: {}", radius));
+        }
+
+        let mut objects = Vec::with_capacity(file.objects.len());
+        let mut expiries = Vec::new();
+        for record in file.objects {
+            let expires_at = record.expires_at;
+            let mut object = object_from_record(record)?;
+            self.check_type(&object)?;
+            object.point = self.check_position(object.uuid, object.point)?;
+            if let Some(expires_at) = expires_at {
+                expiries.push((object.uuid, expires_at));
+            }
+            objects.push(object);
+        }
+
+        let creation = self.region_creation.lock().unwrap();
+        if self.has_region(region_id) {
+            return Err(format!("Region already exists: {}", region_id));
+        }
+        for object in &objects {
+            let existing = self.persistent_db.get_point_region(object.uuid)
+                .map_err(|e| format!("Failed to look up region of object {}: {}", object.uuid, e))?;
+            if let Some(existing) = existing {
+                return Err(format!("Object {} already exists in region {}", object.uuid, existing));
+            }
+        }
+
+        self.persistent_db.begin_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        let written = self.persistent_db.create_region(region_id, center, radius)
+            .map_err(|e| format!("Failed to persist region to database: {}", e))
+            .and_then(|_| self.write_objects(region_id, &objects))
+            .and_then(|_| expiries.iter().try_for_each(|(object_id, expires_at)| {
+                self.persistent_db.set_point_expiry(*object_id, Some(*expires_at))
+                    .map_err(|e| format!("Failed to store object expiry: {}", e))
+            }));
+        if let Err(e) = written {
+            let _ = self.persistent_db.rollback_transaction();
+            return Err(e);
+        }
+        self.persistent_db.commit_transaction()
+            .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+        let added = self.has_subscribers().then(|| objects.clone());
+        let mut region = VaultRegion::new(region_id, center, radius);
+        self.index_objects(region_id, objects.iter().map(|obj| obj.uuid));
+        region.insert_objects(objects);
+        self.regions.write().unwrap().insert(region_id, Arc::new(Mutex::new(region)));
+        drop(creation);
+        self.expiries.lock().unwrap().extend(expiries.into_iter()
+            .map(|(object_id, expires_at)| (object_id, UNIX_EPOCH + Duration::from_millis(expires_at.max(0) as u64))));
+        self.touch_region(region_id);
+        self.sync_auto_persist_regions();
+
+        self.emit([VaultEvent::RegionCreated { region_id, center, radius }]);
+        self.emit(added.into_iter().flatten().map(|object| VaultEvent::ObjectAdded { region_id, object }));
+        self.evict_regions()?;
+        Ok(region_id)
+    }
+
+    /// Moves objects that haven't been written for a while out of the persistent database into cold storage.
+    ///
+    /// Each region's cold objects are written to a compressed chunk under `VaultConfig::archive_dir`,
+    /// then removed from the database and from memory. `get_object` restores an archived object
+    /// into its region the next time it's asked for, so the world can grow without the hot
+    /// database growing with it.
+    ///
+    /// # Arguments
+    ///
+    /// * `untouched_for` - How long an object must have gone without being written to be archived.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<usize, String>` - The number of objects archived if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # use std::time::Duration;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// // Archive everything nobody has changed in 30 days
+    /// let archived = vault_manager.archive_cold_objects(Duration::from_secs(30 * 24 * 60 * 60)).expect("Failed to archive objects");
+    /// println!("Archived {} objects", archived);
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - Fails if `VaultConfig::archive_dir` isn't set.
+    /// - Objects with unpersisted changes, a TTL or attachments are never archived, nor are objects
+    ///   written before this version of PebbleVault started tracking write times.
+    /// - Every region is visited, so unloaded regions are loaded.
+    /// - Archival doesn't emit events, and only `get_object` sees archived objects: queries and
+    ///   the other operations treat them as absent until they're restored.
+    /// - Deleting a region forgets its archived objects; their chunk files are left in place.
+    pub fn archive_cold_objects(&self, untouched_for: Duration) -> Result<usize, String> {
+        let archive_dir = self.config.archive_dir.as_deref()
+            .ok_or_else(|| "No archive directory is configured".to_string())?;
+        let cutoff = SystemTime::now().checked_sub(untouched_for).unwrap_or(UNIX_EPOCH)
+            .duration_since(UNIX_EPOCH)
+            .map(|since| since.as_millis() as i64)
+            .unwrap_or_default();
+
+        let mut archived = 0;
+        for region_id in self.region_ids() {
+            archived += self.archive_region(archive_dir, region_id, cutoff)?;
+        }
+        Ok(archived)
+    }
+
+    /// Archives the objects of a region last written before `cutoff`, in milliseconds since the epoch, into a new chunk.
+    fn archive_region(&self, archive_dir: &Path, region_id: Uuid, cutoff: i64) -> Result<usize, String> {
+        let handle = self.region(region_id)?;
+        let mut region = self.lock_region(region_id, &handle)?;
+        let cold = self.persistent_db.get_points_untouched_since(region_id, cutoff)
+            .map_err(|e| format!("Failed to list cold objects of region {}: {}", region_id, e))?;
+        let expiries = self.expiries.lock().unwrap();
+        let hierarchy = self.hierarchy.lock().unwrap();
+        let objects: Vec<SpatialObject<T>> = cold.into_iter()
+            .filter(|object_id| !region.is_dirty(*object_id) && !expiries.contains_key(object_id))
+            .filter(|object_id| hierarchy.parent(*object_id).is_none() && hierarchy.children(*object_id).is_empty())
+            .filter_map(|object_id| region.get_object(object_id).cloned())
+            .collect();
+        drop(hierarchy);
+        drop(expiries);
+        if objects.is_empty() {
+            return Ok(0);
+        }
+
+        let records = objects.iter()
+            .map(|object| object_record(object, None))
+            .collect::<Result<Vec<ObjectRecord>, String>>()?;
+        let chunk_id = Uuid::new_v4();
+        let path = ArchiveChunk::path(archive_dir, chunk_id);
+        ArchiveChunk::new(region_id, records).write(&path)?;
+
+        // The chunk is written before the rows are removed, so a failure never loses objects
+        let object_ids: Vec<Uuid> = objects.iter().map(|obj| obj.uuid).collect();
+        self.persistent_db.begin_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        let removed = self.persistent_db.archive_points(chunk_id, region_id, &object_ids)
+            .map_err(|e| format!("Failed to archive objects in persistent database: {}", e))
+            .and_then(|removed| match removed == object_ids.len() {
+                true => self.persistent_db.commit_transaction()
+                    .map_err(|e| format!("Failed to commit transaction: {}", e)),
+                false => Err(format!("Persistent database matched {} objects instead of {}", removed, object_ids.len())),
+            });
+        if let Err(e) = removed {
+            let _ = self.persistent_db.rollback_transaction();
+            let _ = std::fs::remove_file(&path);
+            return Err(e);
+        }
+
+        self.unindex_objects(object_ids.iter().copied());
+        let mut removed = Vec::with_capacity(objects.len());
+        for object in &objects {
+            removed.extend(region.remove_object(object));
+        }
+        drop(objects);
+        let mut pool = self.object_pool.lock().unwrap();
+        for object in removed {
+            pool.release(object);
+        }
+        Ok(object_ids.len())
+    }
+
+    /// Restores an object archived by `archive_cold_objects` into its region.
+    ///
+    /// Returns `None` if the object isn't archived, or its region no longer exists. The chunk
+    /// file is deleted once the last object in it has been restored.
+    fn restore_archived(&self, object_id: Uuid) -> Result<Option<SpatialObject<T>>, String> {
+        let Some(archive_dir) = self.config.archive_dir.as_deref() else {
+            return Ok(None);
+        };
+        let archived = self.persistent_db.get_archived_point(object_id)
+            .map_err(|e| format!("Failed to look up archived object {}: {}", object_id, e))?;
+        let Some((region_id, chunk_id)) = archived.filter(|(region_id, _)| self.has_region(*region_id)) else {
+            return Ok(None);
+        };
+        let handle = self.region(region_id)?;
+        let mut region = self.lock_region(region_id, &handle)?;
+        // Another thread may have restored the object while this one waited for the region
+        if let Some(object) = region.get_object(object_id) {
+            return Ok(Some(object.clone()));
+        }
+
+        let path = ArchiveChunk::path(archive_dir, chunk_id);
+        let record = ArchiveChunk::read(&path)?.objects.into_iter()
+            .find(|record| record.uuid == object_id)
+            .ok_or_else(|| format!("Object {} is missing from archive chunk {}", object_id, path.display()))?;
+        let object: SpatialObject<T> = object_from_record(record)?;
+
+        self.persistent_db.begin_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        let remaining = self.write_objects(region_id, std::slice::from_ref(&object))
+            .and_then(|_| self.persistent_db.unarchive_point(object_id, chunk_id)
+                .map_err(|e| format!("Failed to restore archived object {}: {}", object_id, e)));
+        let remaining = match remaining {
+            Ok(remaining) => remaining,
+            Err(e) => {
+                let _ = self.persistent_db.rollback_transaction();
+                return Err(e);
+            }
+        };
+        self.persistent_db.commit_transaction()
+            .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+        self.index_objects(region_id, [object_id]);
+        region.insert_objects(vec![object.clone()]);
+        if remaining == 0 {
+            let _ = std::fs::remove_file(&path);
+        }
+        Ok(Some(object))
+    }
+
+    /// Saves a region template to the persistent database, replacing any template with the same ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `template` - The template to save.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), String>` - An empty result if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{RegionTemplate, VaultManager, CustomData};
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// let camp = RegionTemplate::new("camp").with_object("tent", [0.0, 0.0, 0.0], [4.0, 3.0, 4.0], CustomData { /* ... */ });
+    /// vault_manager.save_template(&camp).expect("Failed to save template");
+    /// ```
+    pub fn save_template(&self, template: &RegionTemplate<T>) -> Result<(), String> {
+        let objects = serde_json::to_value(&template.objects)
+            .map_err(|e| format!("Failed to serialize template objects: {}", e))?;
+        self.persistent_db.save_template(template.id, &template.name, &objects)
+            .map_err(|e| format!("Failed to save template to persistent database: {}", e))
+    }
+
+    /// Loads a region template from the persistent database.
+    ///
+    /// # Arguments
+    ///
+    /// * `template_id` - The UUID of the template.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Option<RegionTemplate<T>>, String>` - The template, `None` if there's no such template, or an error message.
+    pub fn load_template(&self, template_id: Uuid) -> Result<Option<RegionTemplate<T>>, String> {
+        let Some((name, objects)) = self.persistent_db.get_template(template_id)
+            .map_err(|e| format!("Failed to load template from persistent database: {}", e))? else {
+            return Ok(None);
+        };
+        let objects: Vec<TemplateObject<T>> = serde_json::from_value(objects)
+            .map_err(|e| format!("Failed to deserialize template {}: {}", template_id, e))?;
+        Ok(Some(RegionTemplate { id: template_id, name, objects }))
+    }
+
+    /// Returns the ID and name of every saved region template, ordered by name.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<(Uuid, String)>, String>` - The templates if successful, or an error message if not.
+    pub fn templates(&self) -> Result<Vec<(Uuid, String)>, String> {
+        self.persistent_db.get_templates()
+            .map_err(|e| format!("Failed to list templates: {}", e))
+    }
+
+    /// Deletes a region template. Objects already placed from it are left alone.
+    ///
+    /// # Arguments
+    ///
+    /// * `template_id` - The UUID of the template.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<bool, String>` - `true` if a template was deleted, or an error message.
+    pub fn delete_template(&self, template_id: Uuid) -> Result<bool, String> {
+        self.persistent_db.delete_template(template_id)
+            .map_err(|e| format!("Failed to delete template: {}", e))
+    }
+
+    /// Builds a template from the objects of a region.
+    ///
+    /// The template isn't saved; pass it to `save_template` to keep it.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_id` - The UUID of the region to copy the objects of.
+    /// * `origin` - The point the objects' offsets are measured from [x, y, z].
+    /// * `name` - The name of the new template.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<RegionTemplate<T>, String>` - The new template if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # use uuid::Uuid;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let region_id = Uuid::new_v4();
+    /// // A level designer builds a dungeon room once and saves it as a template
+    /// let room = vault_manager.capture_template(region_id, [0.0, 0.0, 0.0], "dungeon room").expect("Failed to capture template");
+    /// vault_manager.save_template(&room).expect("Failed to save template");
+    /// ```
+    pub fn capture_template(&self, region_id: Uuid, origin: [f64; 3], name: &str) -> Result<RegionTemplate<T>, String> {
+        let handle = self.region(region_id)?;
+        let region = self.lock_region(region_id, &handle)?;
+        let mut template = RegionTemplate::new(name);
+        template.objects = region.rtree.iter()
+            .map(|obj| TemplateObject {
+                object_type: obj.object_type,
+                offset: [obj.point[0] - origin[0], obj.point[1] - origin[1], obj.point[2] - origin[2]],
+                size: obj.size,
+                custom_data: (*obj.custom_data).clone(),
+            })
+            .collect();
+        Ok(template)
+    }
+
+    /// Places a copy of every object of a saved template into a region.
+    ///
+    /// Each object gets a new UUID. Its position is its offset, rotated and translated by `transform`.
+    ///
+    /// # Arguments
+    ///
+    /// * `template_id` - The UUID of the saved template.
+    /// * `region_id` - The UUID of the region to add the objects to.
+    /// * `transform` - Where and how to place the template.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<Uuid>, String>` - The UUIDs of the new objects, in the template's order, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{Transform, VaultManager, CustomData};
+    /// # use uuid::Uuid;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let (template_id, region_id) = (Uuid::new_v4(), Uuid::new_v4());
+    /// let transform = Transform::translate([300.0, 0.0, 120.0]).with_yaw(std::f64::consts::FRAC_PI_2);
+    /// let objects = vault_manager.instantiate_template(template_id, region_id, transform).expect("Failed to place template");
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - The objects are added like with `add_objects`: in one transaction, checked against `VaultConfig::world_bounds`.
+    /// - Objects aren't required to lie within the region's bounds.
+    pub fn instantiate_template(&self, template_id: Uuid, region_id: Uuid, transform: Transform) -> Result<Vec<Uuid>, String> {
+        let template = self.load_template(template_id)?
+            .ok_or_else(|| format!("Template not found: {}", template_id))?;
+
+        let objects = template.objects.into_iter()
+            .map(|object| {
+                let uuid = Uuid::new_v4();
+                SpatialObject::new(uuid, object.object_type, transform.apply(object.offset), Arc::new(object.custom_data))
+                    .and_then(|spatial| spatial.with_size(transform.apply_size(object.size)))
+                    .map_err(|e| format!("Invalid object in template {}: {}", template_id, e))
+            })
+            .collect::<Result<Vec<SpatialObject<T>>, String>>()?;
+        let object_ids = objects.iter().map(|obj| obj.uuid).collect();
+
+        self.add_objects(region_id, objects)?;
+        Ok(object_ids)
+    }
+
+    /// Removes an object from its region and the persistent database.
+    ///
+    /// # Arguments
+    ///
+    /// * `object_id` - The UUID of the object to remove.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), String>` - An empty result if successful, or an error message if not.
+    ///
+    /// # Notes
+    ///
+    /// - If several threads remove the same object at once, only one of them succeeds and
+    ///   `VaultEvent::ObjectRemoved` is sent once.
+    pub fn remove_object(&self, object_id: Uuid) -> Result<(), String> {
+        // Find the region containing the object
+        let (region_id, _) = self.find_object(object_id)?
+            .ok_or_else(|| format!("Object not found: {}", object_id))?;
+
+        // The region stays locked until the row is deleted, so a concurrent persist can't write the object back
+        let handle = self.region(region_id)?;
+        let mut region = self.lock_region(region_id, &handle)?;
+        // The object may have changed since it was found, so remove the locked copy
+        let obj = region.get_object(object_id)
+            .cloned()
+            .ok_or_else(|| format!("Object {} was changed concurrently", object_id))?;
+
+        // Remove the object from the persistent database, then from the RTree
+        self.persistent_db.remove_point(object_id)
+            .map_err(|e| format!("Failed to remove point from persistent database: {}", e))?;
+        let removed = region.remove_object(&obj);
+        drop(region);
+        self.unindex_objects([object_id]);
+        self.forget_attachments([object_id]);
+        self.expiries.lock().unwrap().remove(&object_id);
+        drop(obj);
+        if let Some(removed) = removed {
+            self.emit([VaultEvent::ObjectRemoved { region_id, object_id }]);
+            self.object_pool.lock().unwrap().release(removed);
+        }
+        Ok(())
+    }
+
+    /// Removes every object of a region lying entirely inside a box.
+    ///
+    /// Objects are matched like `query_region` matches them. This is meant for resetting a zone
+    /// of the world: the persistent database removes them with a single statement instead of
+    /// one per object.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_id` - The UUID of the region to remove objects from.
+    /// * `min` - The minimum corner of the box [x, y, z].
+    /// * `max` - The maximum corner of the box [x, y, z].
+    ///
+    /// # Returns
+    ///
+    /// * `Result<usize, String>` - The number of objects removed if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # use uuid::Uuid;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let region_id = Uuid::new_v4();
+    /// // Reset the arena
+    /// let removed = vault_manager.remove_in_bbox(region_id, [-50.0, 0.0, -50.0], [50.0, 30.0, 50.0]).expect("Failed to reset arena");
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - Changes to the region that haven't been persisted yet are written first, in the same
+    ///   transaction, so the database removes exactly the objects found in memory.
+    pub fn remove_in_bbox(&self, region_id: Uuid, min: [f64; 3], max: [f64; 3]) -> Result<usize, String> {
+        let envelope = query_box(min, max)?;
+        self.remove_where(
+            region_id,
+            |region| region.rtree.locate_in_envelope(&envelope).cloned().collect(),
+            |db| db.remove_points_in_box(region_id, min, max)
+                .map_err(|e| format!("Failed to remove objects in box from persistent database: {}", e)),
+        )
+    }
+
+    /// Removes every object of a given type from a region.
+    ///
+    /// The persistent database removes them with a single statement instead of one per object.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_id` - The UUID of the region to remove objects from.
+    /// * `object_type` - The type of object to remove (e.g., "resource").
+    ///
+    /// # Returns
+    ///
+    /// * `Result<usize, String>` - The number of objects removed if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # use uuid::Uuid;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let region_id = Uuid::new_v4();
+    /// // Clear the harvested resources before respawning them
+    /// let removed = vault_manager.remove_by_type(region_id, "resource").expect("Failed to remove resources");
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - Only the region's index for `object_type` is visited to find the objects.
+    pub fn remove_by_type(&self, region_id: Uuid, object_type: &str) -> Result<usize, String> {
+        self.remove_where(
+            region_id,
+            |region| region.objects_of_type(object_type)
+                .map(|typed| typed.iter().cloned().collect())
+                .unwrap_or_default(),
+            |db| db.remove_points_of_type(region_id, object_type)
+                .map_err(|e| format!("Failed to remove objects of type {} from persistent database: {}", object_type, e)),
+        )
+    }
+
+    /// Removes every object from a region, keeping the region itself.
+    ///
+    /// This is meant for resetting a region, such as an instanced dungeon between runs. The
+    /// persistent database removes the objects with a single statement instead of one per object.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_id` - The UUID of the region to clear.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<usize, String>` - The number of objects removed if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # use uuid::Uuid;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let region_id = Uuid::new_v4();
+    /// // Reset the dungeon for the next party
+    /// let removed = vault_manager.clear_region(region_id).expect("Failed to clear region");
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - Soft-deleted objects of the region can still be restored afterwards.
+    pub fn clear_region(&self, region_id: Uuid) -> Result<usize, String> {
+        self.remove_where(
+            region_id,
+            |region| region.rtree.iter().cloned().collect(),
+            |db| db.remove_points_in_region(region_id)
+                .map_err(|e| format!("Failed to remove objects of region {} from persistent database: {}", region_id, e)),
+        )
+    }
+
+    /// Removes the objects `select` picks from a region, deleting them from the database with `delete`.
+    ///
+    /// The region's unpersisted changes are written in the same transaction first, so that
+    /// `delete` sees the objects where they are in memory.
+    fn remove_where<S, D>(&self, region_id: Uuid, select: S, delete: D) -> Result<usize, String>
+    where
+        S: FnOnce(&VaultRegion<T>) -> Vec<SpatialObject<T>>,
+        D: FnOnce(&MySQLGeo::Database) -> Result<usize, String>,
+    {
+        let handle = self.region(region_id)?;
+        let mut region = self.lock_region(region_id, &handle)?;
+        let doomed = select(&region);
+        if doomed.is_empty() {
+            return Ok(0);
+        }
+        let dirty: Vec<SpatialObject<T>> = region.rtree.iter()
+            .filter(|obj| region.is_dirty(obj.uuid))
+            .cloned()
+            .collect();
+
+        self.persistent_db.begin_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        let deleted = self.write_objects(region_id, &dirty).and_then(|_| delete(&self.persistent_db));
+        match deleted {
+            Ok(deleted) if deleted == doomed.len() => {}
+            Ok(deleted) => {
+                let _ = self.persistent_db.rollback_transaction();
+                return Err(format!("Persistent database matched {} objects instead of {}", deleted, doomed.len()));
+            }
+            Err(e) => {
+                let _ = self.persistent_db.rollback_transaction();
+                return Err(e);
             }
         }
+        self.persistent_db.commit_transaction()
+            .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+        region.take_dirty();
 
-        pb.finish_with_message("Points persisted");
+        let object_ids: Vec<Uuid> = doomed.iter().map(|obj| obj.uuid).collect();
+        self.unindex_objects(object_ids.iter().copied());
+        self.forget_attachments(object_ids.iter().copied());
+        let mut expiries = self.expiries.lock().unwrap();
+        for object_id in &object_ids {
+            expiries.remove(object_id);
+        }
+        drop(expiries);
+        self.emit(object_ids.iter().map(|&object_id| VaultEvent::ObjectRemoved { region_id, object_id }));
 
-        let duration = start_time.elapsed();
-        println!("Persisted {} points in {:?}", total_points, duration);
-        println!("Average time per point: {:?}", duration / total_points as u32);
+        let mut removed = Vec::with_capacity(doomed.len());
+        for object in &doomed {
+            removed.extend(region.remove_object(object));
+        }
+        drop(doomed);
+        let mut pool = self.object_pool.lock().unwrap();
+        for object in removed {
+            pool.release(object);
+        }
+        Ok(object_ids.len())
+    }
+
+    /// Removes an object from its region but keeps it in the persistent database, so it can be restored.
+    ///
+    /// The object disappears from every query, but its row is only marked deleted. Use
+    /// `restore_object` to bring it back, and `purge_deleted_objects` to remove it for good.
+    ///
+    /// # Arguments
+    ///
+    /// * `object_id` - The UUID of the object to remove.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), String>` - An empty result if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # use uuid::Uuid;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let object_id = Uuid::new_v4();
+    /// vault_manager.soft_remove_object(object_id).expect("Failed to remove object");
+    /// // A game master changes their mind
+    /// vault_manager.restore_object(object_id).expect("Failed to restore object");
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - Unpersisted changes to the object are written before it's marked deleted, so they're restored with it.
+    /// - Deleting the object's region with `delete_region` also removes its soft-deleted objects.
+    pub fn soft_remove_object(&self, object_id: Uuid) -> Result<(), String> {
+        let (region_id, _) = self.find_object(object_id)?
+            .ok_or_else(|| format!("Object not found: {}", object_id))?;
+
+        let handle = self.region(region_id)?;
+        let mut region = self.lock_region(region_id, &handle)?;
+        let object = region.get_object(object_id)
+            .cloned()
+            .ok_or_else(|| format!("Object not found: {}", object_id))?;
+
+        self.persistent_db.begin_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        let written = self.write_objects(region_id, std::slice::from_ref(&object))
+            .and_then(|_| {
+                self.persistent_db.soft_delete_point(object_id)
+                    .map_err(|e| format!("Failed to mark point deleted in persistent database: {}", e))
+            });
+        if let Err(e) = written {
+            let _ = self.persistent_db.rollback_transaction();
+            return Err(e);
+        }
+        self.persistent_db.commit_transaction()
+            .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+        region.remove_object(&object);
+        self.unindex_objects([object_id]);
+        self.emit([VaultEvent::ObjectRemoved { region_id, object_id }]);
+        Ok(())
+    }
+
+    /// Restores an object removed with `soft_remove_object` to the region it was removed from.
+    ///
+    /// # Arguments
+    ///
+    /// * `object_id` - The UUID of the object to restore.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Uuid, String>` - The UUID of the region holding the restored object, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # use uuid::Uuid;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let object_id = Uuid::new_v4();
+    /// let region_id = vault_manager.restore_object(object_id).expect("Failed to restore object");
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - Restoring fails if the object was purged or its region no longer exists.
+    pub fn restore_object(&self, object_id: Uuid) -> Result<Uuid, String> {
+        let (region_id, point) = self.persistent_db.get_deleted_point(object_id)
+            .map_err(|e| format!("Failed to load deleted point from persistent database: {}", e))?
+            .ok_or_else(|| format!("No deleted object found: {}", object_id))?;
+        if !self.has_region(region_id) {
+            return Err(format!("Region of deleted object {} no longer exists: {}", object_id, region_id));
+        }
+        let custom_data: T = serde_json::from_value(point.custom_data)
+            .map_err(|e| format!("Failed to deserialize custom data: {}", e))?;
+        let object = SpatialObject {
+            uuid: object_id,
+            object_type: ObjectType::from(point.object_type),
+            point: [point.x, point.y, point.z],
+            custom_data: Arc::new(custom_data),
+            size: point.size,
+            version: point.version,
+            velocity: point.velocity,
+            orientation: point.orientation,
+        };
+
+        self.persistent_db.restore_point(object_id)
+            .map_err(|e| format!("Failed to restore point in persistent database: {}", e))?;
+
+        // A region that wasn't loaded picks the restored object up when it's loaded here
+        let handle = self.region(region_id)?;
+        let mut region = self.lock_region(region_id, &handle)?;
+        if !region.contains_object(object_id) {
+            self.index_objects(region_id, [object_id]);
+            region.insert_object(object.clone());
+        }
+        self.emit([VaultEvent::ObjectAdded { region_id, object }]);
+        Ok(region_id)
+    }
+
+    /// Permanently removes objects that were soft-deleted at least `older_than` ago.
+    ///
+    /// Run this periodically, for example once a day, to keep the database from growing with
+    /// objects nobody is going to restore.
+    ///
+    /// # Arguments
+    ///
+    /// * `older_than` - How long a soft-deleted object is kept before it's purged.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<usize, String>` - The number of objects purged, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # use std::time::Duration;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// let purged = vault_manager.purge_deleted_objects(Duration::from_secs(7 * 24 * 3600)).expect("Failed to purge objects");
+    /// println!("Purged {} deleted objects", purged);
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - Deletion times have a resolution of one second.
+    pub fn purge_deleted_objects(&self, older_than: Duration) -> Result<usize, String> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)
+            .map_err(|e| format!("Failed to read the system clock: {}", e))?;
+        let deleted_before = now.saturating_sub(older_than).as_secs() as i64;
+
+        self.persistent_db.begin_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        let purged = match self.persistent_db.purge_deleted_points(deleted_before) {
+            Ok(purged) => purged,
+            Err(e) => {
+                let _ = self.persistent_db.rollback_transaction();
+                return Err(format!("Failed to purge deleted points: {}", e));
+            }
+        };
+        self.persistent_db.commit_transaction()
+            .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+        Ok(purged)
+    }
+
+    /// Deletes the data files of custom data that no object refers to anymore.
+    ///
+    /// Data files are released as objects are removed, so this only finds files left behind by
+    /// interrupted writes, such as a crash in the middle of a transaction. Run it occasionally,
+    /// for example when the server starts.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<usize, String>` - The number of data files deleted, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// let deleted = vault_manager.vacuum().expect("Failed to vacuum data files");
+    /// println!("Deleted {} orphaned data files", deleted);
+    /// ```
+    pub fn vacuum(&self) -> Result<usize, String> {
+        self.persistent_db.vacuum()
+            .map_err(|e| format!("Failed to vacuum data files: {}", e))
+    }
+
+    /// Adds an object that is removed automatically once `ttl` has passed.
+    ///
+    /// Use this for short-lived objects such as dropped loot or projectiles. Expired objects are
+    /// removed by `sweep_expired`, which the game calls regularly, for example once per tick.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_id` - The UUID of the region to add the object to.
+    /// * `object` - The object to add.
+    /// * `ttl` - How long the object lives.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), String>` - An empty result if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{SpatialObject, VaultManager, CustomData};
+    /// # use uuid::Uuid;
+    /// # use std::sync::Arc;
+    /// # use std::time::Duration;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let region_id = Uuid::new_v4();
+    /// let loot = SpatialObject::new(Uuid::new_v4(), "loot", [4.0, 0.0, 2.0], Arc::new(CustomData { /* ... */ })).unwrap();
+    /// vault_manager.add_object_with_ttl(region_id, loot, Duration::from_secs(300)).expect("Failed to drop loot");
+    /// ```
+    pub fn add_object_with_ttl(&self, region_id: Uuid, object: SpatialObject<T>, ttl: Duration) -> Result<(), String> {
+        let object_id = object.uuid;
+        self.add_spatial_object(region_id, object)?;
+        self.set_ttl(object_id, Some(ttl))
+    }
+
+    /// Sets how long an existing object lives from now, or makes it permanent again.
+    ///
+    /// # Arguments
+    ///
+    /// * `object_id` - The UUID of the object.
+    /// * `ttl` - How long the object lives from now, or `None` to never expire it.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), String>` - An empty result if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # use uuid::Uuid;
+    /// # use std::time::Duration;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let loot_id = Uuid::new_v4();
+    /// // A player marked the loot, so it should stay
+    /// vault_manager.set_ttl(loot_id, None).expect("Failed to keep loot");
+    /// ```
+    pub fn set_ttl(&self, object_id: Uuid, ttl: Option<Duration>) -> Result<(), String> {
+        if self.find_object(object_id)?.is_none() {
+            return Err(format!("Object not found: {}", object_id));
+        }
+        let expires_at = ttl.map(|ttl| SystemTime::now() + ttl);
+        let expires_at_ms = expires_at
+            .map(|expires_at| expires_at.duration_since(UNIX_EPOCH).map(|since| since.as_millis() as i64))
+            .transpose()
+            .map_err(|e| format!("Failed to read the system clock: {}", e))?;
+
+        self.persistent_db.set_point_expiry(object_id, expires_at_ms)
+            .map_err(|e| format!("Failed to store object expiry: {}", e))?;
+        let mut expiries = self.expiries.lock().unwrap();
+        match expires_at {
+            Some(expires_at) => expiries.insert(object_id, expires_at),
+            None => expiries.remove(&object_id),
+        };
         Ok(())
     }
 
-    /// Gets a reference to a region by its ID.
-    ///
-    /// This method is useful when you need to perform operations on a specific region.
+    /// Returns how long an object has left to live.
+    ///
+    /// # Arguments
+    ///
+    /// * `object_id` - The UUID of the object.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Duration>` - The time left, zero if the object expired but wasn't swept yet, or `None` if the object has no TTL.
+    pub fn ttl_remaining(&self, object_id: Uuid) -> Option<Duration> {
+        let expires_at = *self.expiries.lock().unwrap().get(&object_id)?;
+        Some(expires_at.duration_since(SystemTime::now()).unwrap_or(Duration::ZERO))
+    }
+
+    /// Removes every object whose TTL has passed from its region and the persistent database.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<Uuid>, String>` - The UUIDs of the removed objects, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// loop {
+    ///     // ... simulate the tick ...
+    ///     for object_id in vault_manager.sweep_expired().unwrap() {
+    ///         println!("Despawned {}", object_id);
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - Until it's swept, an expired object is still returned by queries.
+    /// - The TTL of an object that was removed some other way is dropped without error. A
+    ///   soft-deleted object loses its TTL this way, and doesn't expire once it's restored.
+    pub fn sweep_expired(&self) -> Result<Vec<Uuid>, String> {
+        let now = SystemTime::now();
+        let mut expired: Vec<Uuid> = self.expiries.lock().unwrap().iter()
+            .filter(|(_, expires_at)| **expires_at <= now)
+            .map(|(object_id, _)| *object_id)
+            .collect();
+        expired.sort();
+
+        let mut removed = Vec::new();
+        for object_id in expired {
+            if self.find_object(object_id)?.is_some() {
+                self.remove_object(object_id)?;
+                removed.push(object_id);
+            } else {
+                self.persistent_db.set_point_expiry(object_id, None)
+                    .map_err(|e| format!("Failed to clear object expiry: {}", e))?;
+            }
+            self.expiries.lock().unwrap().remove(&object_id);
+        }
+        Ok(removed)
+    }
+
+    /// Moves every object with a velocity as far as it travels in a time step.
+    ///
+    /// Call this once per server tick to dead-reckon objects between authoritative updates,
+    /// such as projectiles or vehicles, without a separate physics store. Each object moves by
+    /// its velocity times `dt`, and is reindexed at its new position.
+    ///
+    /// # Arguments
+    ///
+    /// * `dt` - The time elapsed since the last step.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<usize, String>` - The number of objects moved, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, SpatialObject, CustomData};
+    /// # use uuid::Uuid;
+    /// # use std::sync::Arc;
+    /// # use std::time::Duration;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let region_id = Uuid::new_v4();
+    /// let arrow = SpatialObject::new(Uuid::new_v4(), "projectile", [0.0, 2.0, 0.0], Arc::new(CustomData { /* ... */ }))
+    ///     .and_then(|arrow| arrow.with_velocity([30.0, 0.0, 0.0]))
+    ///     .unwrap();
+    /// vault_manager.add_spatial_object(region_id, arrow).unwrap();
+    /// loop {
+    ///     vault_manager.advance(Duration::from_millis(50)).expect("Failed to advance objects");
+    /// }
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - Only objects of loaded regions are moved.
+    /// - Objects that stay in their region are changed in memory and persisted later, like with
+    ///   `update_object`. Objects leaving their region are moved like with `move_object`, and are
+    ///   written immediately.
+    /// - New positions are checked against `VaultConfig::world_bounds`. With `BoundsPolicy::Reject`,
+    ///   an object that would leave the world stays where it is.
+    /// - Objects attached with `attach_object` don't follow their parent here; give them the same velocity.
+    pub fn advance(&self, dt: Duration) -> Result<usize, String> {
+        let seconds = dt.as_secs_f64();
+        let mut advanced_in_place = 0;
+        let mut crossings = Vec::new();
+        for (region_id, handle) in self.region_handles() {
+            if !self.is_region_loaded(region_id) {
+                continue;
+            }
+            let mut region = self.locks.acquire(region_id, &handle);
+            let bounds = region.bounds();
+            let moving: Vec<SpatialObject<T>> = region.rtree.iter()
+                .filter(|obj| obj.velocity.is_some_and(|velocity| velocity != [0.0; 3]))
+                .cloned()
+                .collect();
+
+            let mut events = Vec::new();
+            for object in moving {
+                let velocity = object.velocity.unwrap_or_default();
+                let target = std::array::from_fn(|axis| object.point[axis] + velocity[axis] * seconds);
+                let Ok(position) = self.check_position(object.uuid, target) else {
+                    continue;
+                };
+                if position == object.point {
+                    continue;
+                }
+                if !bounds.contains_point(&position) {
+                    crossings.push((object.uuid, region_id, position));
+                    continue;
+                }
+
+                let object_id = object.uuid;
+                let advanced = SpatialObject { point: position, version: object.version + 1, ..object.clone() };
+                if self.journal_object(&advanced).is_err() {
+                    continue;
+                }
+                region.remove_object(&object);
+                region.insert_object(advanced);
+                region.mark_dirty(object_id);
+                events.push(VaultEvent::ObjectMoved { object_id, from_region: region_id, to_region: region_id, position });
+                advanced_in_place += 1;
+            }
+            drop(region);
+            self.emit(events);
+        }
+
+        if let Some(worker) = &*self.auto_persist.lock().unwrap() {
+            for _ in 0..advanced_in_place {
+                worker.record_mutation();
+            }
+        }
+
+        // Objects changing regions take both region locks, so they're moved once none is held
+        let crossed = crossings.len();
+        for (object_id, region_id, position) in crossings {
+            self.relocate_object(object_id, region_id, position, None)?;
+        }
+        Ok(advanced_in_place + crossed)
+    }
+
+    /// Gets a reference to an object by its ID.
+    ///
+    /// The region holding the object is found through the object index, and the object within
+    /// it through the region's spatial index, so the lookup doesn't scan any region.
+    ///
+    /// # Arguments
+    ///
+    /// * `object_id` - The UUID of the object to retrieve.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Option<SpatialObject<T>>, String>` - An `Option` containing a clone of the object if found, or `None` if not found.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # use uuid::Uuid;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let object_id = Uuid::new_v4();
+    /// if let Ok(Some(object)) = vault_manager.get_object(object_id) {
+    ///     println!("Found object: {:?}", object);
+    /// } else {
+    ///     println!("Object not found");
+    /// }
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - This method returns a clone of the `SpatialObject`, including the `Arc<T>` custom data.
+    /// - If the object index is out of date, the loaded regions are searched and the index is corrected.
+    /// - If the object isn't in a loaded region, the region holding it is looked up in the persistent database and loaded.
+    /// - An object moved to cold storage by `archive_cold_objects` is restored into its region first.
+    pub fn get_object(&self, object_id: Uuid) -> Result<Option<SpatialObject<T>>, String> {
+        match self.find_object(object_id)? {
+            Some((_, obj)) => Ok(Some(obj)),
+            None => self.restore_archived(object_id),
+        }
+    }
+
+    /// Checks whether an object exists, without cloning it.
     ///
     /// # Arguments
     ///
-    /// * `region_id` - The UUID of the region to retrieve.
+    /// * `object_id` - The UUID of the object to look for.
     ///
     /// # Returns
     ///
-    /// * `Option<Arc<Mutex<VaultRegion<T>>>>` - An `Option` containing a reference to the region if found, or `None` if not found.
-    pub fn get_region(&self, region_id: Uuid) -> Option<Arc<Mutex<VaultRegion<T>>>> {
-        self.regions.get(&region_id).cloned()
-    }
-
-    /// Removes an object from its region and the persistent database.
+    /// * `Result<bool, String>` - `true` if the object exists, or an error message if the lookup fails.
     ///
-    /// # Arguments
+    /// # Examples
     ///
-    /// * `object_id` - The UUID of the object to remove.
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # use uuid::Uuid;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let object_id = Uuid::new_v4();
+    /// if vault_manager.contains_object(object_id).expect("Failed to look up object") {
+    ///     println!("Object still exists");
+    /// }
+    /// ```
     ///
-    /// # Returns
+    /// # Notes
     ///
-    /// * `Result<(), String>` - An empty result if successful, or an error message if not.
-    pub fn remove_object(&mut self, object_id: Uuid) -> Result<(), String> {
-        // Find the region containing the object
-        for (region_id, region) in &mut self.regions {
-            let mut region = region.lock().unwrap();
-            // Find and remove the object from the RTree
-            let mut object_to_remove = None;
-            for obj in region.rtree.iter() {
-                if obj.uuid == object_id {
-                    object_to_remove = Some(obj.clone());
-                    break;
-                }
-            }
-            
-            if let Some(obj) = object_to_remove {
-                region.rtree.remove(&obj);
-                // Remove the object from the persistent database
-                self.persistent_db.remove_point(object_id)
-                    .map_err(|e| format!("Failed to remove point from persistent database: {}", e))?;
-                return Ok(());
-            }
+    /// - Loaded regions answer in constant time each. Objects of unloaded regions are looked up in
+    ///   the persistent database, and unlike `get_object`, their region isn't loaded.
+    /// - Soft-deleted objects don't exist until they're restored.
+    pub fn contains_object(&self, object_id: Uuid) -> Result<bool, String> {
+        let loaded = self.region_handles().into_iter().any(|(region_id, region)| {
+            self.locks.acquire(region_id, &region).contains_object(object_id)
+        });
+        if loaded {
+            return Ok(true);
         }
-        Err(format!("Object not found: {}", object_id))
+
+        let stored_region = self.persistent_db.get_point_region(object_id)
+            .map_err(|e| format!("Failed to look up region of object {}: {}", object_id, e))?;
+        Ok(stored_region.is_some_and(|region_id| {
+            self.has_region(region_id) && !self.is_region_loaded(region_id)
+        }))
     }
 
-    /// Gets a reference to an object by its ID.
+    /// Gets an object by its ID without loading the region holding it.
     ///
-    /// This method searches for an object with the given UUID across all regions.
+    /// Objects of loaded regions come from memory, like with `get_object`. An object of an
+    /// unloaded region is read from its row in the persistent database instead, which is much
+    /// cheaper than loading the whole region for a single lookup.
     ///
     /// # Arguments
     ///
@@ -552,7 +5647,7 @@ impl<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> Vault
     ///
     /// # Returns
     ///
-    /// * `Result<Option<SpatialObject<T>>, String>` - An `Option` containing a clone of the object if found, or `None` if not found.
+    /// * `Result<Option<SpatialObject<T>>, String>` - A clone of the object if found, `None` if not, or an error message if the lookup fails.
     ///
     /// # Examples
     ///
@@ -561,32 +5656,86 @@ impl<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> Vault
     /// # use uuid::Uuid;
     /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
     /// # let object_id = Uuid::new_v4();
-    /// if let Ok(Some(object)) = vault_manager.get_object(object_id) {
-    ///     println!("Found object: {:?}", object);
-    /// } else {
-    ///     println!("Object not found");
+    /// // Show an offline player's profile without loading their region
+    /// if let Some(player) = vault_manager.peek_object(object_id).expect("Failed to look up object") {
+    ///     println!("Last seen at {:?}", player.point);
     /// }
     /// ```
     ///
     /// # Notes
     ///
-    /// - This method returns a clone of the `SpatialObject`, including the `Arc<T>` custom data.
-    /// - The search is performed across all regions, which may be slow for a large number of regions or objects.
-    pub fn get_object(&self, object_id: Uuid) -> Result<Option<SpatialObject<T>>, String> {
-        for (_, region) in &self.regions {
-            let region = region.lock().unwrap();
-            let object = region.rtree.iter().find(|obj| obj.uuid == object_id).cloned();
-            if let Some(obj) = object {
-                return Ok(Some(obj));
+    /// - Archived objects aren't restored; use `get_object` for them.
+    pub fn peek_object(&self, object_id: Uuid) -> Result<Option<SpatialObject<T>>, String> {
+        let loaded = self.region_handles().into_iter().find_map(|(region_id, region)| {
+            self.locks.acquire(region_id, &region).get_object(object_id).cloned()
+        });
+        if loaded.is_some() {
+            return Ok(loaded);
+        }
+
+        let stored = self.persistent_db.get_point(object_id)
+            .map_err(|e| format!("Failed to read object {}: {}", object_id, e))?;
+        match stored {
+            Some((region_id, point)) if self.has_region(region_id) && !self.is_region_loaded(region_id) => {
+                from_point(point).map(Some)
             }
+            _ => Ok(None),
         }
-        Ok(None)
+    }
+
+    /// Gets every object of a given type in a region, without loading the region.
+    ///
+    /// A loaded region answers from its index for the type. For an unloaded region, only the
+    /// rows of the requested type are read from the persistent database.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_id` - The UUID of the region to search.
+    /// * `object_type` - The type of object to retrieve (e.g., "player").
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<SpatialObject<T>>, String>` - The objects of the given type if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # use uuid::Uuid;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let region_id = Uuid::new_v4();
+    /// let buildings = vault_manager.get_objects_by_type(region_id, "building").expect("Failed to get buildings");
+    /// println!("{} buildings on the map", buildings.len());
+    /// ```
+    pub fn get_objects_by_type(&self, region_id: Uuid, object_type: &str) -> Result<Vec<SpatialObject<T>>, String> {
+        let handle = self.region(region_id)?;
+        // Regions are unloaded while locked, so the check can't go stale while the lock is held
+        let region = self.locks.acquire(region_id, &handle);
+        let mut objects = if self.is_region_loaded(region_id) {
+            region.objects_of_type(object_type)
+                .map(|typed| typed.iter().cloned().collect())
+                .unwrap_or_default()
+        } else {
+            drop(region);
+            self.persistent_db.get_points_by_type(object_type, region_id)
+                .map_err(|e| format!("Failed to read objects of type {} in region {}: {}", object_type, region_id, e))?
+                .into_iter()
+                .map(from_point)
+                .collect::<Result<Vec<SpatialObject<T>>, String>>()?
+        };
+        self.order_results(&mut objects, None);
+        Ok(objects)
     }
 
     /// Updates an existing object in the VaultManager's in-memory storage.
     ///
     /// This method updates only the in-memory representation of the object.
     /// It does not update the persistent storage. Use `persist_to_disk` for saving changes to the database.
+    /// The new position is checked against `VaultConfig::world_bounds` like in `add_object`.
+    ///
+    /// Updates are optimistic: `object.version` must be the version the caller read. If the stored
+    /// object has a newer version, another system changed it in the meantime and the update is
+    /// rejected with a version conflict instead of overwriting that change.
     ///
     /// # Arguments
     ///
@@ -594,7 +5743,7 @@ impl<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> Vault
     ///
     /// # Returns
     ///
-    /// * `Result<(), String>` - Ok if the update is successful, or an error message if it fails.
+    /// * `Result<u64, String>` - The object's new version if the update is successful, or an error message if it fails.
     ///
     /// # Examples
     ///
@@ -602,34 +5751,523 @@ impl<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> Vault
     /// # use your_crate::{VaultManager, SpatialObject, CustomData};
     /// # use uuid::Uuid;
     /// # use std::sync::Arc;
-    /// # let mut vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
     /// # let object_id = Uuid::new_v4();
     /// # let mut object = vault_manager.get_object(object_id).unwrap().unwrap();
     /// // Modify the object
     /// object.custom_data = Arc::new(CustomData { /* ... */ });
-    /// vault_manager.update_object(&object).expect("Failed to update object");
+    /// object.version = vault_manager.update_object(&object).expect("Failed to update object");
     /// ```
-    pub fn update_object(&mut self, object: &SpatialObject<T>) -> Result<(), String> {
-        let mut updated = false;
+    pub fn update_object(&self, object: &SpatialObject<T>) -> Result<u64, String> {
+        self.check_type(object)?;
+        let mut object = object.clone();
+        object.point = self.check_position(object.uuid, object.point)?;
 
         // Find the region containing the object
-        for (_, region) in &mut self.regions {
-            let mut region = region.lock().unwrap();
-            let existing_obj = region.rtree.iter().find(|obj| obj.uuid == object.uuid).cloned();
-            
-            if let Some(existing) = existing_obj {
-                // Remove the existing object and insert the updated one
-                region.rtree.remove(&existing);
-                region.rtree.insert(object.clone());
-                updated = true;
-                break;
+        let (region_id, existing) = self.find_object(object.uuid)?
+            .ok_or_else(|| format!("Object not found in any region: {}", object.uuid))?;
+
+        check_version(&existing, object.version)?;
+        object.version = existing.version + 1;
+        let version = object.version;
+
+        // Remove the existing object and insert the updated one
+        let handle = self.region(region_id)?;
+        let mut region = self.lock_region(region_id, &handle)?;
+        self.journal_object(&object)?;
+        region.remove_object(&existing);
+        let object_id = object.uuid;
+        if self.has_subscribers() {
+            self.emit(VaultEvent::for_change(Some((region_id, &existing)), Some((region_id, &object))));
+        }
+        region.insert_object(object);
+        region.mark_dirty(object_id);
+        drop(region);
+
+        if let Some(worker) = &*self.auto_persist.lock().unwrap() {
+            worker.record_mutation();
+        }
+        Ok(version)
+    }
+
+    /// Validates an object's position against the configured world bounds.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<[f64; 3], String>` - The position to store, which is clamped into the world if the
+    ///   bounds policy says so, or an error message if the position is rejected.
+    fn check_position(&self, object_id: Uuid, point: [f64; 3]) -> Result<[f64; 3], String> {
+        validate_point(point).map_err(|e| format!("Invalid position for object {}: {}", object_id, e))?;
+        match self.config.world_bounds {
+            Some(bounds) if !bounds.contains(point) => match self.config.out_of_bounds {
+                BoundsPolicy::Reject => Err(format!("Object {} is outside the world bounds: {:?}", object_id, point)),
+                BoundsPolicy::Clamp => Ok(bounds.clamp(point)),
+            },
+            _ => Ok(point),
+        }
+    }
+
+    /// Checks an object against the definition of its type.
+    ///
+    /// Objects of unregistered types pass unless `VaultConfig::reject_unknown_types` is set.
+    fn check_type(&self, object: &SpatialObject<T>) -> Result<(), String> {
+        match self.object_types.read().unwrap().get(&object.object_type) {
+            Some(definition) => definition.check(object.size, &*object.custom_data)
+                .map_err(|e| format!("Invalid object {}: {}", object.uuid, e)),
+            None if self.config.reject_unknown_types => Err(format!("Unknown object type for object {}: {}", object.uuid, object.object_type)),
+            None => Ok(()),
+        }
+    }
+
+    /// Wraps custom data in an `Arc`, reusing the allocation of a removed object if one is pooled.
+    ///
+    /// Use this instead of `Arc::new` for short-lived objects such as projectiles. Pooling is
+    /// controlled by `VaultConfig::object_pool_capacity`; when it's zero this is just `Arc::new`.
+    ///
+    /// # Arguments
+    ///
+    /// * `custom_data` - The custom data for a new object.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # use uuid::Uuid;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let region_id = Uuid::new_v4();
+    /// let data = vault_manager.pooled_custom_data(CustomData { /* ... */ });
+    /// vault_manager.add_object(region_id, Uuid::new_v4(), "projectile", 0.0, 0.0, 0.0, data).expect("Failed to add object");
+    /// ```
+    pub fn pooled_custom_data(&self, custom_data: T) -> Arc<T> {
+        self.object_pool.lock().unwrap().acquire(custom_data)
+    }
+
+    /// Returns counters describing how often pooled allocations were reused.
+    pub fn object_pool_stats(&self) -> PoolStats {
+        self.object_pool.lock().unwrap().stats()
+    }
+
+    /// Returns the I/O the persistent database has performed since the manager was opened.
+    ///
+    /// Take a snapshot before and after an operation and subtract them with `IoStats::since`
+    /// to see what that operation cost.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # use uuid::Uuid;
+    /// # use std::sync::Arc;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let region_id = Uuid::new_v4();
+    /// let before = vault_manager.io_stats();
+    /// vault_manager.add_object(region_id, Uuid::new_v4(), "tree", 0.0, 0.0, 0.0, Arc::new(CustomData { /* ... */ })).unwrap();
+    /// println!("{:?}", vault_manager.io_stats().since(&before));
+    /// ```
+    pub fn io_stats(&self) -> IoStats {
+        self.persistent_db.io_stats()
+    }
+
+    /// Returns the features the persistent storage backend supports natively.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// let capabilities = vault_manager.backend_capabilities();
+    /// println!("Transactions: {}, spatial index: {}", capabilities.transactions, capabilities.spatial_index);
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - Persistence writes points in multi-row batches only when `batch_writes` is set, and one
+    ///   point per statement otherwise.
+    pub fn backend_capabilities(&self) -> BackendCapabilities {
+        self.persistent_db.capabilities()
+    }
+
+    /// Decides which regions are simulated on a game tick, and how.
+    ///
+    /// Regions within `policy.active_distance` of a player are simulated fully on every tick.
+    /// The others are only due every `policy.inactive_interval` ticks, so the game can apply one
+    /// aggregated update for the whole gap instead of simulating them tick by tick. The tick each
+    /// region is returned for is recorded, so a region whose players return reports the ticks it
+    /// skipped, letting the game catch up before resuming full simulation.
+    ///
+    /// # Arguments
+    ///
+    /// * `tick` - The current game tick, increasing from call to call.
+    /// * `policy` - Which regions count as active and how often inactive ones are simulated.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<RegionTick>, String>` - The regions due this tick, ordered by UUID, or an error message if the policy is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData, LodPolicy, SimulationLevel};
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// # let tick = 0;
+    /// for region in vault_manager.schedule_tick(tick, &LodPolicy::new(200.0, 20)).unwrap() {
+    ///     if region.level == SimulationLevel::Reduced {
+    ///         println!("Regrowing resources in {} for {} ticks", region.region_id, region.elapsed_ticks);
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - Only players in loaded regions are considered; a region kept loaded by nobody has no players in view.
+    /// - The recorded ticks live in memory and start over when the manager is reopened.
+    pub fn schedule_tick(&self, tick: u64, policy: &LodPolicy) -> Result<Vec<RegionTick>, String> {
+        if !policy.active_distance.is_finite() || policy.active_distance < 0.0 {
+            return Err(format!("Invalid active distance: {}", policy.active_distance));
+        }
+
+        let mut players = Vec::new();
+        let mut bounds = Vec::with_capacity(self.region_count());
+        for (region_id, region) in self.region_handles() {
+            let region = self.locks.acquire(region_id, &region);
+            if let Some(index) = region.objects_of_type(&policy.player_type) {
+                players.extend(index.iter().map(|player| player.point));
+            }
+            bounds.push((region_id, region.bounds()));
+        }
+        bounds.sort_unstable_by_key(|(region_id, _)| *region_id);
+
+        let reach_2 = policy.active_distance.powi(2);
+        let mut last_ticks = self.simulation_ticks.lock().unwrap();
+        let mut scheduled = Vec::new();
+        for (region_id, bounds) in bounds {
+            let active = players.iter().any(|player| bounds.distance_2(player) <= reach_2);
+            let elapsed_ticks = match last_ticks.get(&region_id) {
+                Some(last_tick) => tick.saturating_sub(*last_tick),
+                None => {
+                    last_ticks.insert(region_id, tick.saturating_sub(1));
+                    1
+                }
+            };
+            let (level, interval) = if active {
+                (SimulationLevel::Full, 1)
+            } else {
+                (SimulationLevel::Reduced, policy.inactive_interval.max(1))
+            };
+            if elapsed_ticks >= interval {
+                last_ticks.insert(region_id, tick);
+                scheduled.push(RegionTick { region_id, level, elapsed_ticks });
             }
         }
+        Ok(scheduled)
+    }
+
+    /// Returns the tick at which a region was last returned by `schedule_tick`.
+    pub fn last_simulated_tick(&self, region_id: Uuid) -> Option<u64> {
+        self.simulation_ticks.lock().unwrap().get(&region_id).copied()
+    }
+
+    /// Returns how the region locks have been used since the manager was opened or the
+    /// diagnostics were last reset.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// let diagnostics = vault_manager.lock_diagnostics();
+    /// for (region_id, stats) in diagnostics.hot_spots(3) {
+    ///     println!("{} waited {:?} (held at most {:?})", region_id, stats.total_wait, stats.max_hold);
+    /// }
+    /// for violation in &diagnostics.order_violations {
+    ///     println!("{} locked while holding {}", violation.acquired, violation.held);
+    /// }
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - Only locks taken by the manager are counted, not locks taken on regions returned by `get_region`.
+    pub fn lock_diagnostics(&self) -> LockDiagnostics {
+        self.locks.snapshot()
+    }
+
+    /// Resets the region lock diagnostics to zero.
+    pub fn reset_lock_diagnostics(&self) {
+        self.locks.reset();
+    }
+
+    /// Returns `true` if there are in-memory changes that haven't been persisted yet.
+    ///
+    /// Changes made through `update_object` only live in memory until `persist_to_disk` is called.
+    pub fn has_unflushed_changes(&self) -> bool {
+        self.region_handles().into_iter().any(|(region_id, region)| self.locks.acquire(region_id, &region).dirty_count() > 0)
+    }
+
+    /// Stops the background auto-persist worker, if it's running.
+    ///
+    /// The worker flushes every changed object one last time before it exits.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), String>` - An empty result if successful, or an error message if the final flush failed.
+    pub fn disable_auto_persist(&self) -> Result<(), String> {
+        let worker = self.auto_persist.lock().unwrap().take();
+        match worker {
+            Some(mut worker) => worker.stop(),
+            None => Ok(()),
+        }
+    }
+
+    /// Hands the current set of regions to the auto-persist worker, if it's running.
+    fn sync_auto_persist_regions(&self) {
+        if let Some(worker) = &*self.auto_persist.lock().unwrap() {
+            worker.set_regions(self.region_handles().iter().map(|(region_id, region)| (*region_id, Arc::downgrade(region))).collect());
+        }
+    }
+
+    /// Shuts the VaultManager down gracefully.
+    ///
+    /// This method flushes any unpersisted changes to the database and then releases the manager,
+    /// closing the database connection. Call it when a game server stops instead of simply
+    /// dropping the manager.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), String>` - An empty result if successful, or an error message if the final flush failed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{VaultManager, CustomData};
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// vault_manager.shutdown().expect("Failed to shut down VaultManager");
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - If the final flush fails the manager is still dropped, and a warning about the unflushed data is printed.
+    /// - A running auto-persist worker is drained and stopped first.
+    pub fn shutdown(self) -> Result<(), String> {
+        self.disable_auto_persist()?;
+        if self.has_unflushed_changes() {
+            self.persist_to_disk()?;
+        }
+        Ok(())
+    }
+}
 
-        if !updated {
-            return Err(format!("Object not found in any region: {}", object.uuid));
+impl<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized + Send + Sync + 'static> VaultManager<T> {
+    /// Starts a background thread that flushes changed objects to the persistent database.
+    ///
+    /// The thread writes the objects changed since the last flush every `settings.interval`,
+    /// and sooner once `settings.max_mutations` changes have accumulated, so game servers don't
+    /// have to call `persist_to_disk` on their main tick.
+    ///
+    /// # Arguments
+    ///
+    /// * `settings` - When the worker flushes.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), String>` - An empty result if successful, or an error message if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use your_crate::{AutoPersist, VaultManager, CustomData};
+    /// # use std::time::Duration;
+    /// # let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+    /// vault_manager.enable_auto_persist(AutoPersist::every(Duration::from_secs(10)).or_after(500))
+    ///     .expect("Failed to start auto-persist");
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - The worker opens a second connection to the database file, so it isn't available for in-memory databases.
+    /// - Its I/O isn't included in `io_stats`.
+    /// - Failed flushes are reported on stderr and retried on the next interval.
+    /// - Call `shutdown` or `disable_auto_persist` to drain the worker; dropping the manager does so too.
+    pub fn enable_auto_persist(&self, settings: AutoPersist) -> Result<(), String> {
+        let mut auto_persist = self.auto_persist.lock().unwrap();
+        if auto_persist.is_some() {
+            return Err("Auto-persist is already enabled".to_string());
         }
+        if settings.interval.is_zero() {
+            return Err("Auto-persist interval must not be zero".to_string());
+        }
+        let path = self.persistent_db.path()
+            .filter(|path| !path.is_empty())
+            .ok_or("Auto-persist requires a database file")?;
+
+        let mut db = MySQLGeo::Database::new(&path)
+            .map_err(|e| format!("Failed to open auto-persist connection: {}", e))?;
+        db.set_compression_threshold(self.config.compression_threshold);
+        db.set_compression(self.config.compression);
+        db.create_table()
+            .map_err(|e| format!("Failed to create table: {}", e))?;
 
+        *auto_persist = Some(AutoPersistWorker::start(settings, db, self.locks.clone())?);
+        drop(auto_persist);
+        self.sync_auto_persist_regions();
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+impl<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized> Drop for VaultManager<T> {
+    /// Warns when the manager is dropped with changes that were never persisted.
+    fn drop(&mut self) {
+        if let Err(e) = self.disable_auto_persist() {
+            eprintln!("Warning: final auto-persist failed: {}", e);
+        }
+        if self.has_unflushed_changes() {
+            eprintln!("Warning: VaultManager dropped with unflushed changes; call persist_to_disk() or shutdown() to keep them");
+        }
+    }
+}
+
+/// Writes objects to a database as points of the given region.
+pub(crate) fn write_points<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized>(db: &MySQLGeo::Database, region_id: Uuid, objects: &[SpatialObject<T>]) -> Result<(), String> {
+    for chunk in objects.chunks(MySQLGeo::POINT_BATCH_SIZE) {
+        let points = chunk.iter()
+            .map(|object| to_point(object).map(|point| (point, region_id)))
+            .collect::<Result<Vec<_>, String>>()?;
+        db.upsert_points(&points)
+            .map_err(|e| format!("Failed to add points to persistent database: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Converts an object into the record stored in region files and archive chunks.
+fn object_record<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized>(object: &SpatialObject<T>, expires_at: Option<i64>) -> Result<ObjectRecord, String> {
+    Ok(ObjectRecord {
+        uuid: object.uuid,
+        object_type: object.object_type,
+        point: object.point,
+        size: object.size,
+        version: object.version,
+        velocity: object.velocity,
+        orientation: object.orientation,
+        custom_data: serde_json::to_value(&*object.custom_data)
+            .map_err(|e| format!("Failed to serialize custom data of object {}: {}", object.uuid, e))?,
+        expires_at,
+    })
+}
+
+/// Converts a record read from a region file or archive chunk back into an object, ignoring its expiry.
+fn object_from_record<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized>(record: ObjectRecord) -> Result<SpatialObject<T>, String> {
+    let custom_data: T = serde_json::from_value(record.custom_data)
+        .map_err(|e| format!("Failed to deserialize custom data of object {}: {}", record.uuid, e))?;
+    Ok(SpatialObject {
+        uuid: record.uuid,
+        object_type: record.object_type,
+        point: record.point,
+        custom_data: Arc::new(custom_data),
+        size: record.size,
+        version: record.version,
+        velocity: record.velocity,
+        orientation: record.orientation,
+    })
+}
+
+/// Converts a point read from the persistent database into an object.
+fn from_point<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized>(point: Point) -> Result<SpatialObject<T>, String> {
+    let uuid = point.id.ok_or("Stored point has no ID")?;
+    let custom_data: T = serde_json::from_value(point.custom_data)
+        .map_err(|e| format!("Failed to deserialize custom data of object {}: {}", uuid, e))?;
+    Ok(SpatialObject {
+        uuid,
+        object_type: ObjectType::from(point.object_type),
+        point: [point.x, point.y, point.z],
+        custom_data: Arc::new(custom_data),
+        size: point.size,
+        version: point.version,
+        velocity: point.velocity,
+        orientation: point.orientation,
+    })
+}
+
+/// Converts an object into the point the persistent database stores for it.
+fn to_point<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized>(object: &SpatialObject<T>) -> Result<Point, String> {
+    let custom_data = serde_json::to_value(&*object.custom_data)
+        .map_err(|e| format!("Failed to serialize custom data: {}", e))?;
+    let mut point = Point::new(Some(object.uuid), object.point[0], object.point[1], object.point[2], object.object_type.to_string(), custom_data);
+    point.size = object.size;
+    point.version = object.version;
+    point.velocity = object.velocity;
+    point.orientation = object.orientation;
+    Ok(point)
+}
+
+/// Rejects a change based on `expected_version` if the stored object has been changed since.
+fn check_version<T: Clone + Serialize + for<'de> Deserialize<'de> + PartialEq + Sized>(stored: &SpatialObject<T>, expected_version: u64) -> Result<(), String> {
+    if stored.version > expected_version {
+        return Err(format!(
+            "Version conflict on object {}: stored version {} is newer than {}",
+            stored.uuid, stored.version, expected_version
+        ));
+    }
+    Ok(())
+}
+
+/// Converts a corrupt stored point into the public `LoadFailure` representation.
+fn load_failure(corrupt: CorruptPoint, region_id: Uuid) -> LoadFailure {
+    LoadFailure {
+        point_id: corrupt.id,
+        region_id,
+        object_type: corrupt.object_type,
+        point: [corrupt.x, corrupt.y, corrupt.z],
+        raw_custom_data: corrupt.raw_custom_data,
+        error: corrupt.error,
+    }
+}
+
+/// Builds the search box of a box query, rejecting corners that are NaN.
+///
+/// Infinite corners are allowed so that callers can express unbounded searches.
+fn query_box(min: [f64; 3], max: [f64; 3]) -> Result<AABB<[f64; 3]>, String> {
+    if min.iter().chain(max.iter()).any(|c| c.is_nan()) {
+        return Err(format!("Invalid query box: {:?} to {:?}", min, max));
+    }
+    Ok(AABB::from_corners(min, max))
+}
+
+/// Returns the center of a query box.
+fn box_center(min: [f64; 3], max: [f64; 3]) -> [f64; 3] {
+    [(min[0] + max[0]) / 2.0, (min[1] + max[1]) / 2.0, (min[2] + max[2]) / 2.0]
+}
+
+/// Returns the index of the octant around `center` that contains `point`.
+///
+/// Bit 0 is set for the upper half along x, bit 1 for y and bit 2 for z.
+fn octant_of(center: [f64; 3], point: [f64; 3]) -> usize {
+    (0..3).filter(|&axis| point[axis] >= center[axis]).map(|axis| 1 << axis).sum()
+}
+
+/// Returns the distance along a normalized ray at which it enters `bounds`, if it does so within `max_distance`.
+///
+/// Uses the slab method: the ray is clipped against the pair of planes bounding each axis.
+fn ray_box_distance(origin: [f64; 3], direction: [f64; 3], max_distance: f64, bounds: &AABB<[f64; 3]>) -> Option<f64> {
+    let lower = bounds.lower();
+    let upper = bounds.upper();
+    let mut t_min = 0.0_f64;
+    let mut t_max = max_distance;
+
+    for axis in 0..3 {
+        if direction[axis] == 0.0 {
+            // Parallel to this slab, so the origin must already lie between its planes
+            if origin[axis] < lower[axis] || origin[axis] > upper[axis] {
+                return None;
+            }
+            continue;
+        }
+        let t1 = (lower[axis] - origin[axis]) / direction[axis];
+        let t2 = (upper[axis] - origin[axis]) / direction[axis];
+        t_min = t_min.max(t1.min(t2));
+        t_max = t_max.min(t1.max(t2));
+        if t_min > t_max {
+            return None;
+        }
+    }
+
+    Some(t_min)
+}
@@ -0,0 +1,39 @@
+//! # Named World Snapshots
+//!
+//! This module defines `WorldSnapshot`, the description of a named copy of the whole world
+//! taken with `VaultManager::snapshot`. A snapshot holds every region and object, including
+//! custom data and TTLs, so `VaultManager::restore` can roll the world back to it later. Game
+//! servers use snapshots for save slots, checkpoints before risky events, and rollbacks.
+//!
+//! Snapshots are stored in the persistent database as compressed archives, so they survive
+//! restarts and are shared by every server using the database.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use your_crate::{VaultManager, CustomData};
+//!
+//! let vault_manager: VaultManager<CustomData> = VaultManager::new("path/to/database.db").unwrap();
+//! vault_manager.snapshot("before-boss-fight").unwrap();
+//! // ... the fight goes badly ...
+//! vault_manager.restore("before-boss-fight").unwrap();
+//!
+//! for snapshot in vault_manager.snapshots().unwrap() {
+//!     println!("{}: {} objects", snapshot.name, snapshot.object_count);
+//! }
+//! ```
+
+use std::time::SystemTime;
+
+/// A named copy of the world, taken with `VaultManager::snapshot`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WorldSnapshot {
+    /// Name the snapshot was saved under
+    pub name: String,
+    /// Time the snapshot was taken, to millisecond precision
+    pub created_at: SystemTime,
+    /// Number of regions in the snapshot
+    pub region_count: usize,
+    /// Number of objects in the snapshot
+    pub object_count: usize,
+}